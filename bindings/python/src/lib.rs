@@ -0,0 +1,346 @@
+//! # Robin Python bindings
+//!
+//! Exposes [`RobinClient`] to Python as the `robin` module. All queries are coroutines
+//! running on a Tokio runtime shared by the module, so they can be awaited from
+//! `asyncio`. Table entries and settings are returned as `dict`s with the field names
+//! of the Rust models; MAC addresses are formatted as strings.
+//!
+//! ```python
+//! import asyncio
+//! import robin
+//!
+//! async def main():
+//!     client = robin.Client()
+//!     for o in await client.originators("bat0"):
+//!         print(o["originator"], o["tq"], o["is_best"])
+//!
+//! asyncio.run(main())
+//! ```
+//!
+//! The bindings are only compiled with the `python` feature, see `pyproject.toml`.
+#![cfg(feature = "python")]
+
+use batman_robin::{
+    Bandwidth, DEBUGFS_ROOT, Gateway, GatewayInfo, GwMode, Interface, Neighbor, Originator,
+    RetryPolicy, RobinClient, RobinError, TransglobalEntry, TranslocalEntry,
+};
+
+use std::future::Future;
+
+use pyo3::create_exception;
+use pyo3::exceptions::{PyException, PyOSError, PyPermissionError, PyTimeoutError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use pyo3_async_runtimes::tokio::future_into_py;
+
+create_exception!(
+    robin,
+    Error,
+    PyException,
+    "Raised when a batman-adv query or setting fails."
+);
+
+/// Maps a `RobinError` to the closest Python exception.
+///
+/// Kernel errors become `OSError` carrying the errno; everything else not covered
+/// by a builtin exception becomes `robin.Error`.
+fn to_py_err(err: RobinError) -> PyErr {
+    match err {
+        RobinError::PermissionDenied => PyPermissionError::new_err(err.to_string()),
+        RobinError::Timeout => PyTimeoutError::new_err(err.to_string()),
+        RobinError::KernelError { errno, .. } => PyOSError::new_err((errno, err.to_string())),
+        _ => Error::new_err(err.to_string()),
+    }
+}
+
+/// Conversion of a model into the `dict` handed to Python.
+trait ToDict {
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>>;
+}
+
+impl ToDict for Originator {
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("originator", self.originator.to_string())?;
+        dict.set_item("next_hop", self.next_hop.to_string())?;
+        dict.set_item("outgoing_if", &self.outgoing_if)?;
+        dict.set_item("last_seen_ms", self.last_seen.as_millis())?;
+        dict.set_item("tq", self.tq)?;
+        dict.set_item("throughput", self.throughput)?;
+        dict.set_item("is_best", self.is_best)?;
+        Ok(dict)
+    }
+}
+
+impl ToDict for Neighbor {
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("neigh", self.neigh.to_string())?;
+        dict.set_item("outgoing_if", &self.outgoing_if)?;
+        dict.set_item("hard_ifindex", self.hard_ifindex)?;
+        dict.set_item("hard_addr", self.hard_addr.map(|mac| mac.to_string()))?;
+        dict.set_item("last_seen_ms", self.last_seen.as_millis())?;
+        dict.set_item("throughput_kbps", self.throughput_kbps)?;
+        dict.set_item("is_best", self.is_best)?;
+        Ok(dict)
+    }
+}
+
+impl ToDict for Gateway {
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("mac_addr", self.mac_addr.to_string())?;
+        dict.set_item("router", self.router.to_string())?;
+        dict.set_item("outgoing_if", &self.outgoing_if)?;
+        dict.set_item("bandwidth_down", self.bandwidth_down.map(Bandwidth::kbps))?;
+        dict.set_item("bandwidth_up", self.bandwidth_up.map(Bandwidth::kbps))?;
+        dict.set_item("throughput", self.throughput.map(Bandwidth::kbps))?;
+        dict.set_item("tq", self.tq)?;
+        dict.set_item("is_best", self.is_best)?;
+        Ok(dict)
+    }
+}
+
+impl ToDict for GatewayInfo {
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let mode = match self.mode {
+            GwMode::Off => "off",
+            GwMode::Client => "client",
+            GwMode::Server => "server",
+            GwMode::Unknown(_) => "unknown",
+        };
+
+        let dict = PyDict::new(py);
+        dict.set_item("mode", mode)?;
+        dict.set_item("sel_class", self.sel_class)?;
+        dict.set_item("bandwidth_down", self.bandwidth_down.kbps())?;
+        dict.set_item("bandwidth_up", self.bandwidth_up.kbps())?;
+        dict.set_item("algo", &self.algo)?;
+        Ok(dict)
+    }
+}
+
+impl ToDict for TranslocalEntry {
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("client", self.client.to_string())?;
+        dict.set_item("vid", self.vid)?;
+        dict.set_item("flags", self.flags.bits())?;
+        dict.set_item("crc32", self.crc32)?;
+        let last_seen = self.last_seen.as_duration();
+        dict.set_item("last_seen_secs", last_seen.as_secs())?;
+        dict.set_item("last_seen_msecs", last_seen.subsec_millis())?;
+        Ok(dict)
+    }
+}
+
+impl ToDict for TransglobalEntry {
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("client", self.client.to_string())?;
+        dict.set_item("orig", self.orig.to_string())?;
+        dict.set_item("vid", self.vid)?;
+        dict.set_item("ttvn", self.ttvn)?;
+        dict.set_item("last_ttvn", self.last_ttvn)?;
+        dict.set_item("flags", self.flags.bits())?;
+        dict.set_item("crc32", self.crc32)?;
+        dict.set_item("is_best", self.is_best)?;
+        Ok(dict)
+    }
+}
+
+impl ToDict for Interface {
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("ifname", &self.ifname)?;
+        dict.set_item("active", self.active)?;
+        dict.set_item("operstate", self.operstate.to_string())?;
+        dict.set_item("carrier", self.carrier)?;
+        dict.set_item("rx_bytes", self.stats.map(|s| s.rx_bytes))?;
+        dict.set_item("rx_packets", self.stats.map(|s| s.rx_packets))?;
+        dict.set_item("tx_bytes", self.stats.map(|s| s.tx_bytes))?;
+        dict.set_item("tx_packets", self.stats.map(|s| s.tx_packets))?;
+        Ok(dict)
+    }
+}
+
+/// Runs `fut` as a Python awaitable returning a `list` of `dict`s.
+fn list_of_dicts<'py, T, F>(py: Python<'py>, fut: F) -> PyResult<Bound<'py, PyAny>>
+where
+    T: ToDict + Send + 'static,
+    F: Future<Output = Result<Vec<T>, RobinError>> + Send + 'static,
+{
+    future_into_py(py, async move {
+        let entries = fut.await.map_err(to_py_err)?;
+        Python::attach(|py| {
+            let list = PyList::empty(py);
+            for entry in &entries {
+                list.append(entry.to_dict(py)?)?;
+            }
+            Ok(list.unbind())
+        })
+    })
+}
+
+/// Client for the BATMAN-adv kernel module of the local node.
+///
+/// Methods are coroutines. Reading works unprivileged; changing settings requires
+/// root or `CAP_NET_ADMIN` and raises `PermissionError` otherwise.
+#[pyclass(name = "Client", module = "robin", frozen)]
+struct Client {
+    inner: RobinClient,
+}
+
+#[pymethods]
+impl Client {
+    /// Creates a client; nothing is connected until the first query.
+    ///
+    /// `retries` retries requests failing with a transient netlink error that many
+    /// times. With `debugfs_fallback`, tables are read from debugfs on batman-adv
+    /// releases without netlink support.
+    #[new]
+    #[pyo3(signature = (*, retries = None, debugfs_fallback = false))]
+    fn new(retries: Option<u32>, debugfs_fallback: bool) -> Self {
+        let mut builder = RobinClient::builder();
+        if let Some(retries) = retries {
+            builder = builder.retry_policy(RetryPolicy::new(retries));
+        }
+        if debugfs_fallback {
+            builder = builder.debugfs_fallback(DEBUGFS_ROOT);
+        }
+        Self {
+            inner: builder.build(),
+        }
+    }
+
+    /// Returns the originator table as a list of dicts.
+    #[pyo3(signature = (mesh_if = "bat0".to_string()))]
+    fn originators<'py>(&self, py: Python<'py>, mesh_if: String) -> PyResult<Bound<'py, PyAny>> {
+        let client = self.inner.clone();
+        list_of_dicts(py, async move { client.originators(&mesh_if).await })
+    }
+
+    /// Returns the neighbor table as a list of dicts.
+    #[pyo3(signature = (mesh_if = "bat0".to_string()))]
+    fn neighbors<'py>(&self, py: Python<'py>, mesh_if: String) -> PyResult<Bound<'py, PyAny>> {
+        let client = self.inner.clone();
+        list_of_dicts(py, async move { client.neighbors(&mesh_if).await })
+    }
+
+    /// Returns the gateway list as a list of dicts.
+    #[pyo3(signature = (mesh_if = "bat0".to_string()))]
+    fn gateways<'py>(&self, py: Python<'py>, mesh_if: String) -> PyResult<Bound<'py, PyAny>> {
+        let client = self.inner.clone();
+        list_of_dicts(py, async move { client.gateways(&mesh_if).await })
+    }
+
+    /// Returns the local translation table as a list of dicts.
+    #[pyo3(signature = (mesh_if = "bat0".to_string()))]
+    fn translocal<'py>(&self, py: Python<'py>, mesh_if: String) -> PyResult<Bound<'py, PyAny>> {
+        let client = self.inner.clone();
+        list_of_dicts(py, async move { client.translocal(&mesh_if).await })
+    }
+
+    /// Returns the global translation table as a list of dicts.
+    #[pyo3(signature = (mesh_if = "bat0".to_string()))]
+    fn transglobal<'py>(&self, py: Python<'py>, mesh_if: String) -> PyResult<Bound<'py, PyAny>> {
+        let client = self.inner.clone();
+        list_of_dicts(py, async move { client.transglobal(&mesh_if).await })
+    }
+
+    /// Returns the hard interfaces of the mesh as a list of dicts.
+    #[pyo3(signature = (mesh_if = "bat0".to_string()))]
+    fn interfaces<'py>(&self, py: Python<'py>, mesh_if: String) -> PyResult<Bound<'py, PyAny>> {
+        let client = self.inner.clone();
+        list_of_dicts(py, async move { client.get_interface(&mesh_if).await })
+    }
+
+    /// Returns the mesh settings as a dict with the keys `aggregation`, `ap_isolation`,
+    /// `bridge_loop_avoidance` and `gateway`, the latter a dict of the gateway mode.
+    #[pyo3(signature = (mesh_if = "bat0".to_string()))]
+    fn settings<'py>(&self, py: Python<'py>, mesh_if: String) -> PyResult<Bound<'py, PyAny>> {
+        let client = self.inner.clone();
+        future_into_py(py, async move {
+            let aggregation = client.get_aggregation(&mesh_if).await.map_err(to_py_err)?;
+            let ap_isolation = client.get_ap_isolation(&mesh_if).await.map_err(to_py_err)?;
+            let bla = client
+                .get_bridge_loop_avoidance(&mesh_if)
+                .await
+                .map_err(to_py_err)?;
+            let gateway = client.get_gw_mode(&mesh_if).await.map_err(to_py_err)?;
+
+            Python::attach(|py| {
+                let dict = PyDict::new(py);
+                dict.set_item("aggregation", aggregation)?;
+                dict.set_item("ap_isolation", ap_isolation)?;
+                dict.set_item("bridge_loop_avoidance", bla)?;
+                dict.set_item("gateway", gateway.to_dict(py)?)?;
+                Ok(dict.unbind())
+            })
+        })
+    }
+
+    /// Enables or disables OGM aggregation.
+    fn set_aggregation<'py>(
+        &self,
+        py: Python<'py>,
+        mesh_if: String,
+        enabled: bool,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let client = self.inner.clone();
+        future_into_py(py, async move {
+            client
+                .set_aggregation(&mesh_if, enabled)
+                .await
+                .map_err(to_py_err)
+        })
+    }
+
+    /// Enables or disables AP isolation.
+    fn set_ap_isolation<'py>(
+        &self,
+        py: Python<'py>,
+        mesh_if: String,
+        enabled: bool,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let client = self.inner.clone();
+        future_into_py(py, async move {
+            client
+                .set_ap_isolation(&mesh_if, enabled)
+                .await
+                .map_err(to_py_err)
+        })
+    }
+
+    /// Enables or disables bridge loop avoidance.
+    fn set_bridge_loop_avoidance<'py>(
+        &self,
+        py: Python<'py>,
+        mesh_if: String,
+        enabled: bool,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let client = self.inner.clone();
+        future_into_py(py, async move {
+            client
+                .set_bridge_loop_avoidance(&mesh_if, enabled)
+                .await
+                .map_err(to_py_err)
+        })
+    }
+
+    /// Returns the default routing algorithm for new mesh interfaces.
+    fn default_routing_algo<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let client = self.inner.clone();
+        future_into_py(py, async move {
+            client.get_default_routing_algo().await.map_err(to_py_err)
+        })
+    }
+}
+
+/// The `robin` Python module.
+#[pymodule]
+fn robin(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Client>()?;
+    m.add("Error", m.py().get_type::<Error>())?;
+    Ok(())
+}
@@ -0,0 +1,162 @@
+//! Derive macro for batman-robin, decoding the attributes of a `batadv` dump
+//! message into a model struct.
+//!
+//! The generated code refers to items of the `batman-robin` crate by `crate::`
+//! paths, so the derive is only usable within that crate.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Field, Fields, Ident, Type, parse_macro_input};
+
+/// Implements `FromAttrs` for a struct with named fields.
+///
+/// Every field takes a `#[batadv(...)]` annotation:
+///
+/// - `#[batadv(BatadvAttrX)]`: the payload of `BatadvAttrX`, converted with
+///   `AttrPayload`. Required unless the field is an `Option`, in which case a
+///   missing or malformed attribute gives `None`.
+/// - `#[batadv(BatadvAttrX, default)]`: as above, but a missing attribute gives
+///   the default value of the field.
+/// - `#[batadv(BatadvAttrX, flag)]`: `true` if the attribute is present.
+/// - `#[batadv(hard_ifname)]`: the name of the hard interface, from
+///   `BatadvAttrHardIfname` or by resolving `BatadvAttrHardIfindex`.
+///
+/// ```ignore
+/// #[derive(FromBatadvAttrs)]
+/// pub struct DatCacheEntry {
+///     #[batadv(BatadvAttrDatCacheIp4Address)]
+///     pub ip: Ipv4Addr,
+///     #[batadv(BatadvAttrLastSeenMsecs, default)]
+///     pub last_seen: LastSeen,
+/// }
+/// ```
+#[proc_macro_derive(FromBatadvAttrs, attributes(batadv))]
+pub fn derive_from_batadv_attrs(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// How a field is decoded.
+enum Source {
+    /// The payload of an attribute.
+    Attr {
+        attr: Ident,
+        default: bool,
+        flag: bool,
+    },
+    /// The hard interface name, resolved from its index if needed.
+    HardIfname,
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(input, "expected a struct"));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(input, "expected named fields"));
+    };
+
+    let mut uses_sock = false;
+    let mut values = Vec::new();
+    for field in &fields.named {
+        let name = &field.ident;
+        let value = match source(field)? {
+            Source::HardIfname => {
+                uses_sock = true;
+                quote!(crate::commands::hard_ifname(attrs, sock).await?)
+            }
+            Source::Attr {
+                attr, flag: true, ..
+            } => quote!(attrs.get_attribute(crate::model::Attribute::#attr.into()).is_some()),
+            Source::Attr {
+                attr,
+                default: true,
+                ..
+            } => quote!(
+                crate::netlink::attr_optional(attrs, crate::model::Attribute::#attr)
+                    .unwrap_or_default()
+            ),
+            Source::Attr { attr, .. } if is_option(&field.ty) => {
+                quote!(crate::netlink::attr_optional(attrs, crate::model::Attribute::#attr))
+            }
+            Source::Attr { attr, .. } => {
+                quote!(crate::netlink::attr_required(attrs, crate::model::Attribute::#attr)?)
+            }
+        };
+        values.push(quote!(#name: #value));
+    }
+
+    let ident = &input.ident;
+    let sock = if uses_sock {
+        format_ident!("sock")
+    } else {
+        format_ident!("_sock")
+    };
+    Ok(quote! {
+        impl crate::commands::FromAttrs for #ident {
+            async fn from_attrs(
+                attrs: &::neli::genl::GenlAttrHandle<'_, u16>,
+                #sock: &impl crate::netlink::MeshTransport,
+            ) -> Result<Self, crate::error::RobinError> {
+                Ok(Self { #(#values),* })
+            }
+        }
+    })
+}
+
+/// Reads the `#[batadv(...)]` annotation of `field`.
+fn source(field: &Field) -> syn::Result<Source> {
+    let Some(annotation) = field.attrs.iter().find(|a| a.path().is_ident("batadv")) else {
+        return Err(syn::Error::new_spanned(
+            field,
+            "missing #[batadv(...)] annotation",
+        ));
+    };
+
+    let mut attr = None;
+    let mut default = false;
+    let mut flag = false;
+    let mut hard_ifname = false;
+    annotation.parse_nested_meta(|meta| {
+        if meta.path.is_ident("default") {
+            default = true;
+        } else if meta.path.is_ident("flag") {
+            flag = true;
+        } else if meta.path.is_ident("hard_ifname") {
+            hard_ifname = true;
+        } else if let Some(ident) = meta.path.get_ident() {
+            attr = Some(ident.clone());
+        } else {
+            return Err(meta.error("expected an attribute name"));
+        }
+        Ok(())
+    })?;
+
+    match (attr, hard_ifname) {
+        (None, true) => Ok(Source::HardIfname),
+        (Some(attr), false) => Ok(Source::Attr {
+            attr,
+            default,
+            flag,
+        }),
+        _ => Err(syn::Error::new_spanned(
+            annotation,
+            "expected either an attribute name or `hard_ifname`",
+        )),
+    }
+}
+
+/// Returns `true` if `ty` is spelled `Option<..>`.
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
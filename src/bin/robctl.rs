@@ -1,29 +1,113 @@
 // Binary entry point for robctl
 // Uses the CLI functionality from the batman_robin crate
 
-use batman_robin::RobinClient;
 use batman_robin::cli::*;
+use batman_robin::config::MeshConfig;
+use batman_robin::failover::{self, FailoverMonitor, GatewayChange};
+use batman_robin::liveness::{self, LivenessEvent, LivenessMonitor};
+use batman_robin::logfile::LogFile;
+use batman_robin::output::{self, Document};
+use batman_robin::record::{self, Recorder};
+use batman_robin::roaming::{self, RoamEvent, RoamHistory};
+#[cfg(feature = "webhook")]
+use batman_robin::webhook::Webhook;
+use batman_robin::{
+    AttrValueForSend, Attribute, Command, DEBUGFS_ROOT, GenlAttrBuilder, IfaceRef, RobinClient,
+    RobinError, TtTableStats,
+};
+use clap::ArgMatches;
+use clap::parser::ValueSource;
+use macaddr::MacAddr6;
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "webhook")]
+use std::sync::mpsc;
+use std::time::Duration;
+use tokio::time::{self, MissedTickBehavior};
+
+/// The `--log-file` standard output is redirected to, if any.
+static LOG: Mutex<Option<LogFile>> = Mutex::new(None);
+
+/// Whether errors are reported as JSON documents, set with `--json`.
+static JSON_ERRORS: AtomicBool = AtomicBool::new(false);
 
 /// Handle a `RobinError` in a CLI-friendly way by printing the error and exiting.
-fn exit_on_error<T>(res: Result<T, batman_robin::RobinError>) -> T {
+fn exit_on_error<T>(res: Result<T, RobinError>) -> T {
     match res {
         Ok(v) => v,
         Err(e) => {
-            eprintln!("{}", e);
+            report(&e);
             std::process::exit(1);
         }
     }
 }
 
+/// Prints `err` on standard error, as an `ErrorDocument` with `--json`.
+fn report(err: &RobinError) {
+    match err {
+        _ if JSON_ERRORS.load(Ordering::Relaxed) => utils::print_json_error(err),
+        RobinError::PermissionDenied => {
+            eprintln!("Error - permission denied: run robctl as root (or grant it CAP_NET_ADMIN)")
+        }
+        _ => eprintln!("{}", err),
+    }
+}
+
 #[tokio::main]
 async fn main() {
-    let client = RobinClient::new();
+    let client = RobinClient::builder()
+        .debugfs_fallback(DEBUGFS_ROOT)
+        .build();
     let matches = app::build_cli().get_matches();
+    JSON_ERRORS.store(app::json_output(&matches), Ordering::Relaxed);
     let mesh_if = matches
         .get_one::<String>("meshif")
         .map(String::as_str)
         .unwrap_or("bat0");
 
+    // The schema of the output does not depend on the kernel.
+    if let Some(("schema", sub_m)) = matches.subcommand() {
+        schema::print_schema(sub_m.get_one::<String>("command").map(String::as_str));
+        return;
+    }
+
+    // Neither do the attributes known to robctl.
+    if let Some(("attrs", sub_m)) = matches.subcommand()
+        && !sub_m.contains_id("command")
+    {
+        attrs::print_attributes();
+        return;
+    }
+
+    if let Some(path) = matches.get_one::<String>("bat_hosts") {
+        exit_on_error(bat_hosts::install(Path::new(path)));
+    }
+
+    let batch = matches.get_one::<String>("batch").map(|path| {
+        let script = match path.as_str() {
+            "-" => std::io::read_to_string(std::io::stdin()),
+            path => std::fs::read_to_string(path),
+        };
+        let script = script.unwrap_or_else(|e| {
+            eprintln!("Error - failed to read {}: {}", path, e);
+            std::process::exit(1);
+        });
+        app::parse_batch(&script).unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        })
+    });
+
+    // Creating a mesh interface on a fresh boot needs the module loaded first.
+    if creates_mesh(&matches) || batch.iter().flatten().any(|(_, m)| creates_mesh(m)) {
+        exit_on_error(batman_robin::ensure_module_loaded());
+    }
+
+    if let Some(path) = matches.get_one::<String>("log_file") {
+        open_log(path, &matches);
+    }
+
     let algo_name = exit_on_error(client.get_default_routing_algo().await);
     if matches.get_flag("version") {
         println!(
@@ -34,14 +118,136 @@ async fn main() {
         return;
     }
 
+    if let Some(commands) = batch {
+        // Commands share the client, and with it the netlink sockets; the first
+        // failing command ends the batch.
+        for (i, (line, line_matches)) in commands.iter().enumerate() {
+            let json = app::json_output(line_matches);
+            JSON_ERRORS.store(json, Ordering::Relaxed);
+            if !json {
+                if i > 0 {
+                    println!();
+                }
+                println!("[{}]", line);
+            }
+            execute(&client, mesh_if, &algo_name, line_matches).await;
+        }
+        return;
+    }
+
+    if let Some(&interval) = matches.get_one::<Duration>("watch") {
+        if !app::is_mesh_query(&matches) {
+            eprintln!("Error - --watch only applies to commands displaying mesh state");
+            std::process::exit(1);
+        }
+        // Tables are redrawn in place; JSON documents are appended, one per line, so
+        // the output can be piped into a log shipper.
+        let redraw = !app::json_output(&matches) && !matches.contains_id("log_file");
+        let mut ticks = time::interval(interval);
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticks.tick().await;
+            if redraw {
+                print!("\x1b[2J\x1b[H");
+            }
+            execute(&client, mesh_if, &algo_name, &matches).await;
+            rotate_log();
+        }
+    }
+
+    execute(&client, mesh_if, &algo_name, &matches).await;
+}
+
+/// Runs the parsed command line, on every mesh interface with `--all-meshes`.
+///
+/// `default_mesh` is used unless the command line names a mesh interface.
+async fn execute(client: &RobinClient, default_mesh: &str, algo_name: &str, matches: &ArgMatches) {
+    let mesh_if = matches
+        .get_one::<String>("meshif")
+        .map(String::as_str)
+        .unwrap_or(default_mesh);
+
+    if matches.get_flag("vendor") && !cfg!(feature = "oui") {
+        eprintln!("Error - robctl was built without the 'oui' feature, --vendor is unavailable");
+        std::process::exit(1);
+    }
+
+    // Refuse what the kernel lacks instead of failing with a raw errno; if the
+    // kernel cannot be probed, the command reports why itself.
+    if let Some(cmd) = app::required_command(matches)
+        && let Ok(capabilities) = client.capabilities().await
+    {
+        exit_on_error(capabilities.require(cmd));
+    }
+
+    if matches.get_flag("all_meshes") {
+        // A mesh interface from $ROBIN_MESHIF is only a default, so it does not
+        // conflict with --all-meshes.
+        if matches.value_source("meshif") == Some(ValueSource::CommandLine) {
+            eprintln!("Error - --all-meshes cannot be combined with --meshif");
+            std::process::exit(1);
+        }
+        if !app::is_mesh_query(matches) {
+            eprintln!("Error - --all-meshes only applies to commands displaying mesh state");
+            std::process::exit(1);
+        }
+
+        let meshes = exit_on_error(client.list_meshes().await);
+        for (i, mesh_if) in meshes.iter().enumerate() {
+            // JSON documents name their mesh interface themselves.
+            if !app::json_output(matches) {
+                if i > 0 {
+                    println!();
+                }
+                println!("[{}]", mesh_if);
+            }
+            run(client, mesh_if, algo_name, matches).await;
+        }
+        return;
+    }
+
+    run(client, mesh_if, algo_name, matches).await;
+}
+
+/// Returns the routing algorithm `mesh_if` runs, or `default_algo` if it cannot be
+/// read, e.g. because `mesh_if` does not exist and the command reports that itself.
+async fn mesh_algo(client: &RobinClient, mesh_if: &str, default_algo: &str) -> String {
+    client
+        .routing_algo(mesh_if)
+        .await
+        .unwrap_or_else(|_| default_algo.to_string())
+}
+
+/// Runs the parsed subcommand on `mesh_if`; `algo_name` is the default routing algorithm.
+async fn run(client: &RobinClient, mesh_if: &str, algo_name: &str, matches: &ArgMatches) {
+    let vendor = matches.get_flag("vendor");
+    let json = app::json_output(matches);
     match matches.subcommand() {
-        Some(("neighbors", _)) => {
-            let entries = exit_on_error(client.neighbors(mesh_if).await);
-            neighbors::print_neighbors(&entries, algo_name.as_str());
+        Some(("neighbors", sub_m)) => {
+            let mut entries = match sub_m.get_one::<IfaceRef>("interface") {
+                Some(hard_if) => exit_on_error(client.neighbors_on(mesh_if, hard_if).await),
+                None => exit_on_error(client.neighbors(mesh_if).await),
+            };
+            utils::retain_max_age(&mut entries, sub_m.get_one("max_age"));
+            let stations = exit_on_error(client.neighbor_stations(&entries).await);
+            if json {
+                let data = entries.iter().zip(&stations);
+                let data = data.map(|(n, sta)| output::NeighborOutput::new(n, *sta));
+                utils::print_json(&Document::new(mesh_if, data.collect::<Vec<_>>()));
+            } else {
+                let algo_name = mesh_algo(client, mesh_if, algo_name).await;
+                neighbors::print_neighbors(&entries, &algo_name, &stations, vendor);
+            }
         }
         Some(("gateways", _)) => {
             let entries = exit_on_error(client.gateways(mesh_if).await);
-            gateways::print_gwl(&entries, algo_name.as_str());
+            if json {
+                let data = entries.iter().map(output::GatewayOutput::from).collect();
+                utils::print_json::<Vec<_>>(&Document::new(mesh_if, data));
+            } else {
+                let algo_name = mesh_algo(client, mesh_if, algo_name).await;
+                gateways::print_gwl(&entries, &algo_name);
+            }
         }
         Some(("gw_mode", sub_m)) => {
             let mode_str = sub_m.get_one::<String>("mode").map(String::as_str);
@@ -49,7 +255,12 @@ async fn main() {
 
             if mode_str.is_none() {
                 let entries = exit_on_error(client.get_gw_mode(mesh_if).await);
-                gw_mode::print_gw(&entries);
+                if json {
+                    let data = output::GwModeOutput::from(&entries);
+                    utils::print_json(&Document::new(mesh_if, data));
+                } else {
+                    gw_mode::print_gw(&entries);
+                }
                 return;
             }
 
@@ -64,10 +275,11 @@ async fn main() {
             };
 
             let (down, up, sel_class) = if let Some(param) = param_str {
-                match gw_mode::parse_gw_param(mode, param) {
+                let algo_name = mesh_algo(client, mesh_if, algo_name).await;
+                match batman_robin::parse_gw_param(mode, param, &algo_name) {
                     Ok(values) => values,
                     Err(e) => {
-                        eprintln!("{}", e);
+                        report(&e);
                         std::process::exit(1);
                     }
                 }
@@ -77,17 +289,110 @@ async fn main() {
 
             exit_on_error(client.set_gw_mode(mode, down, up, sel_class, mesh_if).await);
         }
-        Some(("originators", _)) => {
-            let entries = exit_on_error(client.originators(mesh_if).await);
-            originators::print_originators(&entries, algo_name.as_str());
+        Some(("originators", sub_m)) => {
+            let mut entries = match sub_m.get_one::<IfaceRef>("interface") {
+                Some(hard_if) => exit_on_error(client.originators_on(mesh_if, hard_if).await),
+                None => exit_on_error(client.originators(mesh_if).await),
+            };
+            utils::retain_max_age(&mut entries, sub_m.get_one("max_age"));
+            if json {
+                let data = entries.iter().map(output::OriginatorOutput::from).collect();
+                utils::print_json::<Vec<_>>(&Document::new(mesh_if, data));
+            } else {
+                let algo_name = mesh_algo(client, mesh_if, algo_name).await;
+                originators::print_originators(&entries, &algo_name, vendor);
+            }
+        }
+        Some(("translocal", sub_m)) => {
+            let mut entries = exit_on_error(client.translocal(mesh_if).await);
+            utils::retain_max_age(&mut entries, sub_m.get_one("max_age"));
+            utils::retain_flags(&mut entries, sub_m);
+            if sub_m.get_flag("summary") {
+                let stats = TtTableStats::local(&entries);
+                if json {
+                    let data = output::TtSummaryOutput::from(&stats);
+                    utils::print_json(&Document::new(mesh_if, data));
+                } else {
+                    utils::print_tt_summary(&stats, vendor);
+                }
+            } else if json {
+                let data = entries.iter().map(output::TranslocalOutput::from).collect();
+                utils::print_json::<Vec<_>>(&Document::new(mesh_if, data));
+            } else {
+                translocal::print_translocal(&entries, vendor);
+            }
+        }
+        Some(("transglobal", sub_m)) => {
+            let mut entries = exit_on_error(client.transglobal(mesh_if).await);
+            utils::retain_flags(&mut entries, sub_m);
+            if sub_m.get_flag("summary") {
+                let stats = TtTableStats::global(&entries);
+                if json {
+                    let data = output::TtSummaryOutput::from(&stats);
+                    utils::print_json(&Document::new(mesh_if, data));
+                } else {
+                    utils::print_tt_summary(&stats, vendor);
+                }
+            } else if json {
+                let data = entries
+                    .iter()
+                    .map(output::TransglobalOutput::from)
+                    .collect();
+                utils::print_json::<Vec<_>>(&Document::new(mesh_if, data));
+            } else {
+                transglobal::print_transglobal(&entries, vendor);
+            }
+        }
+        Some(("dat_cache", sub_m)) => {
+            let mut entries = exit_on_error(client.dat_cache(mesh_if).await);
+            utils::retain_max_age(&mut entries, sub_m.get_one("max_age"));
+            if json {
+                let data = entries.iter().map(output::DatCacheOutput::from).collect();
+                utils::print_json::<Vec<_>>(&Document::new(mesh_if, data));
+            } else {
+                dat_cache::print_dat_cache(&entries, vendor);
+            }
+        }
+        Some(("translate", sub_m)) => {
+            let destination = sub_m
+                .get_one::<translate::Destination>("destination")
+                .expect("destination is a required argument");
+            let mac = match destination {
+                translate::Destination::Mac(mac) => *mac,
+                translate::Destination::Ipv4(ip) => {
+                    exit_on_error(client.resolve_ipv4(mesh_if, *ip).await)
+                }
+            };
+            let originator = exit_on_error(client.translate_mac(mesh_if, mac).await);
+            if json {
+                let data = output::TranslateOutput::new(destination, originator);
+                utils::print_json(&Document::new(mesh_if, data));
+            } else {
+                println!("{}", originator);
+            }
         }
-        Some(("translocal", _)) => {
-            let entries = exit_on_error(client.translocal(mesh_if).await);
-            translocal::print_translocal(&entries);
+        Some(("path", sub_m)) => {
+            let mac = *sub_m
+                .get_one::<MacAddr6>("client")
+                .expect("client is a required argument");
+            let path = exit_on_error(client.path_to_client(mesh_if, mac).await);
+            if json {
+                utils::print_json(&Document::new(mesh_if, output::PathOutput::from(&path)));
+            } else {
+                path::print_path(&path, vendor);
+            }
         }
-        Some(("transglobal", _)) => {
-            let entries = exit_on_error(client.transglobal(mesh_if).await);
-            transglobal::print_transglobal(&entries);
+        Some(("hardif", sub_m)) => {
+            let hard_if = sub_m
+                .get_one::<IfaceRef>("iface")
+                .expect("iface is a required argument");
+            let info = exit_on_error(client.hardif_info(mesh_if, hard_if).await);
+            if json {
+                let data = output::HardifOutput::from(&info);
+                utils::print_json(&Document::new(mesh_if, data));
+            } else {
+                hardif::print_hardif(&info, vendor);
+            }
         }
         Some(("interface", sub_m)) => {
             let manual = sub_m.get_flag("manual");
@@ -99,7 +404,17 @@ async fn main() {
 
             if action.is_none() {
                 let entries = exit_on_error(client.get_interface(mesh_if).await);
-                interface::print_interfaces(&entries);
+                let mut wireless = Vec::with_capacity(entries.len());
+                for iface in &entries {
+                    wireless.push(exit_on_error(client.is_wireless(&iface.ifname).await));
+                }
+                if json {
+                    let data = entries.iter().zip(&wireless);
+                    let data = data.map(|(iface, w)| output::InterfaceOutput::new(iface, *w));
+                    utils::print_json(&Document::new(mesh_if, data.collect::<Vec<_>>()));
+                } else {
+                    interface::print_interfaces(&entries, &wireless, sub_m.get_flag("stats"));
+                }
                 return;
             }
 
@@ -111,7 +426,13 @@ async fn main() {
                         return;
                     }
                     exit_on_error(client.destroy_interface(mesh_if).await);
-                    return;
+                }
+                "rename" => {
+                    let [new_name] = params.as_slice() else {
+                        eprintln!("Error - expected exactly one new name after 'rename'");
+                        return;
+                    };
+                    exit_on_error(client.rename_interface(mesh_if, new_name).await);
                 }
                 "create" | "c" => {
                     let routing_algo = match params.as_slice() {
@@ -125,7 +446,6 @@ async fn main() {
                     };
 
                     exit_on_error(client.create_interface(mesh_if, routing_algo).await);
-                    return;
                 }
                 "add" | "a" | "del" | "d" => {
                     if params.is_empty() {
@@ -141,6 +461,7 @@ async fn main() {
                     let pre_count = exit_on_error(client.count_interfaces(mesh_if).await);
 
                     for iface in &params {
+                        let Ok(iface) = iface.parse::<IfaceRef>();
                         match action {
                             "add" | "a" => {
                                 exit_on_error(client.set_interface(iface, Some(mesh_if)).await);
@@ -152,6 +473,13 @@ async fn main() {
                         }
                     }
 
+                    // Advisory only: the interfaces have been added either way.
+                    if action.starts_with("a")
+                        && let Ok(warnings) = client.check(mesh_if).await
+                    {
+                        check::print_warnings(&warnings);
+                    }
+
                     if !manual && (action == "del" || action == "d") {
                         let cnt = exit_on_error(client.count_interfaces(mesh_if).await);
 
@@ -172,7 +500,7 @@ async fn main() {
                 exit_on_error(client.set_aggregation(mesh_if, *v == 1).await);
             } else {
                 let enabled = exit_on_error(client.get_aggregation(mesh_if).await);
-                println!("{}", if enabled { "enabled" } else { "disabled" });
+                print_setting(mesh_if, enabled, json);
             }
         }
         Some(("ap_isolation", sub_m)) => {
@@ -181,7 +509,7 @@ async fn main() {
                 exit_on_error(client.set_ap_isolation(mesh_if, *v == 1).await);
             } else {
                 let enabled = exit_on_error(client.get_ap_isolation(mesh_if).await);
-                println!("{}", if enabled { "enabled" } else { "disabled" });
+                print_setting(mesh_if, enabled, json);
             }
         }
         Some(("bridge_loop_avoidance", sub_m)) => {
@@ -190,7 +518,7 @@ async fn main() {
                 exit_on_error(client.set_bridge_loop_avoidance(mesh_if, *v == 1).await);
             } else {
                 let enabled = exit_on_error(client.get_bridge_loop_avoidance(mesh_if).await);
-                println!("{}", if enabled { "enabled" } else { "disabled" });
+                print_setting(mesh_if, enabled, json);
             }
         }
         Some(("routing_algo", sub_m)) => {
@@ -200,8 +528,24 @@ async fn main() {
                 return;
             }
 
-            // Active routing algos
             let active = exit_on_error(client.get_active_routing_algos().await);
+            let default_algo = exit_on_error(client.get_default_routing_algo().await);
+            let available = exit_on_error(client.get_available_routing_algos().await);
+
+            if json {
+                let active = active.into_iter();
+                let data = output::RoutingAlgoOutput {
+                    active: active
+                        .map(|(mesh_if, algo)| output::ActiveRoutingAlgo { mesh_if, algo })
+                        .collect(),
+                    default: default_algo,
+                    available,
+                };
+                utils::print_json(&Document::new(mesh_if, data));
+                return;
+            }
+
+            // Active routing algos
             if !active.is_empty() {
                 println!("Active routing protocol configuration:");
                 for (iface, algo) in &active {
@@ -211,17 +555,421 @@ async fn main() {
             }
 
             // Default routing algo
-            let default_algo = exit_on_error(client.get_default_routing_algo().await);
             println!("Selected routing algorithm (used when next batX interface is created):");
             println!(" => {}\n", default_algo);
 
             // Available routing algos
-            let available = exit_on_error(client.get_available_routing_algos().await);
             println!("Available routing algorithms:");
             for algo in available {
                 println!(" * {}", algo);
             }
         }
+        Some(("clients", _)) => {
+            let summary = exit_on_error(client.client_summary(mesh_if).await);
+            if json {
+                let data = output::ClientSummaryOutput::from(&summary);
+                utils::print_json(&Document::new(mesh_if, data));
+            } else {
+                clients::print_client_summary(&summary, vendor);
+            }
+        }
+        Some(("vid", sub_m)) => {
+            let vid = *sub_m
+                .get_one::<u16>("vid")
+                .expect("vid is a required argument");
+            let vlan = exit_on_error(client.clients_for_vid(mesh_if, vid).await);
+            if json {
+                let data = output::VlanClientsOutput::from(&vlan);
+                utils::print_json(&Document::new(mesh_if, data));
+            } else {
+                vid::print_vlan_clients(&vlan, vendor);
+            }
+        }
+        Some(("check", sub_m)) if sub_m.subcommand_matches("duplicates").is_some() => {
+            let duplicates = exit_on_error(client.find_duplicate_clients(mesh_if).await);
+            if json {
+                let data = duplicates
+                    .iter()
+                    .map(output::DuplicateClientOutput::from)
+                    .collect();
+                utils::print_json::<Vec<_>>(&Document::new(mesh_if, data));
+            } else if duplicates.is_empty() {
+                println!("No duplicate clients found on {}", mesh_if);
+            } else {
+                check::print_duplicates(&duplicates, vendor);
+            }
+        }
+        Some(("check", _)) => {
+            let warnings = exit_on_error(client.check(mesh_if).await);
+            if json {
+                let data = warnings.iter().map(output::WarningOutput::from).collect();
+                utils::print_json::<Vec<_>>(&Document::new(mesh_if, data));
+            } else if warnings.is_empty() {
+                println!("No problems found on {}", mesh_if);
+            } else {
+                check::print_warnings(&warnings);
+            }
+        }
+        Some(("apply", sub_m)) => {
+            let file = sub_m
+                .get_one::<String>("file")
+                .expect("file is a required argument");
+            let config = exit_on_error(MeshConfig::from_file(file));
+            let changes = exit_on_error(client.apply(&config).await);
+            apply::print_changes(&changes);
+        }
+        Some(("config", sub_m)) => {
+            if let Some(("export", export_m)) = sub_m.subcommand() {
+                let config = exit_on_error(client.export_config(mesh_if).await);
+                match export_m.get_one::<String>("file") {
+                    Some(file) => exit_on_error(config.to_file(file)),
+                    None => print!("{}", exit_on_error(config.to_toml())),
+                }
+            }
+        }
+        Some(("backup", sub_m)) => {
+            let file = sub_m
+                .get_one::<String>("file")
+                .expect("file is a required argument");
+            let config = exit_on_error(client.export_config(mesh_if).await);
+            exit_on_error(config.to_file(file));
+        }
+        Some(("restore", sub_m)) => {
+            let file = sub_m
+                .get_one::<String>("file")
+                .expect("file is a required argument");
+            let config = exit_on_error(MeshConfig::from_file(file));
+            let plan = exit_on_error(client.plan(&config).await);
+            apply::print_plan(&plan);
+            if !plan.is_empty() && !sub_m.get_flag("dry_run") {
+                exit_on_error(client.apply(&config).await);
+            }
+        }
+        Some(("record", sub_m)) => {
+            let dir = sub_m
+                .get_one::<String>("out")
+                .expect("out is a required argument");
+            let interval = *sub_m
+                .get_one("interval")
+                .expect("interval has a default value");
+            let mut recorder = Recorder::new(dir)
+                .max_files(
+                    *sub_m
+                        .get_one("max_files")
+                        .expect("max_files has a default value"),
+                )
+                .max_file_bytes(
+                    sub_m
+                        .get_one::<u64>("max_size")
+                        .expect("max_size has a default value")
+                        * 1024
+                        * 1024,
+                );
+            exit_on_error(record::record(client, mesh_if, interval, &mut recorder).await);
+        }
+        Some(("monitor", sub_m)) => match sub_m.subcommand() {
+            Some(("liveness", sub_m)) => {
+                monitor_liveness(client, mesh_if, sub_m, json, vendor).await
+            }
+            Some(("gateway", sub_m)) => monitor_gateway(client, mesh_if, sub_m, json, vendor).await,
+            _ => unreachable!("Subcommand required"),
+        },
+        Some(("roam-history", sub_m)) => roam_history(client, mesh_if, sub_m, json, vendor).await,
+        Some(("raw", sub_m)) => raw(client, mesh_if, sub_m).await,
+        Some(("attrs", sub_m)) => match sub_m.get_one::<Command>("command") {
+            Some(&cmd) => attrs(client, mesh_if, cmd).await,
+            None => attrs::print_attributes(),
+        },
+        Some(("schema", sub_m)) => {
+            schema::print_schema(sub_m.get_one::<String>("command").map(String::as_str));
+        }
         _ => unreachable!("Subcommand required"),
     }
 }
+
+/// The `--webhook` endpoints of a monitor subcommand.
+///
+/// Documents are POSTed by a dedicated thread, in order, so a slow or unreachable
+/// endpoint delays neither the hooks nor the polling of the monitor.
+struct Webhooks {
+    #[cfg(feature = "webhook")]
+    sender: Option<mpsc::Sender<Document<serde_json::Value>>>,
+}
+
+impl Webhooks {
+    /// Reads `--webhook` and `--webhook-auth`, exiting if robctl was built without
+    /// the `webhook` feature but webhooks are given.
+    fn new(sub_m: &ArgMatches) -> Self {
+        let urls: Vec<&String> = sub_m.get_many("webhook").into_iter().flatten().collect();
+        #[cfg(not(feature = "webhook"))]
+        if !urls.is_empty() {
+            eprintln!(
+                "Error - robctl was built without the 'webhook' feature, --webhook is unavailable"
+            );
+            std::process::exit(1);
+        }
+
+        #[cfg(feature = "webhook")]
+        {
+            let auth = sub_m.get_one::<String>("webhook_auth").map(|auth| {
+                auth.split_once(':').unwrap_or_else(|| {
+                    eprintln!("Error - --webhook-auth takes USER:PASSWORD");
+                    std::process::exit(1);
+                })
+            });
+            let webhooks: Vec<Webhook> = urls
+                .into_iter()
+                .map(|url| match auth {
+                    Some((user, password)) => Webhook::new(url).basic_auth(user, password),
+                    None => Webhook::new(url),
+                })
+                .collect();
+            if webhooks.is_empty() {
+                return Self { sender: None };
+            }
+
+            let (sender, receiver) = mpsc::channel::<Document<serde_json::Value>>();
+            std::thread::spawn(move || {
+                for doc in receiver {
+                    for webhook in &webhooks {
+                        if let Err(e) = webhook.post(&doc) {
+                            report(&e);
+                        }
+                    }
+                }
+            });
+            Self {
+                sender: Some(sender),
+            }
+        }
+        #[cfg(not(feature = "webhook"))]
+        Self {}
+    }
+
+    /// Queues `doc` for every webhook and returns at once. Failures are reported
+    /// by the sender thread without ending the monitor.
+    fn post<T: serde::Serialize>(&self, doc: &Document<T>) {
+        #[cfg(feature = "webhook")]
+        if let Some(sender) = &self.sender {
+            let data = match serde_json::to_value(&doc.data) {
+                Ok(data) => data,
+                Err(e) => {
+                    report(&RobinError::Parse(format!(
+                        "Failed to serialize document: {}",
+                        e
+                    )));
+                    return;
+                }
+            };
+            // The sender thread only ends with the process.
+            let _ = sender.send(Document {
+                schema_version: doc.schema_version,
+                mesh_if: doc.mesh_if.clone(),
+                data,
+            });
+        }
+        #[cfg(not(feature = "webhook"))]
+        let _ = doc;
+    }
+}
+
+/// Runs `roam-history`: prints each move of the client between originators.
+async fn roam_history(
+    client: &RobinClient,
+    mesh_if: &str,
+    sub_m: &ArgMatches,
+    json: bool,
+    vendor: bool,
+) {
+    let mac = *sub_m
+        .get_one::<MacAddr6>("client")
+        .expect("client is a required argument");
+    let interval = *sub_m
+        .get_one("interval")
+        .expect("interval has a default value");
+
+    // Only the moves of one client are kept.
+    let mut history = RoamHistory::new(0);
+    let on_move = |event: RoamEvent| {
+        if event.client != mac {
+            return;
+        }
+        match json {
+            true => utils::print_json(&Document::new(mesh_if, output::RoamOutput::from(&event))),
+            false => roam_history::print_roam_event(&event, vendor),
+        }
+        rotate_log();
+    };
+    exit_on_error(roaming::monitor(client, mesh_if, interval, &mut history, on_move).await);
+}
+
+/// Runs `raw`: sends the request and prints the attributes of the replies.
+async fn raw(client: &RobinClient, mesh_if: &str, sub_m: &ArgMatches) {
+    let cmd = *sub_m
+        .get_one::<Command>("cmd")
+        .expect("cmd is a required argument");
+    let attrs: Vec<&(Attribute, AttrValueForSend)> =
+        sub_m.get_many("attr").into_iter().flatten().collect();
+
+    let mut builder = GenlAttrBuilder::new();
+    let selects_mesh = attrs.iter().any(|(attr, _)| {
+        matches!(
+            attr,
+            Attribute::BatadvAttrMeshIfindex | Attribute::BatadvAttrMeshIfname
+        )
+    });
+    if !selects_mesh {
+        let ifindex = exit_on_error(client.if_nametoindex(mesh_if).await);
+        exit_on_error(builder.add(Attribute::BatadvAttrMeshIfindex, ifindex));
+    }
+    for (attr, value) in attrs {
+        exit_on_error(builder.add(*attr, value.clone()));
+    }
+
+    if sub_m.get_flag("dump") {
+        let objects = exit_on_error(client.raw_dump(cmd, builder).await);
+        raw::print_objects(&objects);
+    } else if cmd.name().starts_with("BATADV_CMD_SET_") {
+        exit_on_error(client.raw_set(cmd, builder).await);
+    } else {
+        let object = exit_on_error(client.raw_get(cmd, builder).await);
+        raw::print_objects(&[object]);
+    }
+}
+
+/// Runs `attrs COMMAND`: reads the reply to `cmd` on `mesh_if` and lists the
+/// attributes present in it.
+async fn attrs(client: &RobinClient, mesh_if: &str, cmd: Command) {
+    if cmd.reply_attributes().is_empty() {
+        eprintln!(
+            "Error - {} does not return attributes",
+            cmd.name().trim_start_matches("BATADV_CMD_")
+        );
+        std::process::exit(1);
+    }
+
+    let mut builder = GenlAttrBuilder::new();
+    let ifindex = exit_on_error(client.if_nametoindex(mesh_if).await);
+    exit_on_error(builder.add(Attribute::BatadvAttrMeshIfindex, ifindex));
+
+    let objects = match cmd {
+        Command::BatadvCmdGetMeshInfo => vec![exit_on_error(client.raw_get(cmd, builder).await)],
+        _ => exit_on_error(client.raw_dump(cmd, builder).await),
+    };
+    attrs::print_reply_attributes(&objects);
+}
+
+/// Runs `monitor liveness`: prints each originator going down or coming back up.
+async fn monitor_liveness(
+    client: &RobinClient,
+    mesh_if: &str,
+    sub_m: &ArgMatches,
+    json: bool,
+    vendor: bool,
+) {
+    let threshold = *sub_m
+        .get_one("threshold")
+        .expect("threshold has a default value");
+    let interval = *sub_m
+        .get_one("interval")
+        .expect("interval has a default value");
+
+    let webhooks = Webhooks::new(sub_m);
+
+    let mut liveness = LivenessMonitor::new(threshold);
+    let on_event = |event: LivenessEvent| {
+        let doc = Document::new(mesh_if, output::LivenessOutput::from(&event));
+        match json {
+            true => utils::print_json(&doc),
+            false => monitor::print_liveness_event(&event, vendor),
+        }
+        webhooks.post(&doc);
+        rotate_log();
+    };
+    exit_on_error(liveness::monitor(client, mesh_if, interval, &mut liveness, on_event).await);
+}
+
+/// Runs `monitor gateway`: prints each change of the selected gateway and runs the
+/// hooks on it. A failing hook is reported without ending the monitor.
+async fn monitor_gateway(
+    client: &RobinClient,
+    mesh_if: &str,
+    sub_m: &ArgMatches,
+    json: bool,
+    vendor: bool,
+) {
+    let interval = *sub_m
+        .get_one("interval")
+        .expect("interval has a default value");
+    let hooks: Vec<&String> = sub_m.get_many("hook").into_iter().flatten().collect();
+    let webhooks = Webhooks::new(sub_m);
+
+    let mut failover = FailoverMonitor::new();
+    let on_change = async |change: GatewayChange| {
+        let doc = Document::new(mesh_if, output::GatewayChangeOutput::from(&change));
+        match json {
+            true => utils::print_json(&doc),
+            false => monitor::print_gateway_change(&change, vendor),
+        }
+        for hook in &hooks {
+            if let Err(e) = failover::run_hook(hook, &change, mesh_if).await {
+                report(&e);
+            }
+        }
+        webhooks.post(&doc);
+        rotate_log();
+    };
+    exit_on_error(failover::monitor(client, mesh_if, interval, &mut failover, on_change).await);
+}
+
+/// Opens `--log-file` and redirects standard output into it, exiting on failure.
+fn open_log(path: &str, matches: &ArgMatches) {
+    let max_size: u64 = *matches
+        .get_one("log_max_size")
+        .expect("log_max_size has a default value");
+    let keep = *matches
+        .get_one("log_keep")
+        .expect("log_keep has a default value");
+
+    let mut log = exit_on_error(LogFile::open(path))
+        .max_bytes(max_size * 1024)
+        .keep(keep);
+    if let Some(&age) = matches.get_one::<Duration>("log_max_age") {
+        log = log.max_age(age);
+    }
+    exit_on_error(log.capture_stdout());
+    *LOG.lock().unwrap() = Some(log);
+}
+
+/// Rotates the `--log-file` if it is due. A failed rotation is reported, and
+/// output keeps going to the current file.
+fn rotate_log() {
+    if let Some(log) = LOG.lock().unwrap().as_mut()
+        && let Err(e) = log.rotate_if_due()
+    {
+        report(&e);
+    }
+}
+
+/// Returns `true` if the command may create a mesh interface: `interface create`,
+/// or `interface add` without `--manual`.
+fn creates_mesh(matches: &ArgMatches) -> bool {
+    let Some(("interface", sub_m)) = matches.subcommand() else {
+        return false;
+    };
+    match sub_m.get_one::<String>("action").map(String::as_str) {
+        Some("create" | "c") => true,
+        Some("add" | "a") => !sub_m.get_flag("manual"),
+        _ => false,
+    }
+}
+
+/// Prints whether an on/off setting of `mesh_if` is enabled.
+fn print_setting(mesh_if: &str, enabled: bool, json: bool) {
+    if json {
+        let data = output::SettingOutput { enabled };
+        utils::print_json(&Document::new(mesh_if, data));
+    } else {
+        println!("{}", if enabled { "enabled" } else { "disabled" });
+    }
+}
@@ -0,0 +1,55 @@
+use crate::error::RobinError;
+
+use std::fs;
+
+/// Bit of `CAP_NET_ADMIN` in the capability sets (see `capability.h`).
+const CAP_NET_ADMIN: u32 = 12;
+
+/// Checks whether the current process may change the BATMAN-adv configuration.
+///
+/// Setting mesh parameters and adding or removing interfaces requires `CAP_NET_ADMIN`.
+/// The check succeeds if the effective user id is 0 or if `CAP_NET_ADMIN` is part of the
+/// effective capability set, as reported by `/proc/self/status`.
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the process is privileged, `Err(RobinError::PermissionDenied)`
+/// otherwise, or another `RobinError` if the process status could not be read.
+///
+/// # Example
+///
+/// ```no_run
+/// use batman_robin::{RobinError, can_administer};
+///
+/// match can_administer() {
+///     Ok(()) => println!("ready to configure the mesh"),
+///     Err(RobinError::PermissionDenied) => eprintln!("run as root or grant CAP_NET_ADMIN"),
+///     Err(e) => eprintln!("{}", e),
+/// }
+/// ```
+pub fn can_administer() -> Result<(), RobinError> {
+    let status = fs::read_to_string("/proc/self/status").map_err(|e| RobinError::Io {
+        context: "Failed to read /proc/self/status".to_string(),
+        source: e,
+    })?;
+
+    let field = |name: &str| {
+        status
+            .lines()
+            .find_map(|line| line.strip_prefix(name))
+            .map(str::trim)
+    };
+
+    let euid = field("Uid:")
+        .and_then(|uids| uids.split_whitespace().nth(1))
+        .and_then(|euid| euid.parse::<u32>().ok());
+    if euid == Some(0) {
+        return Ok(());
+    }
+
+    let cap_eff = field("CapEff:").and_then(|caps| u64::from_str_radix(caps, 16).ok());
+    match cap_eff {
+        Some(caps) if caps & (1 << CAP_NET_ADMIN) != 0 => Ok(()),
+        _ => Err(RobinError::PermissionDenied),
+    }
+}
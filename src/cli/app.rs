@@ -1,15 +1,30 @@
 use super::aggregation::cmd_aggregation;
 use super::ap_isolation::cmd_ap_isolation;
+use super::apply::{cmd_apply, cmd_backup, cmd_config, cmd_restore};
+use super::attrs::cmd_attrs;
 use super::bridge_loop_avoidance::cmd_bridge_loop_avoidance;
+use super::check::cmd_check;
+use super::clients::cmd_clients;
+use super::dat_cache::cmd_dat_cache;
 use super::gateways::cmd_gateways;
 use super::gw_mode::cmd_gw_mode;
+use super::hardif::cmd_hardif;
 use super::interface::cmd_interfaces;
+use super::monitor::cmd_monitor;
 use super::neighbors::cmd_neighbors;
 use super::originators::cmd_originators;
+use super::path::cmd_path;
+use super::raw::cmd_raw;
+use super::record::cmd_record;
+use super::roam_history::cmd_roam_history;
 use super::routing_algo::cmd_routing_algo;
+use super::schema::cmd_schema;
 use super::transglobal::cmd_transglobal;
+use super::translate::cmd_translate;
 use super::translocal::cmd_translocal;
-use clap::{Arg, Command};
+use super::utils::parse_duration;
+use super::vid::cmd_vid;
+use clap::{Arg, ArgMatches, Command};
 
 /// Builds the command-line interface (CLI) for `robctl`.
 ///
@@ -17,8 +32,24 @@ use clap::{Arg, Command};
 /// and all subcommands.
 ///
 /// # Global Options
-/// - `--meshif`, `-m` : Specify the batman-adv mesh interface to operate on (default: `bat0`).
+/// - `--meshif`, `-m` : Specify the batman-adv mesh interface to operate on (default:
+///   `$ROBIN_MESHIF`, else `bat0`).
 /// - `--version`, `-v` : Print the `robctl` version and the batman-adv kernel module version (if loaded).
+/// - `--vendor` : Append the vendor of MAC addresses in tables (needs the `oui` feature).
+/// - `--json` : Print the output of display commands as JSON documents (see
+///   [`output`](crate::output)).
+/// - `--output FORMAT` : `table` or `json` (default: `$ROBIN_OUTPUT`, else `table`); `--json`
+///   takes precedence (see [`json_output`]).
+/// - `--bat-hosts FILE` : Show the host names of a batctl `bat-hosts` file instead of MAC
+///   addresses (default: `$ROBIN_BAT_HOSTS`, see [`bat_hosts`](super::bat_hosts)).
+/// - `--all-meshes` : Run a display command on every mesh interface (see [`is_mesh_query`]).
+/// - `--batch FILE` : Run one command per line of `FILE` (`-` for stdin) over a single
+///   connection (see [`parse_batch`]).
+/// - `--watch DURATION` : Rerun a display command every `DURATION`, redrawing the table,
+///   or printing one JSON document per refresh with `--json` (see [`is_mesh_query`]).
+/// - `--log-file PATH` : Write the output to `PATH` instead of stdout, rotated at
+///   `--log-max-size KiB` (default 1024) or after `--log-max-age DURATION`, keeping
+///   `--log-keep N` rotated files (default 3, see [`LogFile`](crate::logfile::LogFile)).
 ///
 /// # Subcommands
 /// - `neighbors` (`n`) : Display the neighbor table.
@@ -27,11 +58,28 @@ use clap::{Arg, Command};
 /// - `originators` (`o`) : Display the originator table.
 /// - `translocal` (`tl`) : Display local translation table.
 /// - `transglobal` (`tg`) : Display global translation table.
+/// - `dat_cache` (`dc`) : Display the local D.A.T. cache.
+/// - `translate` (`t`) : Translate a MAC or IPv4 address to the originator responsible for it.
+/// - `path` : Show the originator, next hop and outgoing interface a client is reached through.
 /// - `interface` (`if`) : Display or modify batman-adv interface settings.
+/// - `hardif` (`hi`) : Display the settings of a hard interface.
 /// - `ap_isolation` (`ap`) : Display or modify AP isolation setting.
 /// - `aggregation` (`ag`) : Display or modify aggregation setting.
 /// - `bridge_loop_avoidance` (`bl`) : Display or modify bridge loop avoidance setting.
 /// - `routing_algo` (`ra`) : Display or modify the routing algorithm.
+/// - `check` : Check the mesh interface for likely misconfigurations.
+/// - `clients` : Count the clients of the mesh, per originator and per VLAN.
+/// - `vid <vid> clients` : List the clients of a VLAN, grouped by originator.
+/// - `apply` : Apply a declarative mesh configuration file.
+/// - `config export` : Export the running configuration of the mesh interface.
+/// - `backup` : Back up the mesh settings to a file.
+/// - `restore` : Restore the mesh settings from a file, showing what changes.
+/// - `record` : Record snapshots of the mesh tables to rotating files.
+/// - `monitor liveness` : Report originators going silent and coming back.
+/// - `roam-history` : Show when a client moves between originators.
+/// - `raw` : Send a raw BATMAN-adv request and print the reply.
+/// - `attrs` : List the BATMAN-adv attributes, or the ones a command returns.
+/// - `schema` : Print the JSON Schema of the `--json` output.
 ///
 /// # Returns
 /// A `clap::Command` ready to parse command-line arguments.
@@ -53,8 +101,86 @@ pub fn build_cli() -> Command {
                 .long("meshif")
                 .short('m')
                 .value_name("IFACE")
+                .env("ROBIN_MESHIF")
                 .help("Batman-adv mesh interface to operate on (default: bat0)"),
         )
+        .arg(
+            Arg::new("vendor")
+                .long("vendor")
+                .help("Append the vendor to MAC addresses in tables")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .help("Print the output of display commands as JSON")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("output")
+                .long("output")
+                .value_name("FORMAT")
+                .value_parser(["table", "json"])
+                .env("ROBIN_OUTPUT")
+                .help("Output format of display commands"),
+        )
+        .arg(
+            Arg::new("bat_hosts")
+                .long("bat-hosts")
+                .value_name("FILE")
+                .env("ROBIN_BAT_HOSTS")
+                .help("Show the host names of a bat-hosts file instead of MAC addresses"),
+        )
+        .arg(
+            Arg::new("all_meshes")
+                .long("all-meshes")
+                .help("Run a display command on every batman-adv mesh interface")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("batch")
+                .long("batch")
+                .value_name("FILE")
+                .help("Run one command per line of FILE ('-' for stdin) over a single connection")
+                .conflicts_with("all_meshes"),
+        )
+        .arg(
+            Arg::new("watch")
+                .long("watch")
+                .value_name("DURATION")
+                .value_parser(parse_duration)
+                .help("Rerun a display command every DURATION, e.g. 2s; one JSON line per run with --json")
+                .conflicts_with("batch"),
+        )
+        .arg(
+            Arg::new("log_file")
+                .long("log-file")
+                .value_name("PATH")
+                .help("Write the output to PATH, rotated by size and age, e.g. with --watch or monitor"),
+        )
+        .arg(
+            Arg::new("log_max_size")
+                .long("log-max-size")
+                .value_name("KiB")
+                .default_value("1024")
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .help("Size at which the log file is rotated"),
+        )
+        .arg(
+            Arg::new("log_max_age")
+                .long("log-max-age")
+                .value_name("DURATION")
+                .value_parser(parse_duration)
+                .help("Age at which the log file is rotated, e.g. 1h"),
+        )
+        .arg(
+            Arg::new("log_keep")
+                .long("log-keep")
+                .value_name("N")
+                .default_value("3")
+                .value_parser(clap::value_parser!(usize))
+                .help("Number of rotated log files kept"),
+        )
         .arg(
             Arg::new("version")
                 .short('v')
@@ -68,9 +194,141 @@ pub fn build_cli() -> Command {
         .subcommand(cmd_originators())
         .subcommand(cmd_translocal())
         .subcommand(cmd_transglobal())
+        .subcommand(cmd_dat_cache())
+        .subcommand(cmd_translate())
+        .subcommand(cmd_path())
         .subcommand(cmd_interfaces())
+        .subcommand(cmd_hardif())
         .subcommand(cmd_ap_isolation())
         .subcommand(cmd_aggregation())
         .subcommand(cmd_bridge_loop_avoidance())
         .subcommand(cmd_routing_algo())
+        .subcommand(cmd_check())
+        .subcommand(cmd_clients())
+        .subcommand(cmd_vid())
+        .subcommand(cmd_apply())
+        .subcommand(cmd_config())
+        .subcommand(cmd_backup())
+        .subcommand(cmd_restore())
+        .subcommand(cmd_record())
+        .subcommand(cmd_monitor())
+        .subcommand(cmd_roam_history())
+        .subcommand(cmd_raw())
+        .subcommand(cmd_attrs())
+        .subcommand(cmd_schema())
+}
+
+/// Returns `true` if display commands should print JSON, i.e. with `--json` or
+/// `--output json`, the latter possibly from `$ROBIN_OUTPUT`.
+pub fn json_output(matches: &ArgMatches) -> bool {
+    matches.get_flag("json")
+        || matches.get_one::<String>("output").map(String::as_str) == Some("json")
+}
+
+/// Returns `true` if the parsed subcommand only displays the state of one mesh
+/// interface, so it can be run on every mesh interface with `--all-meshes`.
+///
+/// These are the table commands, `check`, `clients`, `vid <vid> clients`, and the
+/// setting commands given without a new value.
+pub fn is_mesh_query(matches: &ArgMatches) -> bool {
+    match matches.subcommand() {
+        Some((
+            "neighbors" | "gateways" | "originators" | "translocal" | "transglobal" | "dat_cache"
+            | "check" | "clients" | "vid",
+            _,
+        )) => true,
+        Some(("gw_mode", sub_m)) => !sub_m.contains_id("mode"),
+        Some(("interface", sub_m)) => !sub_m.contains_id("action"),
+        Some(("aggregation" | "ap_isolation" | "bridge_loop_avoidance", sub_m)) => {
+            !sub_m.contains_id("value")
+        }
+        _ => false,
+    }
+}
+
+/// Returns the batman-adv command the parsed subcommand sends, so it can be refused
+/// up front on kernels lacking it (see [`Capabilities`](crate::Capabilities)).
+///
+/// The originator and translation tables are left out, since they fall back to
+/// debugfs on releases without netlink support.
+///
+/// # Example
+/// ```
+/// use batman_robin::Command;
+/// use batman_robin::cli::app::{build_cli, required_command};
+///
+/// let matches = build_cli().get_matches_from(["robctl", "neighbors"]);
+/// assert_eq!(required_command(&matches), Some(Command::BatadvCmdGetNeighbors));
+///
+/// let matches = build_cli().get_matches_from(["robctl", "aggregation", "1"]);
+/// assert_eq!(required_command(&matches), Some(Command::BatadvCmdSetMesh));
+/// ```
+pub fn required_command(matches: &ArgMatches) -> Option<crate::Command> {
+    use crate::Command as Cmd;
+
+    let setting = |sub_m: &ArgMatches, id: &str| match sub_m.contains_id(id) {
+        true => Cmd::BatadvCmdSetMesh,
+        false => Cmd::BatadvCmdGetMeshInfo,
+    };
+    Some(match matches.subcommand()? {
+        ("neighbors", _) => Cmd::BatadvCmdGetNeighbors,
+        ("gateways", _) => Cmd::BatadvCmdGetGateways,
+        ("dat_cache", _) => Cmd::BatadvCmdGetDatCache,
+        ("hardif", _) => Cmd::BatadvCmdGetHardif,
+        ("interface", sub_m) if !sub_m.contains_id("action") => Cmd::BatadvCmdGetHardif,
+        ("gw_mode", sub_m) => setting(sub_m, "mode"),
+        ("aggregation" | "ap_isolation" | "bridge_loop_avoidance", sub_m) => {
+            setting(sub_m, "value")
+        }
+        _ => return None,
+    })
+}
+
+/// Parses a batch script for `robctl --batch`, one command line per line.
+///
+/// Each line holds the arguments that would follow `robctl` on the command line,
+/// split on whitespace; empty lines and lines starting with `#` are skipped. All
+/// lines are parsed before any is run, so a typo does not leave a provisioning
+/// script half applied.
+///
+/// # Returns
+/// - `Ok` with each command line and its parsed arguments, in order.
+/// - `Err` naming the first line that does not parse, or that nests `--batch`.
+///
+/// # Example
+/// ```
+/// use batman_robin::cli::app::parse_batch;
+///
+/// let script = "# provision bat0\n\
+///               interface add eth0\n\
+///               -m bat1 aggregation 0\n";
+/// let commands = parse_batch(script).unwrap();
+/// assert_eq!(commands.len(), 2);
+/// assert_eq!(commands[1].0, "-m bat1 aggregation 0");
+/// assert_eq!(commands[1].1.get_one::<String>("meshif").unwrap(), "bat1");
+///
+/// assert!(parse_batch("no_such_command").is_err());
+/// ```
+pub fn parse_batch(script: &str) -> Result<Vec<(String, ArgMatches)>, String> {
+    let mut commands = Vec::new();
+    for (i, line) in script.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let args = std::iter::once("robctl").chain(line.split_whitespace());
+        let matches = build_cli().try_get_matches_from(args).map_err(|e| {
+            format!(
+                "Error - line {}: {}",
+                i + 1,
+                e.render().to_string().trim_end()
+            )
+        })?;
+        if matches.contains_id("batch") {
+            return Err(format!("Error - line {}: --batch cannot be nested", i + 1));
+        }
+        commands.push((line.to_string(), matches));
+    }
+    Ok(commands)
 }
@@ -0,0 +1,147 @@
+use crate::config::Change;
+
+use clap::{Arg, Command};
+
+/// Creates the CLI command for applying a declarative mesh configuration.
+///
+/// # Returns
+/// - A `clap::Command` configured with:
+///   - Name: `"apply"`
+///   - Short and long description: `"Apply a mesh configuration file."`
+///   - Required positional argument `file`: TOML or YAML file describing the mesh
+///   - Version flag disabled
+pub fn cmd_apply() -> Command {
+    Command::new("apply")
+        .about("Apply a mesh configuration file.")
+        .long_about(
+            "Apply a mesh configuration file.\n\
+             Creates the mesh interface and changes the settings differing from \
+             the TOML or YAML file; settings not in the file are left untouched.",
+        )
+        .arg(
+            Arg::new("file")
+                .index(1)
+                .required(true)
+                .value_name("FILE")
+                .help("Mesh configuration (.toml, .yaml or .yml)"),
+        )
+        .disable_version_flag(true)
+}
+
+/// Creates the CLI command for managing declarative mesh configurations.
+///
+/// # Returns
+/// - A `clap::Command` configured with:
+///   - Name: `"config"`
+///   - Short and long description: `"Manage mesh configuration files."`
+///   - Subcommand `export [FILE]`: write the running configuration of the mesh
+///     interface to `FILE` (TOML, or YAML for `.yaml`/`.yml`), or as TOML to stdout
+///   - Version flag disabled
+pub fn cmd_config() -> Command {
+    Command::new("config")
+        .about("Manage mesh configuration files.")
+        .long_about("Manage mesh configuration files.")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("export")
+                .about("Export the running configuration of the mesh interface.")
+                .arg(
+                    Arg::new("file")
+                        .index(1)
+                        .value_name("FILE")
+                        .help("Output file (.toml, .yaml or .yml); stdout if omitted"),
+                ),
+        )
+        .disable_version_flag(true)
+}
+
+/// Creates the CLI command for backing up the settings of the mesh interface.
+///
+/// # Returns
+/// - A `clap::Command` configured with:
+///   - Name: `"backup"`
+///   - Short and long description: `"Back up the mesh settings to a file."`
+///   - Required positional argument `file`: output file (TOML, or YAML for `.yaml`/`.yml`)
+///   - Version flag disabled
+pub fn cmd_backup() -> Command {
+    Command::new("backup")
+        .about("Back up the mesh settings to a file.")
+        .long_about(
+            "Back up the mesh, hard interface and VLAN settings of the mesh interface \
+             to a file that `restore` or `apply` can apply later or on another node.",
+        )
+        .arg(
+            Arg::new("file")
+                .index(1)
+                .required(true)
+                .value_name("FILE")
+                .help("Backup file (.toml, .yaml or .yml)"),
+        )
+        .disable_version_flag(true)
+}
+
+/// Creates the CLI command for restoring mesh settings from a backup.
+///
+/// # Returns
+/// - A `clap::Command` configured with:
+///   - Name: `"restore"`
+///   - Short and long description: `"Restore the mesh settings from a file."`
+///   - Required positional argument `file`: backup file written by `backup`
+///   - Flag `-n, --dry-run`: only show what would change
+///   - Version flag disabled
+pub fn cmd_restore() -> Command {
+    Command::new("restore")
+        .about("Restore the mesh settings from a file.")
+        .long_about(
+            "Restore the mesh settings from a file.\n\
+             Shows the settings that differ from the backup, then applies it.",
+        )
+        .arg(
+            Arg::new("file")
+                .index(1)
+                .required(true)
+                .value_name("FILE")
+                .help("Backup file (.toml, .yaml or .yml)"),
+        )
+        .arg(
+            Arg::new("dry_run")
+                .short('n')
+                .long("dry-run")
+                .help("Only show what would change")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .disable_version_flag(true)
+}
+
+/// Prints the changes a configuration would make, as a diff against the running
+/// settings.
+///
+/// # Behavior
+/// - Prints `"- target: setting old"` and `"+ target: setting new"` for each change,
+///   omitting the `-` line for settings without a previous value.
+/// - Prints `"no changes"` if the mesh already matches the configuration.
+pub fn print_plan(changes: &[Change]) {
+    if changes.is_empty() {
+        println!("no changes");
+    }
+    for change in changes {
+        if let Some(from) = &change.from {
+            println!("- {}: {} {}", change.target, change.setting, from);
+        }
+        println!("+ {}: {} {}", change.target, change.setting, change.to);
+    }
+}
+
+/// Prints the changes made by applying a configuration.
+///
+/// # Behavior
+/// - Prints one change per line, e.g. `"bat0: aggregation 1 -> 0"`.
+/// - Prints `"no changes"` if the mesh already matched the configuration.
+pub fn print_changes(changes: &[Change]) {
+    if changes.is_empty() {
+        println!("no changes");
+    }
+    for change in changes {
+        println!("{}", change);
+    }
+}
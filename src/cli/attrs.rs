@@ -0,0 +1,135 @@
+use super::raw::{format_hex, format_value};
+use crate::model::{AttrObject, AttrValue, Attribute, Command};
+
+use comfy_table::presets::UTF8_FULL;
+use comfy_table::{Cell, CellAlignment, ContentArrangement, Table};
+
+/// Creates the CLI command for listing BATMAN-adv attributes.
+///
+/// # Returns
+/// - A `clap::Command` configured with:
+///   - Name: `"attrs"`
+///   - Short description: `"List the BATMAN-adv attributes, or the ones a command returns."`
+///   - Optional positional `command`: a `GET_*` command, as named in
+///     `linux/uapi/batman_adv.h`, whose reply is read and inspected
+///   - Version flag disabled
+pub fn cmd_attrs() -> clap::Command {
+    clap::Command::new("attrs")
+        .about("List the BATMAN-adv attributes, or the ones a command returns.")
+        .long_about(
+            "List the BATMAN-adv attributes known to robctl with their id, payload type \
+             and the commands returning them. Given a GET_* command, read its reply on \
+             the mesh interface and list every attribute present, including the ones \
+             robctl does not display or know.",
+        )
+        .override_usage("\trobctl [options] attrs [command]\n")
+        .arg(
+            clap::Arg::new("command")
+                .value_name("command")
+                .value_parser(|s: &str| {
+                    s.parse::<Command>()
+                        .map_err(|e| e.to_string().trim_start_matches("Error - ").to_string())
+                })
+                .help("Command whose reply is inspected, e.g. GET_ORIGINATORS"),
+        )
+        .disable_version_flag(true)
+}
+
+/// Prints every attribute known to the crate with its id, payload type and the
+/// commands returning it (see [`Attribute::returned_by`]).
+pub fn print_attributes() {
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic);
+
+    table.set_header(vec![
+        Cell::new("Attribute").set_alignment(CellAlignment::Center),
+        Cell::new("ID").set_alignment(CellAlignment::Center),
+        Cell::new("Type").set_alignment(CellAlignment::Center),
+        Cell::new("Returned by").set_alignment(CellAlignment::Center),
+    ]);
+
+    for attr in Attribute::ALL.into_iter().skip(1) {
+        let commands: Vec<&str> = attr
+            .returned_by()
+            .into_iter()
+            .map(|cmd| cmd.name().trim_start_matches("BATADV_CMD_"))
+            .collect();
+        table.add_row(vec![
+            Cell::new(attr.name().trim_start_matches("BATADV_ATTR_")),
+            Cell::new(attr as u16).set_alignment(CellAlignment::Right),
+            Cell::new(attr.payload_type()),
+            Cell::new(match commands.is_empty() {
+                true => "-".to_string(),
+                false => commands.join(", "),
+            }),
+        ]);
+    }
+
+    println!("{table}");
+}
+
+/// Prints the attributes present in the replies to a command, with the number
+/// of replies carrying each and its first value.
+///
+/// Attributes unknown to the crate are listed by id with their payload in hex.
+pub fn print_reply_attributes(objects: &[AttrObject]) {
+    // Attributes in order of first appearance, with their count and first value.
+    let mut seen: Vec<(String, String, &str, usize, String)> = Vec::new();
+    let mut count = |key: String, id: String, ty: &'static str, value: String| match seen
+        .iter_mut()
+        .find(|(k, ..)| *k == key)
+    {
+        Some((.., n, _)) => *n += 1,
+        None => seen.push((key, id, ty, 1, value)),
+    };
+
+    for obj in objects {
+        for (attr, value) in obj {
+            let value = match value {
+                AttrValue::Nested(nested) => format!("{} nested attributes", nested.len()),
+                value => format_value(value),
+            };
+            count(
+                attr.name().trim_start_matches("BATADV_ATTR_").to_string(),
+                (attr as u16).to_string(),
+                attr.payload_type(),
+                value,
+            );
+        }
+        for (id, payload) in obj.unknown() {
+            count(
+                format!("#{}", id),
+                id.to_string(),
+                "unknown",
+                format_hex(payload),
+            );
+        }
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic);
+
+    table.set_header(vec![
+        Cell::new("Attribute").set_alignment(CellAlignment::Center),
+        Cell::new("ID").set_alignment(CellAlignment::Center),
+        Cell::new("Type").set_alignment(CellAlignment::Center),
+        Cell::new("Replies").set_alignment(CellAlignment::Center),
+        Cell::new("First value").set_alignment(CellAlignment::Center),
+    ]);
+
+    for (name, id, ty, n, value) in seen {
+        table.add_row(vec![
+            Cell::new(name),
+            Cell::new(id).set_alignment(CellAlignment::Right),
+            Cell::new(ty),
+            Cell::new(format!("{}/{}", n, objects.len())).set_alignment(CellAlignment::Right),
+            Cell::new(value),
+        ]);
+    }
+
+    println!("{table}");
+}
@@ -0,0 +1,57 @@
+use crate::RobinError;
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use macaddr::MacAddr6;
+
+/// Host names loaded with [`install`], shown instead of MAC addresses in tables.
+static HOSTS: OnceLock<HashMap<MacAddr6, String>> = OnceLock::new();
+
+/// Parses a batctl `bat-hosts` file: one `<MAC address> <name>` pair per line.
+///
+/// Empty lines, lines starting with `#` and lines not starting with a MAC address
+/// are skipped.
+///
+/// # Example
+/// ```
+/// use batman_robin::cli::bat_hosts::parse;
+///
+/// let hosts = parse("# backbone\n02:00:00:00:00:01 gw-north\n\nnot-a-mac host\n");
+/// assert_eq!(hosts.len(), 1);
+/// assert_eq!(hosts[&"02:00:00:00:00:01".parse().unwrap()], "gw-north");
+/// ```
+pub fn parse(content: &str) -> HashMap<MacAddr6, String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let (mac, name) = line.split_once(char::is_whitespace)?;
+            let name = name.trim();
+            if name.is_empty() {
+                return None;
+            }
+            Some((mac.parse().ok()?, name.to_string()))
+        })
+        .collect()
+}
+
+/// Reads the `bat-hosts` file at `path` and shows its names instead of MAC
+/// addresses in the tables printed afterwards.
+///
+/// Only the first call takes effect.
+pub fn install(path: &Path) -> Result<(), RobinError> {
+    let content = std::fs::read_to_string(path).map_err(|e| RobinError::Io {
+        context: format!("Failed to read {}", path.display()),
+        source: e,
+    })?;
+    let _ = HOSTS.set(parse(&content));
+    Ok(())
+}
+
+/// Returns the host name of `mac` from the installed `bat-hosts` file, if any.
+pub fn name(mac: MacAddr6) -> Option<&'static str> {
+    HOSTS.get()?.get(&mac).map(String::as_str)
+}
@@ -0,0 +1,75 @@
+use super::utils::{print_mac, print_vid};
+use crate::{DuplicateClient, MeshWarning};
+
+use clap::Command;
+
+/// Creates the CLI command for checking the mesh interface for likely misconfigurations.
+///
+/// # Returns
+/// - A `clap::Command` configured with:
+///   - Name: `"check"`
+///   - Short description: `"Check the mesh interface for likely misconfigurations."`
+///   - Usage override:
+///       ```text
+///       robctl [options] check
+///       robctl [options] check duplicates
+///       ```
+///   - Optional subcommand `duplicates`: list client MAC addresses announced by
+///     several originators within the same VLAN
+///   - Version flag disabled
+pub fn cmd_check() -> Command {
+    Command::new("check")
+        .about("Check the mesh interface for likely misconfigurations.")
+        .long_about(
+            "Check the mesh interface for likely misconfigurations, e.g. a bridged mesh \
+             without bridge loop avoidance, and print the command fixing each.",
+        )
+        .override_usage("\trobctl [options] check\n\trobctl [options] check duplicates\n")
+        .subcommand(
+            Command::new("duplicates")
+                .about("List client MAC addresses announced by several originators.")
+                .long_about(
+                    "List client MAC addresses announced by several originators within the \
+                     same VLAN in the global translation table, an address conflict making \
+                     the connectivity of the clients flap. Roaming clients are left out.",
+                )
+                .disable_version_flag(true),
+        )
+        .disable_version_flag(true)
+}
+
+/// Prints each warning and the command fixing it.
+///
+/// # Example output
+/// ```text
+/// Warning: bat0 is part of bridge br-lan but bridge loop avoidance is disabled on bat0
+///   fix with: robctl -m bat0 bridge_loop_avoidance 1
+/// ```
+pub fn print_warnings(warnings: &[MeshWarning]) {
+    for warning in warnings {
+        println!("Warning: {}", warning);
+        println!("  fix with: {}", warning.advice());
+    }
+}
+
+/// Prints each duplicate client and the originators announcing it.
+///
+/// # Example output
+/// ```text
+/// Warning: client 02:00:00:00:00:AA (VID -1) is announced by 2 originators:
+///   02:00:00:00:00:01
+///   02:00:00:00:00:02
+/// ```
+pub fn print_duplicates(duplicates: &[DuplicateClient], vendor: bool) {
+    for duplicate in duplicates {
+        println!(
+            "Warning: client {} (VID {}) is announced by {} originators:",
+            print_mac(duplicate.client, vendor),
+            print_vid(duplicate.vid),
+            duplicate.originators.len()
+        );
+        for orig in &duplicate.originators {
+            println!("  {}", print_mac(*orig, vendor));
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use super::utils::{print_mac, print_vid};
+use crate::ClientSummary;
+
+use clap::Command;
+
+/// Creates the CLI command for counting the clients of the mesh.
+///
+/// # Returns
+/// - A `clap::Command` configured with:
+///   - Name: `"clients"`
+///   - Short description: `"Count the clients of the mesh."`
+///   - Version flag disabled
+pub fn cmd_clients() -> Command {
+    Command::new("clients")
+        .about("Count the clients of the mesh.")
+        .long_about(
+            "Count the clients of the mesh in total, per originator and per VLAN, from the \
+             global translation table and the originator table. Each client is counted \
+             once, for the originator announcing it; originators without clients are \
+             listed with 0.",
+        )
+        .override_usage("\trobctl [options] clients\n")
+        .disable_version_flag(true)
+}
+
+/// Prints the number of clients of the mesh, per originator and per VLAN.
+///
+/// # Example output
+/// ```text
+/// clients: 3
+/// per originator:
+///   02:00:00:00:00:01: 2
+///   02:00:00:00:00:02: 1
+///   02:00:00:00:00:03: 0
+/// per VID:
+///   -1: 3
+/// ```
+pub fn print_client_summary(summary: &ClientSummary, vendor: bool) {
+    println!("clients: {}", summary.clients);
+    println!("per originator:");
+    for (orig, count) in &summary.per_originator {
+        println!("  {}: {}", print_mac(*orig, vendor), count);
+    }
+    println!("per VID:");
+    for (vid, count) in &summary.per_vid {
+        println!("  {}: {}", print_vid(*vid), count);
+    }
+}
@@ -0,0 +1,65 @@
+use super::utils::{arg_max_age, print_mac, print_vid};
+use crate::DatCacheEntry;
+
+use clap::Command;
+use comfy_table::presets::UTF8_FULL;
+use comfy_table::{Cell, CellAlignment, ContentArrangement, Table};
+
+/// Creates the CLI command for displaying the distributed ARP table cache.
+///
+/// # Returns
+/// - A `clap::Command` configured with:
+///   - Name: `"dat_cache"`
+///   - Alias: `"dc"`
+///   - Short and long description: `"Display the local D.A.T. cache."`
+///   - Usage override:
+///       ```text
+///       robctl [options] dat_cache|dc [options]
+///       ```
+///   - Optional `--max-age SECONDS`: hide entries last seen more than SECONDS ago
+///   - Version flag disabled
+pub fn cmd_dat_cache() -> Command {
+    Command::new("dat_cache")
+        .alias("dc")
+        .about("Display the local D.A.T. cache.")
+        .long_about("Display the local D.A.T. cache.")
+        .override_usage("\trobctl [options] dat_cache|dc [options]\n")
+        .arg(arg_max_age())
+        .disable_version_flag(true)
+}
+
+/// Pretty-prints a list of `DatCacheEntry` into a table.
+///
+/// # Arguments
+/// - `entries`: Slice of `DatCacheEntry` to display
+/// - `vendor`: Append the vendor of MAC addresses (see [`print_mac`]).
+///
+/// # Table columns
+/// - `IPv4`: IPv4 address of the entry
+/// - `MAC`: MAC address the IPv4 address resolves to
+/// - `VID`: VLAN ID
+/// - `Last seen`: Time since last refreshed, formatted for humans, e.g. `4.2s`
+pub fn print_dat_cache(entries: &[DatCacheEntry], vendor: bool) {
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic);
+
+    table.set_header(vec![
+        Cell::new("IPv4").set_alignment(CellAlignment::Center),
+        Cell::new("MAC").set_alignment(CellAlignment::Center),
+        Cell::new("VID").set_alignment(CellAlignment::Center),
+        Cell::new("Last seen").set_alignment(CellAlignment::Center),
+    ]);
+
+    for e in entries {
+        table.add_row(vec![
+            Cell::new(e.ip).set_alignment(CellAlignment::Right),
+            Cell::new(print_mac(e.mac, vendor)),
+            Cell::new(print_vid(e.vid)),
+            Cell::new(e.last_seen),
+        ]);
+    }
+
+    println!("{table}");
+}
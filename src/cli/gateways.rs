@@ -1,3 +1,4 @@
+use super::utils::table_algo;
 use crate::Gateway;
 
 use clap::Command;
@@ -30,39 +31,35 @@ pub fn cmd_gateways() -> Command {
 ///
 /// # Behavior
 /// - Configures the table headers differently depending on the algorithm:
-///   - `"BATMAN_IV"`: Router, TQ, Next Hop, OutgoingIF, Bandwidth Down, Bandwidth Up
-///   - `"BATMAN_V"`: Router, Throughput, Next Hop, OutgoingIF, Bandwidth Down, Bandwidth Up
+///   - `"BATMAN_IV"`: Router, TQ, Next Hop, OutgoingIF, Bandwidth
+///   - `"BATMAN_V"`: Router, Throughput, Next Hop, OutgoingIF, Bandwidth
 /// - Highlights the best gateway with an asterisk (`*`) before the MAC address.
-/// - Displays optional fields (`TQ`, `Throughput`, Bandwidth) with `0` if missing.
+/// - Formats the throughput as `"X.Y MBit"` and the announced bandwidths as
+///   `"down/up MBit"`, e.g. `"10.0/2.0 MBit"`, like `batctl gwl`.
+/// - The metric column follows the metric the entries carry if it contradicts
+///   `algo_name` (see [`table_algo`]).
+/// - Displays missing metrics and bandwidths with `-`.
 pub fn print_gwl(entries: &[Gateway], algo_name: &str) {
+    let algo_name = table_algo(
+        algo_name,
+        entries.iter().any(|g| g.tq.is_some()),
+        entries.iter().any(|g| g.throughput.is_some()),
+    );
     let mut table = Table::new();
     table
         .load_preset(UTF8_FULL)
         .set_content_arrangement(ContentArrangement::Dynamic);
 
-    match algo_name {
-        "BATMAN_IV" => {
-            table.set_header(vec![
-                Cell::new("Router").set_alignment(CellAlignment::Center),
-                Cell::new("TQ").set_alignment(CellAlignment::Center),
-                Cell::new("Next Hop").set_alignment(CellAlignment::Center),
-                Cell::new("OutgoingIF").set_alignment(CellAlignment::Center),
-                Cell::new("Bandwidth Down (Mbit/s)").set_alignment(CellAlignment::Center),
-                Cell::new("Bandwidth Up (Mbit/s)").set_alignment(CellAlignment::Center),
-            ]);
-        }
-        "BATMAN_V" => {
-            table.set_header(vec![
-                Cell::new("Router").set_alignment(CellAlignment::Center),
-                Cell::new("Throughput").set_alignment(CellAlignment::Center),
-                Cell::new("Next Hop").set_alignment(CellAlignment::Center),
-                Cell::new("OutgoingIF").set_alignment(CellAlignment::Center),
-                Cell::new("Bandwidth Down (Mbit/s)").set_alignment(CellAlignment::Center),
-                Cell::new("Bandwidth Up (Mbit/s)").set_alignment(CellAlignment::Center),
-            ]);
-        }
-        _ => return,
-    }
+    let metric = match algo_name {
+        "BATMAN_IV" => "TQ",
+        "BATMAN_V" => "Throughput",
+        _ => unreachable!(),
+    };
+    table.set_header(
+        ["Router", metric, "Next Hop", "OutgoingIF", "Bandwidth"]
+            .into_iter()
+            .map(|name| Cell::new(name).set_alignment(CellAlignment::Center)),
+    );
 
     for g in entries {
         let router_text = if g.is_best {
@@ -70,32 +67,28 @@ pub fn print_gwl(entries: &[Gateway], algo_name: &str) {
         } else {
             g.mac_addr.to_string()
         };
-        let router_cell = Cell::new(router_text);
-        let next_hop_cell = Cell::new(g.router.to_string());
+        let metric_cell = match algo_name {
+            "BATMAN_IV" => Cell::new(match g.tq {
+                Some(tq) => tq.to_string(),
+                None => "-".to_string(),
+            }),
+            _ => Cell::new(match g.throughput {
+                Some(throughput) => throughput.to_string(),
+                None => "-".to_string(),
+            }),
+        };
+        let bandwidth = match (g.bandwidth_down, g.bandwidth_up) {
+            (Some(down), Some(up)) => format!("{:#}/{}", down, up),
+            _ => "-".to_string(),
+        };
 
-        match algo_name {
-            "BATMAN_IV" => {
-                table.add_row(vec![
-                    router_cell.set_alignment(CellAlignment::Right),
-                    Cell::new(g.tq.unwrap_or(0)),
-                    next_hop_cell,
-                    Cell::new(&g.outgoing_if),
-                    Cell::new(g.bandwidth_down.unwrap_or(0)),
-                    Cell::new(g.bandwidth_up.unwrap_or(0)),
-                ]);
-            }
-            "BATMAN_V" => {
-                table.add_row(vec![
-                    router_cell.set_alignment(CellAlignment::Right),
-                    Cell::new(g.throughput.unwrap_or(0)),
-                    next_hop_cell,
-                    Cell::new(&g.outgoing_if),
-                    Cell::new(g.bandwidth_down.unwrap_or(0)),
-                    Cell::new(g.bandwidth_up.unwrap_or(0)),
-                ]);
-            }
-            _ => {}
-        }
+        table.add_row(vec![
+            Cell::new(router_text).set_alignment(CellAlignment::Right),
+            metric_cell,
+            Cell::new(g.router.to_string()),
+            Cell::new(&g.outgoing_if),
+            Cell::new(bandwidth),
+        ]);
     }
 
     println!("{table}");
@@ -1,9 +1,7 @@
-use crate::{GatewayInfo, GwMode, RobinError};
+use crate::{GatewayInfo, GwMode};
 
 use clap::{Arg, Command};
 
-type GwParseResult = (Option<u32>, Option<u32>, Option<u32>);
-
 /// Creates the CLI command for displaying or modifying the gateway mode.
 ///
 /// # Returns
@@ -47,7 +45,7 @@ pub fn cmd_gw_mode() -> Command {
 ///   - `Off`: prints `"off"`
 ///   - `Client`: prints `"client (selection class: ... MBit)"`
 ///   - `Server`: prints `"server (announced bw: down/up MBit)"`
-///   - `Unknown`: prints `"unknown"`
+///   - `Unknown`: prints `"unknown (<raw mode>)"`
 /// - For BATMAN_V algorithm, selection class is printed with one decimal place.
 pub fn print_gw(info: &GatewayInfo) {
     match info.mode {
@@ -55,7 +53,7 @@ pub fn print_gw(info: &GatewayInfo) {
             println!("off");
         }
         GwMode::Client => {
-            if info.algo == "BATMAN_V" {
+            if info.algo.as_deref() == Some("BATMAN_V") {
                 println!(
                     "client (selection class: {}.{} MBit)",
                     info.sel_class / 10,
@@ -66,77 +64,13 @@ pub fn print_gw(info: &GatewayInfo) {
             }
         }
         GwMode::Server => {
-            let down = info.bandwidth_down;
-            let up = info.bandwidth_up;
-
             println!(
-                "server (announced bw: {}.{}/{}.{} MBit)",
-                down / 10,
-                down % 10,
-                up / 10,
-                up % 10
+                "server (announced bw: {:#}/{})",
+                info.bandwidth_down, info.bandwidth_up
             );
         }
-        GwMode::Unknown => {
-            println!("unknown");
-        }
-    }
-}
-
-/// Parses a gateway parameter string according to the gateway mode.
-///
-/// # Arguments
-/// - `mode`: The `GwMode` to interpret the parameter for.
-/// - `param`: The parameter string, e.g., selection class or bandwidth (`"1000/500"`).
-///
-/// # Returns
-/// - `Ok((Option<down>, Option<up>, Option<sel_class>))`
-///   - `down` and `up` are bandwidth values in kbit for `Server` mode.
-///   - `sel_class` is used for `Client` mode.
-///   - `None` values for `Off` mode.
-/// - `Err(RobinError)` if parsing fails or mode is `Unknown`.
-///
-/// # Notes
-/// - For server mode, the `param` can be `"down/up"` and supports optional `"kbit"` or `"MBit"` suffix.
-/// - For client mode, `param` is parsed as a selection class integer.
-pub fn parse_gw_param(mode: GwMode, param: &str) -> Result<GwParseResult, RobinError> {
-    match mode {
-        GwMode::Off => Ok((None, None, None)),
-        GwMode::Client => {
-            let sel_class = param.parse::<u32>().map_err(|e| {
-                RobinError::Parse(format!("Invalid sel_class '{}': {:?}", param, e))
-            })?;
-            Ok((None, None, Some(sel_class)))
-        }
-        GwMode::Server => {
-            let parts: Vec<&str> = param.split('/').collect();
-            let parse_value = |s: &str| -> Result<u32, RobinError> {
-                let s = s.trim().to_lowercase();
-                if s.ends_with("kbit") {
-                    Ok(s.trim_end_matches("kbit").parse::<u32>().map_err(|e| {
-                        RobinError::Parse(format!("Invalid bandwidth '{}': {:?}", s, e))
-                    })?)
-                } else if s.ends_with("mbit") {
-                    let val = s.trim_end_matches("mbit").parse::<u32>().map_err(|e| {
-                        RobinError::Parse(format!("Invalid bandwidth '{}': {:?}", s, e))
-                    })?;
-                    Ok(val * 1000)
-                } else {
-                    Ok(s.parse::<u32>().map_err(|e| {
-                        RobinError::Parse(format!("Invalid bandwidth '{}': {:?}", s, e))
-                    })?)
-                }
-            };
-
-            let down = parse_value(parts[0])?;
-            let up = if let Some(u) = parts.get(1) {
-                Some(parse_value(u)?)
-            } else {
-                Some(down / 5)
-            };
-
-            Ok((Some(down), up, None))
+        GwMode::Unknown(mode) => {
+            println!("unknown ({})", mode);
         }
-        GwMode::Unknown => Err(RobinError::NotFound("Unknown mode".to_string())),
     }
 }
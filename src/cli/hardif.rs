@@ -0,0 +1,62 @@
+use super::utils::print_mac;
+use crate::{Bandwidth, HardifInfo, IfaceRef};
+
+use clap::{Arg, Command, value_parser};
+
+/// Creates the CLI command for displaying the settings of a hard interface.
+///
+/// # Returns
+/// - A `clap::Command` configured with:
+///   - Name: `"hardif"`
+///   - Alias: `"hi"`
+///   - Short and long description: `"Display the settings of a hard interface."`
+///   - Usage override:
+///       ```text
+///       robctl [options] hardif|hi <iface>
+///       ```
+///   - Required positional argument `"iface"`: the hard interface, by name or index
+///   - Version flag disabled
+pub fn cmd_hardif() -> Command {
+    Command::new("hardif")
+        .alias("hi")
+        .about("Display the settings of a hard interface.")
+        .long_about("Display the settings of a hard interface.")
+        .override_usage("\trobctl [options] hardif|hi <iface>\n")
+        .arg(
+            Arg::new("iface")
+                .value_name("iface")
+                .required(true)
+                .value_parser(value_parser!(IfaceRef))
+                .help("Hard interface attached to the mesh (name or index)"),
+        )
+        .disable_version_flag(true)
+}
+
+/// Prints the settings and state of a hard interface, one `name: value` line each.
+///
+/// # Arguments
+/// - `info`: `HardifInfo` of the interface.
+/// - `vendor`: Append the vendor of its MAC address (see [`print_mac`]).
+///
+/// # Behavior
+/// - Prints the interface name and index, MAC address and `active`/`inactive` state.
+/// - Prints the hop penalty, ELP interval (ms) and throughput override (Mbit/s with
+///   one decimal, `"auto"` if measured) when the kernel reports them.
+pub fn print_hardif(info: &HardifInfo, vendor: bool) {
+    println!("interface: {} (index {})", info.ifname, info.ifindex);
+    if let Some(mac) = info.mac {
+        println!("address: {}", print_mac(mac, vendor));
+    }
+    println!("state: {}", if info.active { "active" } else { "inactive" });
+    if let Some(hop_penalty) = info.hop_penalty {
+        println!("hop_penalty: {}", hop_penalty);
+    }
+    if let Some(elp_interval) = info.elp_interval_ms {
+        println!("elp_interval: {} ms", elp_interval);
+    }
+    match info.throughput_override {
+        Some(Bandwidth::ZERO) => println!("throughput_override: auto"),
+        Some(throughput) => println!("throughput_override: {:#} Mbit/s", throughput),
+        None => {}
+    }
+}
@@ -1,4 +1,4 @@
-use crate::Interface;
+use crate::{Interface, OperState};
 
 use clap::{Arg, Command};
 
@@ -14,11 +14,13 @@ use clap::{Arg, Command};
 ///       robctl [options] interface|if [options] [add|del iface(s)]
 ///       robctl [options] interface|if [options] create [routing_algo|ra RA_NAME]
 ///       robctl [options] interface|if [options] destroy
+///       robctl [options] interface|if [options] rename NEW_NAME
 ///       ```
 ///   - Optional flags and arguments:
 ///       - `-M, --manual`: Disable automatic creation/destruction of batman-adv interface
-///       - `action`: Command name, one of `add`, `a`, `del`, `d`, `create`, `c`, `destroy`, `D`
-///       - `params`: Additional parameters, either interface names (for add/del) or routing algorithm name (for create)
+///       - `-s, --stats`: Show the RX/TX byte and packet counters of each interface
+///       - `action`: Command name, one of `add`, `a`, `del`, `d`, `create`, `c`, `destroy`, `D`, `rename`
+///       - `params`: Additional parameters, either interface names (for add/del), routing algorithm name (for create) or new name (for rename)
 ///   - Version flag disabled
 pub fn cmd_interfaces() -> Command {
     Command::new("interface")
@@ -28,7 +30,8 @@ pub fn cmd_interfaces() -> Command {
         .override_usage(
             "\trobctl [options] interface|if [options] [add|del iface(s)]\n\
                     \trobctl [options] interface|if [options] create [routing_algo|ra RA_NAME]\n\
-                    \trobctl [options] interface|if [options] destroy\n",
+                    \trobctl [options] interface|if [options] destroy\n\
+                    \trobctl [options] interface|if [options] rename NEW_NAME\n",
         )
         .arg(
             Arg::new("manual")
@@ -36,11 +39,20 @@ pub fn cmd_interfaces() -> Command {
                 .help("Disable automatic creation/destruction of batman-adv interface")
                 .action(clap::ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new("stats")
+                .short('s')
+                .long("stats")
+                .help("Show the RX/TX byte and packet counters of each interface")
+                .action(clap::ArgAction::SetTrue),
+        )
         .arg(
             Arg::new("action")
                 .index(1)
                 .value_name("command")
-                .value_parser(["add", "a", "del", "d", "create", "c", "destroy", "D"])
+                .value_parser([
+                    "add", "a", "del", "d", "create", "c", "destroy", "D", "rename",
+                ])
                 .help("Command name:"),
         )
         .arg(
@@ -48,7 +60,7 @@ pub fn cmd_interfaces() -> Command {
                 .index(2)
                 .value_name("parameters")
                 .num_args(0..)
-                .help("Interfaces (add/del) or routing algorithm (create)"),
+                .help("Interfaces (add/del), routing algorithm (create) or new name (rename)"),
         )
         .disable_version_flag(true)
 }
@@ -59,13 +71,39 @@ pub fn cmd_interfaces() -> Command {
 /// - `interfaces`: Slice of `Interface` structs, each containing:
 ///     - `ifname`: Name of the interface
 ///     - `active`: Boolean indicating whether the interface is active
+///     - `operstate` and `carrier`: Link state of the interface
+/// - `wireless`: Whether each interface is wireless, in the same order.
+/// - `stats`: Whether to print the traffic counters of each interface.
 ///
 /// # Behavior
 /// - Prints each interface in the format: `"iface_name: active"` or `"iface_name: inactive"`.
-pub fn print_interfaces(interfaces: &[Interface]) {
-    for iface in interfaces {
+/// - A known link state follows in brackets, e.g. `" [UP]"` or `" [DOWN, no carrier]"`,
+///   so an unplugged cable can be told apart from an interface batman-adv disabled.
+/// - Wireless interfaces are followed by `" (wifi)"`.
+/// - With `stats`, known counters follow, e.g. `" rx 1520 B/12 pkts tx 980 B/8 pkts"`,
+///   so the traffic distribution across the uplinks of the mesh can be compared.
+pub fn print_interfaces(interfaces: &[Interface], wireless: &[bool], stats: bool) {
+    for (i, iface) in interfaces.iter().enumerate() {
         let status = if iface.active { "active" } else { "inactive" };
+        let link = match (iface.operstate, iface.carrier) {
+            (OperState::Unknown, None) => String::new(),
+            (operstate, Some(false)) => format!(" [{}, no carrier]", operstate),
+            (operstate, _) => format!(" [{}]", operstate),
+        };
+        let wifi = if wireless.get(i).copied().unwrap_or(false) {
+            " (wifi)"
+        } else {
+            ""
+        };
+
+        let counters = match iface.stats {
+            Some(s) if stats => format!(
+                " rx {} B/{} pkts tx {} B/{} pkts",
+                s.rx_bytes, s.rx_packets, s.tx_bytes, s.tx_packets
+            ),
+            _ => String::new(),
+        };
 
-        println!("{}: {}", iface.ifname, status);
+        println!("{}: {}{}{}{}", iface.ifname, status, link, wifi, counters);
     }
 }
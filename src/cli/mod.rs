@@ -5,13 +5,28 @@
 pub mod aggregation;
 pub mod ap_isolation;
 pub mod app;
+pub mod apply;
+pub mod attrs;
+pub mod bat_hosts;
 pub mod bridge_loop_avoidance;
+pub mod check;
+pub mod clients;
+pub mod dat_cache;
 pub mod gateways;
 pub mod gw_mode;
+pub mod hardif;
 pub mod interface;
+pub mod monitor;
 pub mod neighbors;
 pub mod originators;
+pub mod path;
+pub mod raw;
+pub mod record;
+pub mod roam_history;
 pub mod routing_algo;
+pub mod schema;
 pub mod transglobal;
+pub mod translate;
 pub mod translocal;
 pub mod utils;
+pub mod vid;
@@ -0,0 +1,154 @@
+use super::utils::{parse_duration, print_mac};
+use crate::failover::GatewayChange;
+use crate::liveness::LivenessEvent;
+
+use clap::{Arg, ArgAction, Command};
+
+/// Creates the CLI command for monitoring the mesh.
+///
+/// # Returns
+/// - A `clap::Command` configured with:
+///   - Name: `"monitor"`
+///   - Short description: `"Monitor the mesh and report changes as they happen."`
+///   - Required subcommand, either of:
+///     - `liveness`: report originators not seen for longer than `--threshold`
+///       (default `30s`) and their return, reading the originator table every
+///       `--interval` (default `5s`, see [`parse_duration`])
+///     - `gateway`: report changes of the selected gateway and run each `--hook`
+///       command on them (see [`run_hook`](crate::failover::run_hook)), reading the
+///       gateway table every `--interval` (default `5s`)
+///   - Both subcommands take `--webhook URL`, repeatable, and `--webhook-auth
+///     USER:PASSWORD` to also POST each event as a JSON document (`webhook` feature)
+///   - Version flag disabled
+pub fn cmd_monitor() -> Command {
+    Command::new("monitor")
+        .about("Monitor the mesh and report changes as they happen.")
+        .long_about("Monitor the mesh and report changes as they happen, until interrupted.")
+        .override_usage(
+            "\trobctl [options] monitor liveness [--threshold <duration>] [--interval <duration>]\n\
+             \trobctl [options] monitor gateway [--hook <command>]... [--interval <duration>]\n",
+        )
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("liveness")
+                .about("Report originators going silent and coming back.")
+                .long_about(
+                    "Report originators not seen for longer than the threshold, also once \
+                     purged from the originator table, and their return.",
+                )
+                .arg(
+                    Arg::new("threshold")
+                        .long("threshold")
+                        .short('t')
+                        .value_name("duration")
+                        .default_value("30s")
+                        .value_parser(parse_duration)
+                        .help("Time without news after which a node is down, e.g. 30s or 2m"),
+                )
+                .arg(
+                    Arg::new("interval")
+                        .long("interval")
+                        .short('i')
+                        .value_name("duration")
+                        .default_value("5s")
+                        .value_parser(parse_duration)
+                        .help("Time between reads of the originator table, e.g. 5s or 500ms"),
+                )
+                .args(webhook_args())
+                .disable_version_flag(true),
+        )
+        .subcommand(
+            Command::new("gateway")
+                .about("Report changes of the selected gateway and run hooks on them.")
+                .long_about(
+                    "Report when the selected gateway changes or all gateways vanish, and run \
+                     each hook command with ROBIN_GW_EVENT, ROBIN_GW_OLD, ROBIN_GW_NEW, \
+                     ROBIN_GW_NEW_ROUTER, ROBIN_GW_NEW_IF and ROBIN_MESHIF set. Only gateway \
+                     clients select a gateway.",
+                )
+                .arg(
+                    Arg::new("hook")
+                        .long("hook")
+                        .value_name("command")
+                        .action(ArgAction::Append)
+                        .help("Shell command run on every change, may be repeated"),
+                )
+                .arg(
+                    Arg::new("interval")
+                        .long("interval")
+                        .short('i')
+                        .value_name("duration")
+                        .default_value("5s")
+                        .value_parser(parse_duration)
+                        .help("Time between reads of the gateway table, e.g. 5s or 500ms"),
+                )
+                .args(webhook_args())
+                .disable_version_flag(true),
+        )
+        .disable_version_flag(true)
+}
+
+/// Creates the `--webhook URL` and `--webhook-auth USER:PASSWORD` arguments of the
+/// monitor subcommands.
+fn webhook_args() -> [Arg; 2] {
+    [
+        Arg::new("webhook")
+            .long("webhook")
+            .value_name("URL")
+            .action(ArgAction::Append)
+            .help("Also POST every event as JSON to URL, may be repeated"),
+        Arg::new("webhook_auth")
+            .long("webhook-auth")
+            .value_name("USER:PASSWORD")
+            .env("ROBIN_WEBHOOK_AUTH")
+            .hide_env_values(true)
+            .help("HTTP basic authentication for the webhooks"),
+    ]
+}
+
+/// Prints a liveness event as one line.
+///
+/// # Arguments
+/// - `event`: The event to print.
+/// - `vendor`: Append the vendor of the MAC address (see [`print_mac`]).
+///
+/// # Example output
+/// ```text
+/// DOWN 02:00:00:00:00:01: last seen 41.0s ago
+/// UP   02:00:00:00:00:01: back after 59.0s
+/// ```
+pub fn print_liveness_event(event: &LivenessEvent, vendor: bool) {
+    let originator = print_mac(event.originator(), vendor);
+    match event {
+        LivenessEvent::Down { silent_for, .. } => println!(
+            "DOWN {}: last seen {:.1}s ago",
+            originator,
+            silent_for.as_secs_f64()
+        ),
+        LivenessEvent::Up { silent_for, .. } => println!(
+            "UP   {}: back after {:.1}s",
+            originator,
+            silent_for.as_secs_f64()
+        ),
+    }
+}
+
+/// Prints a change of the selected gateway as one line.
+///
+/// # Example output
+/// ```text
+/// GATEWAY changed: 02:00:00:00:00:01 -> 02:00:00:00:00:02
+/// GATEWAY lost: 02:00:00:00:00:02 -> -
+/// ```
+pub fn print_gateway_change(change: &GatewayChange, vendor: bool) {
+    let mac = |gw: &Option<crate::Gateway>| match gw {
+        Some(gw) => print_mac(gw.mac_addr, vendor),
+        None => "-".to_string(),
+    };
+    println!(
+        "GATEWAY {}: {} -> {}",
+        change.kind(),
+        mac(&change.old),
+        mac(&change.new)
+    );
+}
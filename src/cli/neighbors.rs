@@ -1,10 +1,10 @@
-use crate::Neighbor;
+use super::utils::{arg_max_age, print_mac, table_algo};
+use crate::model::StationInfo;
+use crate::{IfaceRef, Neighbor};
 
-use clap::Command;
+use clap::{Arg, Command, value_parser};
 use comfy_table::presets::UTF8_FULL;
 use comfy_table::{Cell, CellAlignment, ContentArrangement, Table};
-use macaddr::MacAddr6;
-use std::collections::HashMap;
 
 /// Creates the CLI command for displaying the neighbor table.
 ///
@@ -17,6 +17,9 @@ use std::collections::HashMap;
 ///       ```text
 ///       robctl [options] neighbors|n [options]
 ///       ```
+///   - Optional `-i, --interface IFACE`: only show the neighbor table of this hard interface,
+///     given by name or index
+///   - Optional `--max-age SECONDS`: hide entries last seen more than SECONDS ago
 ///   - Version flag disabled
 pub fn cmd_neighbors() -> Command {
     Command::new("neighbors")
@@ -24,89 +27,83 @@ pub fn cmd_neighbors() -> Command {
         .about("Display the neighbor table.")
         .long_about("Display the neighbor table.")
         .override_usage("\trobctl [options] neighbors|n [options]\n")
+        .arg(
+            Arg::new("interface")
+                .short('i')
+                .long("interface")
+                .value_name("IFACE")
+                .value_parser(value_parser!(IfaceRef))
+                .help("Only show the neighbor table of this hard interface (name or index)"),
+        )
+        .arg(arg_max_age())
         .disable_version_flag(true)
 }
 
-/// Deduplicates neighbors based on `(neighbor MAC, outgoing interface)`.
-///
-/// # Arguments
-/// - `neighbors`: A vector of `Neighbor` entries.
-///
-/// # Behavior
-/// - If multiple entries exist for the same `(MAC, interface)`, keeps the one with the **latest `last_seen_ms`**.
-/// - Returns a deduplicated `Vec<Neighbor>`.
-pub fn dedup_neighbors(neighbors: Vec<Neighbor>) -> Vec<Neighbor> {
-    let mut map: HashMap<(MacAddr6, String), Neighbor> = HashMap::new();
-
-    for n in neighbors {
-        let key = (n.neigh, n.outgoing_if.clone());
-        match map.get(&key) {
-            Some(existing) => {
-                if n.last_seen_ms < existing.last_seen_ms {
-                    map.insert(key, n);
-                }
-            }
-            None => {
-                map.insert(key, n);
-            }
-        }
-    }
-
-    map.into_values().collect()
-}
-
 /// Prints a neighbor table in a human-readable format.
 ///
 /// # Arguments
 /// - `entries`: Slice of `Neighbor` entries.
 /// - `algo_name`: Name of the routing algorithm (BATMAN_IV or BATMAN_V).
+/// - `stations`: Wireless link state of each neighbor, in the same order; `None` for
+///   neighbors reached over wired interfaces.
+/// - `vendor`: Append the vendor of MAC addresses (see [`print_mac`]).
 ///
 /// # Behavior
 /// - For BATMAN_IV:
 ///     - Columns: `"IF"`, `"Neighbor"`, `"Last seen"`
 /// - For BATMAN_V:
 ///     - Columns: `"Neighbor"`, `"Last seen"`, `"Speed (Mbit/s)"`, `"IF"`
-/// - Deduplicates entries before printing.
-/// - `last_seen_ms` is formatted as seconds with milliseconds precision.
-pub fn print_neighbors(entries: &[Neighbor], algo_name: &str) {
+/// - Neighbors carrying a throughput get the BATMAN_V columns whatever `algo_name`
+///   (see [`table_algo`]).
+/// - Marks the links selected as router with a `*` prefix.
+/// - `last_seen` is formatted for humans, e.g. `"4.2s"` or `"1m 32s"`.
+/// - If any neighbor is reached over a wireless interface, `"Signal (dBm)"` and
+///   `"Bitrate (Mbit/s)"` columns are appended, with `"-"` for the other neighbors.
+pub fn print_neighbors(
+    entries: &[Neighbor],
+    algo_name: &str,
+    stations: &[Option<StationInfo>],
+    vendor: bool,
+) {
+    let algo_name = table_algo(
+        algo_name,
+        false,
+        entries.iter().any(|n| n.throughput_kbps.is_some()),
+    );
     let mut table = Table::new();
     table
         .load_preset(UTF8_FULL)
         .set_content_arrangement(ContentArrangement::Dynamic);
 
-    match algo_name {
-        "BATMAN_IV" => {
-            table.set_header(vec![
-                Cell::new("IF").set_alignment(CellAlignment::Center),
-                Cell::new("Neighbor").set_alignment(CellAlignment::Center),
-                Cell::new("Last seen").set_alignment(CellAlignment::Center),
-            ]);
-        }
-        "BATMAN_V" => {
-            table.set_header(vec![
-                Cell::new("Neighbor").set_alignment(CellAlignment::Center),
-                Cell::new("Last seen").set_alignment(CellAlignment::Center),
-                Cell::new("Speed (Mbit/s)").set_alignment(CellAlignment::Center),
-                Cell::new("IF").set_alignment(CellAlignment::Center),
-            ]);
-        }
-        _ => return,
+    let mut header = match algo_name {
+        "BATMAN_IV" => vec!["IF", "Neighbor", "Last seen"],
+        "BATMAN_V" => vec!["Neighbor", "Last seen", "Speed (Mbit/s)", "IF"],
+        _ => unreachable!(),
+    };
+    let wireless = stations.iter().any(Option::is_some);
+    if wireless {
+        header.extend(["Signal (dBm)", "Bitrate (Mbit/s)"]);
     }
+    table.set_header(
+        header
+            .into_iter()
+            .map(|name| Cell::new(name).set_alignment(CellAlignment::Center)),
+    );
 
-    let dedup_entries = dedup_neighbors(entries.to_vec());
-    for n in dedup_entries {
-        let last_seen_secs = n.last_seen_ms / 1000;
-        let last_seen_msecs = n.last_seen_ms % 1000;
-        let last_seen = format!("{}.{:03}s", last_seen_secs, last_seen_msecs);
+    for (i, n) in entries.iter().enumerate() {
+        let last_seen = n.last_seen.to_string();
+        let neighbor = if n.is_best {
+            format!("* {}", print_mac(n.neigh, vendor))
+        } else {
+            print_mac(n.neigh, vendor)
+        };
 
-        match algo_name {
-            "BATMAN_IV" => {
-                table.add_row(vec![
-                    Cell::new(&n.outgoing_if),
-                    Cell::new(n.neigh.to_string()),
-                    Cell::new(last_seen),
-                ]);
-            }
+        let mut row = match algo_name {
+            "BATMAN_IV" => vec![
+                Cell::new(&n.outgoing_if),
+                Cell::new(&neighbor),
+                Cell::new(last_seen),
+            ],
             "BATMAN_V" => {
                 let speed_cell = match n.throughput_kbps {
                     Some(kbits) => {
@@ -118,15 +115,31 @@ pub fn print_neighbors(entries: &[Neighbor], algo_name: &str) {
                     None => Cell::new("-"),
                 };
 
-                table.add_row(vec![
-                    Cell::new(n.neigh.to_string()),
+                vec![
+                    Cell::new(&neighbor),
                     Cell::new(last_seen),
                     speed_cell,
                     Cell::new(&n.outgoing_if),
-                ]);
+                ]
             }
-            _ => {}
+            _ => unreachable!(),
+        };
+
+        if wireless {
+            let station = stations.get(i).copied().flatten();
+            let signal = station.and_then(|sta| sta.signal_avg_dbm.or(sta.signal_dbm));
+            let bitrate = station.and_then(|sta| sta.tx_bitrate_kbps);
+            row.push(Cell::new(match signal {
+                Some(dbm) => dbm.to_string(),
+                None => "-".to_string(),
+            }));
+            row.push(Cell::new(match bitrate {
+                Some(kbits) => format!("{}.{}", kbits / 1000, (kbits % 1000) / 100),
+                None => "-".to_string(),
+            }));
         }
+
+        table.add_row(row);
     }
 
     println!("{table}");
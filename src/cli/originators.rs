@@ -1,6 +1,7 @@
-use crate::Originator;
+use super::utils::{arg_max_age, print_mac, table_algo};
+use crate::{IfaceRef, Originator};
 
-use clap::Command;
+use clap::{Arg, Command, value_parser};
 use comfy_table::presets::UTF8_FULL;
 use comfy_table::{Cell, CellAlignment, ContentArrangement, Table};
 
@@ -15,6 +16,9 @@ use comfy_table::{Cell, CellAlignment, ContentArrangement, Table};
 ///       ```text
 ///       robctl [options] originators|o [options]
 ///       ```
+///   - Optional `-i, --interface IFACE`: only show the originator table of this hard interface,
+///     given by name or index
+///   - Optional `--max-age SECONDS`: hide entries last seen more than SECONDS ago
 ///   - Version flag disabled
 pub fn cmd_originators() -> Command {
     Command::new("originators")
@@ -22,6 +26,15 @@ pub fn cmd_originators() -> Command {
         .about("Display the originator table.")
         .long_about("Display the originator table.")
         .override_usage("\trobctl [options] originators|o [options]\n")
+        .arg(
+            Arg::new("interface")
+                .short('i')
+                .long("interface")
+                .value_name("IFACE")
+                .value_parser(value_parser!(IfaceRef))
+                .help("Only show the originator table of this hard interface (name or index)"),
+        )
+        .arg(arg_max_age())
         .disable_version_flag(true)
 }
 
@@ -30,6 +43,7 @@ pub fn cmd_originators() -> Command {
 /// # Arguments
 /// - `entries`: Slice of `Originator` entries.
 /// - `algo_name`: Name of the routing algorithm (BATMAN_IV or BATMAN_V).
+/// - `vendor`: Append the vendor of MAC addresses (see [`print_mac`]).
 ///
 /// # Behavior
 /// - For BATMAN_IV:
@@ -38,9 +52,16 @@ pub fn cmd_originators() -> Command {
 /// - For BATMAN_V:
 ///     - Columns: `"Originator"`, `"Last seen"`, `"Throughput (Mbit/s)"`, `"Next hop"`, `"Outgoing IF"`
 ///     - Throughput is converted from kbit/s to Mbit with one decimal place
+/// - The columns follow the metric the entries carry if it contradicts `algo_name`
+///   (see [`table_algo`]); missing metrics are displayed as `-`.
 /// - Marks best originators with a `*` prefix.
-/// - `last_seen_ms` is formatted as seconds with milliseconds precision.
-pub fn print_originators(entries: &[Originator], algo_name: &str) {
+/// - `last_seen` is formatted for humans, e.g. `"4.2s"` or `"1m 32s"`.
+pub fn print_originators(entries: &[Originator], algo_name: &str, vendor: bool) {
+    let algo_name = table_algo(
+        algo_name,
+        entries.iter().any(|o| o.tq.is_some()),
+        entries.iter().any(|o| o.throughput.is_some()),
+    );
     let mut table = Table::new();
     table
         .load_preset(UTF8_FULL)
@@ -65,30 +86,31 @@ pub fn print_originators(entries: &[Originator], algo_name: &str) {
                 Cell::new("Outgoing IF").set_alignment(CellAlignment::Center),
             ]);
         }
-        _ => return,
+        _ => unreachable!(),
     }
 
     for o in entries {
-        let last_seen_secs = o.last_seen_ms / 1000;
-        let last_seen_msecs = o.last_seen_ms % 1000;
-        let last_seen = format!("{}.{:03}s", last_seen_secs, last_seen_msecs);
+        let last_seen = o.last_seen.to_string();
 
         let originator_text = if o.is_best {
-            format!("* {}", o.originator)
+            format!("* {}", print_mac(o.originator, vendor))
         } else {
-            o.originator.to_string()
+            print_mac(o.originator, vendor)
         };
         let originator_cell = Cell::new(originator_text);
-        let next_hop_cell = Cell::new(o.next_hop.to_string());
+        let next_hop_cell = Cell::new(print_mac(o.next_hop, vendor));
 
         match algo_name {
             "BATMAN_IV" => {
-                let tq = o.tq.unwrap_or(0);
+                let tq_cell = match o.tq {
+                    Some(tq) => Cell::new(format!("{}/255", tq)),
+                    None => Cell::new("-"),
+                };
 
                 table.add_row(vec![
                     originator_cell.set_alignment(CellAlignment::Right),
                     Cell::new(last_seen),
-                    Cell::new(format!("{}/255", tq)),
+                    tq_cell,
                     next_hop_cell,
                     Cell::new(&o.outgoing_if),
                 ]);
@@ -0,0 +1,62 @@
+use super::utils::{print_mac, print_vid};
+use crate::{Bandwidth, ClientPath};
+
+use clap::{Arg, Command};
+use macaddr::MacAddr6;
+
+/// Creates the CLI command for showing how this node reaches a client.
+///
+/// # Returns
+/// - A `clap::Command` configured with:
+///   - Name: `"path"`
+///   - Short description: `"Show the path to a client."`
+///   - Required positional `client`: MAC address of the client
+///   - Version flag disabled
+pub fn cmd_path() -> Command {
+    Command::new("path")
+        .about("Show the path to a client.")
+        .long_about(
+            "Show how this node reaches a client: the originator serving it in the \
+             translation tables, and the next hop and outgoing interface of the best \
+             route towards that originator.",
+        )
+        .override_usage("\trobctl [options] path <client>\n")
+        .arg(
+            Arg::new("client")
+                .value_name("client")
+                .required(true)
+                .value_parser(|s: &str| s.parse::<MacAddr6>().map_err(|e| e.to_string()))
+                .help("MAC address of the client"),
+        )
+        .disable_version_flag(true)
+}
+
+/// Prints the path to a client on one line, with the metric of the route if known.
+///
+/// # Example output
+/// ```text
+/// 02:00:00:00:00:AA (VID -1) -> 02:00:00:00:00:01 via 02:00:00:00:00:02 on wlan0 (TQ 230)
+/// 02:00:00:00:00:BB (VID 5) -> local
+/// ```
+pub fn print_path(path: &ClientPath, vendor: bool) {
+    let client = print_mac(path.client, vendor);
+    let Some(route) = &path.route else {
+        println!("{} (VID {}) -> local", client, print_vid(path.vid));
+        return;
+    };
+
+    let metric = match (route.tq, route.throughput) {
+        (Some(tq), _) => format!(" (TQ {})", tq),
+        (None, Some(kbps)) => format!(" ({})", Bandwidth::from_kbps(kbps)),
+        (None, None) => String::new(),
+    };
+    println!(
+        "{} (VID {}) -> {} via {} on {}{}",
+        client,
+        print_vid(path.vid),
+        print_mac(route.originator, vendor),
+        print_mac(route.next_hop, vendor),
+        route.outgoing_if,
+        metric
+    );
+}
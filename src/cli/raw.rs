@@ -0,0 +1,185 @@
+use crate::model::{AttrObject, AttrValue, AttrValueForSend, Attribute, Command};
+
+use clap::{Arg, ArgAction};
+use macaddr::MacAddr6;
+
+/// Creates the CLI command for sending raw BATMAN-adv requests.
+///
+/// # Returns
+/// - A `clap::Command` configured with:
+///   - Name: `"raw"`
+///   - Short description: `"Send a raw BATMAN-adv request and print the reply."`
+///   - `--cmd NAME`: the command, as named in `linux/uapi/batman_adv.h` (see
+///     [`Command::NAMES`]), with or without the `BATADV_CMD_` prefix
+///   - `--attr NAME=TYPE:VALUE`, repeatable: an attribute of the request (see
+///     [`parse_attr`])
+///   - `--dump`: send a dump request instead of a single one
+///   - Version flag disabled
+pub fn cmd_raw() -> clap::Command {
+    clap::Command::new("raw")
+        .about("Send a raw BATMAN-adv request and print the reply.")
+        .long_about(
+            "Send a raw BATMAN-adv request and print the attributes of the reply, for \
+             debugging kernel features not modeled yet. The mesh interface is added as \
+             MESH_IFINDEX unless an attribute selects it. SET_* commands only wait for \
+             the kernel to acknowledge them.",
+        )
+        .override_usage(
+            "\trobctl [options] raw --cmd <name> [--attr <name>=<type>:<value>]... [--dump]\n",
+        )
+        .arg(
+            Arg::new("cmd")
+                .long("cmd")
+                .value_name("name")
+                .required(true)
+                .value_parser(|s: &str| {
+                    s.parse::<Command>()
+                        .map_err(|e| e.to_string().trim_start_matches("Error - ").to_string())
+                })
+                .help("Command to send, e.g. GET_ORIGINATORS"),
+        )
+        .arg(
+            Arg::new("attr")
+                .long("attr")
+                .value_name("name=type:value")
+                .action(ArgAction::Append)
+                .value_parser(parse_attr)
+                .help("Attribute to send, e.g. MESH_IFINDEX=u32:3 or HOP_PENALTY=u8:30"),
+        )
+        .arg(
+            Arg::new("dump")
+                .long("dump")
+                .short('d')
+                .action(ArgAction::SetTrue)
+                .help("Send a dump request and print every reply"),
+        )
+        .disable_version_flag(true)
+}
+
+/// Parses an attribute of `raw` given as `NAME=TYPE:VALUE`.
+///
+/// `NAME` is an attribute of `linux/uapi/batman_adv.h` (see [`Attribute::NAMES`]),
+/// with or without the `BATADV_ATTR_` prefix. `TYPE` is one of `u8`, `u16`, `u32`,
+/// `u64`, `i32`, `str`, `mac` or `hex` (raw bytes, e.g. `0a0b0c`); `NAME=flag`
+/// sends a presence-only attribute.
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::cli::raw::parse_attr;
+/// use batman_robin::{AttrValueForSend, Attribute};
+///
+/// let (attr, value) = parse_attr("MESH_IFINDEX=u32:3").unwrap();
+/// assert_eq!(attr, Attribute::BatadvAttrMeshIfindex);
+/// assert!(matches!(value, AttrValueForSend::U32(3)));
+///
+/// let (_, value) = parse_attr("orig_address=mac:02:00:00:00:00:01").unwrap();
+/// assert!(matches!(value, AttrValueForSend::Bytes(b) if b == [2, 0, 0, 0, 0, 1]));
+///
+/// assert!(parse_attr("HOP_PENALTY=u8:300").is_err());
+/// assert!(parse_attr("HOP_PENALTY").is_err());
+/// ```
+pub fn parse_attr(s: &str) -> Result<(Attribute, AttrValueForSend), String> {
+    let (name, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected NAME=TYPE:VALUE, got '{}'", s))?;
+    let attr = name
+        .parse::<Attribute>()
+        .map_err(|e| e.to_string().trim_start_matches("Error - ").to_string())?;
+    if value == "flag" {
+        return Ok((attr, AttrValueForSend::Flag));
+    }
+
+    let (ty, value) = value
+        .split_once(':')
+        .ok_or_else(|| format!("expected TYPE:VALUE after '{}=', got '{}'", name, value))?;
+    let invalid = |e: &dyn std::fmt::Display| format!("invalid {} value '{}': {}", ty, value, e);
+    let value = match ty {
+        "u8" => AttrValueForSend::U8(value.parse().map_err(|e| invalid(&e))?),
+        "u16" => AttrValueForSend::U16(value.parse().map_err(|e| invalid(&e))?),
+        "u32" => AttrValueForSend::U32(value.parse().map_err(|e| invalid(&e))?),
+        "u64" => AttrValueForSend::U64(value.parse().map_err(|e| invalid(&e))?),
+        "i32" => AttrValueForSend::I32(value.parse().map_err(|e| invalid(&e))?),
+        "str" => AttrValueForSend::String(value.to_string()),
+        "mac" => {
+            let mac: MacAddr6 = value.parse().map_err(|e| invalid(&e))?;
+            AttrValueForSend::Bytes(mac.as_bytes().to_vec())
+        }
+        "hex" => AttrValueForSend::Bytes(parse_hex(value).ok_or_else(|| invalid(&"not hex"))?),
+        other => {
+            return Err(format!(
+                "unknown type '{}', expected u8, u16, u32, u64, i32, str, mac, hex or flag",
+                other
+            ));
+        }
+    };
+    Ok((attr, value))
+}
+
+/// Decodes a string of hex digit pairs.
+fn parse_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Prints the attributes of the replies to a `raw` request, one per line and named
+/// as in `linux/uapi/batman_adv.h`, with a blank line between replies.
+///
+/// Nested attribute sets are indented; attributes unknown to this crate are
+/// printed by number with their payload in hex.
+pub fn print_objects(objects: &[AttrObject]) {
+    for (i, obj) in objects.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        print_object(obj, 0);
+    }
+}
+
+/// Prints the attributes of `obj`, indented by `depth` levels.
+fn print_object(obj: &AttrObject, depth: usize) {
+    let indent = "  ".repeat(depth);
+    for (attr, value) in obj {
+        let name = attr.name().trim_start_matches("BATADV_ATTR_");
+        match value {
+            AttrValue::Nested(nested) => {
+                println!("{}{}:", indent, name);
+                print_object(nested, depth + 1);
+            }
+            value => println!("{}{}: {}", indent, name, format_value(value)),
+        }
+    }
+    for (number, payload) in obj.unknown() {
+        println!("{}#{}: {}", indent, number, format_hex(payload));
+    }
+}
+
+/// Formats a decoded attribute value.
+pub(crate) fn format_value(value: &AttrValue) -> String {
+    match value {
+        AttrValue::U8(v) => v.to_string(),
+        AttrValue::U16(v) => v.to_string(),
+        AttrValue::U32(v) => v.to_string(),
+        AttrValue::U64(v) => v.to_string(),
+        AttrValue::I32(v) => v.to_string(),
+        AttrValue::Flag => "true".to_string(),
+        AttrValue::Mac(mac) => mac.to_string(),
+        AttrValue::Bytes(bytes) => format_hex(bytes),
+        AttrValue::String(s) => format!("{:?}", s),
+        AttrValue::Nested(_) => "{...}".to_string(),
+    }
+}
+
+/// Formats bytes as space-separated hex digit pairs.
+pub(crate) fn format_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,64 @@
+use super::utils::parse_duration;
+
+use clap::{Arg, Command, value_parser};
+
+/// Creates the CLI command for recording snapshots of the mesh tables.
+///
+/// # Returns
+/// - A `clap::Command` configured with:
+///   - Name: `"record"`
+///   - Short description: `"Record snapshots of the mesh tables to rotating files."`
+///   - Usage override:
+///       ```text
+///       robctl [options] record --out <dir> [--interval <duration>] [--max-files <n>] [--max-size <MiB>]
+///       ```
+///   - Required option `--out`: directory the files are written to
+///   - Option `--interval`: time between snapshots (default `10s`, see [`parse_duration`])
+///   - Option `--max-files`: number of files kept (default 10)
+///   - Option `--max-size`: size in MiB at which a new file is started (default 10)
+///   - Version flag disabled
+pub fn cmd_record() -> Command {
+    Command::new("record")
+        .about("Record snapshots of the mesh tables to rotating files.")
+        .long_about(
+            "Record snapshots of the originator, neighbor, gateway and translation tables \
+             to rotating JSON Lines files, one snapshot per line, until interrupted.",
+        )
+        .override_usage(
+            "\trobctl [options] record --out <dir> [--interval <duration>] [--max-files <n>] [--max-size <MiB>]\n",
+        )
+        .arg(
+            Arg::new("out")
+                .long("out")
+                .short('o')
+                .value_name("dir")
+                .required(true)
+                .help("Directory the snapshot files are written to"),
+        )
+        .arg(
+            Arg::new("interval")
+                .long("interval")
+                .short('i')
+                .value_name("duration")
+                .default_value("10s")
+                .value_parser(parse_duration)
+                .help("Time between snapshots, e.g. 10s, 500ms or 5m"),
+        )
+        .arg(
+            Arg::new("max_files")
+                .long("max-files")
+                .value_name("n")
+                .default_value("10")
+                .value_parser(value_parser!(usize))
+                .help("Number of files kept, the oldest are deleted"),
+        )
+        .arg(
+            Arg::new("max_size")
+                .long("max-size")
+                .value_name("MiB")
+                .default_value("10")
+                .value_parser(value_parser!(u64).range(1..))
+                .help("Size at which a new file is started"),
+        )
+        .disable_version_flag(true)
+}
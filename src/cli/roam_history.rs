@@ -0,0 +1,72 @@
+use super::utils::{parse_duration, print_mac, print_vid};
+use crate::roaming::RoamEvent;
+
+use std::time::UNIX_EPOCH;
+
+use clap::{Arg, Command};
+use macaddr::MacAddr6;
+
+/// Creates the CLI command for following the moves of a client between originators.
+///
+/// # Returns
+/// - A `clap::Command` configured with:
+///   - Name: `"roam-history"`, alias `"roam_history"`
+///   - Short description: `"Show when a client moves between originators."`
+///   - Required positional `client`: MAC address of the client
+///   - `--interval`: time between reads of the global translation table (default
+///     `5s`, see [`parse_duration`])
+///   - Version flag disabled
+pub fn cmd_roam_history() -> Command {
+    Command::new("roam-history")
+        .alias("roam_history")
+        .about("Show when a client moves between originators.")
+        .long_about(
+            "Follow the global translation table and show when a client moves between \
+             originators, until interrupted, and whether the mesh handled the move as \
+             roaming (ROAM flag).",
+        )
+        .override_usage("\trobctl [options] roam-history <client> [--interval <duration>]\n")
+        .arg(
+            Arg::new("client")
+                .value_name("client")
+                .required(true)
+                .value_parser(|s: &str| s.parse::<MacAddr6>().map_err(|e| e.to_string()))
+                .help("MAC address of the client"),
+        )
+        .arg(
+            Arg::new("interval")
+                .long("interval")
+                .short('i')
+                .value_name("duration")
+                .default_value("5s")
+                .value_parser(parse_duration)
+                .help("Time between reads of the global translation table, e.g. 5s"),
+        )
+        .disable_version_flag(true)
+}
+
+/// Prints a move of a client, with the UTC time it was seen.
+///
+/// # Example output
+/// ```text
+/// 14:02:11 UTC  02:00:00:00:00:AA (VID -1): 02:00:00:00:00:01 -> 02:00:00:00:00:02 (roaming)
+/// ```
+pub fn print_roam_event(event: &RoamEvent, vendor: bool) {
+    let secs = event
+        .at
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    println!(
+        "{:02}:{:02}:{:02} UTC  {} (VID {}): {} -> {}{}",
+        secs / 3600 % 24,
+        secs / 60 % 60,
+        secs % 60,
+        print_mac(event.client, vendor),
+        print_vid(event.vid),
+        event
+            .from
+            .map_or_else(|| "-".to_string(), |from| print_mac(from, vendor)),
+        print_mac(event.to, vendor),
+        if event.roam_flagged { " (roaming)" } else { "" }
+    );
+}
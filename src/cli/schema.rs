@@ -0,0 +1,50 @@
+use crate::output::{COMMANDS, schema};
+
+use clap::{Arg, Command};
+
+/// Creates the CLI command for printing the JSON Schema of the `--json` output.
+///
+/// # Returns
+/// - A `clap::Command` configured with:
+///   - Name: `"schema"`
+///   - Short and long description: `"Print the JSON Schema of the --json output."`
+///   - Usage override:
+///       ```text
+///       robctl schema [COMMAND]
+///       ```
+///   - Optional positional argument `"command"`: one of [`COMMANDS`]
+///   - Version flag disabled
+pub fn cmd_schema() -> Command {
+    Command::new("schema")
+        .about("Print the JSON Schema of the --json output.")
+        .long_about(
+            "Print the JSON Schema of the --json output of COMMAND, or an object mapping \
+             every command to its schema.",
+        )
+        .override_usage("\trobctl schema [COMMAND]\n")
+        .arg(
+            Arg::new("command")
+                .value_name("COMMAND")
+                .value_parser(COMMANDS.to_vec())
+                .help("Command to print the output schema of"),
+        )
+        .disable_version_flag(true)
+}
+
+/// Prints the JSON Schema of the output of `command`, or an object mapping every
+/// command with JSON output to its schema.
+pub fn print_schema(command: Option<&str>) {
+    let value = match command {
+        Some(command) => serde_json::to_value(schema(command)),
+        None => COMMANDS
+            .iter()
+            .map(|command| Ok((command.to_string(), serde_json::to_value(schema(command))?)))
+            .collect::<Result<serde_json::Map<_, _>, _>>()
+            .map(serde_json::Value::Object),
+    };
+    let value = value.expect("JSON Schemas serialize to JSON");
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&value).expect("JSON values serialize")
+    );
+}
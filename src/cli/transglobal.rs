@@ -1,4 +1,4 @@
-use super::utils::print_vid;
+use super::utils::{arg_flag_filters, arg_summary, print_mac, print_vid};
 use crate::TransglobalEntry;
 use crate::model::ClientFlags;
 
@@ -17,6 +17,9 @@ use comfy_table::{Cell, CellAlignment, ContentArrangement, Table};
 ///       ```text
 ///       robctl [options] transglobal|tg [options]
 ///       ```
+///   - Optional `--roaming`, `--isolated`, `--temp`, `--wifi`: only show the clients
+///     with all of the given flags
+///   - Optional `--summary`: show entry counts instead of the entries
 ///   - Version flag disabled
 pub fn cmd_transglobal() -> Command {
     Command::new("transglobal")
@@ -24,6 +27,8 @@ pub fn cmd_transglobal() -> Command {
         .about("Display global translation table.")
         .long_about("Display global translation table.")
         .override_usage("\trobctl [options] transglobal|tg [options]\n")
+        .args(arg_flag_filters())
+        .arg(arg_summary())
         .disable_version_flag(true)
 }
 
@@ -31,6 +36,7 @@ pub fn cmd_transglobal() -> Command {
 ///
 /// # Arguments
 /// - `entries`: Slice of `TransglobalEntry` to display
+/// - `vendor`: Append the vendor of MAC addresses (see [`print_mac`]).
 ///
 /// # Table columns
 /// - `Client`: MAC address of the client, with `*` prefix if it is the best entry
@@ -41,7 +47,7 @@ pub fn cmd_transglobal() -> Command {
 /// - `Originator`: MAC address of the originator node
 /// - `TTVN`: Current translation table version number for this entry
 /// - `CRC32`: CRC32 checksum in hexadecimal
-pub fn print_transglobal(entries: &[TransglobalEntry]) {
+pub fn print_transglobal(entries: &[TransglobalEntry], vendor: bool) {
     let mut table = Table::new();
     table
         .load_preset(UTF8_FULL)
@@ -80,12 +86,12 @@ pub fn print_transglobal(entries: &[TransglobalEntry]) {
         };
 
         let client_text = if e.is_best {
-            format!("* {}", e.client)
+            format!("* {}", print_mac(e.client, vendor))
         } else {
-            e.client.to_string()
+            print_mac(e.client, vendor)
         };
         let client_cell = Cell::new(client_text);
-        let orig_cell = Cell::new(e.orig.to_string());
+        let orig_cell = Cell::new(print_mac(e.orig, vendor));
 
         table.add_row(vec![
             client_cell.set_alignment(CellAlignment::Right),
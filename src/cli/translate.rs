@@ -0,0 +1,79 @@
+use std::fmt;
+use std::net::Ipv4Addr;
+
+use clap::{Arg, Command};
+use macaddr::MacAddr6;
+
+/// A destination given to `translate`: a MAC address or an IPv4 address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Destination {
+    /// A client or originator MAC address.
+    Mac(MacAddr6),
+    /// An IPv4 address, resolved through the DAT cache or the local ARP table.
+    Ipv4(Ipv4Addr),
+}
+
+impl fmt::Display for Destination {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Destination::Mac(mac) => mac.fmt(f),
+            Destination::Ipv4(ip) => ip.fmt(f),
+        }
+    }
+}
+
+/// Creates the CLI command for translating a destination to its originator.
+///
+/// # Returns
+/// - A `clap::Command` configured with:
+///   - Name: `"translate"`
+///   - Alias: `"t"`
+///   - Short and long description: `"Translate a destination to the originator responsible for it."`
+///   - Usage override:
+///       ```text
+///       robctl [options] translate|t MAC|IPv4
+///       ```
+///   - Required positional argument `destination`, parsed by [`parse_destination`]
+///   - Version flag disabled
+pub fn cmd_translate() -> Command {
+    Command::new("translate")
+        .alias("t")
+        .about("Translate a destination to the originator responsible for it.")
+        .long_about(
+            "Translate a destination to the originator responsible for it.\n\
+             IPv4 addresses are resolved through the D.A.T. cache, then the local ARP table.",
+        )
+        .override_usage("\trobctl [options] translate|t MAC|IPv4\n")
+        .arg(
+            Arg::new("destination")
+                .index(1)
+                .required(true)
+                .value_name("MAC|IPv4")
+                .value_parser(parse_destination)
+                .help("MAC address or IPv4 address to translate"),
+        )
+        .disable_version_flag(true)
+}
+
+/// Parses a `translate` destination.
+///
+/// # Example
+/// ```
+/// use batman_robin::cli::translate::{Destination, parse_destination};
+///
+/// assert_eq!(
+///     parse_destination("10.0.0.5"),
+///     Ok(Destination::Ipv4("10.0.0.5".parse().unwrap()))
+/// );
+/// assert!(matches!(parse_destination("02:00:00:00:00:05"), Ok(Destination::Mac(_))));
+/// assert!(parse_destination("bat0").is_err());
+/// ```
+pub fn parse_destination(value: &str) -> Result<Destination, String> {
+    if let Ok(ip) = value.parse() {
+        return Ok(Destination::Ipv4(ip));
+    }
+    match value.parse() {
+        Ok(mac) => Ok(Destination::Mac(mac)),
+        Err(_) => Err(format!("'{}' is neither a MAC nor an IPv4 address", value)),
+    }
+}
@@ -1,4 +1,4 @@
-use super::utils::print_vid;
+use super::utils::{arg_flag_filters, arg_max_age, arg_summary, print_mac, print_vid};
 use crate::TranslocalEntry;
 use crate::model::ClientFlags;
 
@@ -17,6 +17,10 @@ use comfy_table::{Cell, CellAlignment, ContentArrangement, Table};
 ///       ```text
 ///       robctl [options] translocal|tl [options]
 ///       ```
+///   - Optional `--max-age SECONDS`: hide entries last seen more than SECONDS ago
+///   - Optional `--roaming`, `--isolated`, `--temp`, `--wifi`: only show the clients
+///     with all of the given flags
+///   - Optional `--summary`: show entry counts instead of the entries
 ///   - Version flag disabled
 pub fn cmd_translocal() -> Command {
     Command::new("translocal")
@@ -24,6 +28,9 @@ pub fn cmd_translocal() -> Command {
         .about("Display local translation table.")
         .long_about("Display local translation table.")
         .override_usage("\trobctl [options] translocal|tl [options]\n")
+        .arg(arg_max_age())
+        .args(arg_flag_filters())
+        .arg(arg_summary())
         .disable_version_flag(true)
 }
 
@@ -31,6 +38,7 @@ pub fn cmd_translocal() -> Command {
 ///
 /// # Arguments
 /// - `entries`: Slice of `TranslocalEntry` to display
+/// - `vendor`: Append the vendor of MAC addresses (see [`print_mac`]).
 ///
 /// # Table columns
 /// - `Client`: MAC address of the client
@@ -38,9 +46,9 @@ pub fn cmd_translocal() -> Command {
 /// - `Flags`: Concatenation of client flags:
 ///     - `R` = ROAM, `P` = NOPURGE, `N` = NEW, `X` = PENDING,
 ///       `W` = WIFI, `I` = ISOLA; `.` if flag not set
-/// - `Last seen`: Time since last seen, in seconds.milliseconds
+/// - `Last seen`: Time since last seen, formatted for humans, e.g. `4.2s`
 /// - `CRC32`: CRC32 checksum in hexadecimal
-pub fn print_translocal(entries: &[TranslocalEntry]) {
+pub fn print_translocal(entries: &[TranslocalEntry], vendor: bool) {
     let mut table = Table::new();
     table
         .load_preset(UTF8_FULL)
@@ -86,13 +94,13 @@ pub fn print_translocal(entries: &[TranslocalEntry]) {
             '.'
         };
 
-        let client_cell = Cell::new(e.client.to_string());
+        let client_cell = Cell::new(print_mac(e.client, vendor));
 
         table.add_row(vec![
             client_cell,
             Cell::new(print_vid(e.vid)),
             Cell::new(format!("[{}{}{}{}{}{}]", r, p, n, x, w, i)),
-            Cell::new(format!("{}.{:03}", e.last_seen_secs, e.last_seen_msecs)),
+            Cell::new(e.last_seen),
             Cell::new(format!("0x{:08x}", e.crc32)),
         ]);
     }
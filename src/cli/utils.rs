@@ -1,3 +1,9 @@
+use crate::model::{ClientFlags, TtTableStats};
+
+use clap::{Arg, ArgAction, ArgMatches};
+use macaddr::MacAddr6;
+use std::time::Duration;
+
 /// Converts a VLAN ID stored in a `u16` to a printable integer.
 ///
 /// The `vid` format uses the highest bit (bit 15) as a validity flag:
@@ -28,3 +34,216 @@ pub fn print_vid(vid: u16) -> i32 {
         -1
     }
 }
+
+/// Formats a MAC address, followed by its vendor in parentheses if `vendor` is set
+/// and the vendor is known.
+///
+/// The address is replaced by its host name if a `bat-hosts` file is installed
+/// (see [`bat_hosts`](super::bat_hosts)) and names it. Vendors are only looked up
+/// with the `oui` feature; without it the address is formatted alone.
+///
+/// # Example
+/// ```
+/// use batman_robin::cli::utils::print_mac;
+///
+/// let mac = "02:00:00:00:00:01".parse().unwrap();
+/// assert_eq!(print_mac(mac, false), "02:00:00:00:00:01");
+/// // Locally administered addresses have no vendor.
+/// assert_eq!(print_mac(mac, true), "02:00:00:00:00:01");
+/// ```
+pub fn print_mac(mac: MacAddr6, vendor: bool) -> String {
+    let host = match super::bat_hosts::name(mac) {
+        Some(name) => name.to_string(),
+        None => mac.to_string(),
+    };
+
+    #[cfg(feature = "oui")]
+    if let Some(name) = crate::oui::vendor(mac).filter(|_| vendor) {
+        return format!("{} ({})", host, name);
+    }
+    #[cfg(not(feature = "oui"))]
+    let _ = vendor;
+
+    host
+}
+
+/// Returns the algorithm whose columns a table of entries should be printed with.
+///
+/// The metrics the entries carry win over `algo_name`: entries with only a TQ
+/// are `"BATMAN_IV"`, entries with only a throughput `"BATMAN_V"`. This keeps
+/// tables readable when `algo_name` is a guess, e.g. the default routing algorithm
+/// of a kernel not reporting the algorithm of the mesh, or an algorithm unknown to
+/// robctl, which gets the `"BATMAN_IV"` columns.
+///
+/// # Arguments
+/// - `algo_name`: The routing algorithm of the mesh interface.
+/// - `tq`: Whether any entry carries a TQ.
+/// - `throughput`: Whether any entry carries a throughput.
+///
+/// # Example
+/// ```
+/// use batman_robin::cli::utils::table_algo;
+///
+/// assert_eq!(table_algo("BATMAN_V", false, false), "BATMAN_V");
+/// assert_eq!(table_algo("BATMAN_IV", false, true), "BATMAN_V");
+/// assert_eq!(table_algo("BATMAN_VI", false, false), "BATMAN_IV");
+/// ```
+pub fn table_algo(algo_name: &str, tq: bool, throughput: bool) -> &str {
+    match (tq, throughput, algo_name) {
+        (true, false, _) => "BATMAN_IV",
+        (false, true, _) => "BATMAN_V",
+        (_, _, "BATMAN_V") => "BATMAN_V",
+        _ => "BATMAN_IV",
+    }
+}
+
+/// Parses a number of seconds, possibly fractional, into a `Duration`.
+///
+/// # Example
+/// ```
+/// use batman_robin::cli::utils::parse_seconds;
+/// use std::time::Duration;
+///
+/// assert_eq!(parse_seconds("30"), Ok(Duration::from_secs(30)));
+/// assert_eq!(parse_seconds("1.5"), Ok(Duration::from_millis(1500)));
+/// assert!(parse_seconds("-1").is_err());
+/// ```
+pub fn parse_seconds(s: &str) -> Result<Duration, String> {
+    let secs: f64 = s
+        .parse()
+        .map_err(|_| format!("invalid number of seconds: {}", s))?;
+    Duration::try_from_secs_f64(secs).map_err(|_| format!("invalid number of seconds: {}", s))
+}
+
+/// Parses a duration with an optional `ms`, `s`, `m` or `h` unit, seconds by default.
+///
+/// # Example
+/// ```
+/// use batman_robin::cli::utils::parse_duration;
+/// use std::time::Duration;
+///
+/// assert_eq!(parse_duration("10s"), Ok(Duration::from_secs(10)));
+/// assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+/// assert_eq!(parse_duration("2m"), Ok(Duration::from_secs(120)));
+/// assert_eq!(parse_duration("1.5"), Ok(Duration::from_millis(1500)));
+/// assert!(parse_duration("10d").is_err());
+/// ```
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let (value, scale) = if let Some(ms) = s.strip_suffix("ms") {
+        (ms, 0.001)
+    } else if let Some(secs) = s.strip_suffix('s') {
+        (secs, 1.0)
+    } else if let Some(mins) = s.strip_suffix('m') {
+        (mins, 60.0)
+    } else if let Some(hours) = s.strip_suffix('h') {
+        (hours, 3600.0)
+    } else {
+        (s, 1.0)
+    };
+
+    let value: f64 = value
+        .parse()
+        .map_err(|_| format!("invalid duration: {}", s))?;
+    Duration::try_from_secs_f64(value * scale).map_err(|_| format!("invalid duration: {}", s))
+}
+
+/// Creates the `--max-age SECONDS` argument hiding table entries last seen longer
+/// ago than the given number of seconds.
+pub fn arg_max_age() -> Arg {
+    Arg::new("max_age")
+        .long("max-age")
+        .value_name("SECONDS")
+        .value_parser(parse_seconds)
+        .help("Hide entries last seen more than SECONDS ago")
+}
+
+/// Removes the entries last seen more than `max_age` ago, if set.
+pub fn retain_max_age<T: crate::LastSeenEntry>(entries: &mut Vec<T>, max_age: Option<&Duration>) {
+    if let Some(max_age) = max_age {
+        entries.retain(|entry| entry.seen_within(*max_age));
+    }
+}
+
+/// Client flag filters of the translation table commands: the argument id, the
+/// flag it selects and its help.
+const FLAG_FILTERS: [(&str, ClientFlags, &str); 4] = [
+    ("roaming", ClientFlags::ROAM, "Only show roaming clients"),
+    ("isolated", ClientFlags::ISOLA, "Only show isolated clients"),
+    ("temp", ClientFlags::TEMP, "Only show temporary clients"),
+    (
+        "wifi",
+        ClientFlags::WIFI,
+        "Only show clients connected via Wi-Fi",
+    ),
+];
+
+/// Creates the `--roaming`, `--isolated`, `--temp` and `--wifi` arguments showing
+/// only the translation table entries with the given client flags.
+pub fn arg_flag_filters() -> impl Iterator<Item = Arg> {
+    FLAG_FILTERS
+        .into_iter()
+        .map(|(id, _, help)| Arg::new(id).long(id).action(ArgAction::SetTrue).help(help))
+}
+
+/// Removes the entries lacking any of the client flags selected with the
+/// [`arg_flag_filters`] arguments.
+pub fn retain_flags<T: crate::ClientFlagsEntry>(entries: &mut Vec<T>, matches: &ArgMatches) {
+    let wanted = FLAG_FILTERS
+        .into_iter()
+        .filter(|(id, _, _)| matches.get_flag(id))
+        .fold(ClientFlags::empty(), |wanted, (_, flag, _)| wanted | flag);
+    if !wanted.is_empty() {
+        entries.retain(|entry| entry.has_flags(wanted));
+    }
+}
+
+/// Creates the `--summary` argument printing statistics of a translation table
+/// instead of its entries.
+pub fn arg_summary() -> Arg {
+    Arg::new("summary")
+        .long("summary")
+        .action(ArgAction::SetTrue)
+        .help("Show entry counts per VID, flag and originator instead of the entries")
+}
+
+/// Prints the statistics of a translation table, one count per line.
+///
+/// The per-originator counts are left out if there are none, i.e. for the local
+/// table.
+pub fn print_tt_summary(stats: &TtTableStats, vendor: bool) {
+    println!("entries: {}", stats.entries);
+    println!("clients: {}", stats.clients);
+    println!("per VID:");
+    for (vid, count) in &stats.per_vid {
+        println!("  {}: {}", print_vid(*vid), count);
+    }
+    println!("per flag:");
+    for (flag, count) in &stats.per_flag {
+        println!("  {}: {}", flag, count);
+    }
+    if !stats.per_originator.is_empty() {
+        println!("per originator:");
+        for (orig, count) in &stats.per_originator {
+            println!("  {}: {}", print_mac(*orig, vendor), count);
+        }
+    }
+}
+
+/// Prints the [`ErrorDocument`](crate::output::ErrorDocument) of `err` on a single
+/// line of standard error.
+pub fn print_json_error(err: &crate::RobinError) {
+    eprintln!(
+        "{}",
+        serde_json::to_string(&crate::output::ErrorDocument::from(err))
+            .expect("output documents serialize to JSON")
+    );
+}
+
+/// Prints a JSON output document on a single line, so the documents of several
+/// commands, e.g. with `--all-meshes` or `--batch`, form a JSON Lines stream.
+pub fn print_json<T: serde::Serialize>(doc: &crate::output::Document<T>) {
+    println!(
+        "{}",
+        serde_json::to_string(doc).expect("output documents serialize to JSON")
+    );
+}
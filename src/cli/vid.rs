@@ -0,0 +1,78 @@
+use super::utils::print_mac;
+use crate::VlanClients;
+use crate::model::ClientFlags;
+
+use clap::{Arg, Command};
+use macaddr::MacAddr6;
+
+/// Creates the CLI command for displaying the state of one VLAN of the mesh.
+///
+/// # Returns
+/// - A `clap::Command` configured with:
+///   - Name: `"vid"`
+///   - Short description: `"Display the clients of a VLAN."`
+///   - Required positional `vid`: VLAN id, 0 to 4095
+///   - Subcommand `clients`: list the clients of the VLAN, grouped by originator
+///   - Version flag disabled
+pub fn cmd_vid() -> Command {
+    Command::new("vid")
+        .about("Display the clients of a VLAN.")
+        .long_about(
+            "Display the clients of a VLAN, from the local and global translation tables, \
+             grouped by the originator announcing them.",
+        )
+        .override_usage("\trobctl [options] vid <vid> clients\n")
+        .arg(
+            Arg::new("vid")
+                .value_name("vid")
+                .required(true)
+                .value_parser(clap::value_parser!(u16).range(0..4096))
+                .help("VLAN id, 0 to 4095"),
+        )
+        .subcommand(
+            Command::new("clients")
+                .about("List the clients of the VLAN, grouped by originator.")
+                .disable_version_flag(true),
+        )
+        .subcommand_required(true)
+        .disable_version_flag(true)
+}
+
+/// Prints the clients of a VLAN: first the clients of this node, then those of each
+/// originator. The best entry of a client is marked with `*`; flags set on an entry
+/// follow in parentheses.
+///
+/// # Example output
+/// ```text
+/// VLAN 5: 3 clients
+/// local (1):
+///   02:00:00:00:00:AA (WIFI)
+/// 02:00:00:00:00:01 (2):
+///   * 02:00:00:00:00:BB
+///     02:00:00:00:00:CC (ROAM)
+/// ```
+pub fn print_vlan_clients(vlan: &VlanClients, vendor: bool) {
+    println!("VLAN {}: {} clients", vlan.vid, vlan.clients());
+    if !vlan.local.is_empty() {
+        println!("local ({}):", vlan.local.len());
+        for e in &vlan.local {
+            println!("  {}", print_client(e.client, e.flags, vendor));
+        }
+    }
+    for (orig, entries) in &vlan.per_originator {
+        println!("{} ({}):", print_mac(*orig, vendor), entries.len());
+        for e in entries {
+            let best = if e.is_best { "*" } else { " " };
+            println!("  {} {}", best, print_client(e.client, e.flags, vendor));
+        }
+    }
+}
+
+/// Formats a client and the names of its flags, if any.
+fn print_client(client: MacAddr6, flags: ClientFlags, vendor: bool) -> String {
+    let names: Vec<&str> = flags.iter_names().map(|(name, _)| name).collect();
+    match names.is_empty() {
+        true => print_mac(client, vendor),
+        false => format!("{} ({})", print_mac(client, vendor), names.join(", ")),
+    }
+}
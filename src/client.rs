@@ -1,6 +1,17 @@
 use crate::commands;
 use crate::error::RobinError;
 use crate::model;
+use crate::netlink::{self, MeshTransport, NetlinkTransport, RateLimit, RetryPolicy};
+use crate::query_cache::{Query, QueryCache};
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::{Stream, TryStreamExt, stream};
+use macaddr::MacAddr6;
 
 /// High-level client for interacting with the BATMAN-adv mesh network.
 ///
@@ -8,6 +19,13 @@ use crate::model;
 /// translation tables, routing algorithms, gateways, and neighbors.
 ///
 /// All methods return a `Result` containing either the requested data or a `RobinError`.
+/// Methods changing the mesh configuration check [`can_administer`](crate::can_administer)
+/// first and fail early with `RobinError::PermissionDenied` when run unprivileged.
+///
+/// Requests are sent over a [`MeshTransport`], by default the [`NetlinkTransport`]
+/// to the local kernel. Other backends are plugged in with
+/// [`RobinClient::with_transport`]; interface management and the default routing
+/// algorithm go through rtnetlink and are only available with the default transport.
 ///
 /// # Example
 ///
@@ -28,12 +46,10 @@ use crate::model;
 /// # Ok(())
 /// # }
 /// ```
-pub struct RobinClient;
-
-impl Default for RobinClient {
-    fn default() -> Self {
-        Self::new()
-    }
+#[derive(Debug, Clone, Default)]
+pub struct RobinClient<T = NetlinkTransport> {
+    transport: T,
+    cache: Option<Arc<QueryCache>>,
 }
 
 impl RobinClient {
@@ -46,33 +62,28 @@ impl RobinClient {
     /// let client = RobinClient::new();
     /// ```
     pub fn new() -> Self {
-        Self {}
+        Self::default()
     }
 
-    /// Converts a network interface name to its corresponding index.
-    ///
-    /// # Arguments
-    /// * `ifname` - Name of the network interface (e.g., "wlan0").
+    /// Returns a builder to configure a `RobinClient`.
     ///
     /// # Example
     ///
-    /// ```no_run
-    /// # use batman_robin::RobinClient;
-    /// # async fn example() -> Result<(), batman_robin::RobinError> {
-    /// # let client = RobinClient::new();
-    /// let idx = client.if_nametoindex("bat0").await?;
-    /// println!("Interface index: {}", idx);
-    /// # Ok(())
-    /// # }
     /// ```
-    pub async fn if_nametoindex(&self, ifname: &str) -> Result<u32, RobinError> {
-        commands::if_nametoindex(ifname).await
+    /// use batman_robin::{RetryPolicy, RobinClient};
+    /// let client = RobinClient::builder()
+    ///     .retry_policy(RetryPolicy::default())
+    ///     .build();
+    /// ```
+    pub fn builder() -> RobinClientBuilder {
+        RobinClientBuilder::default()
     }
 
-    /// Converts a network interface index to its corresponding name.
+    /// Adds or removes a physical interface from the mesh.
     ///
     /// # Arguments
-    /// * `ifindex` - Interface index.
+    /// * `iface` - Physical interface name or index
+    /// * `mesh_if` - Some(mesh_if) to add, None to remove
     ///
     /// # Example
     ///
@@ -80,16 +91,24 @@ impl RobinClient {
     /// # use batman_robin::RobinClient;
     /// # async fn example() -> Result<(), batman_robin::RobinError> {
     /// # let client = RobinClient::new();
-    /// let ifname = client.if_indextoname(3).await?;
-    /// println!("Interface name: {}", ifname);
+    /// client.set_interface("wlan1", Some("bat0")).await?;
+    /// client.set_interface("wlan1", None).await?;
+    /// client.set_interface(7, Some("bat0")).await?; // by ifindex
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn if_indextoname(&self, ifindex: u32) -> Result<String, RobinError> {
-        commands::if_indextoname(ifindex).await
+    pub async fn set_interface(
+        &self,
+        iface: impl Into<model::IfaceRef>,
+        mesh_if: Option<&str>,
+    ) -> Result<(), RobinError> {
+        self.change(async {
+            commands::set_interface(&self.transport.rtnl().await?, &iface.into(), mesh_if).await
+        })
+        .await
     }
 
-    /// Retrieves the list of originators for the given mesh interface.
+    /// Creates a new BATMAN-adv mesh interface with an optional routing algorithm.
     ///
     /// # Example
     ///
@@ -97,18 +116,23 @@ impl RobinClient {
     /// # use batman_robin::RobinClient;
     /// # async fn example() -> Result<(), batman_robin::RobinError> {
     /// # let client = RobinClient::new();
-    /// let originators = client.originators("bat0").await?;
-    /// for o in originators {
-    ///     println!("Originator: {}", o.originator);
-    /// }
+    /// client.create_interface("bat0", Some("BATMAN_V")).await?;
+    /// client.create_interface("bat1", None).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn originators(&self, mesh_if: &str) -> Result<Vec<model::Originator>, RobinError> {
-        commands::get_originators(mesh_if).await
+    pub async fn create_interface(
+        &self,
+        mesh_if: &str,
+        routing_algo: Option<&str>,
+    ) -> Result<(), RobinError> {
+        self.change(async {
+            commands::create_interface(&self.transport.rtnl().await?, mesh_if, routing_algo).await
+        })
+        .await
     }
 
-    /// Retrieves the list of gateways for the given mesh interface.
+    /// Destroys a BATMAN-adv mesh interface.
     ///
     /// # Example
     ///
@@ -116,18 +140,18 @@ impl RobinClient {
     /// # use batman_robin::RobinClient;
     /// # async fn example() -> Result<(), batman_robin::RobinError> {
     /// # let client = RobinClient::new();
-    /// let gateways = client.gateways("bat0").await?;
-    /// for g in gateways {
-    ///     println!("Gateway: {}", g.mac_addr);
-    /// }
+    /// client.destroy_interface("bat0").await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn gateways(&self, mesh_if: &str) -> Result<Vec<model::Gateway>, RobinError> {
-        commands::get_gateways_list(mesh_if).await
+    pub async fn destroy_interface(&self, mesh_if: &str) -> Result<(), RobinError> {
+        self.change(async {
+            commands::destroy_interface(&self.transport.rtnl().await?, mesh_if).await
+        })
+        .await
     }
 
-    /// Gets the current gateway mode and configuration for the mesh interface.
+    /// Renames a mesh interface, keeping its settings and hard interfaces.
     ///
     /// # Example
     ///
@@ -135,47 +159,35 @@ impl RobinClient {
     /// # use batman_robin::RobinClient;
     /// # async fn example() -> Result<(), batman_robin::RobinError> {
     /// # let client = RobinClient::new();
-    /// let gw_info = client.get_gw_mode("bat0").await?;
-    /// println!("Gateway mode: {:?}", gw_info.mode);
+    /// client.rename_interface("bat9", "bat0").await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn get_gw_mode(&self, mesh_if: &str) -> Result<model::GatewayInfo, RobinError> {
-        commands::get_gateway(mesh_if).await
+    pub async fn rename_interface(&self, old: &str, new: &str) -> Result<(), RobinError> {
+        self.change(async {
+            commands::rename_interface(&self.transport.rtnl().await?, old, new).await
+        })
+        .await
     }
 
-    /// Sets the gateway mode and optional bandwidth/selection parameters for the mesh interface.
-    ///
-    /// # Arguments
-    /// * `mode` - Gateway mode (`Off`, `Client`, `Server`)
-    /// * `down` - Optional downlink bandwidth in kbit/s
-    /// * `up` - Optional uplink bandwidth in kbit/s
-    /// * `sel_class` - Optional selection class (for clients)
-    /// * `mesh_if` - Mesh interface name
+    /// Counts the number of physical interfaces attached to the mesh.
     ///
     /// # Example
     ///
     /// ```no_run
-    /// use batman_robin::GwMode;
     /// # use batman_robin::RobinClient;
     /// # async fn example() -> Result<(), batman_robin::RobinError> {
     /// # let client = RobinClient::new();
-    /// client.set_gw_mode(GwMode::Server, Some(50000), Some(10000), None, "bat0").await?;
+    /// let count = client.count_interfaces("bat0").await?;
+    /// println!("Attached interfaces: {}", count);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn set_gw_mode(
-        &self,
-        mode: model::GwMode,
-        down: Option<u32>,
-        up: Option<u32>,
-        sel_class: Option<u32>,
-        mesh_if: &str,
-    ) -> Result<(), RobinError> {
-        commands::set_gateway(mode, down, up, sel_class, mesh_if).await
+    pub async fn count_interfaces(&self, mesh_if: &str) -> Result<u32, RobinError> {
+        commands::count_interfaces(&self.transport.rtnl().await?, mesh_if).await
     }
 
-    /// Retrieves the global translation table entries.
+    /// Lists the names of all BATMAN-adv mesh interfaces.
     ///
     /// # Example
     ///
@@ -183,43 +195,63 @@ impl RobinClient {
     /// # use batman_robin::RobinClient;
     /// # async fn example() -> Result<(), batman_robin::RobinError> {
     /// # let client = RobinClient::new();
-    /// let tg = client.transglobal("bat0").await?;
-    /// for entry in tg {
-    ///     println!("Client: {}", entry.client);
+    /// for mesh_if in client.list_meshes().await? {
+    ///     println!("{}: {} neighbors", mesh_if, client.neighbors(&mesh_if).await?.len());
     /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn transglobal(
-        &self,
-        mesh_if: &str,
-    ) -> Result<Vec<model::TransglobalEntry>, RobinError> {
-        commands::get_transglobal(mesh_if).await
+    pub async fn list_meshes(&self) -> Result<Vec<String>, RobinError> {
+        commands::list_meshes(&self.transport.rtnl().await?).await
     }
 
-    /// Retrieves the local translation table entries.
+    /// Streams the changes to mesh interfaces and their hard interfaces: interfaces
+    /// created, destroyed, added to or removed from a mesh, renamed, or changing
+    /// their link state.
+    ///
+    /// Every event also drops the cached interface names and indexes and the
+    /// [query cache](RobinClientBuilder::cache_ttl), so later queries of the client
+    /// see the change. The stream ends after the first error.
     ///
     /// # Example
     ///
     /// ```no_run
-    /// # use batman_robin::RobinClient;
+    /// # use batman_robin::{LinkEvent, RobinClient};
+    /// use futures::TryStreamExt;
     /// # async fn example() -> Result<(), batman_robin::RobinError> {
     /// # let client = RobinClient::new();
-    /// let tl = client.translocal("bat0").await?;
-    /// for entry in tl {
-    ///     println!("Client: {}", entry.client);
+    /// let mut events = std::pin::pin!(client.link_events());
+    /// while let Some(event) = events.try_next().await? {
+    ///     if let LinkEvent::HardifRemoved { ifname, mesh_if, .. } = event {
+    ///         println!("{} left {}", ifname, mesh_if);
+    ///     }
     /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn translocal(
-        &self,
-        mesh_if: &str,
-    ) -> Result<Vec<model::TranslocalEntry>, RobinError> {
-        commands::get_translocal(mesh_if).await
+    pub fn link_events(&self) -> impl Stream<Item = Result<model::LinkEvent, RobinError>> {
+        let client = self.clone();
+        stream::once(async move {
+            let watcher = client.transport.link_watcher().await?;
+            Ok::<_, RobinError>((client, watcher))
+        })
+        .map_ok(|state| {
+            stream::unfold(state, |(client, mut watcher)| async move {
+                let event = watcher.next().await?;
+                client.transport.invalidate_links();
+                client.invalidate_cache();
+                Some((event, (client, watcher)))
+            })
+        })
+        .try_flatten()
     }
 
-    /// Retrieves the list of neighbors.
+    /// Looks for likely misconfigurations of the mesh interface `mesh_if`.
+    ///
+    /// Currently warns if `mesh_if`, or one of its hard interfaces, is part of a
+    /// bridge while bridge loop avoidance is disabled, a setup in which nodes
+    /// bridging the mesh into the same LAN loop broadcasts between them. Each
+    /// warning carries the `robctl` command fixing it (`robctl check`).
     ///
     /// # Example
     ///
@@ -227,18 +259,17 @@ impl RobinClient {
     /// # use batman_robin::RobinClient;
     /// # async fn example() -> Result<(), batman_robin::RobinError> {
     /// # let client = RobinClient::new();
-    /// let neighbors = client.neighbors("bat0").await?;
-    /// for n in neighbors {
-    ///     println!("Neighbor: {}", n.neigh);
+    /// for warning in client.check("bat0").await? {
+    ///     eprintln!("Warning: {} (fix: {})", warning, warning.advice());
     /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn neighbors(&self, mesh_if: &str) -> Result<Vec<model::Neighbor>, RobinError> {
-        commands::get_neighbors(mesh_if).await
+    pub async fn check(&self, mesh_if: &str) -> Result<Vec<model::MeshWarning>, RobinError> {
+        commands::check_mesh(&self.transport.rtnl().await?, &self.transport, mesh_if).await
     }
 
-    /// Retrieves the list of physical interfaces attached to the mesh.
+    /// Retrieves the system default routing algorithm for BATMAN-adv.
     ///
     /// # Example
     ///
@@ -246,22 +277,19 @@ impl RobinClient {
     /// # use batman_robin::RobinClient;
     /// # async fn example() -> Result<(), batman_robin::RobinError> {
     /// # let client = RobinClient::new();
-    /// let interfaces = client.get_interface("bat0").await?;
-    /// for iface in interfaces {
-    ///     println!("{}: {}", iface.ifname, iface.active);
-    /// }
+    /// let default_algo = client.get_default_routing_algo().await?;
+    /// println!("Default routing algorithm: {}", default_algo);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn get_interface(&self, mesh_if: &str) -> Result<Vec<model::Interface>, RobinError> {
-        commands::get_interfaces(mesh_if).await
+    pub async fn get_default_routing_algo(&self) -> Result<String, RobinError> {
+        commands::get_default_routing_algo().await
     }
 
-    /// Adds or removes a physical interface from the mesh.
+    /// Retrieves all active routing algorithms currently in use along with
+    /// their corresponding mesh interfaces.
     ///
-    /// # Arguments
-    /// * `iface` - Physical interface name
-    /// * `mesh_if` - Some(mesh_if) to add, None to remove
+    /// Returns a vector of tuples `(interface_name, routing_algo_name)`.
     ///
     /// # Example
     ///
@@ -269,20 +297,18 @@ impl RobinClient {
     /// # use batman_robin::RobinClient;
     /// # async fn example() -> Result<(), batman_robin::RobinError> {
     /// # let client = RobinClient::new();
-    /// client.set_interface("wlan1", Some("bat0")).await?;
-    /// client.set_interface("wlan1", None).await?;
+    /// let active_algos = client.get_active_routing_algos().await?;
+    /// for (iface, algo) in active_algos {
+    ///     println!("Interface {} uses {}", iface, algo);
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn set_interface(
-        &self,
-        iface: &str,
-        mesh_if: Option<&str>,
-    ) -> Result<(), RobinError> {
-        commands::set_interface(iface, mesh_if).await
+    pub async fn get_active_routing_algos(&self) -> Result<Vec<(String, String)>, RobinError> {
+        commands::get_active_routing_algos(&self.transport.rtnl().await?, &self.transport).await
     }
 
-    /// Creates a new BATMAN-adv mesh interface with an optional routing algorithm.
+    /// Sets the system default routing algorithm.
     ///
     /// # Example
     ///
@@ -290,20 +316,33 @@ impl RobinClient {
     /// # use batman_robin::RobinClient;
     /// # async fn example() -> Result<(), batman_robin::RobinError> {
     /// # let client = RobinClient::new();
-    /// client.create_interface("bat0", Some("BATMAN_V")).await?;
-    /// client.create_interface("bat1", None).await?;
+    /// client.set_default_routing_algo("BATMAN_V").await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn create_interface(
+    pub async fn set_default_routing_algo(&self, algo: &str) -> Result<(), RobinError> {
+        self.change(commands::set_default_routing_algo(algo)).await
+    }
+
+    /// Sets the default routing algorithm unless it already is `algo`.
+    ///
+    /// See [`ensure_aggregation`](RobinClient::ensure_aggregation) for the outcome.
+    pub async fn ensure_default_routing_algo(
         &self,
-        mesh_if: &str,
-        routing_algo: Option<&str>,
-    ) -> Result<(), RobinError> {
-        commands::create_interface(mesh_if, routing_algo).await
+        algo: &str,
+    ) -> Result<model::SetOutcome, RobinError> {
+        if self.get_default_routing_algo().await? == algo {
+            return Ok(model::SetOutcome::Unchanged);
+        }
+        self.set_default_routing_algo(algo).await?;
+        Ok(model::SetOutcome::Changed)
     }
 
-    /// Destroys a BATMAN-adv mesh interface.
+    /// Returns `true` if `ifname` is a wireless interface, e.g. a hard interface of
+    /// the mesh on which neighbors are reached over the air.
+    ///
+    /// Interfaces are wireless if `nl80211` knows them; without cfg80211 in the
+    /// kernel, none is.
     ///
     /// # Example
     ///
@@ -311,15 +350,24 @@ impl RobinClient {
     /// # use batman_robin::RobinClient;
     /// # async fn example() -> Result<(), batman_robin::RobinError> {
     /// # let client = RobinClient::new();
-    /// client.destroy_interface("bat0").await?;
+    /// for iface in client.get_interface("bat0").await? {
+    ///     if client.is_wireless(&iface.ifname).await? {
+    ///         println!("{} is a wireless link", iface.ifname);
+    ///     }
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn destroy_interface(&self, mesh_if: &str) -> Result<(), RobinError> {
-        commands::destroy_interface(mesh_if).await
+    pub async fn is_wireless(&self, ifname: &str) -> Result<bool, RobinError> {
+        let ifindex = self.transport.if_nametoindex(ifname).await?;
+        let iftype = self.transport.nl80211().await?.iftype(ifindex).await?;
+        Ok(iftype.is_some())
     }
 
-    /// Counts the number of physical interfaces attached to the mesh.
+    /// Retrieves the link state of the wireless station `mac` on `ifname`, like
+    /// `iw dev <ifname> station get <mac>`.
+    ///
+    /// Returns `None` if `ifname` is not wireless or has no such station.
     ///
     /// # Example
     ///
@@ -327,22 +375,26 @@ impl RobinClient {
     /// # use batman_robin::RobinClient;
     /// # async fn example() -> Result<(), batman_robin::RobinError> {
     /// # let client = RobinClient::new();
-    /// let count = client.count_interfaces("bat0").await?;
-    /// println!("Attached interfaces: {}", count);
+    /// for n in client.neighbors("bat0").await? {
+    ///     if let Some(sta) = client.station(&n.outgoing_if, n.neigh).await? {
+    ///         println!("{}: {:?} dBm", n.neigh, sta.signal_dbm);
+    ///     }
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn count_interfaces(&self, mesh_if: &str) -> Result<u32, RobinError> {
-        commands::count_interfaces(mesh_if).await
+    pub async fn station(
+        &self,
+        ifname: &str,
+        mac: MacAddr6,
+    ) -> Result<Option<model::StationInfo>, RobinError> {
+        let ifindex = self.transport.if_nametoindex(ifname).await?;
+        self.transport.nl80211().await?.station(ifindex, mac).await
     }
 
-    /// Checks whether packet aggregation is enabled on a BATMAN-adv mesh interface.
-    ///
-    /// Packet aggregation combines multiple packets into one to reduce overhead
-    /// and improve throughput.
+    /// Retrieves the wireless link state of each of `neighbors`, in the same order.
     ///
-    /// # Arguments
-    /// * `mesh_if` - The name of the mesh interface (e.g., `"bat0"`).
+    /// Entries are `None` for neighbors reached over wired hard interfaces.
     ///
     /// # Example
     ///
@@ -350,90 +402,212 @@ impl RobinClient {
     /// # use batman_robin::RobinClient;
     /// # async fn example() -> Result<(), batman_robin::RobinError> {
     /// # let client = RobinClient::new();
-    /// let enabled = client.get_aggregation("bat0").await?;
-    /// println!("Aggregation enabled? {}", enabled);
+    /// let neighbors = client.neighbors("bat0").await?;
+    /// let stations = client.neighbor_stations(&neighbors).await?;
+    /// for (n, sta) in neighbors.iter().zip(stations) {
+    ///     let signal = sta.and_then(|sta| sta.signal_dbm);
+    ///     println!("{}: {:?} dBm", n.neigh, signal);
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn get_aggregation(&self, mesh_if: &str) -> Result<bool, RobinError> {
-        commands::get_aggregation(mesh_if).await
+    pub async fn neighbor_stations(
+        &self,
+        neighbors: &[model::Neighbor],
+    ) -> Result<Vec<Option<model::StationInfo>>, RobinError> {
+        let mut stations = Vec::with_capacity(neighbors.len());
+        for n in neighbors {
+            stations.push(self.station(&n.outgoing_if, n.neigh).await?);
+        }
+        Ok(stations)
     }
 
-    /// Enables or disables packet aggregation on a mesh interface.
+    /// Converges the mesh interface described by `config` to it, creating the
+    /// interface and changing only the settings that differ.
     ///
-    /// # Arguments
-    /// * `mesh_if` - The mesh interface name.
-    /// * `val` - `true` to enable aggregation, `false` to disable.
+    /// Returns the changes made, empty if the running state already matched.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use batman_robin::RobinClient;
+    /// # use batman_robin::config::MeshConfig;
     /// # async fn example() -> Result<(), batman_robin::RobinError> {
     /// # let client = RobinClient::new();
-    /// // Enable aggregation
-    /// client.set_aggregation("bat0", true).await?;
-    ///
-    /// // Disable aggregation
-    /// client.set_aggregation("bat0", false).await?;
+    /// let config = MeshConfig::from_file("/etc/robin/bat0.toml")?;
+    /// for change in client.apply(&config).await? {
+    ///     println!("{}", change);
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn set_aggregation(&self, mesh_if: &str, val: bool) -> Result<(), RobinError> {
-        commands::set_aggregation(mesh_if, val).await
+    #[cfg(feature = "config")]
+    pub async fn apply(
+        &self,
+        config: &crate::config::MeshConfig,
+    ) -> Result<Vec<crate::config::Change>, RobinError> {
+        crate::config::apply(self, config, false).await
     }
 
-    /// Checks whether AP isolation is enabled on a mesh interface.
-    ///
-    /// AP isolation prevents clients on the same Wi-Fi network from communicating
-    /// directly, improving security in multi-client networks.
+    /// Returns the changes [`apply`](RobinClient::apply) would make for `config`,
+    /// without changing anything.
     ///
-    /// # Arguments
-    /// * `mesh_if` - The mesh interface name.
+    /// Settings of a mesh interface that would be created or recreated, and of hard
+    /// interfaces that would be added to it, are all reported as changes.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use batman_robin::RobinClient;
+    /// # use batman_robin::config::MeshConfig;
     /// # async fn example() -> Result<(), batman_robin::RobinError> {
     /// # let client = RobinClient::new();
-    /// let isolated = client.get_ap_isolation("bat0").await?;
-    /// println!("AP isolation enabled? {}", isolated);
+    /// let config = MeshConfig::from_file("bat0.toml")?;
+    /// for change in client.plan(&config).await? {
+    ///     println!("would change {}", change);
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn get_ap_isolation(&self, mesh_if: &str) -> Result<bool, RobinError> {
-        commands::get_ap_isolation(mesh_if).await
+    #[cfg(feature = "config")]
+    pub async fn plan(
+        &self,
+        config: &crate::config::MeshConfig,
+    ) -> Result<Vec<crate::config::Change>, RobinError> {
+        crate::config::apply(self, config, true).await
     }
+}
 
-    /// Enables or disables AP isolation on a mesh interface.
+impl<T: MeshTransport> RobinClient<T> {
+    /// Creates a client sending its requests over `transport`.
     ///
-    /// # Arguments
-    /// * `mesh_if` - Mesh interface name.
-    /// * `val` - `true` to enable isolation, `false` to disable.
+    /// # Example
+    ///
+    /// ```
+    /// use batman_robin::{Command, MockBackend, RobinClient};
+    /// use std::sync::Arc;
+    ///
+    /// let mock = Arc::new(
+    ///     MockBackend::new()
+    ///         .with_link(7, "bat0")
+    ///         .with_reply(Command::BatadvCmdGetOriginators, Vec::new()),
+    /// );
+    /// let configured = RobinClient::builder().mock_backend(mock).build();
+    ///
+    /// // Any `MeshTransport` can be plugged in; here, the one of another client.
+    /// let client = RobinClient::with_transport(configured.transport().clone());
+    ///
+    /// let runtime = tokio::runtime::Runtime::new().unwrap();
+    /// assert!(runtime.block_on(client.originators("bat0"))?.is_empty());
+    /// # Ok::<(), batman_robin::RobinError>(())
+    /// ```
+    pub fn with_transport(transport: T) -> Self {
+        Self {
+            transport,
+            cache: None,
+        }
+    }
+
+    /// Returns the transport requests are sent over.
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Drops the cached query results, e.g. on link events, so the next queries
+    /// read the tables from the kernel again.
+    ///
+    /// Changes made through the client invalidate the cache already; this hook is
+    /// for changes made by others, such as interfaces going down or being added to
+    /// the mesh with `ip link`. Without [`cache_ttl`](RobinClientBuilder::cache_ttl)
+    /// or [`coalesce_queries`](RobinClientBuilder::coalesce_queries), it does nothing.
+    pub fn invalidate_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.invalidate();
+        }
+    }
+
+    /// Runs the `cmd` table query of `mesh_if` and `hard_if` through the query
+    /// cache, if enabled.
+    async fn table<R, F, Fut>(
+        &self,
+        cmd: model::Command,
+        mesh_if: &str,
+        hard_if: Option<model::IfaceRef>,
+        query: F,
+    ) -> Result<R, RobinError>
+    where
+        R: Clone + Send + Sync + 'static,
+        F: FnOnce(T, String, Option<model::IfaceRef>) -> Fut,
+        Fut: Future<Output = Result<R, RobinError>> + Send + 'static,
+    {
+        let run = query(self.transport.clone(), mesh_if.to_string(), hard_if.clone());
+        match &self.cache {
+            Some(cache) => {
+                let key = Query::new(cmd, mesh_if, hard_if.as_ref());
+                cache.run(key, run).await
+            }
+            None => run.await,
+        }
+    }
+
+    /// Checks that a configuration change is allowed and admitted by the transport.
+    fn admit_change(&self) -> Result<(), RobinError> {
+        self.transport.can_administer()?;
+        self.transport.admit_change()
+    }
+
+    /// Makes the configuration change `change` once admitted, then invalidates the
+    /// query cache.
+    async fn change<R>(
+        &self,
+        change: impl Future<Output = Result<R, RobinError>>,
+    ) -> Result<R, RobinError> {
+        self.admit_change()?;
+        let result = change.await;
+        self.invalidate_cache();
+        result
+    }
+
+    /// Sends the `command` SET request computed by a settings diff, if any.
+    pub(crate) async fn set_changed(
+        &self,
+        command: model::Command,
+        attrs: Option<netlink::GenlAttrBuilder>,
+    ) -> Result<model::SetOutcome, RobinError> {
+        let Some(attrs) = attrs else {
+            return Ok(model::SetOutcome::Unchanged);
+        };
+        self.change(commands::raw_set(&self.transport, command, attrs))
+            .await?;
+        Ok(model::SetOutcome::Changed)
+    }
+
+    /// Returns the commands and attributes the running batman-adv supports.
+    ///
+    /// Read from the `batadv` Generic Netlink family when the client connects, so
+    /// probing costs no extra request. Use it to hide or refuse features of newer
+    /// releases instead of failing with `EOPNOTSUPP`.
     ///
     /// # Example
     ///
     /// ```no_run
-    /// # use batman_robin::RobinClient;
+    /// # use batman_robin::{Command, RobinClient};
     /// # async fn example() -> Result<(), batman_robin::RobinError> {
     /// # let client = RobinClient::new();
-    /// client.set_ap_isolation("bat0", true).await?; // enable
-    /// client.set_ap_isolation("bat0", false).await?; // disable
+    /// if client.capabilities().await?.supports(Command::BatadvCmdGetVlan) {
+    ///     println!("{:?}", client.get_vlan_settings("bat0", 10).await?);
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn set_ap_isolation(&self, mesh_if: &str, val: bool) -> Result<(), RobinError> {
-        commands::set_ap_isolation(mesh_if, val).await
+    pub async fn capabilities(&self) -> Result<model::Capabilities, RobinError> {
+        self.transport.capabilities().await
     }
 
-    /// Checks whether bridge loop avoidance is enabled.
-    ///
-    /// Bridge loop avoidance prevents loops when multiple interfaces connect
-    /// the same network segment.
+    /// Converts a network interface name to its corresponding index.
     ///
     /// # Arguments
-    /// * `mesh_if` - Mesh interface name.
+    /// * `ifname` - Name of the network interface (e.g., "wlan0").
     ///
     /// # Example
     ///
@@ -441,20 +615,19 @@ impl RobinClient {
     /// # use batman_robin::RobinClient;
     /// # async fn example() -> Result<(), batman_robin::RobinError> {
     /// # let client = RobinClient::new();
-    /// let enabled = client.get_bridge_loop_avoidance("bat0").await?;
-    /// println!("Bridge loop avoidance: {}", enabled);
+    /// let idx = client.if_nametoindex("bat0").await?;
+    /// println!("Interface index: {}", idx);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn get_bridge_loop_avoidance(&self, mesh_if: &str) -> Result<bool, RobinError> {
-        commands::get_bridge_loop_avoidance(mesh_if).await
+    pub async fn if_nametoindex(&self, ifname: &str) -> Result<u32, RobinError> {
+        self.transport.if_nametoindex(ifname).await
     }
 
-    /// Enables or disables bridge loop avoidance.
+    /// Converts a network interface index to its corresponding name.
     ///
     /// # Arguments
-    /// * `mesh_if` - Mesh interface name.
-    /// * `val` - `true` to enable, `false` to disable.
+    /// * `ifindex` - Interface index.
     ///
     /// # Example
     ///
@@ -462,20 +635,16 @@ impl RobinClient {
     /// # use batman_robin::RobinClient;
     /// # async fn example() -> Result<(), batman_robin::RobinError> {
     /// # let client = RobinClient::new();
-    /// client.set_bridge_loop_avoidance("bat0", true).await?; // enable
-    /// client.set_bridge_loop_avoidance("bat0", false).await?; // disable
+    /// let ifname = client.if_indextoname(3).await?;
+    /// println!("Interface name: {}", ifname);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn set_bridge_loop_avoidance(
-        &self,
-        mesh_if: &str,
-        val: bool,
-    ) -> Result<(), RobinError> {
-        commands::set_bridge_loop_avoidance(mesh_if, val).await
+    pub async fn if_indextoname(&self, ifindex: u32) -> Result<String, RobinError> {
+        self.transport.if_indextoname(ifindex).await
     }
 
-    /// Retrieves the system default routing algorithm for BATMAN-adv.
+    /// Retrieves the list of originators for the given mesh interface.
     ///
     /// # Example
     ///
@@ -483,19 +652,873 @@ impl RobinClient {
     /// # use batman_robin::RobinClient;
     /// # async fn example() -> Result<(), batman_robin::RobinError> {
     /// # let client = RobinClient::new();
-    /// let default_algo = client.get_default_routing_algo().await?;
-    /// println!("Default routing algorithm: {}", default_algo);
+    /// let originators = client.originators("bat0").await?;
+    /// for o in originators {
+    ///     println!("Originator: {}", o.originator);
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn get_default_routing_algo(&self) -> Result<String, RobinError> {
-        commands::get_default_routing_algo().await
+    pub async fn originators(&self, mesh_if: &str) -> Result<Vec<model::Originator>, RobinError> {
+        self.table(
+            model::Command::BatadvCmdGetOriginators,
+            mesh_if,
+            None,
+            |transport, mesh_if, _| async move {
+                commands::get_originators(&transport, &mesh_if, None).await
+            },
+        )
+        .await
+    }
+
+    /// Streams the originators of the given mesh interface as they are received.
+    ///
+    /// Entries are yielded while the kernel dump is still in progress instead of
+    /// being collected into a `Vec` first, which is preferable on very large meshes.
+    ///
+    /// To read only part of the table, stop the stream with
+    /// [`DumpStreamExt`](crate::DumpStreamExt), which discards the rest of the dump.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// use futures::TryStreamExt;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let mut originators = std::pin::pin!(client.originators_stream("bat0"));
+    /// while let Some(o) = originators.try_next().await? {
+    ///     println!("Originator: {}", o.originator);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn originators_stream(
+        &self,
+        mesh_if: &str,
+    ) -> impl Stream<Item = Result<model::Originator, RobinError>> {
+        commands::get_originators_stream(self.transport.clone(), mesh_if, None)
+    }
+
+    /// Retrieves the originator table of one hard interface of the mesh, like
+    /// `batctl o -i <hard_if>`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// for o in client.originators_on("bat0", "wlan0").await? {
+    ///     println!("Originator: {}", o.originator);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn originators_on(
+        &self,
+        mesh_if: &str,
+        hard_if: impl Into<model::IfaceRef>,
+    ) -> Result<Vec<model::Originator>, RobinError> {
+        self.table(
+            model::Command::BatadvCmdGetOriginators,
+            mesh_if,
+            Some(hard_if.into()),
+            |transport, mesh_if, hard_if| async move {
+                commands::get_originators(&transport, &mesh_if, hard_if.as_ref()).await
+            },
+        )
+        .await
+    }
+
+    /// Retrieves the list of gateways for the given mesh interface.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let gateways = client.gateways("bat0").await?;
+    /// for g in gateways {
+    ///     println!("Gateway: {}", g.mac_addr);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn gateways(&self, mesh_if: &str) -> Result<Vec<model::Gateway>, RobinError> {
+        self.table(
+            model::Command::BatadvCmdGetGateways,
+            mesh_if,
+            None,
+            |transport, mesh_if, _| async move {
+                commands::get_gateways_list(&transport, &mesh_if).await
+            },
+        )
+        .await
+    }
+
+    /// Streams the gateways of the given mesh interface as they are received.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// use futures::TryStreamExt;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let mut gateways = std::pin::pin!(client.gateways_stream("bat0"));
+    /// while let Some(g) = gateways.try_next().await? {
+    ///     println!("Gateway: {}", g.mac_addr);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn gateways_stream(
+        &self,
+        mesh_if: &str,
+    ) -> impl Stream<Item = Result<model::Gateway, RobinError>> {
+        commands::get_gateways_list_stream(self.transport.clone(), mesh_if)
+    }
+
+    /// Gets the current gateway mode and configuration for the mesh interface.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let gw_info = client.get_gw_mode("bat0").await?;
+    /// println!("Gateway mode: {:?}", gw_info.mode);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_gw_mode(&self, mesh_if: &str) -> Result<model::GatewayInfo, RobinError> {
+        commands::get_gateway(&self.transport, mesh_if).await
+    }
+
+    /// Sets the gateway mode and optional bandwidth/selection parameters for the mesh interface.
+    ///
+    /// # Arguments
+    /// * `mode` - Gateway mode (`Off`, `Client`, `Server`)
+    /// * `down` - Optional downlink bandwidth, 10 MBit by default
+    /// * `up` - Optional uplink bandwidth, 2 MBit by default
+    /// * `sel_class` - Optional selection class (for clients)
+    /// * `mesh_if` - Mesh interface name
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use batman_robin::{Bandwidth, GwMode};
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let (down, up) = (Bandwidth::from_mbps(50), Bandwidth::from_mbps(10));
+    /// client.set_gw_mode(GwMode::Server, Some(down), Some(up), None, "bat0").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn set_gw_mode(
+        &self,
+        mode: model::GwMode,
+        down: Option<model::Bandwidth>,
+        up: Option<model::Bandwidth>,
+        sel_class: Option<u32>,
+        mesh_if: &str,
+    ) -> Result<(), RobinError> {
+        self.change(commands::set_gateway(
+            &self.transport,
+            mode,
+            down,
+            up,
+            sel_class,
+            mesh_if,
+        ))
+        .await
+    }
+
+    /// Sets the gateway mode unless the gateway settings already match.
+    ///
+    /// The arguments are those of [`set_gw_mode`](RobinClient::set_gw_mode), including
+    /// its defaults for omitted server bandwidths. See
+    /// [`ensure_aggregation`](RobinClient::ensure_aggregation) for the outcome.
+    pub async fn ensure_gw_mode(
+        &self,
+        mode: model::GwMode,
+        down: Option<model::Bandwidth>,
+        up: Option<model::Bandwidth>,
+        sel_class: Option<u32>,
+        mesh_if: &str,
+    ) -> Result<model::SetOutcome, RobinError> {
+        let current = self.get_gw_mode(mesh_if).await?;
+        if commands::gateway_matches(&current, mode, down, up, sel_class) {
+            return Ok(model::SetOutcome::Unchanged);
+        }
+        self.set_gw_mode(mode, down, up, sel_class, mesh_if).await?;
+        Ok(model::SetOutcome::Changed)
+    }
+
+    /// Retrieves the global translation table entries.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let tg = client.transglobal("bat0").await?;
+    /// for entry in tg {
+    ///     println!("Client: {}", entry.client);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn transglobal(
+        &self,
+        mesh_if: &str,
+    ) -> Result<Vec<model::TransglobalEntry>, RobinError> {
+        self.table(
+            model::Command::BatadvCmdGetTranstableGlobal,
+            mesh_if,
+            None,
+            |transport, mesh_if, _| async move { commands::get_transglobal(&transport, &mesh_if).await },
+        )
+        .await
+    }
+
+    /// Summarizes the local and global translation tables: entries per VID, per
+    /// client flag and per originator, and the table sizes.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let stats = client.tt_stats("bat0").await?;
+    /// println!("{} local, {} global clients", stats.local.clients, stats.global.clients);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn tt_stats(&self, mesh_if: &str) -> Result<model::TtStats, RobinError> {
+        Ok(model::TtStats {
+            local: model::TtTableStats::local(&self.translocal(mesh_if).await?),
+            global: model::TtTableStats::global(&self.transglobal(mesh_if).await?),
+        })
+    }
+
+    /// Counts the clients of the mesh in total, per originator and per VLAN, from
+    /// the global translation table and the originator table (see
+    /// [`ClientSummary`](model::ClientSummary)).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let summary = client.client_summary("bat0").await?;
+    /// println!("{} clients on {} nodes", summary.clients, summary.per_originator.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn client_summary(&self, mesh_if: &str) -> Result<model::ClientSummary, RobinError> {
+        Ok(model::ClientSummary::new(
+            &self.transglobal(mesh_if).await?,
+            &self.originators(mesh_if).await?,
+        ))
+    }
+
+    /// Finds client MAC addresses announced by several originators within the same
+    /// VLAN in the global translation table, leaving out clients that are roaming
+    /// (see [`DuplicateClient::find`](model::DuplicateClient::find)).
+    ///
+    /// Such address conflicts make the connectivity of the clients flap.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// for duplicate in client.find_duplicate_clients("bat0").await? {
+    ///     eprintln!("Warning: {}", duplicate);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn find_duplicate_clients(
+        &self,
+        mesh_if: &str,
+    ) -> Result<Vec<model::DuplicateClient>, RobinError> {
+        Ok(model::DuplicateClient::find(
+            &self.transglobal(mesh_if).await?,
+        ))
+    }
+
+    /// Streams the global translation table entries as they are received.
+    ///
+    /// The global translation table can hold tens of thousands of clients on large
+    /// meshes; streaming avoids buffering all of them before the first one is handled.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// use futures::TryStreamExt;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let mut tg = std::pin::pin!(client.transglobal_stream("bat0"));
+    /// while let Some(entry) = tg.try_next().await? {
+    ///     println!("Client: {}", entry.client);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn transglobal_stream(
+        &self,
+        mesh_if: &str,
+    ) -> impl Stream<Item = Result<model::TransglobalEntry, RobinError>> {
+        commands::get_transglobal_stream(self.transport.clone(), mesh_if)
+    }
+
+    /// Retrieves the local translation table entries.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let tl = client.translocal("bat0").await?;
+    /// for entry in tl {
+    ///     println!("Client: {}", entry.client);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn translocal(
+        &self,
+        mesh_if: &str,
+    ) -> Result<Vec<model::TranslocalEntry>, RobinError> {
+        self.table(
+            model::Command::BatadvCmdGetTranstableLocal,
+            mesh_if,
+            None,
+            |transport, mesh_if, _| async move { commands::get_translocal(&transport, &mesh_if).await },
+        )
+        .await
+    }
+
+    /// Streams the local translation table entries as they are received.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// use futures::TryStreamExt;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let mut tl = std::pin::pin!(client.translocal_stream("bat0"));
+    /// while let Some(entry) = tl.try_next().await? {
+    ///     println!("Client: {}", entry.client);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn translocal_stream(
+        &self,
+        mesh_if: &str,
+    ) -> impl Stream<Item = Result<model::TranslocalEntry, RobinError>> {
+        commands::get_translocal_stream(self.transport.clone(), mesh_if)
+    }
+
+    /// Retrieves the distributed ARP table (DAT) cache entries.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// for entry in client.dat_cache("bat0").await? {
+    ///     println!("{} is at {}", entry.ip, entry.mac);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn dat_cache(&self, mesh_if: &str) -> Result<Vec<model::DatCacheEntry>, RobinError> {
+        self.table(
+            model::Command::BatadvCmdGetDatCache,
+            mesh_if,
+            None,
+            |transport, mesh_if, _| async move { commands::get_dat_cache(&transport, &mesh_if).await },
+        )
+        .await
+    }
+
+    /// Streams the DAT cache entries as they are received.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// use futures::TryStreamExt;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let mut dc = std::pin::pin!(client.dat_cache_stream("bat0"));
+    /// while let Some(entry) = dc.try_next().await? {
+    ///     println!("{} is at {}", entry.ip, entry.mac);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn dat_cache_stream(
+        &self,
+        mesh_if: &str,
+    ) -> impl Stream<Item = Result<model::DatCacheEntry, RobinError>> {
+        commands::get_dat_cache_stream(self.transport.clone(), mesh_if)
+    }
+
+    /// Translates a client MAC address to the originator announcing it in the
+    /// global translation table, like `batctl translate`.
+    ///
+    /// Addresses without a global translation table entry are returned unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let orig = client.translate_mac("bat0", "02:00:00:00:00:05".parse().unwrap()).await?;
+    /// println!("Reached via {}", orig);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn translate_mac(
+        &self,
+        mesh_if: &str,
+        mac: MacAddr6,
+    ) -> Result<MacAddr6, RobinError> {
+        commands::translate_mac(&self.transport, mesh_if, mac).await
+    }
+
+    /// Resolves how this node reaches a client: the originator serving it in the
+    /// translation tables, then the next hop and outgoing interface of the best
+    /// route towards that originator (see [`ClientPath`](model::ClientPath)).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let path = client.path_to_client("bat0", "02:00:00:00:00:05".parse().unwrap()).await?;
+    /// println!("{}", path);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn path_to_client(
+        &self,
+        mesh_if: &str,
+        client: MacAddr6,
+    ) -> Result<model::ClientPath, RobinError> {
+        model::ClientPath::resolve(
+            client,
+            &self.translocal(mesh_if).await?,
+            &self.transglobal(mesh_if).await?,
+            &self.originators(mesh_if).await?,
+        )
+    }
+
+    /// Resolves an IPv4 address to a MAC address through the DAT cache, falling
+    /// back to the ARP table of the local node.
+    ///
+    /// Combined with [`translate_mac`](RobinClient::translate_mac), this finds the
+    /// originator serving an IP address.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let mac = client.resolve_ipv4("bat0", "10.0.0.5".parse().unwrap()).await?;
+    /// let orig = client.translate_mac("bat0", mac).await?;
+    /// println!("10.0.0.5 is {} via {}", mac, orig);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn resolve_ipv4(
+        &self,
+        mesh_if: &str,
+        ip: std::net::Ipv4Addr,
+    ) -> Result<MacAddr6, RobinError> {
+        commands::resolve_ipv4(&self.transport, mesh_if, ip).await
+    }
+
+    /// Retrieves the list of neighbors.
+    ///
+    /// Returns one entry per neighbor and hard interface; duplicate reports of the
+    /// same link are merged, keeping the most recently seen one.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let neighbors = client.neighbors("bat0").await?;
+    /// for n in neighbors {
+    ///     println!("Neighbor: {}", n.neigh);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn neighbors(&self, mesh_if: &str) -> Result<Vec<model::Neighbor>, RobinError> {
+        self.table(
+            model::Command::BatadvCmdGetNeighbors,
+            mesh_if,
+            None,
+            |transport, mesh_if, _| async move {
+                commands::get_neighbors(&transport, &mesh_if, None).await
+            },
+        )
+        .await
+    }
+
+    /// Streams the neighbors as they are received.
+    ///
+    /// Unlike [`neighbors`](RobinClient::neighbors), entries are yielded exactly as the
+    /// kernel reports them, without deduplication.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// use futures::TryStreamExt;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let mut neighbors = std::pin::pin!(client.neighbors_stream("bat0"));
+    /// while let Some(n) = neighbors.try_next().await? {
+    ///     println!("Neighbor: {}", n.neigh);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn neighbors_stream(
+        &self,
+        mesh_if: &str,
+    ) -> impl Stream<Item = Result<model::Neighbor, RobinError>> {
+        commands::get_neighbors_stream(self.transport.clone(), mesh_if, None)
+    }
+
+    /// Retrieves the neighbors reached over one hard interface of the mesh, like
+    /// `batctl n -i <hard_if>`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// for n in client.neighbors_on("bat0", "wlan0").await? {
+    ///     println!("Neighbor: {}", n.neigh);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn neighbors_on(
+        &self,
+        mesh_if: &str,
+        hard_if: impl Into<model::IfaceRef>,
+    ) -> Result<Vec<model::Neighbor>, RobinError> {
+        self.table(
+            model::Command::BatadvCmdGetNeighbors,
+            mesh_if,
+            Some(hard_if.into()),
+            |transport, mesh_if, hard_if| async move {
+                commands::get_neighbors(&transport, &mesh_if, hard_if.as_ref()).await
+            },
+        )
+        .await
+    }
+
+    /// Retrieves the list of physical interfaces attached to the mesh.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let interfaces = client.get_interface("bat0").await?;
+    /// for iface in interfaces {
+    ///     println!("{}: {}", iface.ifname, iface.active);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_interface(&self, mesh_if: &str) -> Result<Vec<model::Interface>, RobinError> {
+        commands::get_interfaces(&self.transport, mesh_if).await
+    }
+
+    /// Retrieves the settings and state of one hard interface of the mesh, like
+    /// `batctl hardif <hard_if>`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let info = client.hardif_info("bat0", "wlan0").await?;
+    /// println!("{}: hop penalty {:?}", info.ifname, info.hop_penalty);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn hardif_info(
+        &self,
+        mesh_if: &str,
+        hard_if: impl Into<model::IfaceRef>,
+    ) -> Result<model::HardifInfo, RobinError> {
+        commands::get_hardif_info(&self.transport, mesh_if, &hard_if.into()).await
+    }
+
+    /// Checks whether packet aggregation is enabled on a BATMAN-adv mesh interface.
+    ///
+    /// Packet aggregation combines multiple packets into one to reduce overhead
+    /// and improve throughput.
+    ///
+    /// # Arguments
+    /// * `mesh_if` - The name of the mesh interface (e.g., `"bat0"`).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let enabled = client.get_aggregation("bat0").await?;
+    /// println!("Aggregation enabled? {}", enabled);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_aggregation(&self, mesh_if: &str) -> Result<bool, RobinError> {
+        commands::get_aggregation(&self.transport, mesh_if).await
+    }
+
+    /// Enables or disables packet aggregation on a mesh interface.
+    ///
+    /// # Arguments
+    /// * `mesh_if` - The mesh interface name.
+    /// * `val` - `true` to enable aggregation, `false` to disable.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// // Enable aggregation
+    /// client.set_aggregation("bat0", true).await?;
+    ///
+    /// // Disable aggregation
+    /// client.set_aggregation("bat0", false).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn set_aggregation(&self, mesh_if: &str, val: bool) -> Result<(), RobinError> {
+        self.change(commands::set_aggregation(&self.transport, mesh_if, val))
+            .await
+    }
+
+    /// Enables or disables packet aggregation unless it already is, for callers
+    /// that need to know whether anything changed, e.g. configuration management.
+    ///
+    /// # Returns
+    /// - `SetOutcome::Unchanged` if the setting already had the value; no request
+    ///   changing the configuration is sent then.
+    /// - `SetOutcome::Changed` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use batman_robin::{Attribute, Command, GenlAttrBuilder, MockBackend, RobinClient, SetOutcome};
+    /// use std::sync::Arc;
+    ///
+    /// let mut info = GenlAttrBuilder::new();
+    /// info.add(Attribute::BatadvAttrAggregatedOgmsEnabled, 1u8)?;
+    /// let mock = Arc::new(
+    ///     MockBackend::new()
+    ///         .with_link(7, "bat0")
+    ///         .with_reply(Command::BatadvCmdGetMeshInfo, vec![info]),
+    /// );
+    /// let client = RobinClient::builder().mock_backend(mock).build();
+    ///
+    /// let runtime = tokio::runtime::Runtime::new().unwrap();
+    /// let outcome = runtime.block_on(client.ensure_aggregation("bat0", true))?;
+    /// assert_eq!(outcome, SetOutcome::Unchanged);
+    /// # Ok::<(), batman_robin::RobinError>(())
+    /// ```
+    pub async fn ensure_aggregation(
+        &self,
+        mesh_if: &str,
+        val: bool,
+    ) -> Result<model::SetOutcome, RobinError> {
+        if self.get_aggregation(mesh_if).await? == val {
+            return Ok(model::SetOutcome::Unchanged);
+        }
+        self.set_aggregation(mesh_if, val).await?;
+        Ok(model::SetOutcome::Changed)
+    }
+
+    /// Retrieves all mesh-level tunables of a mesh interface with a single request.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let settings = client.get_settings("bat0").await?;
+    /// println!("hop penalty: {:?}", settings.hop_penalty);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_settings(&self, mesh_if: &str) -> Result<model::MeshSettings, RobinError> {
+        commands::get_mesh_settings(&self.transport, mesh_if).await
+    }
+
+    /// Applies the mesh-level tunables set in `settings` to a mesh interface.
+    ///
+    /// Only the settings differing from the running ones are sent, in a single
+    /// `BATADV_CMD_SET_MESH` request; settings left `None` are not touched.
+    ///
+    /// # Returns
+    /// - `SetOutcome::Unchanged` if all settings already had the requested values; no
+    ///   request changing the configuration is sent then.
+    /// - `SetOutcome::Changed` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use batman_robin::{
+    ///     Attribute, Command, GenlAttrBuilder, MeshSettings, MockBackend, RobinClient, SetOutcome,
+    /// };
+    /// use std::sync::Arc;
+    ///
+    /// let mut running = GenlAttrBuilder::new();
+    /// running.add(Attribute::BatadvAttrHopPenalty, 15u8)?;
+    /// running.add(Attribute::BatadvAttrOrigInterval, 1000u32)?;
+    /// let mock = Arc::new(
+    ///     MockBackend::new()
+    ///         .with_link(7, "bat0")
+    ///         .with_reply(Command::BatadvCmdGetMeshInfo, vec![running])
+    ///         .with_reply(Command::BatadvCmdSetMesh, Vec::new()),
+    /// );
+    /// let client = RobinClient::builder().mock_backend(mock).build();
+    ///
+    /// let settings = MeshSettings {
+    ///     hop_penalty: Some(30),
+    ///     orig_interval_ms: Some(1000),
+    ///     ..Default::default()
+    /// };
+    /// let runtime = tokio::runtime::Runtime::new().unwrap();
+    /// let outcome = runtime.block_on(client.apply_settings("bat0", &settings))?;
+    /// assert_eq!(outcome, SetOutcome::Changed);
+    /// # Ok::<(), batman_robin::RobinError>(())
+    /// ```
+    pub async fn apply_settings(
+        &self,
+        mesh_if: &str,
+        settings: &model::MeshSettings,
+    ) -> Result<model::SetOutcome, RobinError> {
+        let attrs = commands::changed_mesh_settings(&self.transport, mesh_if, settings).await?;
+        self.set_changed(model::Command::BatadvCmdSetMesh, attrs)
+            .await
+    }
+
+    /// Retrieves the tunables of a hard interface of a mesh with a single request.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let settings = client.get_hardif_settings("bat0", "wlan0").await?;
+    /// println!("ELP interval: {:?} ms", settings.elp_interval_ms);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_hardif_settings(
+        &self,
+        mesh_if: &str,
+        hard_if: impl Into<model::IfaceRef>,
+    ) -> Result<model::HardifSettings, RobinError> {
+        commands::get_hardif_settings(&self.transport, mesh_if, &hard_if.into()).await
+    }
+
+    /// Applies the tunables set in `settings` to a hard interface of a mesh.
+    ///
+    /// Only the settings differing from the running ones are sent, in a single
+    /// `BATADV_CMD_SET_HARDIF` request; see [`apply_settings`](RobinClient::apply_settings)
+    /// for the outcome.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use batman_robin::HardifSettings;
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let settings = HardifSettings {
+    ///     elp_interval_ms: Some(500),
+    ///     hop_penalty: Some(10),
+    ///     ..Default::default()
+    /// };
+    /// client.apply_hardif_settings("bat0", "wlan0", &settings).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn apply_hardif_settings(
+        &self,
+        mesh_if: &str,
+        hard_if: impl Into<model::IfaceRef>,
+        settings: &model::HardifSettings,
+    ) -> Result<model::SetOutcome, RobinError> {
+        let hard_if = hard_if.into();
+        let attrs =
+            commands::changed_hardif_settings(&self.transport, mesh_if, &hard_if, settings).await?;
+        self.set_changed(model::Command::BatadvCmdSetHardif, attrs)
+            .await
+    }
+
+    /// Lists the VLANs configured on a mesh interface, by VLAN id.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// for vid in client.vlans("bat0").await? {
+    ///     let settings = client.get_vlan_settings("bat0", vid).await?;
+    ///     println!("VLAN {}: AP isolation {:?}", vid, settings.ap_isolation);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn vlans(&self, mesh_if: &str) -> Result<Vec<u16>, RobinError> {
+        commands::list_vlans(&self.transport, mesh_if).await
     }
 
-    /// Retrieves all active routing algorithms currently in use along with
-    /// their corresponding mesh interfaces.
-    ///
-    /// Returns a vector of tuples `(interface_name, routing_algo_name)`.
+    /// Lists the clients of VLAN `vid` (0 to 4095) on a mesh interface, from the
+    /// local and global translation tables, grouping the global entries by the
+    /// originator announcing them (see [`VlanClients`](model::VlanClients)).
     ///
     /// # Example
     ///
@@ -503,15 +1526,190 @@ impl RobinClient {
     /// # use batman_robin::RobinClient;
     /// # async fn example() -> Result<(), batman_robin::RobinError> {
     /// # let client = RobinClient::new();
-    /// let active_algos = client.get_active_routing_algos().await?;
-    /// for (iface, algo) in active_algos {
-    ///     println!("Interface {} uses {}", iface, algo);
+    /// let vlan = client.clients_for_vid("bat0", 5).await?;
+    /// for (orig, entries) in &vlan.per_originator {
+    ///     println!("{}: {} clients", orig, entries.len());
     /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn get_active_routing_algos(&self) -> Result<Vec<(String, String)>, RobinError> {
-        commands::get_active_routing_algos().await
+    pub async fn clients_for_vid(
+        &self,
+        mesh_if: &str,
+        vid: u16,
+    ) -> Result<model::VlanClients, RobinError> {
+        Ok(model::VlanClients::new(
+            vid,
+            &self.translocal(mesh_if).await?,
+            &self.transglobal(mesh_if).await?,
+        ))
+    }
+
+    /// Retrieves the tunables of VLAN `vid` on a mesh interface with a single request.
+    pub async fn get_vlan_settings(
+        &self,
+        mesh_if: &str,
+        vid: u16,
+    ) -> Result<model::VlanSettings, RobinError> {
+        commands::get_vlan_settings(&self.transport, mesh_if, vid).await
+    }
+
+    /// Applies the tunables set in `settings` to VLAN `vid` on a mesh interface.
+    ///
+    /// Only the settings differing from the running ones are sent, in a single
+    /// `BATADV_CMD_SET_VLAN` request; see [`apply_settings`](RobinClient::apply_settings)
+    /// for the outcome.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use batman_robin::VlanSettings;
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let settings = VlanSettings { ap_isolation: Some(true) };
+    /// client.apply_vlan_settings("bat0", 10, &settings).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn apply_vlan_settings(
+        &self,
+        mesh_if: &str,
+        vid: u16,
+        settings: &model::VlanSettings,
+    ) -> Result<model::SetOutcome, RobinError> {
+        let attrs =
+            commands::changed_vlan_settings(&self.transport, mesh_if, vid, settings).await?;
+        self.set_changed(model::Command::BatadvCmdSetVlan, attrs)
+            .await
+    }
+
+    /// Checks whether AP isolation is enabled on a mesh interface.
+    ///
+    /// AP isolation prevents clients on the same Wi-Fi network from communicating
+    /// directly, improving security in multi-client networks.
+    ///
+    /// # Arguments
+    /// * `mesh_if` - The mesh interface name.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let isolated = client.get_ap_isolation("bat0").await?;
+    /// println!("AP isolation enabled? {}", isolated);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_ap_isolation(&self, mesh_if: &str) -> Result<bool, RobinError> {
+        commands::get_ap_isolation(&self.transport, mesh_if).await
+    }
+
+    /// Enables or disables AP isolation on a mesh interface.
+    ///
+    /// # Arguments
+    /// * `mesh_if` - Mesh interface name.
+    /// * `val` - `true` to enable isolation, `false` to disable.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// client.set_ap_isolation("bat0", true).await?; // enable
+    /// client.set_ap_isolation("bat0", false).await?; // disable
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn set_ap_isolation(&self, mesh_if: &str, val: bool) -> Result<(), RobinError> {
+        self.change(commands::set_ap_isolation(&self.transport, mesh_if, val))
+            .await
+    }
+
+    /// Enables or disables AP isolation unless it already is.
+    ///
+    /// See [`ensure_aggregation`](RobinClient::ensure_aggregation) for the outcome.
+    pub async fn ensure_ap_isolation(
+        &self,
+        mesh_if: &str,
+        val: bool,
+    ) -> Result<model::SetOutcome, RobinError> {
+        if self.get_ap_isolation(mesh_if).await? == val {
+            return Ok(model::SetOutcome::Unchanged);
+        }
+        self.set_ap_isolation(mesh_if, val).await?;
+        Ok(model::SetOutcome::Changed)
+    }
+
+    /// Checks whether bridge loop avoidance is enabled.
+    ///
+    /// Bridge loop avoidance prevents loops when multiple interfaces connect
+    /// the same network segment.
+    ///
+    /// # Arguments
+    /// * `mesh_if` - Mesh interface name.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let enabled = client.get_bridge_loop_avoidance("bat0").await?;
+    /// println!("Bridge loop avoidance: {}", enabled);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_bridge_loop_avoidance(&self, mesh_if: &str) -> Result<bool, RobinError> {
+        commands::get_bridge_loop_avoidance(&self.transport, mesh_if).await
+    }
+
+    /// Enables or disables bridge loop avoidance.
+    ///
+    /// # Arguments
+    /// * `mesh_if` - Mesh interface name.
+    /// * `val` - `true` to enable, `false` to disable.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// client.set_bridge_loop_avoidance("bat0", true).await?; // enable
+    /// client.set_bridge_loop_avoidance("bat0", false).await?; // disable
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn set_bridge_loop_avoidance(
+        &self,
+        mesh_if: &str,
+        val: bool,
+    ) -> Result<(), RobinError> {
+        self.change(commands::set_bridge_loop_avoidance(
+            &self.transport,
+            mesh_if,
+            val,
+        ))
+        .await
+    }
+
+    /// Enables or disables bridge loop avoidance unless it already is.
+    ///
+    /// See [`ensure_aggregation`](RobinClient::ensure_aggregation) for the outcome.
+    pub async fn ensure_bridge_loop_avoidance(
+        &self,
+        mesh_if: &str,
+        val: bool,
+    ) -> Result<model::SetOutcome, RobinError> {
+        if self.get_bridge_loop_avoidance(mesh_if).await? == val {
+            return Ok(model::SetOutcome::Unchanged);
+        }
+        self.set_bridge_loop_avoidance(mesh_if, val).await?;
+        Ok(model::SetOutcome::Changed)
     }
 
     /// Retrieves the list of all routing algorithms available on the system.
@@ -531,10 +1729,15 @@ impl RobinClient {
     /// # }
     /// ```
     pub async fn get_available_routing_algos(&self) -> Result<Vec<String>, RobinError> {
-        commands::get_available_routing_algos().await
+        commands::get_available_routing_algos(&self.transport).await
     }
 
-    /// Sets the system default routing algorithm.
+    /// Retrieves the routing algorithm of the mesh interface, which decides the
+    /// metric its tables carry: the TQ for `BATMAN_IV`, the throughput for `BATMAN_V`.
+    ///
+    /// Unlike [`get_default_routing_algo`](RobinClient::get_default_routing_algo),
+    /// this is the algorithm `mesh_if` runs, not the one new interfaces get. Kernels
+    /// not reporting it fall back to the default.
     ///
     /// # Example
     ///
@@ -542,11 +1745,376 @@ impl RobinClient {
     /// # use batman_robin::RobinClient;
     /// # async fn example() -> Result<(), batman_robin::RobinError> {
     /// # let client = RobinClient::new();
-    /// client.set_default_routing_algo("BATMAN_V").await?;
+    /// let tq = client.routing_algo("bat0").await? == "BATMAN_IV";
+    /// for o in client.originators("bat0").await? {
+    ///     match tq {
+    ///         true => println!("{}: {:?}/255", o.originator, o.tq),
+    ///         false => println!("{}: {:?} kbit/s", o.originator, o.throughput),
+    ///     }
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn set_default_routing_algo(&self, algo: &str) -> Result<(), RobinError> {
-        commands::set_default_routing_algo(algo).await
+    pub async fn routing_algo(&self, mesh_if: &str) -> Result<String, RobinError> {
+        commands::get_mesh_routing_algo(&self.transport, mesh_if).await
+    }
+
+    /// Reads the running mesh, hard interface and VLAN settings of a mesh interface
+    /// into a [`MeshConfig`](crate::config::MeshConfig), which can be written to a
+    /// file and applied elsewhere with [`apply`](RobinClient::apply).
+    ///
+    /// VLANs are found through the local translation table, so only VLANs with local
+    /// clients are exported.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let config = client.export_config("bat0").await?;
+    /// config.to_file("bat0.toml")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "config")]
+    pub async fn export_config(
+        &self,
+        mesh_if: &str,
+    ) -> Result<crate::config::MeshConfig, RobinError> {
+        crate::config::export(self, mesh_if).await
+    }
+
+    /// Sends a BATMAN-adv dump request and returns the decoded replies.
+    ///
+    /// Gives access to commands and attributes the crate does not model yet.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::{Attribute, Command, GenlAttrBuilder, RobinClient};
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let mut attrs = GenlAttrBuilder::new();
+    /// attrs.add(Attribute::BatadvAttrMeshIfindex, client.if_nametoindex("bat0").await?)?;
+    ///
+    /// for obj in client.raw_dump(Command::BatadvCmdGetOriginators, attrs).await? {
+    ///     println!("{}", obj.get_mac(Attribute::BatadvAttrOrigAddress)?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn raw_dump(
+        &self,
+        cmd: model::Command,
+        attrs: netlink::GenlAttrBuilder,
+    ) -> Result<Vec<model::AttrObject>, RobinError> {
+        commands::raw_dump(&self.transport, cmd, attrs).await
+    }
+
+    /// Sends a BATMAN-adv request answered by a single message and returns the
+    /// decoded reply.
+    ///
+    /// Gives access to settings the crate does not model yet.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::{Attribute, Command, GenlAttrBuilder, RobinClient};
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let mut attrs = GenlAttrBuilder::new();
+    /// attrs.add(Attribute::BatadvAttrMeshIfindex, client.if_nametoindex("bat0").await?)?;
+    ///
+    /// let mesh = client.raw_get(Command::BatadvCmdGetMeshInfo, attrs).await?;
+    /// println!("hop penalty: {}", mesh.get_u8(Attribute::BatadvAttrHopPenalty)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn raw_get(
+        &self,
+        cmd: model::Command,
+        attrs: netlink::GenlAttrBuilder,
+    ) -> Result<model::AttrObject, RobinError> {
+        commands::raw_get(&self.transport, cmd, attrs).await
+    }
+
+    /// Sends a BATMAN-adv request and waits for the kernel to acknowledge it.
+    ///
+    /// Gives access to settings the crate does not model yet.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::{Attribute, Command, GenlAttrBuilder, RobinClient};
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// # let client = RobinClient::new();
+    /// let mut attrs = GenlAttrBuilder::new();
+    /// attrs.add(Attribute::BatadvAttrMeshIfindex, client.if_nametoindex("bat0").await?)?;
+    /// attrs.add(Attribute::BatadvAttrHopPenalty, 30u8)?;
+    ///
+    /// client.raw_set(Command::BatadvCmdSetMesh, attrs).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn raw_set(
+        &self,
+        cmd: model::Command,
+        attrs: netlink::GenlAttrBuilder,
+    ) -> Result<(), RobinError> {
+        self.change(commands::raw_set(&self.transport, cmd, attrs))
+            .await
+    }
+}
+
+/// Builder for [`RobinClient`].
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::{RetryPolicy, RobinClient};
+/// use std::time::Duration;
+///
+/// let client = RobinClient::builder()
+///     .retry_policy(RetryPolicy::new(5).with_base_delay(Duration::from_millis(50)))
+///     .build();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RobinClientBuilder {
+    retry: Option<RetryPolicy>,
+    rate_limit: Option<RateLimit>,
+    record: Option<PathBuf>,
+    mock: Option<Arc<netlink::MockBackend>>,
+    debugfs: Option<PathBuf>,
+    mesh_by_name: bool,
+    coalesce: bool,
+    cache_ttl: Option<Duration>,
+    table_ttl: HashMap<model::Command, Duration>,
+}
+
+impl RobinClientBuilder {
+    /// Retries requests failing with a transient netlink error (`EBUSY`, `EAGAIN`,
+    /// `ENOBUFS`) according to `policy`. Retries are disabled by default.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// Refuses configuration changes beyond `limit` with [`RobinError::Throttled`].
+    /// Changes are not limited by default.
+    ///
+    /// The limit covers all clones of the client. See [`RateLimit`] for an example.
+    pub fn rate_limit(mut self, limit: RateLimit) -> Self {
+        self.rate_limit = Some(limit);
+        self
+    }
+
+    /// Records all `batadv` requests and their replies to the file at `path`.
+    ///
+    /// The file is created when the client first connects. A recording can be replayed
+    /// with [`MockBackend::from_recording`](netlink::MockBackend::from_recording), e.g.
+    /// to reproduce a parsing problem seen on a real mesh. Requests should not be
+    /// issued concurrently while recording, as their replies would interleave.
+    pub fn record_to(mut self, path: impl Into<PathBuf>) -> Self {
+        self.record = Some(path.into());
+        self
+    }
+
+    /// Answers all requests from `mock` instead of the kernel, for tests.
+    ///
+    /// See [`MockBackend`](netlink::MockBackend) for an example.
+    pub fn mock_backend(mut self, mock: Arc<netlink::MockBackend>) -> Self {
+        self.mock = Some(mock);
+        self
+    }
+
+    /// Reads the originator and translation tables from the batman-adv debugfs
+    /// directory at `root` when the kernel does not support the netlink dumps.
+    ///
+    /// Older batman-adv releases, still found on OpenWrt images, only expose their
+    /// tables as text files below [`DEBUGFS_ROOT`](crate::DEBUGFS_ROOT), which must be
+    /// mounted and readable. The fallback is used when the `batadv` netlink family is missing
+    /// or a dump fails with `EOPNOTSUPP`; other queries are not affected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use batman_robin::{Command, MockBackend, RobinClient};
+    /// use std::sync::Arc;
+    ///
+    /// let root = std::env::temp_dir().join(format!("robin-debugfs-{}", std::process::id()));
+    /// std::fs::create_dir_all(root.join("bat0"))?;
+    /// std::fs::write(
+    ///     root.join("bat0/originators"),
+    ///     "[B.A.T.M.A.N. adv 2016.1, MainIF/MAC: eth0/02:00:00:00:00:01 (bat0 BATMAN_IV)]\n\
+    ///        Originator      last-seen (#/255)           Nexthop [outgoingIF]:   Potential nexthops ...\n\
+    ///      02:00:00:00:00:02    0.560s   (255) 02:00:00:00:00:02 [      eth0]: 02:00:00:00:00:02 (255)\n",
+    /// )?;
+    ///
+    /// // A kernel without netlink dump support.
+    /// let mock = Arc::new(
+    ///     MockBackend::new()
+    ///         .with_link(7, "bat0")
+    ///         .with_error(libc::EOPNOTSUPP),
+    /// );
+    /// let client = RobinClient::builder()
+    ///     .mock_backend(mock)
+    ///     .debugfs_fallback(&root)
+    ///     .build();
+    ///
+    /// let runtime = tokio::runtime::Runtime::new().unwrap();
+    /// let originators = runtime.block_on(client.originators("bat0"))?;
+    /// assert_eq!(originators[0].originator.to_string(), "02:00:00:00:00:02");
+    /// assert_eq!(originators[0].tq, Some(255));
+    /// assert_eq!(originators[0].last_seen.as_millis(), 560);
+    /// # std::fs::remove_dir_all(&root)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn debugfs_fallback(mut self, root: impl Into<PathBuf>) -> Self {
+        self.debugfs = Some(root.into());
+        self
+    }
+
+    /// Selects the mesh interface of requests by name (`BATADV_ATTR_MESH_IFNAME`)
+    /// instead of by index. Requests use the index by default.
+    ///
+    /// Saves the rtnetlink lookup of the index before each request, and works where
+    /// rtnetlink is restricted, e.g. in sandboxes. The kernel must accept the name:
+    /// batman-adv releases selecting the mesh interface by index only reject such
+    /// requests (`EINVAL`). Hard interfaces, e.g. of
+    /// [`neighbors_on`](RobinClient::neighbors_on), are still sent by index, and
+    /// interface management (`set_interface`, `create_interface`, ...) still uses
+    /// rtnetlink.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use batman_robin::{Command, MockBackend, RobinClient};
+    /// use std::sync::Arc;
+    ///
+    /// // The mock knows no links: resolving `bat0` would fail.
+    /// let mock = Arc::new(
+    ///     MockBackend::new().with_reply(Command::BatadvCmdGetOriginators, Vec::new()),
+    /// );
+    /// let client = RobinClient::builder()
+    ///     .mock_backend(mock.clone())
+    ///     .mesh_by_name(true)
+    ///     .build();
+    ///
+    /// let runtime = tokio::runtime::Runtime::new().unwrap();
+    /// runtime.block_on(client.originators("bat0"))?;
+    /// assert!(mock.requests()[0].windows(4).any(|name| name == b"bat0"));
+    /// # Ok::<(), batman_robin::RobinError>(())
+    /// ```
+    pub fn mesh_by_name(mut self, enabled: bool) -> Self {
+        self.mesh_by_name = enabled;
+        self
+    }
+
+    /// Runs concurrent identical table queries only once and shares their result.
+    /// Queries are not coalesced by default.
+    ///
+    /// When several tasks, e.g. a metrics exporter and a dashboard, ask for the
+    /// same table of the same interface while a dump of it is in flight, they all
+    /// get the entries of that dump instead of each starting their own. The
+    /// originator, neighbor, gateway, translation table and DAT cache queries are
+    /// coalesced, across all clones of the client; streams are not.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use batman_robin::RobinClient;
+    /// # async fn example() -> Result<(), batman_robin::RobinError> {
+    /// let client = RobinClient::builder().coalesce_queries(true).build();
+    ///
+    /// // A single originator dump serves both callers.
+    /// let (exporter, dashboard) =
+    ///     futures::try_join!(client.originators("bat0"), client.originators("bat0"))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn coalesce_queries(mut self, enabled: bool) -> Self {
+        self.coalesce = enabled;
+        self
+    }
+
+    /// Caches the results of table queries for `ttl`. Results are not cached by
+    /// default.
+    ///
+    /// High-frequency consumers, e.g. an API polled by several dashboards, then
+    /// only cause one dump per table and interface every `ttl`, keeping the load
+    /// off small routers. Configuration changes made through the client drop the
+    /// cache; [`RobinClient::invalidate_cache`] drops it on changes made by others.
+    /// The same tables as with [`coalesce_queries`](Self::coalesce_queries) are
+    /// cached, and caching implies coalescing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use batman_robin::{Command, MockBackend, RobinClient};
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    ///
+    /// let mock = Arc::new(
+    ///     MockBackend::new()
+    ///         .with_link(7, "bat0")
+    ///         .with_reply(Command::BatadvCmdGetOriginators, Vec::new())
+    ///         .with_reply(Command::BatadvCmdGetOriginators, Vec::new()),
+    /// );
+    /// let client = RobinClient::builder()
+    ///     .mock_backend(mock.clone())
+    ///     .cache_ttl(Duration::from_secs(5))
+    ///     .build();
+    ///
+    /// let runtime = tokio::runtime::Runtime::new().unwrap();
+    /// runtime.block_on(client.originators("bat0"))?;
+    /// runtime.block_on(client.originators("bat0"))?;
+    /// assert_eq!(mock.requests().len(), 1);
+    ///
+    /// // e.g. on a link event
+    /// client.invalidate_cache();
+    /// runtime.block_on(client.originators("bat0"))?;
+    /// assert_eq!(mock.requests().len(), 2);
+    /// # Ok::<(), batman_robin::RobinError>(())
+    /// ```
+    pub fn cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Caches the results of the `table` dump, e.g.
+    /// [`Command::BatadvCmdGetOriginators`](model::Command::BatadvCmdGetOriginators),
+    /// for `ttl` instead of the [`cache_ttl`](Self::cache_ttl). A zero `ttl` turns
+    /// caching off for the table.
+    ///
+    /// ```
+    /// use batman_robin::{Command, RobinClient};
+    /// use std::time::Duration;
+    ///
+    /// // Routes change quickly, translation tables slowly.
+    /// let client = RobinClient::builder()
+    ///     .cache_ttl(Duration::from_secs(30))
+    ///     .table_cache_ttl(Command::BatadvCmdGetOriginators, Duration::from_secs(1))
+    ///     .build();
+    /// ```
+    pub fn table_cache_ttl(mut self, table: model::Command, ttl: Duration) -> Self {
+        self.table_ttl.insert(table, ttl);
+        self
+    }
+
+    /// Builds the configured `RobinClient`.
+    pub fn build(self) -> RobinClient {
+        RobinClient {
+            transport: NetlinkTransport::new(
+                self.retry,
+                self.rate_limit,
+                self.record,
+                self.mock,
+                self.debugfs,
+                self.mesh_by_name,
+            ),
+            cache: (self.coalesce || self.cache_ttl.is_some() || !self.table_ttl.is_empty())
+                .then(|| Arc::new(QueryCache::new(self.cache_ttl, self.table_ttl))),
+        }
     }
 }
@@ -0,0 +1,145 @@
+use crate::commands::get_bridge_loop_avoidance;
+use crate::error::RobinError;
+use crate::model::MeshWarning;
+use crate::netlink::{self, MeshTransport};
+
+use std::collections::HashMap;
+
+use neli::consts::{
+    nl::NlmF,
+    rtnl::{Ifla, IflaInfo, RtAddrFamily, Rtm},
+};
+use neli::nl::Nlmsghdr;
+use neli::rtnl::{Ifinfomsg, IfinfomsgBuilder};
+
+/// What the checks need to know about a link.
+struct Link {
+    name: String,
+    /// `IFLA_INFO_KIND`, e.g. `"bridge"`.
+    kind: Option<String>,
+    /// `IFLA_MASTER`, e.g. the bridge the link is a port of.
+    master: Option<u32>,
+    /// `IFLA_LINK`, the lower link of e.g. a VLAN.
+    lower: Option<u32>,
+}
+
+/// Looks for likely misconfigurations of the mesh interface `mesh_if`.
+///
+/// Warns if `mesh_if` is a bridge port, or if one of its hard interfaces is a
+/// bridge or sits on a bridge port (e.g. a VLAN of one), while bridge loop
+/// avoidance is disabled.
+///
+/// # Returns
+///
+/// The warnings found, empty if the configuration looks fine, or a `RobinError`
+/// if the link dump or the settings query fails.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(rtnl, sock), err)
+)]
+pub async fn check_mesh(
+    rtnl: &netlink::RtnlSocket,
+    sock: &impl MeshTransport,
+    mesh_if: &str,
+) -> Result<Vec<MeshWarning>, RobinError> {
+    let links = dump_links(rtnl).await?;
+    let Some((&mesh_ifindex, _)) = links.iter().find(|(_, link)| link.name == mesh_if) else {
+        return Err(RobinError::InterfaceNotFound {
+            name: mesh_if.to_string(),
+        });
+    };
+
+    let is_bridge = |index: &u32| {
+        links
+            .get(index)
+            .is_some_and(|link| link.kind.as_deref() == Some("bridge"))
+    };
+    // The bridge `index` is, or is a port of.
+    let bridge_of = |index: u32| -> Option<u32> {
+        let link = links.get(&index)?;
+        match is_bridge(&index) {
+            true => Some(index),
+            false => link.master.filter(is_bridge),
+        }
+    };
+
+    let mut bridged = Vec::new();
+    if let Some(bridge) = links[&mesh_ifindex].master.filter(is_bridge) {
+        bridged.push((mesh_ifindex, bridge));
+    }
+    let mut hardifs: Vec<_> = links
+        .iter()
+        .filter(|(_, link)| link.master == Some(mesh_ifindex))
+        .map(|(&index, link)| (index, link))
+        .collect();
+    hardifs.sort_by_key(|&(index, _)| index);
+    for (index, link) in hardifs {
+        let lower = link.lower.filter(|&lower| lower != index);
+        if let Some(bridge) = bridge_of(index).or_else(|| lower.and_then(bridge_of)) {
+            bridged.push((index, bridge));
+        }
+    }
+
+    if bridged.is_empty() || get_bridge_loop_avoidance(sock, mesh_if).await? {
+        return Ok(Vec::new());
+    }
+
+    Ok(bridged
+        .into_iter()
+        .map(|(index, bridge)| MeshWarning::BridgedWithoutBla {
+            mesh_if: mesh_if.to_string(),
+            ifname: links[&index].name.clone(),
+            bridge: links[&bridge].name.clone(),
+        })
+        .collect())
+}
+
+/// Dumps all links (`RTM_GETLINK`), by index.
+async fn dump_links(rtnl: &netlink::RtnlSocket) -> Result<HashMap<u32, Link>, RobinError> {
+    let msg = IfinfomsgBuilder::default()
+        .ifi_family(RtAddrFamily::Unspecified)
+        .build()
+        .map_err(|e| RobinError::Netlink(format!("Failed to build Ifinfomsg: {:?}", e)))?;
+
+    let mut response = rtnl
+        .send(Rtm::Getlink, NlmF::REQUEST | NlmF::DUMP | NlmF::ACK, msg)
+        .await?;
+
+    let mut links = HashMap::new();
+    while let Some(msg) = response.next().await {
+        let msg: Nlmsghdr<Rtm, Ifinfomsg> = msg?;
+        let Some(payload) = msg.get_payload() else {
+            continue;
+        };
+
+        let attrs = payload.rtattrs().get_attr_handle();
+        let Ok(name) = attrs.get_attr_payload_as_with_len::<String>(Ifla::Ifname) else {
+            continue;
+        };
+        let kind = attrs
+            .get_nested_attributes::<IflaInfo>(Ifla::Linkinfo)
+            .ok()
+            .and_then(|info| {
+                info.get_attr_payload_as_with_len::<String>(IflaInfo::Kind)
+                    .ok()
+            });
+        let nonzero = |attr| {
+            attrs
+                .get_attr_payload_as::<u32>(attr)
+                .ok()
+                .filter(|&index| index != 0)
+        };
+
+        links.insert(
+            payload.ifi_index().cast_unsigned(),
+            Link {
+                name,
+                kind,
+                master: nonzero(Ifla::Master),
+                lower: nonzero(Ifla::Link),
+            },
+        );
+    }
+
+    Ok(links)
+}
@@ -0,0 +1,47 @@
+use crate::commands::dump_entries;
+use crate::error::RobinError;
+use crate::model::{Command, DatCacheEntry};
+use crate::netlink::MeshTransport;
+
+use futures::{Stream, TryStreamExt};
+
+/// Retrieves the distributed ARP table (DAT) cache of a given BATMAN-adv mesh interface.
+///
+/// This corresponds to the `batctl dc` command.
+///
+/// # Arguments
+///
+/// * `mesh_if` - The name of the BATMAN-adv mesh interface to query.
+///
+/// # Returns
+///
+/// A vector of `DatCacheEntry` structs, or a `RobinError` if any netlink operation
+/// or parsing fails.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(sock), err)
+)]
+pub async fn get_dat_cache(
+    sock: &impl MeshTransport,
+    mesh_if: &str,
+) -> Result<Vec<DatCacheEntry>, RobinError> {
+    get_dat_cache_stream(sock.clone(), mesh_if)
+        .try_collect()
+        .await
+}
+
+/// Streams the DAT cache entries of a BATMAN-adv mesh interface as the dump is received.
+///
+/// # Arguments
+///
+/// * `mesh_if` - The name of the BATMAN-adv mesh interface (e.g., `"bat0"`).
+///
+/// # Returns
+///
+/// A stream of `DatCacheEntry` results. The stream ends after the first error.
+pub fn get_dat_cache_stream(
+    sock: impl MeshTransport,
+    mesh_if: &str,
+) -> impl Stream<Item = Result<DatCacheEntry, RobinError>> {
+    dump_entries(sock, mesh_if, Command::BatadvCmdGetDatCache, None)
+}
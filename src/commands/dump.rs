@@ -0,0 +1,87 @@
+use crate::commands::{mesh_selector, resolve_iface};
+use crate::error::RobinError;
+use crate::model::{Attribute, Command, IfaceRef};
+use crate::netlink::{self, MeshResponse, MeshTransport};
+
+use futures::{Stream, TryStreamExt, stream};
+use neli::genl::GenlAttrHandle;
+
+/// A table entry decoded from one message of a `batadv` dump.
+///
+/// Implemented next to the command dumping the table; [`dump_entries`] takes care
+/// of the request and of the messages ending the dump.
+pub(crate) trait FromAttrs: Sized + Send {
+    /// Decodes the entry carried by `attrs`. Interface indexes are resolved to names
+    /// on `sock`, the transport the dump was received on.
+    fn from_attrs(
+        attrs: &GenlAttrHandle<'_, u16>,
+        sock: &impl MeshTransport,
+    ) -> impl Future<Output = Result<Self, RobinError>> + Send;
+}
+
+/// Returns the name of the hard interface of a dump entry, from
+/// `BATADV_ATTR_HARD_IFNAME` or by resolving `BATADV_ATTR_HARD_IFINDEX` on `sock`.
+pub(crate) async fn hard_ifname(
+    attrs: &GenlAttrHandle<'_, u16>,
+    sock: &impl MeshTransport,
+) -> Result<String, RobinError> {
+    if let Some(name) = netlink::attr_str(attrs, Attribute::BatadvAttrHardIfname) {
+        return Ok(name.into_owned());
+    }
+
+    let ifindex: u32 = netlink::attr_required(attrs, Attribute::BatadvAttrHardIfindex)?;
+    sock.if_indextoname(ifindex).await
+}
+
+/// Streams the entries of the `cmd` dump of `mesh_if`, restricted to the hard
+/// interface `hard_if` if set.
+///
+/// Entries are yielded as soon as the corresponding netlink dump message arrives
+/// instead of being buffered into a `Vec`. The stream ends after the first error.
+pub(crate) fn dump_entries<T: FromAttrs>(
+    sock: impl MeshTransport,
+    mesh_if: &str,
+    cmd: Command,
+    hard_if: Option<&IfaceRef>,
+) -> impl Stream<Item = Result<T, RobinError>> {
+    stream::once(request_dump(sock, mesh_if, cmd, hard_if))
+        .map_ok(|dump| stream::try_unfold(dump, next_entry))
+        .try_flatten()
+}
+
+/// Sends the `cmd` dump request for `mesh_if`, restricted to `hard_if` if set.
+async fn request_dump<T: MeshTransport>(
+    sock: T,
+    mesh_if: &str,
+    cmd: Command,
+    hard_if: Option<&IfaceRef>,
+) -> Result<T::Response, RobinError> {
+    let mut attrs = mesh_selector(&sock, mesh_if).await?;
+    if let Some(hard_if) = hard_if {
+        attrs.add(
+            Attribute::BatadvAttrHardIfindex,
+            resolve_iface(&sock, hard_if).await?,
+        )?;
+    }
+
+    let msg = netlink::build_genl_msg(cmd, attrs.build())?;
+    sock.dump(msg).await
+}
+
+/// Reads the next entry from an in-flight dump.
+///
+/// Returns `Ok(None)` once the end of the dump has been reached.
+async fn next_entry<T: FromAttrs, R: MeshResponse>(
+    mut dump: R,
+) -> Result<Option<(T, R)>, RobinError> {
+    let Some(msg) = dump.next().await else {
+        return Ok(None);
+    };
+    let msg = msg?;
+    let Some(payload) = netlink::genl_payload(&msg)? else {
+        return Ok(None);
+    };
+
+    let entry = T::from_attrs(&payload.attrs().get_attr_handle(), dump.transport()).await?;
+    Ok(Some((entry, dump)))
+}
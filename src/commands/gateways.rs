@@ -1,12 +1,9 @@
-use crate::commands::{if_indextoname, if_nametoindex};
+use crate::commands::dump_entries;
 use crate::error::RobinError;
-use crate::model::{AttrValueForSend, Attribute, Command, Gateway};
-use crate::netlink;
+use crate::model::{Command, Gateway};
+use crate::netlink::MeshTransport;
 
-use macaddr::MacAddr6;
-use neli::consts::nl::{NlmF, Nlmsg};
-use neli::genl::Genlmsghdr;
-use neli::nl::{NlPayload, Nlmsghdr};
+use futures::{Stream, TryStreamExt};
 
 /// Retrieves the list of gateways known to a BATMAN-adv mesh interface.
 ///
@@ -35,126 +32,34 @@ use neli::nl::{NlPayload, Nlmsghdr};
 /// }
 /// # }
 /// ```
-pub async fn get_gateways_list(mesh_if: &str) -> Result<Vec<Gateway>, RobinError> {
-    let mut attrs = netlink::GenlAttrBuilder::new();
-    let ifindex = if_nametoindex(mesh_if).await.map_err(|_| {
-        RobinError::Netlink(format!(
-            "Error - interface '{}' is not present or not a batman-adv interface",
-            mesh_if
-        ))
-    })?;
-
-    attrs
-        .add(
-            Attribute::BatadvAttrMeshIfindex,
-            AttrValueForSend::U32(ifindex),
-        )
-        .map_err(|_| {
-            RobinError::Netlink("Error - could not set mesh interface index".to_string())
-        })?;
-
-    let msg = netlink::build_genl_msg(Command::BatadvCmdGetGateways, attrs.build())
-        .map_err(|_| RobinError::Netlink("Error - failed to build netlink message".to_string()))?;
-
-    let mut socket = netlink::BatadvSocket::connect().await.map_err(|_| {
-        RobinError::Netlink("Error - failed to connect to batman-adv netlink socket".to_string())
-    })?;
-
-    let mut response = socket
-        .send(NlmF::REQUEST | NlmF::DUMP, msg)
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(sock), err)
+)]
+pub async fn get_gateways_list(
+    sock: &impl MeshTransport,
+    mesh_if: &str,
+) -> Result<Vec<Gateway>, RobinError> {
+    get_gateways_list_stream(sock.clone(), mesh_if)
+        .try_collect()
         .await
-        .map_err(|_| RobinError::Netlink("Error - failed to send netlink request".to_string()))?;
-
-    let mut gateways = Vec::new();
-
-    while let Some(msg) = response.next().await {
-        let msg: Nlmsghdr<u16, Genlmsghdr<u8, u16>> = msg.map_err(|_| {
-            RobinError::Netlink("Error - failed to parse netlink response".to_string())
-        })?;
-
-        match *msg.nl_type() {
-            x if x == Nlmsg::Done.into() => break,
-            x if x == Nlmsg::Error.into() => {
-                match &msg.nl_payload() {
-                    NlPayload::Err(err) if *err.error() == 0 => break, // end of dump
-                    NlPayload::Err(err) => {
-                        return Err(RobinError::Netlink(format!(
-                            "Netlink error {}",
-                            err.error()
-                        )));
-                    }
-                    _ => {
-                        return Err(RobinError::Netlink(
-                            "Unknown netlink error payload".to_string(),
-                        ));
-                    }
-                }
-            }
-            _ => {}
-        }
-
-        let attrs = msg
-            .get_payload()
-            .ok_or_else(|| RobinError::Parse("Error - netlink message has no payload".into()))?
-            .attrs()
-            .get_attr_handle();
-
-        let is_best = attrs
-            .get_attribute(Attribute::BatadvAttrFlagBest.into())
-            .is_some();
-
-        let mac_addr = attrs
-            .get_attr_payload_as::<[u8; 6]>(Attribute::BatadvAttrOrigAddress.into())
-            .map_err(|_| RobinError::Parse("Error - gateway originator address missing".into()))?;
-
-        let router = attrs
-            .get_attr_payload_as::<[u8; 6]>(Attribute::BatadvAttrRouter.into())
-            .map_err(|_| RobinError::Parse("Error - gateway router address missing".into()))?;
-
-        let outgoing_if =
-            match attrs.get_attr_payload_as::<[u8; 16]>(Attribute::BatadvAttrHardIfname.into()) {
-                Ok(bytes) => {
-                    let nul_pos = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
-                    String::from_utf8_lossy(&bytes[..nul_pos]).into_owned()
-                }
-                Err(_) => {
-                    let ifindex = attrs
-                        .get_attr_payload_as::<u32>(Attribute::BatadvAttrHardIfindex.into())
-                        .map_err(|_| {
-                            RobinError::Parse("Error - gateway hard interface index missing".into())
-                        })?;
-                    if_indextoname(ifindex).await.map_err(|_| {
-                        RobinError::Netlink(
-                            "Error - failed to resolve interface name from index".to_string(),
-                        )
-                    })?
-                }
-            };
-
-        let bandwidth_down = attrs
-            .get_attr_payload_as::<u32>(Attribute::BatadvAttrBandwidthDown.into())
-            .ok();
-        let bandwidth_up = attrs
-            .get_attr_payload_as::<u32>(Attribute::BatadvAttrBandwidthUp.into())
-            .ok();
-        let throughput = attrs
-            .get_attr_payload_as::<u32>(Attribute::BatadvAttrThroughput.into())
-            .ok();
-        let tq = attrs
-            .get_attr_payload_as::<u8>(Attribute::BatadvAttrTq.into())
-            .ok();
-
-        gateways.push(Gateway {
-            mac_addr: MacAddr6::from(mac_addr),
-            router: MacAddr6::from(router),
-            outgoing_if,
-            bandwidth_down,
-            bandwidth_up,
-            throughput,
-            tq,
-            is_best,
-        });
-    }
+}
 
-    Ok(gateways)
+/// Streams the gateways of a BATMAN-adv mesh interface as the dump is received.
+///
+/// Entries are yielded as soon as the corresponding netlink dump message arrives
+/// instead of being buffered into a `Vec`, which keeps memory usage flat on large meshes.
+///
+/// # Arguments
+///
+/// * `mesh_if` - The name of the BATMAN-adv mesh interface (e.g., `"bat0"`).
+///
+/// # Returns
+///
+/// A stream of `Gateway` results. The stream ends after the first error.
+pub fn get_gateways_list_stream(
+    sock: impl MeshTransport,
+    mesh_if: &str,
+) -> impl Stream<Item = Result<Gateway, RobinError>> {
+    dump_entries(sock, mesh_if, Command::BatadvCmdGetGateways, None)
 }
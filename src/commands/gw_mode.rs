@@ -1,11 +1,46 @@
-use crate::commands::if_nametoindex;
+use crate::commands::{mesh_selector, query_mesh};
 use crate::error::RobinError;
-use crate::model::{AttrValueForSend, Attribute, Command, GatewayInfo, GwMode};
-use crate::netlink;
+use crate::model::{AttrValueForSend, Attribute, Bandwidth, Command, GatewayInfo, GwMode};
+use crate::netlink::{self, MeshResponse, MeshTransport};
 
 use neli::consts::nl::NlmF;
-use neli::genl::Genlmsghdr;
-use neli::nl::Nlmsghdr;
+
+/// Downstream bandwidth announced in server mode when none is given.
+const DEFAULT_BANDWIDTH_DOWN: Bandwidth = Bandwidth::from_mbps(10);
+/// Upstream bandwidth announced in server mode when none is given.
+const DEFAULT_BANDWIDTH_UP: Bandwidth = Bandwidth::from_mbps(2);
+
+/// Converts a `BATADV_ATTR_GW_MODE` value to the gateway mode.
+pub(crate) fn gw_mode_from_u8(mode: u8) -> GwMode {
+    match mode {
+        0 => GwMode::Off,
+        1 => GwMode::Client,
+        2 => GwMode::Server,
+        other => GwMode::Unknown(other),
+    }
+}
+
+/// Returns `true` if `current` already holds the settings [`set_gateway`] would send
+/// for `mode`, `down`, `up` and `sel_class`.
+///
+/// Bandwidths and the selection class only matter in server mode.
+pub(crate) fn gateway_matches(
+    current: &GatewayInfo,
+    mode: GwMode,
+    down: Option<Bandwidth>,
+    up: Option<Bandwidth>,
+    sel_class: Option<u32>,
+) -> bool {
+    // Compare in kernel units, as the kernel rounds the bandwidths down to them.
+    let same = |current: Bandwidth, wanted: Bandwidth| current.to_netlink() == wanted.to_netlink();
+    current.mode == mode
+        && (mode != GwMode::Server
+            || (same(
+                current.bandwidth_down,
+                down.unwrap_or(DEFAULT_BANDWIDTH_DOWN),
+            ) && same(current.bandwidth_up, up.unwrap_or(DEFAULT_BANDWIDTH_UP))
+                && current.sel_class == sel_class.unwrap_or(0)))
+}
 
 /// Retrieves the current gateway settings for a BATMAN-adv mesh interface.
 ///
@@ -20,75 +55,25 @@ use neli::nl::Nlmsghdr;
 ///
 /// Returns a `GatewayInfo` struct containing the mode, selection class, bandwidths,
 /// and routing algorithm, or a `RobinError` if the information could not be retrieved.
-pub async fn get_gateway(mesh_if: &str) -> Result<GatewayInfo, RobinError> {
-    let mut attrs = netlink::GenlAttrBuilder::new();
-    let ifindex = if_nametoindex(mesh_if).await.map_err(|_| {
-        RobinError::Netlink(format!(
-            "Error - interface '{}' is not present or not a batman-adv interface",
-            mesh_if
-        ))
-    })?;
-
-    attrs
-        .add(
-            Attribute::BatadvAttrMeshIfindex,
-            AttrValueForSend::U32(ifindex),
-        )
-        .map_err(|_| {
-            RobinError::Netlink("Error - could not set mesh interface index".to_string())
-        })?;
-
-    let msg = netlink::build_genl_msg(Command::BatadvCmdGetMeshInfo, attrs.build())
-        .map_err(|_| RobinError::Netlink("Error - failed to build netlink message".to_string()))?;
-
-    let mut socket = netlink::BatadvSocket::connect().await.map_err(|_| {
-        RobinError::Netlink("Error - failed to connect to batman-adv netlink socket".to_string())
-    })?;
-
-    let mut response = socket
-        .send(NlmF::REQUEST, msg)
-        .await
-        .map_err(|_| RobinError::Netlink("Error - failed to send netlink request".to_string()))?;
-
-    let msg: Nlmsghdr<u16, Genlmsghdr<u8, u16>> = response
-        .next()
-        .await
-        .ok_or_else(|| RobinError::Parse("Error - no response from kernel".into()))?
-        .map_err(|_| RobinError::Netlink("Error - failed to parse netlink response".to_string()))?;
-
-    let attrs = msg
-        .get_payload()
-        .ok_or_else(|| RobinError::Parse("Error - message has no payload".into()))?
-        .attrs()
-        .get_attr_handle();
-
-    let mode = match attrs.get_attr_payload_as::<u8>(Attribute::BatadvAttrGwMode.into()) {
-        Ok(0) => GwMode::Off,
-        Ok(1) => GwMode::Client,
-        Ok(2) => GwMode::Server,
-        Ok(_) => GwMode::Unknown,
-        Err(_) => GwMode::Unknown,
-    };
-
-    let sel_class = attrs
-        .get_attr_payload_as::<u32>(Attribute::BatadvAttrGwSelClass.into())
-        .map_err(|_| RobinError::Parse("Error - gateway selection class missing".into()))?;
-
-    let bandwidth_down = attrs
-        .get_attr_payload_as::<u32>(Attribute::BatadvAttrGwBandwidthDown.into())
-        .map_err(|_| RobinError::Parse("Error - gateway downstream bandwidth missing".into()))?;
-
-    let bandwidth_up = attrs
-        .get_attr_payload_as::<u32>(Attribute::BatadvAttrGwBandwidthUp.into())
-        .map_err(|_| RobinError::Parse("Error - gateway upstream bandwidth missing".into()))?;
-
-    let algo = attrs
-        .get_attr_payload_as_with_len::<Vec<u8>>(Attribute::BatadvAttrAlgoName.into())
-        .map(|bytes| {
-            let nul = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
-            String::from_utf8_lossy(&bytes[..nul]).into_owned()
-        })
-        .map_err(|_| RobinError::Parse("Error - routing algorithm name missing".into()))?;
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(sock), err)
+)]
+pub async fn get_gateway(
+    sock: &impl MeshTransport,
+    mesh_if: &str,
+) -> Result<GatewayInfo, RobinError> {
+    let obj = query_mesh(sock, mesh_if).await?;
+
+    let mode = gw_mode_from_u8(obj.get_u8(Attribute::BatadvAttrGwMode)?);
+    let sel_class = obj.get_u32(Attribute::BatadvAttrGwSelClass)?;
+    let bandwidth_down =
+        Bandwidth::from_netlink(obj.get_u32(Attribute::BatadvAttrGwBandwidthDown)?);
+    let bandwidth_up = Bandwidth::from_netlink(obj.get_u32(Attribute::BatadvAttrGwBandwidthUp)?);
+    let algo = obj
+        .get_string(Attribute::BatadvAttrAlgoName)
+        .ok()
+        .map(str::to_string);
 
     Ok(GatewayInfo {
         mode,
@@ -107,8 +92,8 @@ pub async fn get_gateway(mesh_if: &str) -> Result<GatewayInfo, RobinError> {
 /// # Arguments
 ///
 /// * `mode` - The gateway mode to set (`GwMode::Off`, `GwMode::Client`, `GwMode::Server`).
-/// * `down` - Optional downstream bandwidth in Mbps (used when mode is Server).
-/// * `up` - Optional upstream bandwidth in Mbps (used when mode is Server).
+/// * `down` - Optional downstream bandwidth (used when mode is Server, 10 MBit by default).
+/// * `up` - Optional upstream bandwidth (used when mode is Server, 2 MBit by default).
 /// * `sel_class` - Optional selection class (used when mode is Server).
 /// * `mesh_if` - The name of the BATMAN-adv mesh interface (e.g., "bat0").
 ///
@@ -116,104 +101,61 @@ pub async fn get_gateway(mesh_if: &str) -> Result<GatewayInfo, RobinError> {
 ///
 /// Returns `Ok(())` if the settings were applied successfully, or a `RobinError` if
 /// the operation failed or was rejected by the kernel.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(sock), err)
+)]
 pub async fn set_gateway(
+    sock: &impl MeshTransport,
     mode: GwMode,
-    down: Option<u32>,
-    up: Option<u32>,
+    down: Option<Bandwidth>,
+    up: Option<Bandwidth>,
     sel_class: Option<u32>,
     mesh_if: &str,
 ) -> Result<(), RobinError> {
-    let mut attrs = netlink::GenlAttrBuilder::new();
-    let ifindex = if_nametoindex(mesh_if).await.map_err(|_| {
-        RobinError::Netlink(format!(
-            "Error - interface '{}' is not present or not a batman-adv interface",
-            mesh_if
-        ))
-    })?;
-
-    attrs
-        .add(
-            Attribute::BatadvAttrMeshIfindex,
-            AttrValueForSend::U32(ifindex),
-        )
-        .map_err(|_| {
-            RobinError::Netlink("Error - could not set mesh interface index".to_string())
-        })?;
+    let mut attrs = mesh_selector(sock, mesh_if).await?;
 
     match mode {
         GwMode::Off => {
-            attrs
-                .add(Attribute::BatadvAttrGwMode, AttrValueForSend::U8(0))
-                .map_err(|_| {
-                    RobinError::Netlink("Error - could not set gateway mode to OFF".to_string())
-                })?;
+            attrs.add(Attribute::BatadvAttrGwMode, AttrValueForSend::U8(0))?;
         }
 
         GwMode::Client => {
-            attrs
-                .add(Attribute::BatadvAttrGwMode, AttrValueForSend::U8(1))
-                .map_err(|_| {
-                    RobinError::Netlink("Error - could not set gateway mode to CLIENT".to_string())
-                })?;
+            attrs.add(Attribute::BatadvAttrGwMode, AttrValueForSend::U8(1))?;
         }
 
         GwMode::Server => {
-            attrs
-                .add(Attribute::BatadvAttrGwMode, AttrValueForSend::U8(2))
-                .map_err(|_| {
-                    RobinError::Netlink("Error - could not set gateway mode to SERVER".to_string())
-                })?;
-
-            attrs
-                .add(
-                    Attribute::BatadvAttrGwBandwidthDown,
-                    AttrValueForSend::U32(down.unwrap_or(10000) / 100),
-                )
-                .map_err(|_| {
-                    RobinError::Netlink(
-                        "Error - could not set gateway downstream bandwidth".to_string(),
-                    )
-                })?;
-
-            attrs
-                .add(
-                    Attribute::BatadvAttrGwBandwidthUp,
-                    AttrValueForSend::U32(up.unwrap_or(2000) / 100),
-                )
-                .map_err(|_| {
-                    RobinError::Netlink(
-                        "Error - could not set gateway upstream bandwidth".to_string(),
-                    )
-                })?;
-
-            attrs
-                .add(
-                    Attribute::BatadvAttrGwSelClass,
-                    AttrValueForSend::U32(sel_class.unwrap_or(0)),
-                )
-                .map_err(|_| {
-                    RobinError::Netlink("Error - could not set gateway selection class".to_string())
-                })?;
+            attrs.add(Attribute::BatadvAttrGwMode, AttrValueForSend::U8(2))?;
+
+            attrs.add(
+                Attribute::BatadvAttrGwBandwidthDown,
+                AttrValueForSend::U32(down.unwrap_or(DEFAULT_BANDWIDTH_DOWN).to_netlink()),
+            )?;
+
+            attrs.add(
+                Attribute::BatadvAttrGwBandwidthUp,
+                AttrValueForSend::U32(up.unwrap_or(DEFAULT_BANDWIDTH_UP).to_netlink()),
+            )?;
+
+            attrs.add(
+                Attribute::BatadvAttrGwSelClass,
+                AttrValueForSend::U32(sel_class.unwrap_or(0)),
+            )?;
         }
 
-        GwMode::Unknown => {
+        GwMode::Unknown(_) => {
             return Err(RobinError::Parse(
                 "Cannot set unknown gateway mode".to_string(),
             ));
         }
     }
 
-    let msg = netlink::build_genl_msg(Command::BatadvCmdSetMesh, attrs.build())
-        .map_err(|_| RobinError::Netlink("Error - failed to build netlink message".to_string()))?;
-
-    let mut socket = netlink::BatadvSocket::connect().await.map_err(|_| {
-        RobinError::Netlink("Error - failed to connect to batman-adv netlink socket".to_string())
-    })?;
+    let msg = netlink::build_genl_msg(Command::BatadvCmdSetMesh, attrs.build())?;
 
-    socket
-        .send(NlmF::REQUEST | NlmF::ACK, msg)
-        .await
-        .map_err(|_| RobinError::Netlink("Error - failed to send netlink request".to_string()))?;
+    sock.send(NlmF::REQUEST | NlmF::ACK, msg)
+        .await?
+        .ack()
+        .await?;
 
     Ok(())
 }
@@ -1,19 +1,19 @@
-use crate::commands::{if_indextoname, if_nametoindex};
+use crate::commands::{
+    dump_entries, if_nametoindex, mesh_selector, raw_get, resolve_iface, rtnl_ack,
+};
 use crate::error::RobinError;
-use crate::model::{AttrValueForSend, Attribute, Command, Interface};
-use crate::netlink;
+use crate::model::{Attribute, Bandwidth, Command, HardifInfo, IfaceRef, Interface, OperState};
+use crate::netlink::{self, MeshTransport};
 
+use batman_robin_derive::FromBatadvAttrs;
+use futures::TryStreamExt;
 use neli::consts::{
-    nl::{NlmF, Nlmsg},
+    nl::NlmF,
     rtnl::{Ifla, IflaInfo, RtAddrFamily, Rtm},
-    socket::NlFamily,
 };
-use neli::genl::Genlmsghdr;
-use neli::nl::{NlPayload, Nlmsghdr};
-use neli::router::asynchronous::NlRouter;
+use neli::nl::Nlmsghdr;
 use neli::rtnl::{Ifinfomsg, IfinfomsgBuilder, RtattrBuilder};
 use neli::types::{Buffer, RtBuffer};
-use neli::utils::Groups;
 
 /// Counts the number of physical or virtual interfaces attached to a BATMAN-adv mesh interface.
 ///
@@ -35,24 +35,15 @@ use neli::utils::Groups;
 /// println!("Number of interfaces: {}", count);
 /// # }
 /// ```
-pub async fn count_interfaces(mesh_if: &str) -> Result<u32, RobinError> {
-    let mesh_ifindex = if_nametoindex(mesh_if).await.map_err(|_| {
-        RobinError::Netlink(format!(
-            "Error - interface '{}' is not present or not a batman-adv interface",
-            mesh_if
-        ))
-    })?;
-
-    let (rtnl, _) = NlRouter::connect(NlFamily::Route, None, Groups::empty())
-        .await
-        .map_err(|_| {
-            RobinError::Netlink("Error - failed to connect to netlink router".to_string())
-        })?;
-
-    rtnl.enable_ext_ack(true)
-        .map_err(|_| RobinError::Netlink("Error - failed to enable extended ACK".to_string()))?;
-    rtnl.enable_strict_checking(true)
-        .map_err(|_| RobinError::Netlink("Error - failed to enable strict checking".to_string()))?;
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(rtnl), err)
+)]
+pub async fn count_interfaces(
+    rtnl: &netlink::RtnlSocket,
+    mesh_if: &str,
+) -> Result<u32, RobinError> {
+    let mesh_ifindex = if_nametoindex(rtnl, mesh_if).await?;
 
     let ifinfomsg = IfinfomsgBuilder::default()
         .ifi_family(RtAddrFamily::Unspecified)
@@ -60,19 +51,12 @@ pub async fn count_interfaces(mesh_if: &str) -> Result<u32, RobinError> {
         .map_err(|_| RobinError::Netlink("Error - failed to build Ifinfomsg".to_string()))?;
 
     let mut response = rtnl
-        .send::<_, _, Rtm, Ifinfomsg>(
-            Rtm::Getlink,
-            NlmF::DUMP | NlmF::ACK,
-            NlPayload::Payload(ifinfomsg),
-        )
-        .await
-        .map_err(|_| RobinError::Netlink("Error - failed to send Getlink request".to_string()))?;
+        .send(Rtm::Getlink, NlmF::DUMP | NlmF::ACK, ifinfomsg)
+        .await?;
 
     let mut count = 0u32;
     while let Some(msg) = response.next().await {
-        let msg: Nlmsghdr<Rtm, Ifinfomsg> = msg.map_err(|_| {
-            RobinError::Netlink("Error - failed to parse netlink message".to_string())
-        })?;
+        let msg: Nlmsghdr<Rtm, Ifinfomsg> = msg?;
 
         if let Some(payload) = msg.get_payload() {
             let attrs = payload.rtattrs().get_attr_handle();
@@ -87,6 +71,59 @@ pub async fn count_interfaces(mesh_if: &str) -> Result<u32, RobinError> {
     Ok(count)
 }
 
+/// Lists all BATMAN-adv mesh interfaces, i.e. the links of kind `"batadv"`.
+///
+/// # Returns
+///
+/// The names of the mesh interfaces in the order rtnetlink reports them, or a
+/// `RobinError` if the link dump fails.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(rtnl), err)
+)]
+pub async fn list_meshes(rtnl: &netlink::RtnlSocket) -> Result<Vec<String>, RobinError> {
+    let msg = IfinfomsgBuilder::default()
+        .ifi_family(RtAddrFamily::Unspecified)
+        .build()
+        .map_err(|e| RobinError::Netlink(format!("Failed to build Ifinfomsg: {:?}", e)))?;
+
+    let mut response = rtnl
+        .send(Rtm::Getlink, NlmF::REQUEST | NlmF::DUMP | NlmF::ACK, msg)
+        .await?;
+
+    let mut result = Vec::new();
+    while let Some(msg) = response.next().await {
+        let msg: Nlmsghdr<Rtm, Ifinfomsg> = msg?;
+
+        let payload = match msg.get_payload() {
+            Some(p) => p,
+            None => continue,
+        };
+
+        let attrs = payload.rtattrs().get_attr_handle();
+        let mesh_if = match attrs.get_attr_payload_as_with_len::<String>(Ifla::Ifname) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+
+        let linkinfo = match attrs.get_nested_attributes::<IflaInfo>(Ifla::Linkinfo) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+
+        let kind = match linkinfo.get_attr_payload_as_with_len::<String>(IflaInfo::Kind) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+
+        if kind == "batadv" {
+            result.push(mesh_if);
+        }
+    }
+
+    Ok(result)
+}
+
 /// Retrieves the list of interfaces associated with a BATMAN-adv mesh interface.
 ///
 /// This corresponds to the `batctl if` command. Each entry contains the interface name
@@ -112,88 +149,93 @@ pub async fn count_interfaces(mesh_if: &str) -> Result<u32, RobinError> {
 /// }
 /// # }
 /// ```
-pub async fn get_interfaces(mesh_if: &str) -> Result<Vec<Interface>, RobinError> {
-    let mut attrs = netlink::GenlAttrBuilder::new();
-    let mesh_ifindex = if_nametoindex(mesh_if).await.map_err(|_| {
-        RobinError::Netlink(format!(
-            "Error - interface '{}' is not present or not a batman-adv interface",
-            mesh_if
-        ))
-    })?;
-
-    attrs
-        .add(
-            Attribute::BatadvAttrMeshIfindex,
-            AttrValueForSend::U32(mesh_ifindex),
-        )
-        .map_err(|_| {
-            RobinError::Netlink("Error - failed to add MeshIfindex attribute".to_string())
-        })?;
-
-    let msg = netlink::build_genl_msg(Command::BatadvCmdGetHardif, attrs.build())
-        .map_err(|_| RobinError::Netlink("Error - failed to build netlink message".to_string()))?;
-
-    let mut sock = netlink::BatadvSocket::connect().await.map_err(|_| {
-        RobinError::Netlink("Error - failed to connect to batman-adv socket".to_string())
-    })?;
-
-    let mut response = sock
-        .send(NlmF::REQUEST | NlmF::DUMP, msg)
-        .await
-        .map_err(|_| RobinError::Netlink("Error - failed to send netlink request".to_string()))?;
-
-    let mut interfaces = Vec::new();
-    while let Some(msg) = response.next().await {
-        let msg: Nlmsghdr<u16, Genlmsghdr<u8, u16>> = msg.map_err(|_| {
-            RobinError::Netlink("Error - failed to parse netlink message".to_string())
-        })?;
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(sock), err)
+)]
+pub async fn get_interfaces(
+    sock: &impl MeshTransport,
+    mesh_if: &str,
+) -> Result<Vec<Interface>, RobinError> {
+    let rows: Vec<HardifRow> =
+        dump_entries(sock.clone(), mesh_if, Command::BatadvCmdGetHardif, None)
+            .try_collect()
+            .await?;
+
+    let mut interfaces = Vec::with_capacity(rows.len());
+    for row in rows {
+        let (link, stats) = sock.link_info(row.ifindex).await?.unzip();
+        interfaces.push(Interface {
+            ifname: row.ifname,
+            active: row.active,
+            operstate: link.map_or(OperState::Unknown, |link| link.operstate),
+            carrier: link.map(|link| link.carrier),
+            stats: stats.flatten(),
+        });
+    }
 
-        match *msg.nl_type() {
-            x if x == Nlmsg::Done.into() => break,
-            x if x == Nlmsg::Error.into() => {
-                match &msg.nl_payload() {
-                    NlPayload::Err(err) if *err.error() == 0 => break, // end of dump
-                    NlPayload::Err(err) => {
-                        return Err(RobinError::Netlink(format!(
-                            "Netlink error {}",
-                            err.error()
-                        )));
-                    }
-                    _ => {
-                        return Err(RobinError::Netlink(
-                            "Unknown netlink error payload".to_string(),
-                        ));
-                    }
-                }
-            }
-            _ => {}
-        }
+    Ok(interfaces)
+}
 
-        let attrs = msg
-            .get_payload()
-            .ok_or_else(|| RobinError::Parse("Error - message has no payload".into()))?
-            .attrs()
-            .get_attr_handle();
-
-        let hard_ifindex = attrs
-            .get_attr_payload_as::<u32>(Attribute::BatadvAttrHardIfindex.into())
-            .map_err(|_| RobinError::Parse("Error - missing HARD_IFINDEX".into()))?;
-
-        let ifname = if_indextoname(hard_ifindex).await.map_err(|_| {
-            RobinError::Netlink(format!(
-                "Error - failed to resolve interface index {}",
-                hard_ifindex
-            ))
-        })?;
+/// A hard interface of the `BATADV_CMD_GET_HARDIF` dump, before its link state is
+/// attached.
+#[derive(FromBatadvAttrs)]
+struct HardifRow {
+    #[batadv(BatadvAttrHardIfindex)]
+    ifindex: u32,
 
-        let active = attrs
-            .get_attribute(Attribute::BatadvAttrActive.into())
-            .is_some();
+    #[batadv(hard_ifname)]
+    ifname: String,
 
-        interfaces.push(Interface { ifname, active });
-    }
+    #[batadv(BatadvAttrActive, flag)]
+    active: bool,
+}
 
-    Ok(interfaces)
+/// Retrieves the settings and state of a single hard interface of a BATMAN-adv mesh.
+///
+/// This sends `BATADV_CMD_GET_HARDIF` for `hard_if` only, instead of dumping all
+/// interfaces of the mesh.
+///
+/// # Arguments
+///
+/// * `mesh_if` - The name of the mesh interface.
+/// * `hard_if` - The name or index of the hard interface.
+///
+/// # Returns
+///
+/// Returns the `HardifInfo` of `hard_if`, or a `RobinError` if the query fails,
+/// e.g. because `hard_if` is not attached to `mesh_if`.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(sock), err)
+)]
+pub async fn get_hardif_info(
+    sock: &impl MeshTransport,
+    mesh_if: &str,
+    hard_if: &IfaceRef,
+) -> Result<HardifInfo, RobinError> {
+    let mut attrs = mesh_selector(sock, mesh_if).await?;
+    let ifindex = resolve_iface(sock, hard_if).await?;
+    attrs.add(Attribute::BatadvAttrHardIfindex, ifindex)?;
+
+    let obj = raw_get(sock, Command::BatadvCmdGetHardif, attrs).await?;
+    let u32_of = |attr| obj.get_u32(attr).ok();
+
+    let ifname = match obj.get_string(Attribute::BatadvAttrHardIfname).ok() {
+        Some(ifname) => ifname.to_string(),
+        None => sock.if_indextoname(ifindex).await?,
+    };
+
+    Ok(HardifInfo {
+        ifname,
+        ifindex,
+        mac: obj.get_mac(Attribute::BatadvAttrHardAddress).ok(),
+        active: obj.get(Attribute::BatadvAttrActive).is_some(),
+        hop_penalty: obj.get_u8(Attribute::BatadvAttrHopPenalty).ok(),
+        elp_interval_ms: u32_of(Attribute::BatadvAttrElpInterval),
+        throughput_override: u32_of(Attribute::BatadvAttrThroughputOverride)
+            .map(Bandwidth::from_netlink),
+    })
 }
 
 /// Adds or removes a physical interface from a BATMAN-adv mesh interface.
@@ -202,7 +244,7 @@ pub async fn get_interfaces(mesh_if: &str) -> Result<Vec<Interface>, RobinError>
 ///
 /// # Arguments
 ///
-/// * `iface` - The name of the interface to add or remove.
+/// * `iface` - The name or index of the interface to add or remove.
 /// * `mesh_if` - Optional mesh interface name to attach to. `None` removes it from any mesh.
 ///
 /// # Returns
@@ -217,29 +259,25 @@ pub async fn get_interfaces(mesh_if: &str) -> Result<Vec<Interface>, RobinError>
 /// // set_interface("eth0", None).await?; // remove from mesh
 /// # }
 /// ```
-pub async fn set_interface(iface: &str, mesh_if: Option<&str>) -> Result<(), RobinError> {
-    let iface_ifindex = if_nametoindex(iface)
-        .await
-        .map_err(|_| RobinError::Netlink(format!("Error - interface '{}' not found", iface)))?;
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(rtnl), err)
+)]
+pub async fn set_interface(
+    rtnl: &netlink::RtnlSocket,
+    iface: &IfaceRef,
+    mesh_if: Option<&str>,
+) -> Result<(), RobinError> {
+    let iface_ifindex = match iface {
+        IfaceRef::Name(name) => if_nametoindex(rtnl, name).await?,
+        IfaceRef::Index(index) => *index,
+    };
 
     let mut mesh_ifindex = 0;
     if let Some(mesh) = mesh_if {
-        mesh_ifindex = if_nametoindex(mesh).await.map_err(|_| {
-            RobinError::Netlink(format!("Error - mesh interface '{}' not found", mesh))
-        })?;
+        mesh_ifindex = if_nametoindex(rtnl, mesh).await?;
     }
 
-    let (rtnl, _) = NlRouter::connect(NlFamily::Route, None, Groups::empty())
-        .await
-        .map_err(|_| {
-            RobinError::Netlink("Error - failed to connect to netlink router".to_string())
-        })?;
-
-    rtnl.enable_ext_ack(true)
-        .map_err(|_| RobinError::Netlink("Error - failed to enable extended ACK".to_string()))?;
-    rtnl.enable_strict_checking(true)
-        .map_err(|_| RobinError::Netlink("Error - failed to enable strict checking".to_string()))?;
-
     let master_attr = RtattrBuilder::default()
         .rta_type(Ifla::Master)
         .rta_payload(mesh_ifindex)
@@ -256,13 +294,71 @@ pub async fn set_interface(iface: &str, mesh_if: Option<&str>) -> Result<(), Rob
         .build()
         .map_err(|_| RobinError::Netlink("Error - failed to build Ifinfomsg".to_string()))?;
 
-    rtnl.send::<_, _, Rtm, Ifinfomsg>(
-        Rtm::Setlink,
-        NlmF::REQUEST | NlmF::ACK,
-        NlPayload::Payload(msg),
-    )
-    .await
-    .map_err(|_| RobinError::Netlink("Error - failed to set interface".to_string()))?;
+    let response = rtnl
+        .send(Rtm::Setlink, NlmF::REQUEST | NlmF::ACK, msg)
+        .await?;
+
+    rtnl_ack(response).await?;
+    rtnl.if_cache().invalidate();
+
+    Ok(())
+}
+
+/// Renames a network interface, e.g. a misnamed BATMAN-adv mesh interface.
+///
+/// This corresponds to `ip link set <old> name <new>`. Unlike destroying and
+/// recreating a mesh interface, its settings and hard interfaces are kept.
+///
+/// # Arguments
+///
+/// * `old` - The current name of the interface.
+/// * `new` - The new name of the interface.
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or a `RobinError` if the rename fails, e.g. with
+/// `EBUSY` if the kernel does not allow renaming the interface while it is up.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn example() {
+/// // rename_interface("bat9", "bat0").await?;
+/// # }
+/// ```
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(rtnl), err)
+)]
+pub async fn rename_interface(
+    rtnl: &netlink::RtnlSocket,
+    old: &str,
+    new: &str,
+) -> Result<(), RobinError> {
+    let ifindex = if_nametoindex(rtnl, old).await?;
+
+    let ifname_attr = RtattrBuilder::default()
+        .rta_type(Ifla::Ifname)
+        .rta_payload(new)
+        .build()
+        .map_err(|_| RobinError::Netlink("Error - failed to build IFNAME attribute".to_string()))?;
+
+    let mut rtattrs: RtBuffer<Ifla, Buffer> = RtBuffer::new();
+    rtattrs.push(ifname_attr);
+
+    let msg = IfinfomsgBuilder::default()
+        .ifi_family(RtAddrFamily::Unspecified)
+        .ifi_index(ifindex.cast_signed())
+        .rtattrs(rtattrs)
+        .build()
+        .map_err(|_| RobinError::Netlink("Error - failed to build Ifinfomsg".to_string()))?;
+
+    let response = rtnl
+        .send(Rtm::Setlink, NlmF::REQUEST | NlmF::ACK, msg)
+        .await?;
+
+    rtnl_ack(response).await?;
+    rtnl.if_cache().invalidate();
 
     Ok(())
 }
@@ -287,19 +383,16 @@ pub async fn set_interface(iface: &str, mesh_if: Option<&str>) -> Result<(), Rob
 /// // create_interface("bat0", Some("BATMAN_IV")).await?;
 /// # }
 /// ```
-pub async fn create_interface(mesh_if: &str, routing_algo: Option<&str>) -> Result<(), RobinError> {
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(rtnl), err)
+)]
+pub async fn create_interface(
+    rtnl: &netlink::RtnlSocket,
+    mesh_if: &str,
+    routing_algo: Option<&str>,
+) -> Result<(), RobinError> {
     const IFLA_BATADV_ALGO_NAME: u16 = 1;
-    let (rtnl, _) = NlRouter::connect(NlFamily::Route, None, Groups::empty())
-        .await
-        .map_err(|_| {
-            RobinError::Netlink("Error - failed to connect to netlink router".to_string())
-        })?;
-
-    rtnl.enable_ext_ack(true)
-        .map_err(|_| RobinError::Netlink("Error - failed to enable extended ACK".to_string()))?;
-    rtnl.enable_strict_checking(true)
-        .map_err(|_| RobinError::Netlink("Error - failed to enable strict checking".to_string()))?;
-
     let ifname_attr = RtattrBuilder::default()
         .rta_type(Ifla::Ifname)
         .rta_payload(mesh_if)
@@ -356,13 +449,16 @@ pub async fn create_interface(mesh_if: &str, routing_algo: Option<&str>) -> Resu
         .build()
         .map_err(|_| RobinError::Netlink("Error - failed to build Ifinfomsg".to_string()))?;
 
-    rtnl.send::<_, _, Rtm, Ifinfomsg>(
-        Rtm::Newlink,
-        NlmF::REQUEST | NlmF::CREATE | NlmF::EXCL | NlmF::ACK,
-        NlPayload::Payload(msg),
-    )
-    .await
-    .map_err(|_| RobinError::Netlink("Error - failed to create mesh interface".to_string()))?;
+    let response = rtnl
+        .send(
+            Rtm::Newlink,
+            NlmF::REQUEST | NlmF::CREATE | NlmF::EXCL | NlmF::ACK,
+            msg,
+        )
+        .await?;
+
+    rtnl_ack(response).await?;
+    rtnl.if_cache().invalidate();
 
     Ok(())
 }
@@ -386,18 +482,14 @@ pub async fn create_interface(mesh_if: &str, routing_algo: Option<&str>) -> Resu
 /// // destroy_interface("bat0").await?;
 /// # }
 /// ```
-pub async fn destroy_interface(mesh_if: &str) -> Result<(), RobinError> {
-    let (rtnl, _) = NlRouter::connect(NlFamily::Route, None, Groups::empty())
-        .await
-        .map_err(|_| {
-            RobinError::Netlink("Error - failed to connect to netlink router".to_string())
-        })?;
-
-    rtnl.enable_ext_ack(true)
-        .map_err(|_| RobinError::Netlink("Error - failed to enable extended ACK".to_string()))?;
-    rtnl.enable_strict_checking(true)
-        .map_err(|_| RobinError::Netlink("Error - failed to enable strict checking".to_string()))?;
-
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(rtnl), err)
+)]
+pub async fn destroy_interface(
+    rtnl: &netlink::RtnlSocket,
+    mesh_if: &str,
+) -> Result<(), RobinError> {
     let ifname_attr = RtattrBuilder::default()
         .rta_type(Ifla::Ifname)
         .rta_payload(mesh_if)
@@ -413,13 +505,12 @@ pub async fn destroy_interface(mesh_if: &str) -> Result<(), RobinError> {
         .build()
         .map_err(|_| RobinError::Netlink("Error - failed to build Ifinfomsg".to_string()))?;
 
-    rtnl.send::<_, _, Rtm, Ifinfomsg>(
-        Rtm::Dellink,
-        NlmF::REQUEST | NlmF::ACK,
-        NlPayload::Payload(msg),
-    )
-    .await
-    .map_err(|_| RobinError::Netlink("Error - failed to destroy mesh interface".to_string()))?;
+    let response = rtnl
+        .send(Rtm::Dellink, NlmF::REQUEST | NlmF::ACK, msg)
+        .await?;
+
+    rtnl_ack(response).await?;
+    rtnl.if_cache().invalidate();
 
     Ok(())
 }
@@ -8,25 +8,37 @@
 mod aggregation;
 mod ap_isolation;
 mod bridge_loop_avoidance;
+mod check;
+mod dat_cache;
+mod dump;
 mod gateways;
 mod gw_mode;
 mod interface;
 mod neighbors;
 mod originators;
+mod raw;
 mod routing_algo;
+mod settings;
 mod transglobal;
+mod translate;
 mod translocal;
 mod utils;
 
 pub(crate) use aggregation::*;
 pub(crate) use ap_isolation::*;
 pub(crate) use bridge_loop_avoidance::*;
+pub(crate) use check::*;
+pub(crate) use dat_cache::*;
+pub(crate) use dump::*;
 pub(crate) use gateways::*;
 pub(crate) use gw_mode::*;
 pub(crate) use interface::*;
 pub(crate) use neighbors::*;
 pub(crate) use originators::*;
+pub(crate) use raw::*;
 pub(crate) use routing_algo::*;
+pub(crate) use settings::*;
 pub(crate) use transglobal::*;
+pub(crate) use translate::*;
 pub(crate) use translocal::*;
 pub(crate) use utils::*;
@@ -1,12 +1,13 @@
-use crate::commands::{if_indextoname, if_nametoindex};
+use crate::commands::dump_entries;
 use crate::error::RobinError;
-use crate::model::{AttrValueForSend, Attribute, Command, Neighbor};
-use crate::netlink;
+use crate::model::{Command, IfaceRef, Neighbor};
+use crate::netlink::MeshTransport;
 
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+
+use futures::{Stream, TryStreamExt};
 use macaddr::MacAddr6;
-use neli::consts::nl::{NlmF, Nlmsg};
-use neli::genl::Genlmsghdr;
-use neli::nl::{NlPayload, Nlmsghdr};
 
 /// Retrieves the list of neighbors for a BATMAN-adv mesh interface.
 ///
@@ -14,9 +15,16 @@ use neli::nl::{NlPayload, Nlmsghdr};
 /// the neighbor's MAC address, the outgoing interface used to reach it,
 /// the last time it was seen in milliseconds, and optionally the throughput in kb/s.
 ///
+/// There is one entry per neighbor and hard interface: a neighbor reached over
+/// several hard interfaces appears once for each of them, and should the kernel
+/// report the same link more than once, only the most recently seen entry is kept
+/// (see [`dedup_neighbors`]).
+///
 /// # Arguments
 ///
 /// * `mesh_if` - The name of the mesh interface (e.g., `"bat0"`).
+/// * `hard_if` - If set, only the entries of this hard interface of the mesh
+///   (`batctl n -i`).
 ///
 /// # Returns
 ///
@@ -30,111 +38,73 @@ use neli::nl::{NlPayload, Nlmsghdr};
 /// # let neighbors: Vec<Neighbor> = vec![];
 /// // let neighbors = get_neighbors("bat0").await?;
 /// for n in neighbors {
-///     println!("Neighbor {} via {} (last seen {} ms)", n.neigh, n.outgoing_if, n.last_seen_ms);
+///     println!("Neighbor {} via {} (last seen {})", n.neigh, n.outgoing_if, n.last_seen);
 /// }
 /// # }
 /// ```
-pub async fn get_neighbors(mesh_if: &str) -> Result<Vec<Neighbor>, RobinError> {
-    let mut attrs = netlink::GenlAttrBuilder::new();
-    let ifindex = if_nametoindex(mesh_if).await.map_err(|_| {
-        RobinError::Netlink(format!(
-            "Error - interface '{}' is not present or not a batman-adv interface",
-            mesh_if
-        ))
-    })?;
-
-    attrs
-        .add(
-            Attribute::BatadvAttrMeshIfindex,
-            AttrValueForSend::U32(ifindex),
-        )
-        .map_err(|_| {
-            RobinError::Netlink("Error - failed to add MeshIfindex attribute".to_string())
-        })?;
-
-    let msg = netlink::build_genl_msg(Command::BatadvCmdGetOriginators, attrs.build())
-        .map_err(|_| RobinError::Netlink("Error - failed to build netlink message".to_string()))?;
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(sock), err)
+)]
+pub async fn get_neighbors(
+    sock: &impl MeshTransport,
+    mesh_if: &str,
+    hard_if: Option<&IfaceRef>,
+) -> Result<Vec<Neighbor>, RobinError> {
+    let neighbors = get_neighbors_stream(sock.clone(), mesh_if, hard_if)
+        .try_collect()
+        .await?;
 
-    let mut sock = netlink::BatadvSocket::connect().await.map_err(|_| {
-        RobinError::Netlink("Error - failed to connect to batman-adv socket".to_string())
-    })?;
-
-    let mut response = sock
-        .send(NlmF::REQUEST | NlmF::DUMP, msg)
-        .await
-        .map_err(|_| RobinError::Netlink("Error - failed to send netlink request".to_string()))?;
+    Ok(dedup_neighbors(neighbors))
+}
 
-    let mut neighbors: Vec<Neighbor> = Vec::new();
-    while let Some(msg) = response.next().await {
-        let msg: Nlmsghdr<u16, Genlmsghdr<u8, u16>> = msg.map_err(|_| {
-            RobinError::Netlink("Error - failed to parse netlink message".to_string())
-        })?;
+/// Deduplicates neighbors based on `(neighbor MAC, outgoing interface)`.
+///
+/// If multiple entries exist for the same key, the one with the lowest `last_seen`,
+/// i.e. the freshest, is kept in the position of the first. The order is otherwise
+/// preserved.
+pub(crate) fn dedup_neighbors(neighbors: Vec<Neighbor>) -> Vec<Neighbor> {
+    let mut index: HashMap<(MacAddr6, String), usize> = HashMap::new();
+    let mut result: Vec<Neighbor> = Vec::with_capacity(neighbors.len());
 
-        match *msg.nl_type() {
-            x if x == Nlmsg::Done.into() => break,
-            x if x == Nlmsg::Error.into() => {
-                match &msg.nl_payload() {
-                    NlPayload::Err(err) if *err.error() == 0 => break, // end of dump
-                    NlPayload::Err(err) => {
-                        return Err(RobinError::Netlink(format!(
-                            "Netlink error {}",
-                            err.error()
-                        )));
-                    }
-                    _ => {
-                        return Err(RobinError::Netlink(
-                            "Unknown netlink error payload".to_string(),
-                        ));
-                    }
+    for n in neighbors {
+        match index.entry((n.neigh, n.outgoing_if.clone())) {
+            Entry::Occupied(i) => {
+                let existing = &mut result[*i.get()];
+                if n.last_seen < existing.last_seen {
+                    *existing = n;
                 }
             }
-            _ => {}
+            Entry::Vacant(slot) => {
+                slot.insert(result.len());
+                result.push(n);
+            }
         }
-
-        let attrs = msg
-            .get_payload()
-            .ok_or_else(|| RobinError::Parse("Error - message has no payload".into()))?
-            .attrs()
-            .get_attr_handle();
-
-        let neigh_addr = attrs
-            .get_attr_payload_as::<[u8; 6]>(Attribute::BatadvAttrNeighAddress.into())
-            .map_err(|_| RobinError::Parse("Error - missing NEIGH_ADDRESS".into()))?;
-
-        let last_seen_ms = attrs
-            .get_attr_payload_as::<u32>(Attribute::BatadvAttrLastSeenMsecs.into())
-            .map_err(|_| RobinError::Parse("Error - missing LAST_SEEN_MSECS".into()))?;
-
-        let outgoing_if =
-            match attrs.get_attr_payload_as::<[u8; 16]>(Attribute::BatadvAttrHardIfname.into()) {
-                Ok(bytes) => {
-                    let nul_pos = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
-                    String::from_utf8_lossy(&bytes[..nul_pos]).into_owned()
-                }
-                Err(_) => {
-                    let ifindex = attrs
-                        .get_attr_payload_as::<u32>(Attribute::BatadvAttrHardIfindex.into())
-                        .map_err(|_| RobinError::Parse("Error - missing HARD_IFINDEX".into()))?;
-                    if_indextoname(ifindex).await.map_err(|_| {
-                        RobinError::Netlink(format!(
-                            "Error - failed to resolve interface index {}",
-                            ifindex
-                        ))
-                    })?
-                }
-            };
-
-        let throughput_kbps = attrs
-            .get_attr_payload_as::<u32>(Attribute::BatadvAttrThroughput.into())
-            .ok();
-
-        neighbors.push(Neighbor {
-            neigh: MacAddr6::from(neigh_addr),
-            outgoing_if,
-            last_seen_ms,
-            throughput_kbps,
-        });
     }
 
-    Ok(neighbors)
+    result
+}
+
+/// Streams the neighbors of a BATMAN-adv mesh interface as the dump is received.
+///
+/// Entries are yielded as soon as the corresponding netlink dump message arrives
+/// instead of being buffered into a `Vec`, which keeps memory usage flat on large meshes.
+///
+/// # Arguments
+///
+/// * `mesh_if` - The name of the BATMAN-adv mesh interface (e.g., `"bat0"`).
+/// * `hard_if` - If set, only the entries of this hard interface of the mesh.
+///
+/// # Returns
+///
+/// A stream of `Neighbor` results. The stream ends after the first error.
+///
+/// Entries are passed on as the kernel reports them, without the deduplication of
+/// [`get_neighbors`].
+pub fn get_neighbors_stream(
+    sock: impl MeshTransport,
+    mesh_if: &str,
+    hard_if: Option<&IfaceRef>,
+) -> impl Stream<Item = Result<Neighbor, RobinError>> {
+    dump_entries(sock, mesh_if, Command::BatadvCmdGetNeighbors, hard_if)
 }
@@ -0,0 +1,110 @@
+use crate::error::RobinError;
+use crate::model::{AttrObject, Command};
+use crate::netlink::{self, MeshResponse, MeshTransport};
+
+use neli::consts::nl::NlmF;
+
+/// Sends `cmd` as a dump request and decodes every reply into an [`AttrObject`].
+///
+/// This is the escape hatch for BATMAN-adv commands and attributes that are not
+/// modeled by the crate yet. The attributes are sent unchanged; most dumps require
+/// `BatadvAttrMeshIfindex` to select the mesh interface.
+///
+/// # Arguments
+///
+/// * `cmd` - The BATMAN-adv command to send (e.g., `Command::BatadvCmdGetOriginators`).
+/// * `attrs` - The attributes of the request.
+///
+/// # Returns
+///
+/// Returns one `AttrObject` per reply message, or a `RobinError` if the request
+/// fails or a reply carries a malformed attribute.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(sock, attrs), err)
+)]
+pub async fn raw_dump(
+    sock: &impl MeshTransport,
+    cmd: Command,
+    attrs: netlink::GenlAttrBuilder,
+) -> Result<Vec<AttrObject>, RobinError> {
+    let msg = netlink::build_genl_msg(cmd, attrs.build())?;
+    let mut response = sock.dump(msg).await?;
+    let mut objects = Vec::new();
+
+    while let Some(msg) = response.next().await {
+        match netlink::parse_genl_msg(&msg?)? {
+            Some(obj) => objects.push(obj),
+            None => break,
+        }
+    }
+
+    Ok(objects)
+}
+
+/// Sends `cmd` as a single request and decodes the reply into an [`AttrObject`].
+///
+/// Used for commands answering with one message instead of a dump, such as
+/// `Command::BatadvCmdGetMeshInfo` or `Command::BatadvCmdGetHardif` for a single
+/// hard interface.
+///
+/// # Arguments
+///
+/// * `cmd` - The BATMAN-adv command to send.
+/// * `attrs` - The attributes of the request.
+///
+/// # Returns
+///
+/// Returns the decoded reply, or a `RobinError` if the request fails or the kernel
+/// did not reply.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(sock, attrs), err)
+)]
+pub async fn raw_get(
+    sock: &impl MeshTransport,
+    cmd: Command,
+    attrs: netlink::GenlAttrBuilder,
+) -> Result<AttrObject, RobinError> {
+    let msg = netlink::build_genl_msg(cmd, attrs.build())?;
+    let mut response = sock.send(NlmF::REQUEST, msg).await?;
+
+    while let Some(msg) = response.next().await {
+        if let Some(obj) = netlink::parse_genl_msg(&msg?)? {
+            return Ok(obj);
+        }
+    }
+
+    Err(RobinError::NotFound(format!(
+        "Error - no reply to {:?}",
+        cmd
+    )))
+}
+
+/// Sends `cmd` as a request and waits for the kernel to acknowledge it.
+///
+/// This is the counterpart of [`raw_dump`] for commands changing the mesh
+/// configuration, such as `Command::BatadvCmdSetMesh`.
+///
+/// # Arguments
+///
+/// * `cmd` - The BATMAN-adv command to send.
+/// * `attrs` - The attributes of the request.
+///
+/// # Returns
+///
+/// Returns `Ok(())` once the kernel has acknowledged the request, or a `RobinError`
+/// carrying the kernel's errno if it was rejected.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(sock, attrs), err)
+)]
+pub async fn raw_set(
+    sock: &impl MeshTransport,
+    cmd: Command,
+    attrs: netlink::GenlAttrBuilder,
+) -> Result<(), RobinError> {
+    let msg = netlink::build_genl_msg(cmd, attrs.build())?;
+
+    sock.send(NlmF::REQUEST | NlmF::ACK, msg).await?.ack().await
+}
@@ -1,18 +1,12 @@
 use crate::error::RobinError;
+use crate::kmod;
 use crate::{Attribute, Command, netlink};
 
-use crate::commands::get_algoname_netlink;
-use crate::netlink::GenlAttrBuilder;
-use neli::consts::{
-    nl::NlmF,
-    rtnl::{Ifla, IflaInfo, RtAddrFamily, Rtm},
-    socket::NlFamily,
-};
+use crate::commands::{get_algoname_netlink, list_meshes};
+use crate::netlink::{GenlAttrBuilder, MeshResponse, MeshTransport};
+use neli::consts::nl::NlmF;
 use neli::genl::Genlmsghdr;
-use neli::nl::{NlPayload, Nlmsghdr};
-use neli::router::asynchronous::NlRouter;
-use neli::rtnl::{Ifinfomsg, IfinfomsgBuilder};
-use neli::utils::Groups;
+use neli::nl::Nlmsghdr;
 use std::fs;
 
 /// Returns the default routing algorithm configured for BATMAN-adv.
@@ -21,20 +15,50 @@ use std::fs;
 ///
 /// # Returns
 ///
-/// A `String` representing the default routing algorithm, or a `RobinError` if reading fails.
+/// A `String` representing the default routing algorithm, `RobinError::ModuleNotLoaded`
+/// if the module is not loaded, or another `RobinError` if reading fails.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", err))]
 pub async fn get_default_routing_algo() -> Result<String, RobinError> {
+    if !kmod::module_loaded() {
+        return Err(RobinError::ModuleNotLoaded {
+            module: kmod::MODULE_NAME.to_string(),
+        });
+    }
+
     let path = "/sys/module/batman_adv/parameters/routing_algo";
 
-    let content = fs::read_to_string(path).map_err(|e| {
-        RobinError::Io(format!(
-            "Failed to read default routing algo from {}: {}",
-            path, e
-        ))
+    let content = fs::read_to_string(path).map_err(|e| RobinError::Io {
+        context: format!("Failed to read default routing algo from {}", path),
+        source: e,
     })?;
 
     Ok(content.trim().to_string())
 }
 
+/// Returns the routing algorithm of the mesh interface `mesh_if`.
+///
+/// Kernels that do not report `BATADV_ATTR_ALGO_NAME` in the mesh info get the
+/// default routing algorithm instead, which mesh interfaces are created with.
+///
+/// # Returns
+///
+/// The algorithm name, e.g. `"BATMAN_IV"`, or a `RobinError` if querying fails.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(sock), err)
+)]
+pub async fn get_mesh_routing_algo(
+    sock: &impl MeshTransport,
+    mesh_if: &str,
+) -> Result<String, RobinError> {
+    match get_algoname_netlink(sock, mesh_if).await {
+        Err(RobinError::MissingAttribute {
+            attr: Attribute::BatadvAttrAlgoName,
+        }) => get_default_routing_algo().await,
+        result => result,
+    }
+}
+
 /// Returns a list of currently active BATMAN-adv mesh interfaces and their routing algorithms.
 ///
 /// Each entry is a tuple `(mesh_interface_name, algorithm_name)`.
@@ -46,68 +70,17 @@ pub async fn get_default_routing_algo() -> Result<String, RobinError> {
 /// # Notes
 ///
 /// Only interfaces of kind `"batadv"` are included.
-pub async fn get_active_routing_algos() -> Result<Vec<(String, String)>, RobinError> {
-    let (rtnl, _) = NlRouter::connect(NlFamily::Route, None, Groups::empty())
-        .await
-        .map_err(|e| RobinError::Netlink(format!("Failed to connect to Netlink: {:?}", e)))?;
-
-    rtnl.enable_ext_ack(true)
-        .map_err(|e| RobinError::Netlink(format!("Failed to enable extended ACK: {:?}", e)))?;
-    rtnl.enable_strict_checking(true)
-        .map_err(|e| RobinError::Netlink(format!("Failed to enable strict checking: {:?}", e)))?;
-
-    let msg = IfinfomsgBuilder::default()
-        .ifi_family(RtAddrFamily::Unspecified)
-        .build()
-        .map_err(|e| RobinError::Netlink(format!("Failed to build Ifinfomsg: {:?}", e)))?;
-
-    let mut response = rtnl
-        .send::<_, _, Rtm, Ifinfomsg>(
-            Rtm::Getlink,
-            NlmF::REQUEST | NlmF::DUMP | NlmF::ACK,
-            NlPayload::Payload(msg),
-        )
-        .await
-        .map_err(|e| RobinError::Netlink(format!("Failed to send GETLINK request: {:?}", e)))?;
-
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(rtnl, sock), err)
+)]
+pub async fn get_active_routing_algos(
+    rtnl: &netlink::RtnlSocket,
+    sock: &impl MeshTransport,
+) -> Result<Vec<(String, String)>, RobinError> {
     let mut result = Vec::new();
-    while let Some(msg) = response.next().await {
-        let msg: Nlmsghdr<Rtm, Ifinfomsg> = msg.map_err(|e| {
-            RobinError::Netlink(format!("Failed to parse Netlink message: {:?}", e))
-        })?;
-
-        let payload = match msg.get_payload() {
-            Some(p) => p,
-            None => continue,
-        };
-
-        let attrs = payload.rtattrs().get_attr_handle();
-        let mesh_if = match attrs.get_attr_payload_as_with_len::<String>(Ifla::Ifname) {
-            Ok(v) => v,
-            Err(_) => continue,
-        };
-
-        let linkinfo = match attrs.get_nested_attributes::<IflaInfo>(Ifla::Linkinfo) {
-            Ok(v) => v,
-            Err(_) => continue,
-        };
-
-        let kind = match linkinfo.get_attr_payload_as_with_len::<String>(IflaInfo::Kind) {
-            Ok(v) => v,
-            Err(_) => continue,
-        };
-
-        if kind != "batadv" {
-            continue;
-        }
-
-        let algo = get_algoname_netlink(mesh_if.as_str()).await.map_err(|e| {
-            RobinError::Netlink(format!(
-                "Failed to get routing algorithm for '{}': {:?}",
-                mesh_if, e
-            ))
-        })?;
-
+    for mesh_if in list_meshes(rtnl).await? {
+        let algo = get_algoname_netlink(sock, mesh_if.as_str()).await?;
         result.push((mesh_if, algo));
     }
 
@@ -121,32 +94,24 @@ pub async fn get_active_routing_algos() -> Result<Vec<(String, String)>, RobinEr
 /// # Returns
 ///
 /// A vector of algorithm names as `String`s, or a `RobinError` if none are found or the query fails.
-pub async fn get_available_routing_algos() -> Result<Vec<String>, RobinError> {
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(sock), err)
+)]
+pub async fn get_available_routing_algos(
+    sock: &impl MeshTransport,
+) -> Result<Vec<String>, RobinError> {
     let msg = netlink::build_genl_msg(
         Command::BatadvCmdGetRoutingAlgos,
         GenlAttrBuilder::new().build(),
     )
     .map_err(|e| RobinError::Netlink(format!("Failed to build routing algos request: {:?}", e)))?;
 
-    let mut sock = netlink::BatadvSocket::connect().await.map_err(|e| {
-        RobinError::Netlink(format!(
-            "Failed to connect to batadv netlink socket: {:?}",
-            e
-        ))
-    })?;
-
-    let mut response = sock
-        .send(NlmF::REQUEST | NlmF::DUMP, msg)
-        .await
-        .map_err(|e| {
-            RobinError::Netlink(format!("Failed to send routing algos request: {:?}", e))
-        })?;
+    let mut response = sock.send(NlmF::REQUEST | NlmF::DUMP, msg).await?;
 
     let mut algos = Vec::new();
     while let Some(msg) = response.next().await {
-        let msg: Nlmsghdr<u16, Genlmsghdr<u8, u16>> = msg.map_err(|e| {
-            RobinError::Netlink(format!("Failed to parse routing algos message: {:?}", e))
-        })?;
+        let msg: Nlmsghdr<u16, Genlmsghdr<u8, u16>> = msg?;
 
         let payload = match msg.get_payload() {
             Some(p) => p,
@@ -154,10 +119,8 @@ pub async fn get_available_routing_algos() -> Result<Vec<String>, RobinError> {
         };
 
         for attr in payload.attrs().iter() {
-            if *attr.nla_type().nla_type() == Attribute::BatadvAttrAlgoName.into() {
-                let bytes = attr.nla_payload().as_ref();
-                let nul = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
-                let algo = String::from_utf8_lossy(&bytes[..nul]).to_string();
+            if *attr.nla_type().nla_type() == u16::from(Attribute::BatadvAttrAlgoName) {
+                let algo = netlink::c_str(attr.nla_payload().as_ref()).into_owned();
                 algos.push(algo);
             }
         }
@@ -184,14 +147,22 @@ pub async fn get_available_routing_algos() -> Result<Vec<String>, RobinError> {
 /// # Returns
 ///
 /// Returns `()` on success, or a `RobinError` if writing fails.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", err))]
 pub async fn set_default_routing_algo(algo: &str) -> Result<(), RobinError> {
+    if !kmod::module_loaded() {
+        return Err(RobinError::ModuleNotLoaded {
+            module: kmod::MODULE_NAME.to_string(),
+        });
+    }
+
     let path = "/sys/module/batman_adv/parameters/routing_algo";
 
-    fs::write(path, algo).map_err(|e| {
-        RobinError::Io(format!(
-            "Failed to set default routing algo to '{}': {}",
-            algo, e
-        ))
+    fs::write(path, algo).map_err(|e| match e.kind() {
+        std::io::ErrorKind::PermissionDenied => RobinError::PermissionDenied,
+        _ => RobinError::Io {
+            context: format!("Failed to set default routing algo to '{}'", algo),
+            source: e,
+        },
     })?;
 
     Ok(())
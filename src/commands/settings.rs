@@ -0,0 +1,489 @@
+use crate::commands::{get_translocal, gw_mode_from_u8, mesh_selector, raw_get, resolve_iface};
+use crate::debugfs::VLAN_HAS_TAG;
+use crate::error::RobinError;
+use crate::model::{
+    AttrObject, AttrValue, Attribute, Bandwidth, Command, GwMode, HardifSettings, IfaceRef,
+    MeshSettings, VlanSettings,
+};
+
+use crate::netlink::{self, AttrKind, MeshTransport};
+use std::collections::BTreeSet;
+
+/// Sends a single `BATADV_CMD_GET_MESH` request for `mesh_if`.
+///
+/// # Returns
+///
+/// Returns all attributes of the reply, so one round-trip can answer several
+/// getters, or a `RobinError` if the query fails.
+pub(crate) async fn query_mesh(
+    sock: &impl MeshTransport,
+    mesh_if: &str,
+) -> Result<AttrObject, RobinError> {
+    raw_get(
+        sock,
+        Command::BatadvCmdGetMeshInfo,
+        mesh_selector(sock, mesh_if).await?,
+    )
+    .await
+}
+
+/// Retrieves a single attribute of the `BATADV_CMD_GET_MESH` reply for `mesh_if`.
+///
+/// # Returns
+///
+/// Returns the value of `attr`, or `RobinError::MissingAttribute` if the kernel
+/// does not report it.
+pub(crate) async fn query_mesh_attr(
+    sock: &impl MeshTransport,
+    mesh_if: &str,
+    attr: Attribute,
+) -> Result<AttrValue, RobinError> {
+    query_mesh(sock, mesh_if)
+        .await?
+        .get(attr)
+        .cloned()
+        .ok_or(RobinError::MissingAttribute { attr })
+}
+
+/// Retrieves an on/off setting of `mesh_if`, such as `BATADV_ATTR_AGGREGATED_OGMS_ENABLED`.
+pub(crate) async fn query_mesh_toggle(
+    sock: &impl MeshTransport,
+    mesh_if: &str,
+    attr: Attribute,
+) -> Result<bool, RobinError> {
+    query_mesh_attr(sock, mesh_if, attr)
+        .await?
+        .as_u8()
+        .map(|v| v != 0)
+        .ok_or(RobinError::InvalidAttribute { attr })
+}
+
+/// Retrieves all mesh-level tunables of a BATMAN-adv mesh interface with a single
+/// `BATADV_CMD_GET_MESH` request.
+///
+/// # Arguments
+///
+/// * `mesh_if` - The name of the mesh interface.
+///
+/// # Returns
+///
+/// Returns the `MeshSettings` of `mesh_if`, with `None` for the settings the kernel
+/// does not report, or a `RobinError` if the query fails.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(sock), err)
+)]
+pub async fn get_mesh_settings(
+    sock: &impl MeshTransport,
+    mesh_if: &str,
+) -> Result<MeshSettings, RobinError> {
+    Ok(mesh_settings(&query_mesh(sock, mesh_if).await?))
+}
+
+/// The running settings of an object, read with a single GET request, and the
+/// attributes of the SET request converging them to the wanted ones.
+#[derive(Default)]
+pub(crate) struct SettingsDiff<S> {
+    /// The settings before the change.
+    #[cfg_attr(not(feature = "config"), allow(dead_code))]
+    pub(crate) current: S,
+    /// The attributes of the SET request, or `None` if all settings already match.
+    pub(crate) attrs: Option<netlink::GenlAttrBuilder>,
+}
+
+/// Compares `settings` with the running mesh settings of `mesh_if`.
+///
+/// # Returns
+///
+/// Returns the running settings together with the attributes of a
+/// `BATADV_CMD_SET_MESH` request carrying only the settings that differ.
+pub(crate) async fn diff_mesh_settings(
+    sock: &impl MeshTransport,
+    mesh_if: &str,
+    settings: &MeshSettings,
+) -> Result<SettingsDiff<MeshSettings>, RobinError> {
+    let wanted = mesh_attrs(settings)?;
+    let selector = mesh_selector(sock, mesh_if).await?;
+    let obj = raw_get(sock, Command::BatadvCmdGetMeshInfo, selector.clone()).await?;
+    Ok(SettingsDiff {
+        attrs: changed_attrs(selector, &obj, &wanted)?,
+        current: mesh_settings(&obj),
+    })
+}
+
+/// Reads the mesh settings from a `BATADV_CMD_GET_MESH` reply.
+fn mesh_settings(obj: &AttrObject) -> MeshSettings {
+    let u32_of = |attr| obj.get_u32(attr).ok();
+    let toggle = |attr| obj.get_u8(attr).ok().map(|v| v != 0);
+
+    MeshSettings {
+        aggregation: toggle(Attribute::BatadvAttrAggregatedOgmsEnabled),
+        ap_isolation: toggle(Attribute::BatadvAttrApIsolationEnabled),
+        bonding: toggle(Attribute::BatadvAttrBondingEnabled),
+        bridge_loop_avoidance: toggle(Attribute::BatadvAttrBridgeLoopAvoidanceEnabled),
+        distributed_arp_table: toggle(Attribute::BatadvAttrDistributedArpTableEnabled),
+        fragmentation: toggle(Attribute::BatadvAttrFragmentationEnabled),
+        gw_mode: obj
+            .get_u8(Attribute::BatadvAttrGwMode)
+            .ok()
+            .map(gw_mode_from_u8),
+        gw_bandwidth_down: u32_of(Attribute::BatadvAttrGwBandwidthDown)
+            .map(Bandwidth::from_netlink),
+        gw_bandwidth_up: u32_of(Attribute::BatadvAttrGwBandwidthUp).map(Bandwidth::from_netlink),
+        gw_sel_class: u32_of(Attribute::BatadvAttrGwSelClass),
+        hop_penalty: obj.get_u8(Attribute::BatadvAttrHopPenalty).ok(),
+        isolation_mark: u32_of(Attribute::BatadvAttrIsolationMark),
+        isolation_mask: u32_of(Attribute::BatadvAttrIsolationMask),
+        log_level: u32_of(Attribute::BatadvAttrLogLevel),
+        multicast_forceflood: toggle(Attribute::BatadvAttrMulticastForceFloodEnabled),
+        multicast_fanout: u32_of(Attribute::BatadvAttrMulticastFanout),
+        network_coding: toggle(Attribute::BatadvAttrNetworkCodingEnabled),
+        orig_interval_ms: u32_of(Attribute::BatadvAttrOrigInterval),
+    }
+}
+
+/// Compares `settings` with the running mesh settings of `mesh_if`.
+///
+/// # Returns
+///
+/// Returns the attributes of a `BATADV_CMD_SET_MESH` request carrying only the
+/// settings that differ, or `None` if all of them already match.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(sock), err)
+)]
+pub async fn changed_mesh_settings(
+    sock: &impl MeshTransport,
+    mesh_if: &str,
+    settings: &MeshSettings,
+) -> Result<Option<netlink::GenlAttrBuilder>, RobinError> {
+    Ok(diff_mesh_settings(sock, mesh_if, settings).await?.attrs)
+}
+
+/// Retrieves the tunables of a hard interface of a BATMAN-adv mesh with a single
+/// `BATADV_CMD_GET_HARDIF` request.
+///
+/// # Arguments
+///
+/// * `mesh_if` - The name of the mesh interface.
+/// * `hard_if` - The name or index of the hard interface.
+///
+/// # Returns
+///
+/// Returns the `HardifSettings` of `hard_if`, or a `RobinError` if the query fails,
+/// e.g. because `hard_if` is not attached to `mesh_if`.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(sock), err)
+)]
+pub async fn get_hardif_settings(
+    sock: &impl MeshTransport,
+    mesh_if: &str,
+    hard_if: &IfaceRef,
+) -> Result<HardifSettings, RobinError> {
+    let selector = hardif_selector(sock, mesh_if, hard_if).await?;
+    let obj = raw_get(sock, Command::BatadvCmdGetHardif, selector).await?;
+    Ok(hardif_settings(&obj))
+}
+
+/// Reads the hard interface settings from a `BATADV_CMD_GET_HARDIF` reply.
+fn hardif_settings(obj: &AttrObject) -> HardifSettings {
+    let u32_of = |attr| obj.get_u32(attr).ok();
+
+    HardifSettings {
+        elp_interval_ms: u32_of(Attribute::BatadvAttrElpInterval),
+        hop_penalty: obj.get_u8(Attribute::BatadvAttrHopPenalty).ok(),
+        throughput_override: u32_of(Attribute::BatadvAttrThroughputOverride)
+            .map(Bandwidth::from_netlink),
+    }
+}
+
+/// Compares `settings` with the running settings of the hard interface `hard_if`.
+///
+/// # Returns
+///
+/// Returns the attributes of a `BATADV_CMD_SET_HARDIF` request carrying only the
+/// settings that differ, or `None` if all of them already match.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(sock), err)
+)]
+pub async fn changed_hardif_settings(
+    sock: &impl MeshTransport,
+    mesh_if: &str,
+    hard_if: &IfaceRef,
+    settings: &HardifSettings,
+) -> Result<Option<netlink::GenlAttrBuilder>, RobinError> {
+    Ok(diff_hardif_settings(sock, mesh_if, hard_if, settings)
+        .await?
+        .attrs)
+}
+
+/// Compares `settings` with the running settings of the hard interface `hard_if`.
+///
+/// # Returns
+///
+/// Returns the running settings together with the attributes of a
+/// `BATADV_CMD_SET_HARDIF` request carrying only the settings that differ.
+pub(crate) async fn diff_hardif_settings(
+    sock: &impl MeshTransport,
+    mesh_if: &str,
+    hard_if: &IfaceRef,
+    settings: &HardifSettings,
+) -> Result<SettingsDiff<HardifSettings>, RobinError> {
+    let wanted: Vec<(Attribute, u32)> = [
+        (Attribute::BatadvAttrElpInterval, settings.elp_interval_ms),
+        (
+            Attribute::BatadvAttrHopPenalty,
+            settings.hop_penalty.map(u32::from),
+        ),
+        (
+            Attribute::BatadvAttrThroughputOverride,
+            settings.throughput_override.map(Bandwidth::to_netlink),
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(attr, value)| Some((attr, value?)))
+    .collect();
+
+    let selector = hardif_selector(sock, mesh_if, hard_if).await?;
+    let obj = raw_get(sock, Command::BatadvCmdGetHardif, selector.clone()).await?;
+    Ok(SettingsDiff {
+        attrs: changed_attrs(selector, &obj, &wanted)?,
+        current: hardif_settings(&obj),
+    })
+}
+
+/// Retrieves the tunables of VLAN `vid` on a BATMAN-adv mesh interface with a single
+/// `BATADV_CMD_GET_VLAN` request.
+///
+/// # Arguments
+///
+/// * `mesh_if` - The name of the mesh interface.
+/// * `vid` - The VLAN id, without the `BATADV_VLAN_HAS_TAG` flag.
+///
+/// # Returns
+///
+/// Returns the `VlanSettings` of the VLAN, or a `RobinError` if the query fails,
+/// e.g. because there is no such VLAN on `mesh_if`.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(sock), err)
+)]
+pub async fn get_vlan_settings(
+    sock: &impl MeshTransport,
+    mesh_if: &str,
+    vid: u16,
+) -> Result<VlanSettings, RobinError> {
+    let selector = vlan_selector(sock, mesh_if, vid).await?;
+    let obj = raw_get(sock, Command::BatadvCmdGetVlan, selector).await?;
+    Ok(vlan_settings(&obj))
+}
+
+/// Reads the VLAN settings from a `BATADV_CMD_GET_VLAN` reply.
+fn vlan_settings(obj: &AttrObject) -> VlanSettings {
+    VlanSettings {
+        ap_isolation: obj
+            .get_u8(Attribute::BatadvAttrApIsolationEnabled)
+            .ok()
+            .map(|v| v != 0),
+    }
+}
+
+/// Compares `settings` with the running settings of VLAN `vid` on `mesh_if`.
+///
+/// # Returns
+///
+/// Returns the attributes of a `BATADV_CMD_SET_VLAN` request carrying only the
+/// settings that differ, or `None` if all of them already match.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(sock), err)
+)]
+pub async fn changed_vlan_settings(
+    sock: &impl MeshTransport,
+    mesh_if: &str,
+    vid: u16,
+    settings: &VlanSettings,
+) -> Result<Option<netlink::GenlAttrBuilder>, RobinError> {
+    Ok(diff_vlan_settings(sock, mesh_if, vid, settings)
+        .await?
+        .attrs)
+}
+
+/// Compares `settings` with the running settings of VLAN `vid` on `mesh_if`.
+///
+/// # Returns
+///
+/// Returns the running settings together with the attributes of a
+/// `BATADV_CMD_SET_VLAN` request carrying only the settings that differ.
+pub(crate) async fn diff_vlan_settings(
+    sock: &impl MeshTransport,
+    mesh_if: &str,
+    vid: u16,
+    settings: &VlanSettings,
+) -> Result<SettingsDiff<VlanSettings>, RobinError> {
+    let wanted: Vec<(Attribute, u32)> = settings
+        .ap_isolation
+        .map(|v| (Attribute::BatadvAttrApIsolationEnabled, u32::from(v)))
+        .into_iter()
+        .collect();
+
+    let selector = vlan_selector(sock, mesh_if, vid).await?;
+    let obj = raw_get(sock, Command::BatadvCmdGetVlan, selector.clone()).await?;
+    Ok(SettingsDiff {
+        attrs: changed_attrs(selector, &obj, &wanted)?,
+        current: vlan_settings(&obj),
+    })
+}
+
+/// Lists the VLANs configured on a BATMAN-adv mesh interface.
+///
+/// The kernel cannot dump its VLANs; every VLAN holds a local translation table
+/// entry for the address of the mesh interface, so they are found there.
+///
+/// # Returns
+///
+/// Returns the VLAN ids in ascending order, without the `BATADV_VLAN_HAS_TAG` flag.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(sock), err)
+)]
+pub async fn list_vlans(sock: &impl MeshTransport, mesh_if: &str) -> Result<Vec<u16>, RobinError> {
+    let vids: BTreeSet<u16> = get_translocal(sock, mesh_if)
+        .await?
+        .into_iter()
+        .filter(|entry| entry.vid & VLAN_HAS_TAG != 0)
+        .map(|entry| entry.vid & !VLAN_HAS_TAG)
+        .collect();
+    Ok(vids.into_iter().collect())
+}
+
+/// Builds the attributes selecting the hard interface `hard_if` of `mesh_if`.
+async fn hardif_selector(
+    sock: &impl MeshTransport,
+    mesh_if: &str,
+    hard_if: &IfaceRef,
+) -> Result<netlink::GenlAttrBuilder, RobinError> {
+    let mut attrs = mesh_selector(sock, mesh_if).await?;
+    attrs.add(
+        Attribute::BatadvAttrHardIfindex,
+        resolve_iface(sock, hard_if).await?,
+    )?;
+    Ok(attrs)
+}
+
+/// Builds the attributes selecting VLAN `vid` of `mesh_if`.
+async fn vlan_selector(
+    sock: &impl MeshTransport,
+    mesh_if: &str,
+    vid: u16,
+) -> Result<netlink::GenlAttrBuilder, RobinError> {
+    let mut attrs = mesh_selector(sock, mesh_if).await?;
+    attrs.add(Attribute::BatadvAttrVlanId, vid)?;
+    Ok(attrs)
+}
+
+/// Returns the requested mesh settings as attribute values in kernel units.
+fn mesh_attrs(settings: &MeshSettings) -> Result<Vec<(Attribute, u32)>, RobinError> {
+    let gw_mode = match settings.gw_mode {
+        Some(GwMode::Off) => Some(0),
+        Some(GwMode::Client) => Some(1),
+        Some(GwMode::Server) => Some(2),
+        Some(GwMode::Unknown(_)) => {
+            return Err(RobinError::Parse(
+                "Cannot set unknown gateway mode".to_string(),
+            ));
+        }
+        None => None,
+    };
+
+    let attrs = [
+        (
+            Attribute::BatadvAttrAggregatedOgmsEnabled,
+            settings.aggregation.map(u32::from),
+        ),
+        (
+            Attribute::BatadvAttrApIsolationEnabled,
+            settings.ap_isolation.map(u32::from),
+        ),
+        (
+            Attribute::BatadvAttrBondingEnabled,
+            settings.bonding.map(u32::from),
+        ),
+        (
+            Attribute::BatadvAttrBridgeLoopAvoidanceEnabled,
+            settings.bridge_loop_avoidance.map(u32::from),
+        ),
+        (
+            Attribute::BatadvAttrDistributedArpTableEnabled,
+            settings.distributed_arp_table.map(u32::from),
+        ),
+        (
+            Attribute::BatadvAttrFragmentationEnabled,
+            settings.fragmentation.map(u32::from),
+        ),
+        (Attribute::BatadvAttrGwMode, gw_mode),
+        (
+            Attribute::BatadvAttrGwBandwidthDown,
+            settings.gw_bandwidth_down.map(Bandwidth::to_netlink),
+        ),
+        (
+            Attribute::BatadvAttrGwBandwidthUp,
+            settings.gw_bandwidth_up.map(Bandwidth::to_netlink),
+        ),
+        (Attribute::BatadvAttrGwSelClass, settings.gw_sel_class),
+        (
+            Attribute::BatadvAttrHopPenalty,
+            settings.hop_penalty.map(u32::from),
+        ),
+        (Attribute::BatadvAttrIsolationMark, settings.isolation_mark),
+        (Attribute::BatadvAttrIsolationMask, settings.isolation_mask),
+        (Attribute::BatadvAttrLogLevel, settings.log_level),
+        (
+            Attribute::BatadvAttrMulticastForceFloodEnabled,
+            settings.multicast_forceflood.map(u32::from),
+        ),
+        (
+            Attribute::BatadvAttrMulticastFanout,
+            settings.multicast_fanout,
+        ),
+        (
+            Attribute::BatadvAttrNetworkCodingEnabled,
+            settings.network_coding.map(u32::from),
+        ),
+        (Attribute::BatadvAttrOrigInterval, settings.orig_interval_ms),
+    ];
+
+    Ok(attrs
+        .into_iter()
+        .filter_map(|(attr, value)| Some((attr, value?)))
+        .collect())
+}
+
+/// Appends the `wanted` attribute values differing from `current` to `selector`.
+///
+/// Returns `None` if every value already matches.
+fn changed_attrs(
+    mut selector: netlink::GenlAttrBuilder,
+    current: &AttrObject,
+    wanted: &[(Attribute, u32)],
+) -> Result<Option<netlink::GenlAttrBuilder>, RobinError> {
+    let mut changed = false;
+    for &(attr, value) in wanted {
+        if current.get(attr).and_then(AttrValue::as_u32) == Some(value) {
+            continue;
+        }
+
+        match netlink::get_attr_spec_map().get(&attr) {
+            Some(AttrKind::U8) => selector.add(
+                attr,
+                u8::try_from(value).map_err(|_| RobinError::InvalidAttribute { attr })?,
+            )?,
+            _ => selector.add(attr, value)?,
+        }
+        changed = true;
+    }
+
+    Ok(changed.then_some(selector))
+}
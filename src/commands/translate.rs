@@ -0,0 +1,94 @@
+use crate::commands::{get_dat_cache_stream, get_transglobal_stream};
+use crate::error::RobinError;
+use crate::netlink::MeshTransport;
+
+use std::fs;
+use std::future;
+use std::net::Ipv4Addr;
+use std::path::Path;
+use std::pin::pin;
+
+use futures::TryStreamExt;
+use macaddr::MacAddr6;
+
+/// Where the kernel exposes the ARP table of the local node.
+const PROC_NET_ARP: &str = "/proc/net/arp";
+
+/// `ATF_COM`, set in the flags of complete ARP table entries.
+const ATF_COM: u32 = 0x02;
+
+/// Translates a client MAC address to the originator announcing it.
+///
+/// This corresponds to the `batctl translate` command: the best global translation
+/// table entry of the client gives its originator. Addresses not found there, e.g.
+/// originators or local clients, are returned unchanged.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(sock), err)
+)]
+pub async fn translate_mac(
+    sock: &impl MeshTransport,
+    mesh_if: &str,
+    mac: MacAddr6,
+) -> Result<MacAddr6, RobinError> {
+    let mut entries = pin!(
+        get_transglobal_stream(sock.clone(), mesh_if)
+            .try_filter(|entry| future::ready(entry.client == mac && entry.is_best))
+    );
+    let entry = entries.try_next().await?;
+
+    Ok(entry.map_or(mac, |entry| entry.orig))
+}
+
+/// Resolves an IPv4 address to a MAC address.
+///
+/// The DAT cache of `mesh_if` is searched first, then the ARP table of the local
+/// node in `/proc/net/arp`.
+///
+/// # Returns
+///
+/// The MAC address, or `RobinError::NotFound` if neither table knows the address.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(sock), err)
+)]
+pub async fn resolve_ipv4(
+    sock: &impl MeshTransport,
+    mesh_if: &str,
+    ip: Ipv4Addr,
+) -> Result<MacAddr6, RobinError> {
+    let mut entries = pin!(
+        get_dat_cache_stream(sock.clone(), mesh_if)
+            .try_filter(|entry| future::ready(entry.ip == ip))
+    );
+    if let Some(entry) = entries.try_next().await? {
+        return Ok(entry.mac);
+    }
+
+    read_arp_table(Path::new(PROC_NET_ARP), ip)?
+        .ok_or_else(|| RobinError::NotFound(format!("No MAC address known for {}", ip)))
+}
+
+/// Looks `ip` up in an ARP table in the format of `/proc/net/arp`:
+/// `<ip> <hw type> <flags> <mac> <mask> <device>`, after a header line.
+///
+/// Incomplete entries are skipped.
+fn read_arp_table(path: &Path, ip: Ipv4Addr) -> Result<Option<MacAddr6>, RobinError> {
+    let text = fs::read_to_string(path).map_err(|e| RobinError::Io {
+        context: format!("Failed to read {}", path.display()),
+        source: e,
+    })?;
+
+    let mac = text.lines().skip(1).find_map(|line| {
+        let [addr, _, flags, mac, ..] = line.split_whitespace().collect::<Vec<_>>()[..] else {
+            return None;
+        };
+        let flags = u32::from_str_radix(flags.strip_prefix("0x")?, 16).ok()?;
+        match addr.parse::<Ipv4Addr>() {
+            Ok(addr) if addr == ip && flags & ATF_COM != 0 => mac.parse().ok(),
+            _ => None,
+        }
+    });
+
+    Ok(mac)
+}
@@ -1,14 +1,13 @@
 use crate::error::RobinError;
-use crate::model::{AttrValueForSend, Attribute, Command};
-use crate::netlink;
+use crate::model::{Attribute, Command, IfaceRef};
+use crate::netlink::{self, MeshResponse, MeshTransport};
 use neli::consts::nl::NlmF;
 use neli::consts::rtnl::{Ifla, RtAddrFamily, Rtm};
-use neli::consts::socket::NlFamily;
 use neli::genl::Genlmsghdr;
-use neli::nl::{NlPayload, Nlmsghdr};
-use neli::router::asynchronous::NlRouter;
-use neli::rtnl::{Ifinfomsg, IfinfomsgBuilder};
-use neli::utils::Groups;
+use neli::nl::Nlmsghdr;
+use neli::router::asynchronous::NlRouterReceiverHandle;
+use neli::rtnl::{Ifinfomsg, IfinfomsgBuilder, RtattrBuilder};
+use neli::types::{Buffer, RtBuffer};
 
 /// Retrieves the routing algorithm name associated with a given BATMAN-adv mesh interface.
 ///
@@ -21,39 +20,21 @@ use neli::utils::Groups;
 ///
 /// # Returns
 ///
-/// A `String` containing the algorithm name, or a `RobinError` if the interface
-/// cannot be queried or the algorithm name cannot be found.
-pub async fn get_algoname_netlink(mesh_if: &str) -> Result<String, RobinError> {
-    let ifindex = super::if_nametoindex(mesh_if).await.map_err(|_| {
-        RobinError::Netlink(format!(
-            "Error - interface '{}' is not present or not a batman-adv interface",
-            mesh_if
-        ))
-    })?;
-
-    let mut attrs = netlink::GenlAttrBuilder::new();
-    attrs
-        .add(
-            Attribute::BatadvAttrMeshIfindex,
-            AttrValueForSend::U32(ifindex),
-        )
-        .map_err(|_| RobinError::Netlink("Failed to add MeshIfIndex attribute".to_string()))?;
-
+/// A `String` containing the algorithm name,
+/// `RobinError::MissingAttribute` if the kernel does not report
+/// `BATADV_ATTR_ALGO_NAME`, or another `RobinError` if the interface cannot be queried.
+pub async fn get_algoname_netlink(
+    sock: &impl MeshTransport,
+    mesh_if: &str,
+) -> Result<String, RobinError> {
+    let attrs = mesh_selector(sock, mesh_if).await?;
     let msg = netlink::build_genl_msg(Command::BatadvCmdGetMeshInfo, attrs.build())
         .map_err(|_| RobinError::Netlink("Failed to build Netlink message".to_string()))?;
 
-    let mut sock = netlink::BatadvSocket::connect().await.map_err(|_| {
-        RobinError::Netlink("Failed to connect to batman-adv Netlink socket".to_string())
-    })?;
-
-    let mut response = sock
-        .send(NlmF::REQUEST, msg)
-        .await
-        .map_err(|_| RobinError::Netlink("Failed to send Netlink request".to_string()))?;
+    let mut response = sock.send(NlmF::REQUEST, msg).await?;
 
     while let Some(msg) = response.next().await {
-        let msg: Nlmsghdr<u16, Genlmsghdr<u8, u16>> =
-            msg.map_err(|_| RobinError::Netlink("Failed to parse Netlink message".to_string()))?;
+        let msg: Nlmsghdr<u16, Genlmsghdr<u8, u16>> = msg?;
 
         let payload = match msg.get_payload() {
             Some(p) => p,
@@ -61,24 +42,22 @@ pub async fn get_algoname_netlink(mesh_if: &str) -> Result<String, RobinError> {
         };
 
         for attr in payload.attrs().iter() {
-            if *attr.nla_type().nla_type() == Attribute::BatadvAttrAlgoName.into() {
-                let bytes = attr.nla_payload().as_ref();
-                let nul = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
-                return Ok(String::from_utf8_lossy(&bytes[..nul]).to_string());
+            if *attr.nla_type().nla_type() == u16::from(Attribute::BatadvAttrAlgoName) {
+                return Ok(netlink::c_str(attr.nla_payload().as_ref()).into_owned());
             }
         }
     }
 
-    Err(RobinError::NotFound(format!(
-        "No routing algorithm found for interface '{}'",
-        mesh_if
-    )))
+    Err(RobinError::MissingAttribute {
+        attr: Attribute::BatadvAttrAlgoName,
+    })
 }
 
 /// Converts a network interface name to its corresponding interface index (ifindex).
 ///
-/// This function uses netlink to enumerate all interfaces and find the index
-/// matching the provided interface name.
+/// This function sends a single `RTM_GETLINK` request for `ifname` instead of
+/// enumerating all interfaces. Resolved mappings are cached for a few seconds,
+/// so repeated lookups while parsing a dump do not hit the kernel again.
 ///
 /// # Arguments
 ///
@@ -88,52 +67,70 @@ pub async fn get_algoname_netlink(mesh_if: &str) -> Result<String, RobinError> {
 ///
 /// The `u32` interface index corresponding to `ifname`, or a `RobinError` if
 /// the interface does not exist or a netlink operation fails.
-pub async fn if_nametoindex(ifname: &str) -> Result<u32, RobinError> {
-    let (rtnl, _) = NlRouter::connect(NlFamily::Route, None, Groups::empty())
-        .await
-        .map_err(|_| RobinError::Netlink("Failed to connect to Netlink".to_string()))?;
+pub async fn if_nametoindex(rtnl: &netlink::RtnlSocket, ifname: &str) -> Result<u32, RobinError> {
+    if let Some(index) = rtnl.if_cache().index(ifname) {
+        return Ok(index);
+    }
+
+    let ifname_attr = RtattrBuilder::default()
+        .rta_type(Ifla::Ifname)
+        .rta_payload(ifname)
+        .build()
+        .map_err(|_| RobinError::Netlink("Failed to build IFNAME attribute".to_string()))?;
 
-    rtnl.enable_ext_ack(true).ok();
-    rtnl.enable_strict_checking(true).ok();
+    let mut rtattrs: RtBuffer<Ifla, Buffer> = RtBuffer::new();
+    rtattrs.push(ifname_attr);
 
     let ifinfomsg = IfinfomsgBuilder::default()
         .ifi_family(RtAddrFamily::Unspecified)
+        .rtattrs(rtattrs)
         .build()
         .map_err(|_| RobinError::Netlink("Failed to create Ifinfomsg".to_string()))?;
 
-    let mut response = rtnl
-        .send::<_, _, Rtm, Ifinfomsg>(
-            Rtm::Getlink,
-            NlmF::DUMP | NlmF::ACK,
-            NlPayload::Payload(ifinfomsg),
-        )
-        .await
-        .map_err(|_| RobinError::Netlink("Failed to send Netlink request".to_string()))?;
+    match rtnl.get_link(ifinfomsg).await? {
+        Some((index, _)) => Ok(index),
+        None => Err(RobinError::InterfaceNotFound {
+            name: ifname.to_string(),
+        }),
+    }
+}
 
-    while let Some(msg) = response.next().await {
-        let msg: Nlmsghdr<Rtm, Ifinfomsg> =
-            msg.map_err(|_| RobinError::Netlink("Failed to parse Netlink message".to_string()))?;
-
-        if let Some(payload) = msg.get_payload() {
-            let attrs = payload.rtattrs().get_attr_handle();
-            if let Ok(name) = attrs.get_attr_payload_as_with_len::<String>(Ifla::Ifname)
-                && name == ifname
-            {
-                return Ok(payload.ifi_index().cast_unsigned());
-            }
-        }
+/// Builds the attributes selecting the mesh interface `mesh_if`.
+///
+/// The interface is selected by name (`BatadvAttrMeshIfname`) if `sock` addresses
+/// [mesh interfaces by name](MeshTransport::mesh_by_name), saving the rtnetlink
+/// lookup, and by index (`BatadvAttrMeshIfindex`) otherwise.
+pub(crate) async fn mesh_selector(
+    sock: &impl MeshTransport,
+    mesh_if: &str,
+) -> Result<netlink::GenlAttrBuilder, RobinError> {
+    let mut attrs = netlink::GenlAttrBuilder::new();
+    match sock.mesh_by_name() {
+        true => attrs.add(Attribute::BatadvAttrMeshIfname, mesh_if)?,
+        false => attrs.add(
+            Attribute::BatadvAttrMeshIfindex,
+            sock.if_nametoindex(mesh_if).await?,
+        )?,
     }
+    Ok(attrs)
+}
 
-    Err(RobinError::NotFound(format!(
-        "Interface '{}' not found",
-        ifname
-    )))
+/// Resolves an interface reference to its index, looking names up on `sock`.
+///
+/// Indexes are passed through unchanged; the kernel rejects unknown ones in the
+/// request using them.
+pub async fn resolve_iface(sock: &impl MeshTransport, iface: &IfaceRef) -> Result<u32, RobinError> {
+    match iface {
+        IfaceRef::Name(name) => sock.if_nametoindex(name).await,
+        IfaceRef::Index(index) => Ok(*index),
+    }
 }
 
 /// Converts a network interface index (ifindex) to its corresponding interface name.
 ///
-/// This function uses netlink to enumerate all interfaces and find the name
-/// matching the provided interface index.
+/// This function sends a single `RTM_GETLINK` request for `ifindex` instead of
+/// enumerating all interfaces. Resolved mappings are cached for a few seconds,
+/// so repeated lookups while parsing a dump do not hit the kernel again.
 ///
 /// # Arguments
 ///
@@ -141,46 +138,42 @@ pub async fn if_nametoindex(ifname: &str) -> Result<u32, RobinError> {
 ///
 /// # Returns
 ///
-/// A `String` with the interface name corresponding to `ifindex`, or a `RobinError` if
-/// the interface does not exist or a netlink operation fails.
-pub async fn if_indextoname(ifindex: u32) -> Result<String, RobinError> {
-    let (rtnl, _) = NlRouter::connect(NlFamily::Route, None, Groups::empty())
-        .await
-        .map_err(|_| RobinError::Netlink("Failed to connect to Netlink".to_string()))?;
-
-    rtnl.enable_ext_ack(true).ok();
-    rtnl.enable_strict_checking(true).ok();
+/// A `String` with the interface name corresponding to `ifindex`,
+/// `RobinError::InterfaceNotFound` naming the index if the interface does not exist,
+/// or another `RobinError` if a netlink operation fails.
+pub async fn if_indextoname(
+    rtnl: &netlink::RtnlSocket,
+    ifindex: u32,
+) -> Result<String, RobinError> {
+    if let Some(name) = rtnl.if_cache().name(ifindex) {
+        return Ok(name);
+    }
 
     let ifinfomsg = IfinfomsgBuilder::default()
         .ifi_family(RtAddrFamily::Unspecified)
+        .ifi_index(ifindex.cast_signed())
         .build()
         .map_err(|_| RobinError::Netlink("Failed to create Ifinfomsg".to_string()))?;
 
-    let mut response = rtnl
-        .send::<_, _, Rtm, Ifinfomsg>(
-            Rtm::Getlink,
-            NlmF::DUMP | NlmF::ACK,
-            NlPayload::Payload(ifinfomsg),
-        )
-        .await
-        .map_err(|_| RobinError::Netlink("Failed to send Netlink request".to_string()))?;
-
-    while let Some(msg) = response.next().await {
-        let msg: Nlmsghdr<Rtm, Ifinfomsg> =
-            msg.map_err(|_| RobinError::Netlink("Failed to parse Netlink message".to_string()))?;
-
-        if let Some(payload) = msg.get_payload()
-            && *payload.ifi_index() == ifindex.cast_signed()
-        {
-            let attrs = payload.rtattrs().get_attr_handle();
-            if let Ok(name) = attrs.get_attr_payload_as_with_len::<String>(Ifla::Ifname) {
-                return Ok(name);
-            }
-        }
+    match rtnl.get_link(ifinfomsg).await? {
+        Some((_, name)) => Ok(name),
+        None => Err(RobinError::InterfaceNotFound {
+            name: ifindex.to_string(),
+        }),
     }
+}
 
-    Err(RobinError::NotFound(format!(
-        "Interface with index {} not found",
-        ifindex
-    )))
+/// Waits for the kernel to acknowledge an rtnetlink link request sent with `NlmF::ACK`.
+///
+/// # Returns
+///
+/// `Ok(())` once the acknowledgement has been received, or a `RobinError` carrying the
+/// kernel errno and extended ACK message if the request was rejected.
+pub async fn rtnl_ack(
+    mut response: NlRouterReceiverHandle<Rtm, Ifinfomsg>,
+) -> Result<(), RobinError> {
+    while let Some(msg) = response.next::<Rtm, Ifinfomsg>().await {
+        msg?;
+    }
+    Ok(())
 }
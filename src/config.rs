@@ -0,0 +1,717 @@
+//! Declarative mesh configuration.
+//!
+//! A [`MeshConfig`] describes the desired state of a mesh interface. It is read from
+//! TOML or YAML and applied with [`RobinClient::apply`], which compares it with the
+//! running state and only changes what differs, so applying the same configuration
+//! twice is a no-op.
+//!
+//! ```toml
+//! mesh_if = "bat0"
+//! routing_algo = "BATMAN_V"
+//! interfaces = ["wlan0", "eth1"]
+//! aggregation = true
+//! bridge_loop_avoidance = true
+//! hop_penalty = 30
+//!
+//! [gateway]
+//! mode = "server"
+//! bandwidth_down = 50000
+//! bandwidth_up = 10000
+//!
+//! [hardif.wlan0]
+//! hop_penalty = 10
+//! elp_interval = 500
+//!
+//! [vlan.10]
+//! ap_isolation = true
+//! ```
+//!
+//! Settings left out of the configuration are not touched.
+//!
+//! [`RobinClient::export_config`] reads the running configuration of a mesh
+//! interface into a [`MeshConfig`], which can be written with [`MeshConfig::to_file`]
+//! and applied on another node.
+
+use crate::client::RobinClient;
+use crate::commands::{self, SettingsDiff};
+use crate::error::RobinError;
+use crate::model::{Bandwidth, Command, GwMode, HardifSettings, MeshSettings, VlanSettings};
+use crate::netlink::MeshTransport;
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// Desired state of a BATMAN-adv mesh interface.
+///
+/// Bandwidths and throughputs are given in kbit/s.
+///
+/// Converts to and from [`MeshSettings`], which holds the mesh-level settings; the
+/// mesh interface, routing algorithm, hard interfaces and VLANs are left out.
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::config::MeshConfig;
+/// use batman_robin::{GwMode, MeshSettings};
+///
+/// let config = MeshConfig::from_toml(
+///     r#"
+///     mesh_if = "bat0"
+///     interfaces = ["wlan0"]
+///     aggregation = false
+///
+///     [gateway]
+///     mode = "client"
+///     sel_class = 20
+///     "#,
+/// )?;
+///
+/// assert_eq!(config.interfaces.as_deref(), Some(&["wlan0".to_string()][..]));
+///
+/// let settings = MeshSettings::from(&config);
+/// assert_eq!(settings.aggregation, Some(false));
+/// assert_eq!(settings.gw_sel_class, Some(20));
+/// assert_eq!(config.gateway.map(|gw| gw.mode), Some(GwMode::Client));
+/// # Ok::<(), batman_robin::RobinError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MeshConfig {
+    /// Name of the mesh interface, created if it does not exist.
+    pub mesh_if: String,
+
+    /// Routing algorithm of the mesh interface (e.g. `BATMAN_IV`, `BATMAN_V`).
+    ///
+    /// The routing algorithm of an existing interface cannot be changed, so the
+    /// interface is destroyed and created again if it runs a different one.
+    pub routing_algo: Option<String>,
+
+    /// Hard interfaces enslaved to the mesh interface. Interfaces not listed are
+    /// removed from the mesh.
+    pub interfaces: Option<Vec<String>>,
+
+    /// OGM aggregation.
+    pub aggregation: Option<bool>,
+
+    /// AP isolation.
+    pub ap_isolation: Option<bool>,
+
+    /// Bonding of multiple links to the same neighbor.
+    pub bonding: Option<bool>,
+
+    /// Bridge loop avoidance.
+    pub bridge_loop_avoidance: Option<bool>,
+
+    /// Distributed ARP table.
+    pub distributed_arp_table: Option<bool>,
+
+    /// Fragmentation of packets exceeding the MTU.
+    pub fragmentation: Option<bool>,
+
+    /// Flooding of all multicast traffic, disabling multicast optimizations.
+    pub multicast_forceflood: Option<bool>,
+
+    /// Network coding.
+    pub network_coding: Option<bool>,
+
+    /// Penalty applied to the link quality of forwarded OGMs (0-255).
+    pub hop_penalty: Option<u8>,
+
+    /// Interval between OGMs in milliseconds.
+    pub orig_interval: Option<u32>,
+
+    /// Gateway mode and parameters.
+    pub gateway: Option<GatewayConfig>,
+
+    /// Settings of individual hard interfaces, keyed by interface name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub hardif: BTreeMap<String, HardifConfig>,
+
+    /// Settings of individual VLANs on the mesh interface, keyed by VLAN id.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub vlan: BTreeMap<u16, VlanConfig>,
+}
+
+/// Desired gateway configuration of a mesh interface.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GatewayConfig {
+    /// Gateway mode: `off`, `client` or `server`.
+    pub mode: GwMode,
+
+    /// Advertised downstream bandwidth in kbit/s, for `server` mode.
+    pub bandwidth_down: Option<Bandwidth>,
+
+    /// Advertised upstream bandwidth in kbit/s, for `server` mode.
+    pub bandwidth_up: Option<Bandwidth>,
+
+    /// Gateway selection class, for `client` mode.
+    pub sel_class: Option<u32>,
+}
+
+/// Desired settings of a hard interface.
+///
+/// Converts to and from [`HardifSettings`], so the settings of a hard interface can
+/// be read and applied in one call each.
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::HardifSettings;
+/// use batman_robin::config::MeshConfig;
+///
+/// let config = MeshConfig::from_toml(
+///     r#"
+///     mesh_if = "bat0"
+///
+///     [hardif.wlan0]
+///     elp_interval = 500
+///     "#,
+/// )?;
+///
+/// let settings = HardifSettings::from(config.hardif["wlan0"].clone());
+/// assert_eq!(settings.elp_interval_ms, Some(500));
+/// assert!(settings.hop_penalty.is_none());
+/// # Ok::<(), batman_robin::RobinError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HardifConfig {
+    /// Penalty applied to OGMs forwarded on this interface (0-255).
+    pub hop_penalty: Option<u8>,
+
+    /// Interval between ELP packets in milliseconds (`BATMAN_V`).
+    pub elp_interval: Option<u32>,
+
+    /// Throughput used instead of the estimated one, in kbit/s; 0 disables the
+    /// override (`BATMAN_V`).
+    pub throughput_override: Option<u32>,
+}
+
+impl From<HardifSettings> for HardifConfig {
+    fn from(settings: HardifSettings) -> Self {
+        Self {
+            hop_penalty: settings.hop_penalty,
+            elp_interval: settings.elp_interval_ms,
+            throughput_override: settings.throughput_override.map(Bandwidth::kbps),
+        }
+    }
+}
+
+impl From<HardifConfig> for HardifSettings {
+    fn from(config: HardifConfig) -> Self {
+        Self {
+            elp_interval_ms: config.elp_interval,
+            hop_penalty: config.hop_penalty,
+            throughput_override: config.throughput_override.map(Bandwidth::from_kbps),
+        }
+    }
+}
+
+/// Desired settings of a VLAN on the mesh interface.
+///
+/// Converts to and from [`VlanSettings`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VlanConfig {
+    /// AP isolation of the clients on this VLAN.
+    pub ap_isolation: Option<bool>,
+}
+
+impl From<VlanSettings> for VlanConfig {
+    fn from(settings: VlanSettings) -> Self {
+        Self {
+            ap_isolation: settings.ap_isolation,
+        }
+    }
+}
+
+impl From<VlanConfig> for VlanSettings {
+    fn from(config: VlanConfig) -> Self {
+        Self {
+            ap_isolation: config.ap_isolation,
+        }
+    }
+}
+
+impl From<&MeshConfig> for MeshSettings {
+    fn from(config: &MeshConfig) -> Self {
+        let gateway = config.gateway.as_ref();
+        Self {
+            aggregation: config.aggregation,
+            ap_isolation: config.ap_isolation,
+            bonding: config.bonding,
+            bridge_loop_avoidance: config.bridge_loop_avoidance,
+            distributed_arp_table: config.distributed_arp_table,
+            fragmentation: config.fragmentation,
+            gw_mode: gateway.map(|gw| gw.mode),
+            gw_bandwidth_down: gateway.and_then(|gw| gw.bandwidth_down),
+            gw_bandwidth_up: gateway.and_then(|gw| gw.bandwidth_up),
+            gw_sel_class: gateway.and_then(|gw| gw.sel_class),
+            hop_penalty: config.hop_penalty,
+            multicast_forceflood: config.multicast_forceflood,
+            network_coding: config.network_coding,
+            orig_interval_ms: config.orig_interval,
+            ..Self::default()
+        }
+    }
+}
+
+impl From<MeshSettings> for MeshConfig {
+    fn from(settings: MeshSettings) -> Self {
+        Self {
+            aggregation: settings.aggregation,
+            ap_isolation: settings.ap_isolation,
+            bonding: settings.bonding,
+            bridge_loop_avoidance: settings.bridge_loop_avoidance,
+            distributed_arp_table: settings.distributed_arp_table,
+            fragmentation: settings.fragmentation,
+            multicast_forceflood: settings.multicast_forceflood,
+            network_coding: settings.network_coding,
+            hop_penalty: settings.hop_penalty,
+            orig_interval: settings.orig_interval_ms,
+            gateway: settings.gw_mode.map(|mode| GatewayConfig {
+                mode,
+                bandwidth_down: settings.gw_bandwidth_down,
+                bandwidth_up: settings.gw_bandwidth_up,
+                sel_class: settings.gw_sel_class,
+            }),
+            ..Self::default()
+        }
+    }
+}
+
+impl MeshConfig {
+    /// Parses a configuration from TOML.
+    pub fn from_toml(text: &str) -> Result<Self, RobinError> {
+        toml::from_str(text).map_err(|e| RobinError::Parse(format!("Invalid configuration: {}", e)))
+    }
+
+    /// Parses a configuration from YAML.
+    pub fn from_yaml(text: &str) -> Result<Self, RobinError> {
+        serde_yaml::from_str(text)
+            .map_err(|e| RobinError::Parse(format!("Invalid configuration: {}", e)))
+    }
+
+    /// Reads a configuration file, parsed as YAML if its extension is `.yaml` or
+    /// `.yml` and as TOML otherwise.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, RobinError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| RobinError::Io {
+            context: format!("Failed to read {}", path.display()),
+            source: e,
+        })?;
+
+        match is_yaml(path) {
+            true => Self::from_yaml(&text),
+            false => Self::from_toml(&text),
+        }
+    }
+
+    /// Serializes the configuration to TOML.
+    pub fn to_toml(&self) -> Result<String, RobinError> {
+        toml::to_string_pretty(self)
+            .map_err(|e| RobinError::Parse(format!("Cannot serialize configuration: {}", e)))
+    }
+
+    /// Serializes the configuration to YAML.
+    pub fn to_yaml(&self) -> Result<String, RobinError> {
+        serde_yaml::to_string(self)
+            .map_err(|e| RobinError::Parse(format!("Cannot serialize configuration: {}", e)))
+    }
+
+    /// Writes the configuration to a file, as YAML if its extension is `.yaml` or
+    /// `.yml` and as TOML otherwise.
+    pub fn to_file(&self, path: impl AsRef<Path>) -> Result<(), RobinError> {
+        let path = path.as_ref();
+        let text = match is_yaml(path) {
+            true => self.to_yaml()?,
+            false => self.to_toml()?,
+        };
+
+        std::fs::write(path, text).map_err(|e| RobinError::Io {
+            context: format!("Failed to write {}", path.display()),
+            source: e,
+        })
+    }
+}
+
+/// Returns `true` if `path` names a YAML file.
+fn is_yaml(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|ext| ext.to_str()),
+        Some("yaml" | "yml")
+    )
+}
+
+/// A change made by [`RobinClient::apply`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    /// The interface that was changed.
+    pub target: String,
+
+    /// The setting that was changed.
+    pub setting: String,
+
+    /// The previous value, if there was one.
+    pub from: Option<String>,
+
+    /// The new value.
+    pub to: String,
+}
+
+impl Change {
+    fn new(target: &str, setting: &str, from: Option<String>, to: impl ToString) -> Self {
+        Self {
+            target: target.to_string(),
+            setting: setting.to_string(),
+            from,
+            to: to.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.from {
+            Some(from) => write!(
+                f,
+                "{}: {} {} -> {}",
+                self.target, self.setting, from, self.to
+            ),
+            None => write!(f, "{}: {} -> {}", self.target, self.setting, self.to),
+        }
+    }
+}
+
+/// Converges the running state of `config.mesh_if` to `config`.
+///
+/// With `dry_run`, nothing is changed and the changes that would be made are
+/// returned instead. Settings of interfaces that do not exist yet are then
+/// all reported as changes.
+pub(crate) async fn apply(
+    client: &RobinClient,
+    config: &MeshConfig,
+    dry_run: bool,
+) -> Result<Vec<Change>, RobinError> {
+    let mesh_if = config.mesh_if.as_str();
+    let mut changes = Vec::new();
+
+    // A mesh interface that was just (re)created has no slaves and default settings;
+    // in a dry run it was not, so its settings cannot be read.
+    let fresh = apply_mesh_if(client, config, dry_run, &mut changes).await?;
+    let planned = dry_run && fresh;
+
+    let mut added = Vec::new();
+    if let Some(wanted) = &config.interfaces {
+        let current: Vec<String> = match fresh {
+            true => Vec::new(),
+            false => client
+                .get_interface(mesh_if)
+                .await?
+                .into_iter()
+                .map(|iface| iface.ifname)
+                .collect(),
+        };
+
+        for iface in wanted.iter().filter(|iface| !current.contains(iface)) {
+            if !dry_run {
+                client.set_interface(iface, Some(mesh_if)).await?;
+            }
+            added.push(iface.as_str());
+            changes.push(Change::new(iface, "master", None, mesh_if));
+        }
+        for iface in current.iter().filter(|iface| !wanted.contains(iface)) {
+            if !dry_run {
+                client.set_interface(iface, None).await?;
+            }
+            changes.push(Change::new(
+                iface,
+                "master",
+                Some(mesh_if.to_string()),
+                "none",
+            ));
+        }
+    }
+
+    // The running settings are read once; the changes reported are those of the
+    // very SET request that is sent.
+    let sock = client.transport();
+
+    let wanted = MeshSettings::from(config);
+    let running = match planned {
+        true => SettingsDiff::default(),
+        false => commands::diff_mesh_settings(sock, mesh_if, &wanted).await?,
+    };
+    changes.extend(mesh_changes(mesh_if, &wanted, &running.current));
+    if !dry_run {
+        client
+            .set_changed(Command::BatadvCmdSetMesh, running.attrs)
+            .await?;
+    }
+
+    for (ifname, hardif) in &config.hardif {
+        let wanted = HardifSettings::from(hardif.clone());
+        let running = match planned || (dry_run && added.contains(&ifname.as_str())) {
+            true => SettingsDiff::default(),
+            false => {
+                commands::diff_hardif_settings(sock, mesh_if, &ifname.as_str().into(), &wanted)
+                    .await?
+            }
+        };
+        changes.extend(hardif_changes(ifname, &wanted, &running.current));
+        if !dry_run {
+            client
+                .set_changed(Command::BatadvCmdSetHardif, running.attrs)
+                .await?;
+        }
+    }
+
+    for (&vid, vlan) in &config.vlan {
+        let wanted = VlanSettings::from(vlan.clone());
+        let running = match planned {
+            true => SettingsDiff::default(),
+            false => commands::diff_vlan_settings(sock, mesh_if, vid, &wanted).await?,
+        };
+        let target = format!("{}.{}", mesh_if, vid);
+        diff(
+            &mut changes,
+            &target,
+            "ap_isolation",
+            wanted.ap_isolation,
+            running.current.ap_isolation,
+        );
+        if !dry_run {
+            client
+                .set_changed(Command::BatadvCmdSetVlan, running.attrs)
+                .await?;
+        }
+    }
+
+    Ok(changes)
+}
+
+/// Reads the running configuration of `mesh_if`.
+///
+/// VLANs are found with [`RobinClient::vlans`].
+pub(crate) async fn export<T: MeshTransport>(
+    client: &RobinClient<T>,
+    mesh_if: &str,
+) -> Result<MeshConfig, RobinError> {
+    let settings = client.get_settings(mesh_if).await?;
+
+    let interfaces: Vec<String> = client
+        .get_interface(mesh_if)
+        .await?
+        .into_iter()
+        .map(|iface| iface.ifname)
+        .collect();
+
+    let mut hardif = BTreeMap::new();
+    for ifname in &interfaces {
+        let settings = client.get_hardif_settings(mesh_if, ifname).await?;
+        hardif.insert(ifname.clone(), HardifConfig::from(settings));
+    }
+
+    let mut vlan = BTreeMap::new();
+    for vid in client.vlans(mesh_if).await? {
+        let settings = client.get_vlan_settings(mesh_if, vid).await?;
+        vlan.insert(vid, VlanConfig::from(settings));
+    }
+
+    Ok(MeshConfig {
+        mesh_if: mesh_if.to_string(),
+        routing_algo: Some(client.routing_algo(mesh_if).await?),
+        interfaces: Some(interfaces),
+        hardif,
+        vlan,
+        ..MeshConfig::from(settings)
+    })
+}
+
+/// Creates the mesh interface if needed, recreating it to change its routing algorithm.
+///
+/// Returns `true` if the interface is (or, in a dry run, would be) new.
+async fn apply_mesh_if(
+    client: &RobinClient,
+    config: &MeshConfig,
+    dry_run: bool,
+    changes: &mut Vec<Change>,
+) -> Result<bool, RobinError> {
+    let mesh_if = config.mesh_if.as_str();
+    let algo = config.routing_algo.as_deref();
+
+    let current_algo = match client.if_nametoindex(mesh_if).await {
+        Ok(_) => client.routing_algo(mesh_if).await?,
+        Err(RobinError::InterfaceNotFound { .. }) => {
+            if !dry_run {
+                client.create_interface(mesh_if, algo).await?;
+            }
+            changes.push(Change::new(mesh_if, "interface", None, "created"));
+            return Ok(true);
+        }
+        Err(e) => return Err(e),
+    };
+
+    match algo {
+        Some(algo) if current_algo != algo => {
+            if !dry_run {
+                client.destroy_interface(mesh_if).await?;
+                client.create_interface(mesh_if, Some(algo)).await?;
+            }
+            changes.push(Change::new(
+                mesh_if,
+                "routing_algo",
+                Some(current_algo),
+                algo,
+            ));
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+/// Returns the changes of the mesh settings from `current` to `wanted`.
+fn mesh_changes(target: &str, wanted: &MeshSettings, current: &MeshSettings) -> Vec<Change> {
+    // Changes are reported with the values of the configuration: toggles and the
+    // gateway mode as numbers, bandwidths in kbit/s as the kernel stores them.
+    let gw_mode = |s: &MeshSettings| {
+        s.gw_mode.map(|mode| match mode {
+            GwMode::Off => 0,
+            GwMode::Client => 1,
+            GwMode::Server => 2,
+            GwMode::Unknown(mode) => mode,
+        })
+    };
+    let kbps = |b: Option<Bandwidth>| b.map(|b| Bandwidth::from_netlink(b.to_netlink()).kbps());
+
+    let mut changes = Vec::new();
+    let toggles = [
+        ("aggregation", wanted.aggregation, current.aggregation),
+        ("ap_isolation", wanted.ap_isolation, current.ap_isolation),
+        ("bonding", wanted.bonding, current.bonding),
+        (
+            "bridge_loop_avoidance",
+            wanted.bridge_loop_avoidance,
+            current.bridge_loop_avoidance,
+        ),
+        (
+            "distributed_arp_table",
+            wanted.distributed_arp_table,
+            current.distributed_arp_table,
+        ),
+        ("fragmentation", wanted.fragmentation, current.fragmentation),
+        (
+            "multicast_forceflood",
+            wanted.multicast_forceflood,
+            current.multicast_forceflood,
+        ),
+        (
+            "network_coding",
+            wanted.network_coding,
+            current.network_coding,
+        ),
+    ];
+    for (name, wanted, current) in toggles {
+        diff(&mut changes, target, name, wanted, current);
+    }
+
+    diff(
+        &mut changes,
+        target,
+        "hop_penalty",
+        wanted.hop_penalty,
+        current.hop_penalty,
+    );
+    diff(
+        &mut changes,
+        target,
+        "orig_interval",
+        wanted.orig_interval_ms,
+        current.orig_interval_ms,
+    );
+    diff(
+        &mut changes,
+        target,
+        "gw_mode",
+        gw_mode(wanted),
+        gw_mode(current),
+    );
+    diff(
+        &mut changes,
+        target,
+        "gw_bandwidth_down",
+        kbps(wanted.gw_bandwidth_down),
+        kbps(current.gw_bandwidth_down),
+    );
+    diff(
+        &mut changes,
+        target,
+        "gw_bandwidth_up",
+        kbps(wanted.gw_bandwidth_up),
+        kbps(current.gw_bandwidth_up),
+    );
+    diff(
+        &mut changes,
+        target,
+        "gw_sel_class",
+        wanted.gw_sel_class,
+        current.gw_sel_class,
+    );
+
+    changes
+}
+
+/// Returns the changes of the hard interface settings from `current` to `wanted`.
+fn hardif_changes(target: &str, wanted: &HardifSettings, current: &HardifSettings) -> Vec<Change> {
+    let kbps = |b: Option<Bandwidth>| b.map(|b| Bandwidth::from_netlink(b.to_netlink()).kbps());
+
+    let mut changes = Vec::new();
+    diff(
+        &mut changes,
+        target,
+        "hop_penalty",
+        wanted.hop_penalty,
+        current.hop_penalty,
+    );
+    diff(
+        &mut changes,
+        target,
+        "elp_interval",
+        wanted.elp_interval_ms,
+        current.elp_interval_ms,
+    );
+    diff(
+        &mut changes,
+        target,
+        "throughput_override",
+        kbps(wanted.throughput_override),
+        kbps(current.throughput_override),
+    );
+    changes
+}
+
+/// Records the change of setting `name` of `target` if `wanted` is set and differs
+/// from `current`.
+fn diff<V: Into<u32>>(
+    changes: &mut Vec<Change>,
+    target: &str,
+    name: &str,
+    wanted: Option<V>,
+    current: Option<V>,
+) {
+    let current = current.map(Into::into);
+    if let Some(wanted) = wanted.map(Into::into)
+        && current != Some(wanted)
+    {
+        changes.push(Change::new(
+            target,
+            name,
+            current.map(|v| v.to_string()),
+            wanted,
+        ));
+    }
+}
@@ -0,0 +1,318 @@
+use crate::error::RobinError;
+use crate::model::{ClientFlags, LastSeen, Originator, TransglobalEntry, TranslocalEntry};
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use futures::{Stream, StreamExt, stream};
+use macaddr::MacAddr6;
+
+/// Where batman-adv releases without netlink support expose their tables.
+///
+/// Pass it to [`RobinClientBuilder::debugfs_fallback`](crate::client::RobinClientBuilder::debugfs_fallback)
+/// to read the tables from there on such releases.
+pub const DEBUGFS_ROOT: &str = "/sys/kernel/debug/batman_adv";
+
+/// `BATADV_VLAN_HAS_TAG`, set in the VLAN ids reported over netlink for tagged entries.
+pub(crate) const VLAN_HAS_TAG: u16 = 1 << 15;
+
+/// Returns `true` if `err` means the kernel cannot answer netlink dumps, either
+/// because the `batadv` family does not exist or because the dump is not implemented.
+pub(crate) fn is_unsupported(err: &RobinError) -> bool {
+    matches!(err, RobinError::FamilyNotFound { .. }) || err.errno() == Some(libc::EOPNOTSUPP)
+}
+
+/// Falls back to the entries returned by `fallback` if the first item of `entries`
+/// is an error for which [`is_unsupported`] holds.
+///
+/// Without a fallback, or once the first entry has been received, `entries` is
+/// passed through unchanged.
+pub(crate) fn or_debugfs<T, S, F>(
+    entries: S,
+    fallback: Option<F>,
+) -> impl Stream<Item = Result<T, RobinError>>
+where
+    S: Stream<Item = Result<T, RobinError>>,
+    F: FnOnce() -> Result<Vec<T>, RobinError>,
+{
+    stream::once(async move {
+        let mut entries = Box::pin(entries);
+        match (entries.next().await, fallback) {
+            (Some(Err(e)), Some(fallback)) if is_unsupported(&e) => {
+                let fallback: Vec<_> = match fallback() {
+                    Ok(found) => found.into_iter().map(Ok).collect(),
+                    Err(e) => vec![Err(e)],
+                };
+                stream::iter(fallback).left_stream()
+            }
+            (first, _) => stream::iter(first).chain(entries).right_stream(),
+        }
+    })
+    .flatten()
+}
+
+/// Reads the originator table of `mesh_if` from `<root>/<mesh_if>/originators`.
+///
+/// The table only lists the selected router of each originator, so all entries
+/// are marked as best.
+pub(crate) fn read_originators(root: &Path, mesh_if: &str) -> Result<Vec<Originator>, RobinError> {
+    read_table(root, mesh_if, "originators", parse_originator)
+}
+
+/// Reads the local translation table of `mesh_if` from `<root>/<mesh_if>/transtable_local`.
+///
+/// Untagged entries (VID `-1`) get VID 0, tagged ones their VID with
+/// [`VLAN_HAS_TAG`] set, as over netlink.
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::{ClientFlags, MockBackend, RobinClient};
+/// use std::sync::Arc;
+///
+/// let root = std::env::temp_dir().join(format!("robin-translocal-{}", std::process::id()));
+/// std::fs::create_dir_all(root.join("bat0"))?;
+/// let table = "[B.A.T.M.A.N. adv 2016.1, MainIF/MAC: eth0/02:00:00:00:00:01 (bat0/02:00:00:00:00:0a BATMAN_IV), TTVN: 5]\n\
+///     Locally retrieved addresses (from bat0) announced via TT (TTVN: 5):\n\
+///            Client         VID Flags    Last seen (CRC       )\n \
+///     * 02:00:00:00:00:0a   -1 [.P....]   0.000   (0x1c7a34b2)\n \
+///     * 02:00:00:00:00:aa    5 [....W.]   1.250   (0x9f3e21d0)\n";
+/// std::fs::write(root.join("bat0/transtable_local"), table)?;
+///
+/// let mock = Arc::new(MockBackend::new().with_link(7, "bat0").with_error(libc::EOPNOTSUPP));
+/// let client = RobinClient::builder().mock_backend(mock).debugfs_fallback(&root).build();
+/// let runtime = tokio::runtime::Runtime::new().unwrap();
+///
+/// let entries = runtime.block_on(client.translocal("bat0"))?;
+/// assert_eq!(entries.len(), 2);
+/// assert_eq!(entries[0].vid, 0);
+/// assert!(entries[0].flags.contains(ClientFlags::NOPURGE));
+/// assert_eq!(entries[1].vid, 0x8005);
+/// assert_eq!(entries[1].last_seen.as_millis(), 1250);
+/// assert_eq!(entries[1].crc32, 0x9f3e_21d0);
+///
+/// // An entry line that does not parse fails the read.
+/// let mock = Arc::new(MockBackend::new().with_link(7, "bat0").with_error(libc::EOPNOTSUPP));
+/// let client = RobinClient::builder().mock_backend(mock).debugfs_fallback(&root).build();
+/// let malformed = format!("{} * 02:00:00:00:00:bb    5 [....W.]\n", table);
+/// std::fs::write(root.join("bat0/transtable_local"), malformed)?;
+/// assert!(runtime.block_on(client.translocal("bat0")).is_err());
+/// # std::fs::remove_dir_all(&root)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub(crate) fn read_translocal(
+    root: &Path,
+    mesh_if: &str,
+) -> Result<Vec<TranslocalEntry>, RobinError> {
+    read_table(root, mesh_if, "transtable_local", parse_translocal)
+}
+
+/// Reads the global translation table of `mesh_if` from `<root>/<mesh_if>/transtable_global`.
+///
+/// VIDs are converted as in [`read_translocal`].
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::{ClientFlags, MockBackend, RobinClient};
+/// use std::sync::Arc;
+///
+/// let root = std::env::temp_dir().join(format!("robin-transglobal-{}", std::process::id()));
+/// std::fs::create_dir_all(root.join("bat0"))?;
+/// let table = "[B.A.T.M.A.N. adv 2016.1, MainIF/MAC: eth0/02:00:00:00:00:01 (bat0/02:00:00:00:00:0a BATMAN_IV)]\n\
+///     Globally announced TT entries received via the mesh bat0\n\
+///        Client         VID  (TTVN)       Originator      (Curr TTVN) (CRC       ) Flags\n \
+///     * 02:00:00:00:00:bb   -1   ( 12) via 02:00:00:00:00:02     ( 12)   (0x5e9c0a11) [....]\n \
+///     + 02:00:00:00:00:cc    5   (  7) via 02:00:00:00:00:03     (  8)   (0x0b41d2e7) [R...]\n";
+/// std::fs::write(root.join("bat0/transtable_global"), table)?;
+///
+/// let mock = Arc::new(MockBackend::new().with_link(7, "bat0").with_error(libc::EOPNOTSUPP));
+/// let client = RobinClient::builder().mock_backend(mock).debugfs_fallback(&root).build();
+/// let runtime = tokio::runtime::Runtime::new().unwrap();
+///
+/// let entries = runtime.block_on(client.transglobal("bat0"))?;
+/// assert_eq!(entries.len(), 2);
+/// assert_eq!(entries[0].vid, 0);
+/// assert!(entries[0].is_best);
+/// assert_eq!(entries[0].orig.to_string(), "02:00:00:00:00:02");
+/// assert_eq!(entries[1].vid, 0x8005);
+/// assert!(!entries[1].is_best);
+/// assert_eq!((entries[1].ttvn, entries[1].last_ttvn), (7, 8));
+/// assert!(entries[1].flags.contains(ClientFlags::ROAM));
+///
+/// // An entry line that does not parse fails the read.
+/// let mock = Arc::new(MockBackend::new().with_link(7, "bat0").with_error(libc::EOPNOTSUPP));
+/// let client = RobinClient::builder().mock_backend(mock).debugfs_fallback(&root).build();
+/// let malformed = format!("{} * 02:00:00:00:00:dd    5   ( 12) via\n", table);
+/// std::fs::write(root.join("bat0/transtable_global"), malformed)?;
+/// assert!(runtime.block_on(client.transglobal("bat0")).is_err());
+/// # std::fs::remove_dir_all(&root)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub(crate) fn read_transglobal(
+    root: &Path,
+    mesh_if: &str,
+) -> Result<Vec<TransglobalEntry>, RobinError> {
+    read_table(root, mesh_if, "transtable_global", parse_transglobal)
+}
+
+/// Reads a debugfs table and parses its entry lines with `parse`.
+///
+/// Lines not starting with a MAC address (after an optional `*` or `+` marker) are
+/// headers or notices and are skipped; entry lines that `parse` rejects fail the read.
+fn read_table<T>(
+    root: &Path,
+    mesh_if: &str,
+    table: &str,
+    parse: fn(&[&str]) -> Option<T>,
+) -> Result<Vec<T>, RobinError> {
+    let path = root.join(mesh_if).join(table);
+    let text = fs::read_to_string(&path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound if !root.join(mesh_if).is_dir() => RobinError::InterfaceNotFound {
+            name: mesh_if.to_string(),
+        },
+        _ => RobinError::Io {
+            context: format!("Failed to read {}", path.display()),
+            source: e,
+        },
+    })?;
+
+    text.lines()
+        .filter_map(|line| {
+            let fields = fields(line);
+            let mac = match fields.first() {
+                Some(&"*" | &"+") => fields.get(1),
+                first => first,
+            };
+            mac.filter(|mac| mac.parse::<MacAddr6>().is_ok())?;
+            Some(parse(&fields).ok_or_else(|| {
+                RobinError::Parse(format!("Malformed line in {}: {}", path.display(), line))
+            }))
+        })
+        .collect()
+}
+
+/// Splits a table line into its fields, dropping the brackets and parentheses
+/// around them.
+fn fields(line: &str) -> Vec<&str> {
+    line.split(|c: char| c.is_whitespace() || "()[]".contains(c))
+        .filter(|field| !field.is_empty())
+        .collect()
+}
+
+/// Parses `<orig> <secs>.<msecs>s (<tq or throughput>) <nexthop> [<ifname>]: <nexthops>...`.
+///
+/// The metric is the TQ for `BATMAN_IV` and the throughput in Mbit/s with one
+/// decimal for `BATMAN_V`, converted to kbit/s like the netlink attribute.
+fn parse_originator(fields: &[&str]) -> Option<Originator> {
+    let fields = fields.strip_prefix(&["*"]).unwrap_or(fields);
+    let [orig, last_seen, metric, next_hop, ifname, ..] = fields else {
+        return None;
+    };
+
+    let (tq, throughput) = match metric.split_once('.') {
+        Some((mbits, tenths)) => (
+            None,
+            Some(mbits.parse::<u32>().ok()? * 1000 + tenths.parse::<u32>().ok()? * 100),
+        ),
+        None => (Some(metric.parse().ok()?), None),
+    };
+
+    Some(Originator {
+        originator: orig.parse().ok()?,
+        next_hop: next_hop.parse().ok()?,
+        outgoing_if: ifname.to_string(),
+        last_seen: parse_last_seen(last_seen.strip_suffix('s')?)?,
+        tq,
+        throughput,
+        is_best: true,
+    })
+}
+
+/// Parses ` * <client> <vid> [<flags>] <secs>.<msecs> (<crc>)`.
+fn parse_translocal(fields: &[&str]) -> Option<TranslocalEntry> {
+    let [_, client, vid, flags, last_seen, crc] = fields else {
+        return None;
+    };
+
+    Some(TranslocalEntry {
+        client: client.parse().ok()?,
+        vid: parse_vid(vid)?,
+        flags: parse_flags(flags)?,
+        crc32: parse_crc(crc)?,
+        last_seen: parse_last_seen(last_seen)?,
+    })
+}
+
+/// Parses ` <*|+> <client> <vid> (<ttvn>) via <orig> (<last ttvn>) (<crc>) [<flags>]`,
+/// where `*` marks the best entry of a client.
+fn parse_transglobal(fields: &[&str]) -> Option<TransglobalEntry> {
+    let [
+        marker,
+        client,
+        vid,
+        ttvn,
+        "via",
+        orig,
+        last_ttvn,
+        crc,
+        flags,
+    ] = fields
+    else {
+        return None;
+    };
+
+    Some(TransglobalEntry {
+        client: client.parse().ok()?,
+        orig: orig.parse().ok()?,
+        vid: parse_vid(vid)?,
+        ttvn: ttvn.parse().ok()?,
+        last_ttvn: last_ttvn.parse().ok()?,
+        flags: parse_flags(flags)?,
+        crc32: parse_crc(crc)?,
+        is_best: *marker == "*",
+    })
+}
+
+/// Parses `<secs>.<msecs>` into a last-seen time.
+fn parse_last_seen(field: &str) -> Option<LastSeen> {
+    let (secs, msecs) = field.split_once('.')?;
+    let ms = secs
+        .parse::<u32>()
+        .ok()?
+        .checked_mul(1000)?
+        .checked_add(msecs.parse().ok()?)?;
+    Some(LastSeen::from_millis(ms))
+}
+
+/// Converts a printed VLAN id (`-1` for untagged entries) to its netlink encoding.
+fn parse_vid(field: &str) -> Option<u16> {
+    match field.parse::<i32>().ok()? {
+        -1 => Some(0),
+        vid => Some(u16::try_from(vid).ok()? | VLAN_HAS_TAG),
+    }
+}
+
+/// Parses a CRC printed with `%#.8x`, which omits the `0x` prefix for zero.
+fn parse_crc(field: &str) -> Option<u32> {
+    u32::from_str_radix(field.strip_prefix("0x").unwrap_or(field), 16).ok()
+}
+
+/// Parses the flag letters of a table entry, with `.` for unset flags.
+fn parse_flags(field: &str) -> Option<ClientFlags> {
+    field.chars().try_fold(ClientFlags::empty(), |flags, c| {
+        let flag = match c {
+            '.' => ClientFlags::empty(),
+            'R' => ClientFlags::ROAM,
+            'P' => ClientFlags::NOPURGE,
+            'N' => ClientFlags::NEW,
+            'X' => ClientFlags::PENDING,
+            'W' => ClientFlags::WIFI,
+            'I' => ClientFlags::ISOLA,
+            'T' => ClientFlags::TEMP,
+            _ => return None,
+        };
+        Some(flags | flag)
+    })
+}
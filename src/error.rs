@@ -1,22 +1,129 @@
+use crate::model::Attribute;
+
+use std::fmt::Debug;
+use std::io;
+use std::time::Duration;
+
+use neli::consts::nl::NlmsgerrAttr;
+use neli::err::{Nlmsgerr, RouterError, SocketError};
 use thiserror::Error;
 
 /// Represents all possible errors returned by the Robin library.
 ///
-/// This includes errors from netlink communication, I/O operations, parsing,
-/// and cases where requested items are not found.
+/// The typed variants describe the failure causes callers usually want to react
+/// to (a missing interface, a kernel error code, insufficient privileges, ...),
+/// so they can be matched on instead of inspecting error messages.
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::RobinError;
+///
+/// fn describe(err: &RobinError) -> &'static str {
+///     match err {
+///         RobinError::InterfaceNotFound { .. } => "no such interface",
+///         RobinError::PermissionDenied => "run as root or grant CAP_NET_ADMIN",
+///         RobinError::KernelError { errno, .. } if *errno == 95 => "not supported",
+///         _ => "other error",
+///     }
+/// }
+///
+/// let err = RobinError::InterfaceNotFound { name: "bat0".into() };
+/// assert_eq!(describe(&err), "no such interface");
+/// ```
 #[derive(Error, Debug)]
 pub enum RobinError {
-    /// Represents errors originating from netlink operations.
+    /// The generic netlink family could not be resolved, e.g. because the kernel
+    /// module providing it is not loaded or, for `batadv`, predates netlink support.
+    #[error("Error - generic netlink family '{family}' not found")]
+    FamilyNotFound {
+        /// Name of the generic netlink family.
+        family: String,
+    },
+
+    /// The `batman-adv` kernel module is not loaded.
+    ///
+    /// See [`ensure_module_loaded`](crate::ensure_module_loaded) to load it.
+    #[error("Error - the {module} kernel module is not loaded, load it with: modprobe {module}")]
+    ModuleNotLoaded {
+        /// Name of the kernel module.
+        module: String,
+    },
+
+    /// The named interface does not exist or is not a batman-adv interface.
+    #[error("Error - interface '{name}' is not present or not a batman-adv interface")]
+    InterfaceNotFound {
+        /// Name of the interface.
+        name: String,
+    },
+
+    /// The kernel rejected the request with the given errno.
+    ///
+    /// Displayed as e.g. `EOPNOTSUPP: <extended ACK message>`, falling back to the
+    /// OS description of the errno when the kernel sent no message.
+    #[error("Error - {}: {}", errno_name(*.errno), kernel_message(.message, .source))]
+    KernelError {
+        /// Positive errno value reported by the kernel.
+        errno: i32,
+        /// The extended ACK message (`NLMSGERR_ATTR_MSG`) attached by the kernel, if any.
+        message: Option<String>,
+        /// The errno as an OS error.
+        source: io::Error,
+    },
+
+    /// A reply did not carry a mandatory attribute.
+    #[error("Error - missing attribute {attr:?}")]
+    MissingAttribute {
+        /// The missing attribute.
+        attr: Attribute,
+    },
+
+    /// A reply carried an attribute with an unexpected value or length.
+    #[error("Error - invalid attribute {attr:?}")]
+    InvalidAttribute {
+        /// The malformed attribute.
+        attr: Attribute,
+    },
+
+    /// The running batman-adv does not implement the requested command or
+    /// attribute, see [`Capabilities`](crate::Capabilities).
+    #[error("Error - {feature} is not supported by the running batman-adv")]
+    Unsupported {
+        /// The missing command or attribute, e.g. `BatadvCmdGetVlan`.
+        feature: String,
+    },
+
+    /// The operation requires `CAP_NET_ADMIN`.
+    #[error("Error - permission denied (CAP_NET_ADMIN required)")]
+    PermissionDenied,
+
+    /// A configuration change was refused by the client's
+    /// [`RateLimit`](crate::RateLimit) without reaching the kernel.
+    #[error("Error - too many configuration changes, retry in {retry_after:?}")]
+    Throttled {
+        /// Time until the next change is admitted.
+        retry_after: Duration,
+    },
+
+    /// The kernel did not answer in time.
+    #[error("Error - timed out waiting for the kernel")]
+    Timeout,
+
+    /// Represents other errors originating from netlink operations, such as
+    /// failures to build or encode a message.
     ///
     /// Contains a `String` describing the underlying netlink error.
     #[error("{0}")]
     Netlink(String),
 
     /// Represents I/O related errors.
-    ///
-    /// Contains a `String` describing the underlying I/O failure.
-    #[error("{0}")]
-    Io(String),
+    #[error("{context}: {source}")]
+    Io {
+        /// What was being done when the error occurred.
+        context: String,
+        /// The underlying I/O error.
+        source: io::Error,
+    },
 
     /// Represents errors encountered during parsing of netlink messages or other data.
     ///
@@ -30,3 +137,196 @@ pub enum RobinError {
     #[error("{0}")]
     NotFound(String),
 }
+
+impl RobinError {
+    /// Builds the error matching a (positive) errno reported by the kernel.
+    ///
+    /// `EPERM`/`EACCES` map to [`RobinError::PermissionDenied`], `ETIMEDOUT` to
+    /// [`RobinError::Timeout`] and everything else to [`RobinError::KernelError`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use batman_robin::RobinError;
+    ///
+    /// let err = RobinError::from_errno(95);
+    /// assert_eq!(err.errno(), Some(95));
+    /// assert!(err.to_string().starts_with("Error - EOPNOTSUPP: "));
+    /// ```
+    pub fn from_errno(errno: i32) -> Self {
+        match errno {
+            libc::EPERM | libc::EACCES => RobinError::PermissionDenied,
+            libc::ETIMEDOUT => RobinError::Timeout,
+            _ => RobinError::KernelError {
+                errno,
+                message: None,
+                source: io::Error::from_raw_os_error(errno),
+            },
+        }
+    }
+
+    /// Builds the error for a netlink error message, keeping the extended ACK
+    /// message the kernel attached to it.
+    pub(crate) fn from_nlmsgerr<M>(err: &Nlmsgerr<M>) -> Self {
+        let errno = -*err.error();
+        let message = err
+            .ext_ack()
+            .get_attr_handle()
+            .get_attr_payload_as_with_len::<String>(NlmsgerrAttr::Msg)
+            .ok()
+            .map(|msg| msg.trim_end_matches('\0').to_string())
+            .filter(|msg| !msg.is_empty());
+
+        match (RobinError::from_errno(errno), message) {
+            (RobinError::KernelError { errno, source, .. }, Some(message)) => {
+                RobinError::KernelError {
+                    errno,
+                    message: Some(message),
+                    source,
+                }
+            }
+            (err, _) => err,
+        }
+    }
+
+    /// Returns the name of the variant, e.g. `"PermissionDenied"`, for reporting the
+    /// error to tools that branch on it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use batman_robin::RobinError;
+    ///
+    /// assert_eq!(RobinError::from_errno(1).kind(), "PermissionDenied");
+    /// assert_eq!(RobinError::from_errno(95).kind(), "KernelError");
+    /// ```
+    pub fn kind(&self) -> &'static str {
+        match self {
+            RobinError::FamilyNotFound { .. } => "FamilyNotFound",
+            RobinError::ModuleNotLoaded { .. } => "ModuleNotLoaded",
+            RobinError::InterfaceNotFound { .. } => "InterfaceNotFound",
+            RobinError::KernelError { .. } => "KernelError",
+            RobinError::MissingAttribute { .. } => "MissingAttribute",
+            RobinError::InvalidAttribute { .. } => "InvalidAttribute",
+            RobinError::Unsupported { .. } => "Unsupported",
+            RobinError::PermissionDenied => "PermissionDenied",
+            RobinError::Throttled { .. } => "Throttled",
+            RobinError::Timeout => "Timeout",
+            RobinError::Netlink(_) => "Netlink",
+            RobinError::Io { .. } => "Io",
+            RobinError::Parse(_) => "Parse",
+            RobinError::NotFound(_) => "NotFound",
+        }
+    }
+
+    /// Returns the errno carried by a kernel error, if any.
+    pub fn errno(&self) -> Option<i32> {
+        match self {
+            RobinError::KernelError { errno, .. } => Some(*errno),
+            RobinError::PermissionDenied => Some(libc::EPERM),
+            RobinError::Timeout => Some(libc::ETIMEDOUT),
+            _ => None,
+        }
+    }
+
+    /// Returns a copy of this error for another caller waiting on the same request.
+    ///
+    /// I/O errors are copied by errno, or by kind and message.
+    pub(crate) fn duplicate(&self) -> Self {
+        let io = |e: &io::Error| match e.raw_os_error() {
+            Some(errno) => io::Error::from_raw_os_error(errno),
+            None => io::Error::new(e.kind(), e.to_string()),
+        };
+        match self {
+            RobinError::FamilyNotFound { family } => RobinError::FamilyNotFound {
+                family: family.clone(),
+            },
+            RobinError::ModuleNotLoaded { module } => RobinError::ModuleNotLoaded {
+                module: module.clone(),
+            },
+            RobinError::InterfaceNotFound { name } => {
+                RobinError::InterfaceNotFound { name: name.clone() }
+            }
+            RobinError::KernelError {
+                errno,
+                message,
+                source,
+            } => RobinError::KernelError {
+                errno: *errno,
+                message: message.clone(),
+                source: io(source),
+            },
+            RobinError::MissingAttribute { attr } => RobinError::MissingAttribute { attr: *attr },
+            RobinError::InvalidAttribute { attr } => RobinError::InvalidAttribute { attr: *attr },
+            RobinError::Unsupported { feature } => RobinError::Unsupported {
+                feature: feature.clone(),
+            },
+            RobinError::PermissionDenied => RobinError::PermissionDenied,
+            RobinError::Throttled { retry_after } => RobinError::Throttled {
+                retry_after: *retry_after,
+            },
+            RobinError::Timeout => RobinError::Timeout,
+            RobinError::Netlink(msg) => RobinError::Netlink(msg.clone()),
+            RobinError::Io { context, source } => RobinError::Io {
+                context: context.clone(),
+                source: io(source),
+            },
+            RobinError::Parse(msg) => RobinError::Parse(msg.clone()),
+            RobinError::NotFound(msg) => RobinError::NotFound(msg.clone()),
+        }
+    }
+}
+
+impl<T: Debug, P: Debug> From<RouterError<T, P>> for RobinError {
+    fn from(err: RouterError<T, P>) -> Self {
+        match err {
+            RouterError::Nlmsgerr(e) => RobinError::from_nlmsgerr(&e),
+            RouterError::Socket(SocketError::Io(e)) => match e.raw_os_error() {
+                Some(errno) => RobinError::from_errno(errno),
+                None => RobinError::Netlink(format!("Netlink socket error: {}", e)),
+            },
+            e => RobinError::Netlink(format!("Netlink error: {:?}", e)),
+        }
+    }
+}
+
+/// Returns the extended ACK message, or the OS description of the errno.
+fn kernel_message(message: &Option<String>, source: &io::Error) -> String {
+    match message {
+        Some(message) => message.clone(),
+        None => source.to_string(),
+    }
+}
+
+/// Returns the symbolic name of common errno values, e.g. `EOPNOTSUPP`.
+fn errno_name(errno: i32) -> String {
+    let name = match errno {
+        libc::EPERM => "EPERM",
+        libc::ENOENT => "ENOENT",
+        libc::ESRCH => "ESRCH",
+        libc::EINTR => "EINTR",
+        libc::EIO => "EIO",
+        libc::ENXIO => "ENXIO",
+        libc::E2BIG => "E2BIG",
+        libc::EAGAIN => "EAGAIN",
+        libc::ENOMEM => "ENOMEM",
+        libc::EACCES => "EACCES",
+        libc::EBUSY => "EBUSY",
+        libc::EEXIST => "EEXIST",
+        libc::ENODEV => "ENODEV",
+        libc::EINVAL => "EINVAL",
+        libc::ENOSPC => "ENOSPC",
+        libc::ERANGE => "ERANGE",
+        libc::EMSGSIZE => "EMSGSIZE",
+        libc::EPROTONOSUPPORT => "EPROTONOSUPPORT",
+        libc::EOPNOTSUPP => "EOPNOTSUPP",
+        libc::EAFNOSUPPORT => "EAFNOSUPPORT",
+        libc::ENOBUFS => "ENOBUFS",
+        libc::ENOTCONN => "ENOTCONN",
+        libc::ETIMEDOUT => "ETIMEDOUT",
+        libc::EALREADY => "EALREADY",
+        libc::EINPROGRESS => "EINPROGRESS",
+        _ => return format!("errno {}", errno),
+    };
+    name.to_string()
+}
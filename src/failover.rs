@@ -0,0 +1,242 @@
+use crate::client::RobinClient;
+use crate::error::RobinError;
+use crate::model::Gateway;
+use crate::netlink::MeshTransport;
+
+use std::fmt;
+use std::io;
+use std::time::Duration;
+
+use macaddr::MacAddr6;
+use tokio::process::Command;
+use tokio::time::{self, MissedTickBehavior};
+
+/// A change of the gateway selected by a gateway client, reported by
+/// [`FailoverMonitor`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct GatewayChange {
+    /// The gateway selected before, `None` if there was none.
+    pub old: Option<Gateway>,
+    /// The gateway selected now, `None` if all gateways vanished.
+    pub new: Option<Gateway>,
+}
+
+impl GatewayChange {
+    /// Returns the kind of change: `"selected"` if there was no gateway before,
+    /// `"lost"` if there is none now, `"changed"` otherwise.
+    pub fn kind(&self) -> &'static str {
+        match (&self.old, &self.new) {
+            (None, _) => "selected",
+            (Some(_), None) => "lost",
+            (Some(_), Some(_)) => "changed",
+        }
+    }
+
+    /// Returns the environment passed to hooks by [`run_hook`]:
+    ///
+    /// - `ROBIN_MESHIF`: the mesh interface.
+    /// - `ROBIN_GW_EVENT`: the [`kind`](GatewayChange::kind) of change.
+    /// - `ROBIN_GW_OLD`, `ROBIN_GW_NEW`: MAC address of the old and new gateway.
+    /// - `ROBIN_GW_NEW_ROUTER`, `ROBIN_GW_NEW_IF`: next hop and hard interface
+    ///   towards the new gateway.
+    ///
+    /// Variables of a missing gateway are empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use batman_robin::Gateway;
+    /// use batman_robin::failover::{FailoverMonitor, GatewayChange};
+    ///
+    /// let gw = "02:00:00:00:00:01".parse().unwrap();
+    /// let router = "02:00:00:00:00:02".parse().unwrap();
+    /// let mut gateway = Gateway::new(gw, router, "wlan0");
+    /// gateway.is_best = true;
+    ///
+    /// let mut monitor = FailoverMonitor::new();
+    /// assert!(monitor.update(&[gateway]).is_none());
+    /// let change = monitor.update(&[]).unwrap();
+    ///
+    /// let env = change.env("bat0");
+    /// assert!(env.contains(&("ROBIN_GW_EVENT", "lost".to_string())));
+    /// assert!(env.contains(&("ROBIN_GW_OLD", "02:00:00:00:00:01".to_string())));
+    /// assert!(env.contains(&("ROBIN_GW_NEW", String::new())));
+    /// ```
+    pub fn env(&self, mesh_if: &str) -> Vec<(&'static str, String)> {
+        let mac = |gw: &Option<Gateway>| gw.as_ref().map(|g| g.mac_addr.to_string());
+        vec![
+            ("ROBIN_MESHIF", mesh_if.to_string()),
+            ("ROBIN_GW_EVENT", self.kind().to_string()),
+            ("ROBIN_GW_OLD", mac(&self.old).unwrap_or_default()),
+            ("ROBIN_GW_NEW", mac(&self.new).unwrap_or_default()),
+            (
+                "ROBIN_GW_NEW_ROUTER",
+                self.new
+                    .as_ref()
+                    .map(|g| g.router.to_string())
+                    .unwrap_or_default(),
+            ),
+            (
+                "ROBIN_GW_NEW_IF",
+                self.new
+                    .as_ref()
+                    .map(|g| g.outgoing_if.clone())
+                    .unwrap_or_default(),
+            ),
+        ]
+    }
+}
+
+impl fmt::Display for GatewayChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.old, &self.new) {
+            (None, Some(new)) => write!(f, "gateway {} selected", new.mac_addr),
+            (Some(old), None) => write!(f, "gateway {} lost, no gateway left", old.mac_addr),
+            (Some(old), Some(new)) => write!(
+                f,
+                "gateway changed from {} to {}",
+                old.mac_addr, new.mac_addr
+            ),
+            (None, None) => write!(f, "no gateway"),
+        }
+    }
+}
+
+/// Tracks the gateway selected by a gateway client and reports when it changes.
+///
+/// The monitor is fed with gateway tables as they are read, e.g. by polling
+/// [`RobinClient::gateways`](crate::RobinClient::gateways) or with [`monitor`].
+/// The selected gateway is the entry marked best; only nodes in gateway client
+/// mode select one. The first table sets the initial gateway without reporting
+/// a change.
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::Gateway;
+/// use batman_robin::failover::FailoverMonitor;
+///
+/// let gateway = |mac: &str| {
+///     let mac = mac.parse().unwrap();
+///     let mut gateway = Gateway::new(mac, mac, "wlan0");
+///     gateway.is_best = true;
+///     gateway
+/// };
+///
+/// let mut monitor = FailoverMonitor::new();
+/// assert!(monitor.update(&[gateway("02:00:00:00:00:01")]).is_none());
+/// assert!(monitor.update(&[gateway("02:00:00:00:00:01")]).is_none());
+///
+/// let change = monitor.update(&[gateway("02:00:00:00:00:02")]).unwrap();
+/// assert_eq!(change.kind(), "changed");
+/// assert_eq!(monitor.update(&[]).unwrap().kind(), "lost");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FailoverMonitor {
+    /// The selected gateway, `None` before the first table.
+    selected: Option<Option<Gateway>>,
+}
+
+impl FailoverMonitor {
+    /// Creates a monitor that has not seen a gateway table yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the MAC address of the selected gateway, `None` if there is none or
+    /// no table was seen yet.
+    pub fn selected(&self) -> Option<MacAddr6> {
+        self.selected.clone().flatten().map(|g| g.mac_addr)
+    }
+
+    /// Updates the monitor with a gateway table, returning the change of the
+    /// selected gateway, if any.
+    pub fn update(&mut self, gateways: &[Gateway]) -> Option<GatewayChange> {
+        let new = gateways.iter().find(|g| g.is_best).cloned();
+        let old = self.selected.replace(new.clone())?;
+        let mac = |gw: &Option<Gateway>| gw.as_ref().map(|g| g.mac_addr);
+        (mac(&old) != mac(&new)).then_some(GatewayChange { old, new })
+    }
+}
+
+/// Runs the shell command `hook` for `change` on `mesh_if` and waits for it
+/// without blocking the runtime.
+///
+/// The command is run with `sh -c`, with the environment of
+/// [`GatewayChange::env`] added to the inherited one. Its output is discarded
+/// unless it fails.
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the hook exits successfully, or a `RobinError::Io` if it
+/// cannot be run or fails, carrying its error output.
+pub async fn run_hook(hook: &str, change: &GatewayChange, mesh_if: &str) -> Result<(), RobinError> {
+    let context = format!("Gateway hook '{}' failed", hook);
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(hook)
+        .envs(change.env(mesh_if))
+        .output()
+        .await
+        .map_err(|e| RobinError::Io {
+            context: context.clone(),
+            source: e,
+        })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(RobinError::Io {
+            context,
+            source: io::Error::other(match stderr.is_empty() {
+                true => format!("exited with {}", output.status),
+                false => stderr,
+            }),
+        });
+    }
+    Ok(())
+}
+
+/// Reads the gateway table of `mesh_if` every `interval`, starting now, and passes
+/// the changes of the selected gateway to `on_change`.
+///
+/// Runs until the gateway table cannot be read; spawn it as a task to monitor in
+/// the background. `on_change` is awaited before the next table is read, so
+/// hooks run from it see the changes in order.
+///
+/// # Example
+///
+/// ```no_run
+/// use batman_robin::RobinClient;
+/// use batman_robin::failover::{FailoverMonitor, monitor, run_hook};
+/// use std::time::Duration;
+///
+/// # async fn example() -> Result<(), batman_robin::RobinError> {
+/// let client = RobinClient::new();
+/// let mut failover = FailoverMonitor::new();
+/// monitor(&client, "bat0", Duration::from_secs(5), &mut failover, async |change| {
+///     if let Err(e) = run_hook("/etc/robin/gateway-changed", &change, "bat0").await {
+///         eprintln!("{}", e);
+///     }
+/// })
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn monitor<T: MeshTransport>(
+    client: &RobinClient<T>,
+    mesh_if: &str,
+    interval: Duration,
+    failover: &mut FailoverMonitor,
+    mut on_change: impl AsyncFnMut(GatewayChange),
+) -> Result<(), RobinError> {
+    let mut ticks = time::interval(interval);
+    ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        ticks.tick().await;
+        let gateways = client.gateways(mesh_if).await?;
+        if let Some(change) = failover.update(&gateways) {
+            on_change(change).await;
+        }
+    }
+}
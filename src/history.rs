@@ -0,0 +1,162 @@
+use crate::model::Originator;
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use macaddr::MacAddr6;
+
+/// A link quality sample of an originator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sample {
+    /// When the sample was recorded.
+    pub at: Instant,
+    /// Transmission quality towards the originator (BATMAN_IV).
+    pub tq: Option<u8>,
+    /// Throughput towards the originator (BATMAN_V).
+    pub throughput: Option<u32>,
+}
+
+/// Moving average and extremes of a metric over a window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MetricStats {
+    /// Mean of the samples.
+    pub avg: f64,
+    /// Smallest sample.
+    pub min: u32,
+    /// Largest sample.
+    pub max: u32,
+}
+
+/// Link quality trend of an originator over a window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinkStats {
+    /// Number of samples in the window.
+    pub samples: usize,
+    /// Statistics of the TQ samples, `None` if none carries a TQ.
+    pub tq: Option<MetricStats>,
+    /// Statistics of the throughput samples, `None` if none carries a throughput.
+    pub throughput: Option<MetricStats>,
+}
+
+/// In-memory history of the link quality towards each originator.
+///
+/// Every originator gets a ring buffer of the last `capacity` samples of its
+/// selected router, so trends can be shown instead of instantaneous values. The
+/// history is fed with originator tables as they are read, e.g. by polling
+/// [`RobinClient::originators`](crate::RobinClient::originators).
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::Originator;
+/// use batman_robin::history::LinkHistory;
+/// use std::time::{Duration, Instant};
+///
+/// let originator = |tq| {
+///     let mac = "02:00:00:00:00:01".parse().unwrap();
+///     let mut originator = Originator::new(mac, mac, "wlan0");
+///     originator.tq = Some(tq);
+///     originator.is_best = true;
+///     originator
+/// };
+///
+/// let mut history = LinkHistory::new(60);
+/// let start = Instant::now();
+/// history.record_at(start, &[originator(200)]);
+/// history.record_at(start + Duration::from_secs(10), &[originator(150)]);
+/// history.record_at(start + Duration::from_secs(20), &[originator(100)]);
+///
+/// let mac = "02:00:00:00:00:01".parse().unwrap();
+/// let stats = history.stats(mac, Duration::from_secs(10)).unwrap();
+/// assert_eq!(stats.samples, 2);
+/// let tq = stats.tq.unwrap();
+/// assert_eq!((tq.avg, tq.min, tq.max), (125.0, 100, 150));
+/// assert!(stats.throughput.is_none());
+/// ```
+#[derive(Debug, Clone)]
+pub struct LinkHistory {
+    capacity: usize,
+    samples: HashMap<MacAddr6, VecDeque<Sample>>,
+}
+
+impl LinkHistory {
+    /// Creates an empty history keeping the last `capacity` samples of each originator.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            samples: HashMap::new(),
+        }
+    }
+
+    /// Records the selected routers of an originator table read now.
+    pub fn record(&mut self, originators: &[Originator]) {
+        self.record_at(Instant::now(), originators);
+    }
+
+    /// Records the selected routers of an originator table read at `at`.
+    ///
+    /// Entries of other routers than the selected one are ignored.
+    pub fn record_at(&mut self, at: Instant, originators: &[Originator]) {
+        for o in originators.iter().filter(|o| o.is_best) {
+            let samples = self.samples.entry(o.originator).or_default();
+            if samples.len() == self.capacity {
+                samples.pop_front();
+            }
+            samples.push_back(Sample {
+                at,
+                tq: o.tq,
+                throughput: o.throughput,
+            });
+        }
+    }
+
+    /// Returns the samples of `originator`, oldest first.
+    pub fn samples(&self, originator: MacAddr6) -> impl Iterator<Item = &Sample> {
+        self.samples.get(&originator).into_iter().flatten()
+    }
+
+    /// Returns the originators with samples.
+    pub fn originators(&self) -> impl Iterator<Item = MacAddr6> + '_ {
+        self.samples.keys().copied()
+    }
+
+    /// Returns the link quality trend of `originator` over the `window` before its
+    /// latest sample, or `None` if it has no samples.
+    pub fn stats(&self, originator: MacAddr6, window: Duration) -> Option<LinkStats> {
+        let samples = self.samples.get(&originator)?;
+        let latest = samples.back()?.at;
+        let recent: Vec<&Sample> = samples
+            .iter()
+            .filter(|s| latest.duration_since(s.at) <= window)
+            .collect();
+
+        Some(LinkStats {
+            samples: recent.len(),
+            tq: metric_stats(recent.iter().filter_map(|s| s.tq.map(u32::from))),
+            throughput: metric_stats(recent.iter().filter_map(|s| s.throughput)),
+        })
+    }
+
+    /// Forgets the originators without samples in the `max_age` before `now`, e.g.
+    /// the ones that left the mesh.
+    pub fn prune(&mut self, now: Instant, max_age: Duration) {
+        self.samples.retain(|_, samples| {
+            samples
+                .back()
+                .is_some_and(|s| now.saturating_duration_since(s.at) <= max_age)
+        });
+    }
+}
+
+/// Computes the mean, minimum and maximum of `values`, `None` if there are none.
+fn metric_stats(values: impl Iterator<Item = u32>) -> Option<MetricStats> {
+    let (count, sum, min, max) = values.fold((0u64, 0u64, u32::MAX, 0), |(n, sum, min, max), v| {
+        (n + 1, sum + u64::from(v), min.min(v), max.max(v))
+    });
+
+    (count > 0).then(|| MetricStats {
+        avg: sum as f64 / count as f64,
+        min,
+        max,
+    })
+}
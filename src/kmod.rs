@@ -0,0 +1,82 @@
+use crate::error::RobinError;
+
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+/// Name of the batman-adv kernel module.
+pub const MODULE_NAME: &str = "batman_adv";
+
+/// Where the kernel lists the batman-adv module once it is loaded or built in.
+const SYSFS_MODULE: &str = "/sys/module/batman_adv";
+
+/// Returns `true` if the batman-adv kernel module is loaded, or built into the kernel.
+///
+/// # Example
+///
+/// ```no_run
+/// if !batman_robin::module_loaded() {
+///     eprintln!("batman-adv is not loaded");
+/// }
+/// ```
+pub fn module_loaded() -> bool {
+    Path::new(SYSFS_MODULE).exists()
+}
+
+/// Loads the batman-adv kernel module with `modprobe`, unless it is loaded already.
+///
+/// The library never loads the module by itself; call this before creating the
+/// first mesh interface on a fresh boot. Loading needs `CAP_SYS_MODULE`.
+///
+/// # Returns
+///
+/// Returns `Ok(())` once the module is loaded, or a `RobinError::Io` if `modprobe`
+/// cannot be run or fails, carrying its error output.
+///
+/// # Example
+///
+/// ```no_run
+/// # use batman_robin::RobinClient;
+/// # async fn example() -> Result<(), batman_robin::RobinError> {
+/// batman_robin::ensure_module_loaded()?;
+/// RobinClient::new().create_interface("bat0", None).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn ensure_module_loaded() -> Result<(), RobinError> {
+    if module_loaded() {
+        return Ok(());
+    }
+
+    let context = format!("Failed to load the {} kernel module", MODULE_NAME);
+    let output = Command::new("modprobe")
+        .arg(MODULE_NAME)
+        .output()
+        .map_err(|e| RobinError::Io {
+            context: format!("{} (running modprobe)", context),
+            source: e,
+        })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(RobinError::Io {
+            context,
+            source: io::Error::other(match stderr.is_empty() {
+                true => format!("modprobe exited with {}", output.status),
+                false => stderr,
+            }),
+        });
+    }
+    Ok(())
+}
+
+/// Returns the error for a `batadv` family that could not be resolved:
+/// [`RobinError::ModuleNotLoaded`] if the module is not loaded, `err` otherwise.
+pub(crate) fn family_error(err: RobinError) -> RobinError {
+    match err {
+        RobinError::FamilyNotFound { .. } if !module_loaded() => RobinError::ModuleNotLoaded {
+            module: MODULE_NAME.to_string(),
+        },
+        err => err,
+    }
+}
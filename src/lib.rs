@@ -6,20 +6,71 @@
 //! ## Modules
 //!
 //! - `commands` - Internal implementation of batman-adv commands (netlink message builders, parsing, etc.).
+//! - `capability` - Privilege check for operations that change the mesh configuration.
+//! - `debugfs` - Text parsers for the debugfs tables of batman-adv releases without netlink support.
 //! - `error` - Defines `RobinError`, the unified error type for all operations.
+//! - `kmod` - Detection and opt-in loading of the batman-adv kernel module.
 //! - `netlink` - Low-level wrappers around netlink sockets, generic netlink messages, and attribute builders.
+//!   The sockets, [`build_genl_msg`] and [`parse_genl_msg`] are re-exported as the low-level message API,
+//!   along with the [`neli`] crate their signatures use.
+//!   [`resolve_family`] looks up the id and multicast groups of any Generic Netlink family.
+//! - `config` - Declarative mesh configuration applied with `RobinClient::apply` (`config` feature).
+//! - `oui` - MAC address vendor lookup in an embedded IEEE OUI table (`oui` feature).
+//! - `output` - Versioned JSON output documents and their JSON Schema (`json` feature).
+//! - `record` - Periodic snapshots of the mesh tables to rotating JSON Lines files (`json` feature).
+//! - `webhook` - HTTP webhook sink POSTing JSON output documents (`webhook` feature).
+//! - `logfile` - Log files rotated by size and age, capturing the output of long-running modes.
+//! - `history` - In-memory link quality history of originators (moving averages, min/max).
+//! - `liveness` - Node-down and node-up events from the last-seen progression of originators.
+//! - `failover` - Changes of the selected gateway, with hook commands run on each.
+//! - `roaming` - History of the moves of clients between originators, from the global translation table.
+//! - `stream` - Adapters stopping streamed dumps early and discarding the rest of the table.
 //! - `client` - High-level API providing the `RobinClient` struct for interacting with mesh networks.
 //! - `model` - Data structures representing interfaces, neighbors, originators, gateways, translation tables, etc.
-//! - `cli` - Command-line interface modules (only included when building the binary).
+//! - `cli` - Command-line interface modules (only included with the `cli` feature, enabled by default).
 
+mod capability;
 mod commands;
+mod debugfs;
 mod error;
+mod kmod;
 mod netlink;
+mod query_cache;
 
+#[cfg(feature = "cli")]
 pub mod cli;
 pub mod client;
+#[cfg(feature = "config")]
+pub mod config;
+pub mod failover;
+pub mod history;
+pub mod liveness;
+pub mod logfile;
 pub mod model;
+#[cfg(feature = "oui")]
+pub mod oui;
+#[cfg(feature = "json")]
+pub mod output;
+#[cfg(feature = "json")]
+pub mod record;
+pub mod roaming;
+pub mod stream;
+#[cfg(feature = "webhook")]
+pub mod webhook;
 
+pub use capability::can_administer;
 pub use client::RobinClient;
+pub use debugfs::DEBUGFS_ROOT;
 pub use error::RobinError;
+pub use kmod::{MODULE_NAME, ensure_module_loaded, module_loaded};
 pub use model::*;
+pub use netlink::{
+    BatadvMsg, BatadvResponse, BatadvRouterError, BatadvSocket, GenlAttrBuilder, GenlFamily,
+    MeshResponse, MeshTransport, MockBackend, NetlinkTransport, RateLimit, RetryPolicy, RtnlSocket,
+    build_genl_msg, parse_genl_msg, resolve_family,
+};
+pub use stream::DumpStreamExt;
+
+/// The netlink crate used by the low-level message API, e.g. for
+/// [`NlmF`](neli::consts::nl::NlmF) flags and [`Genlmsghdr`](neli::genl::Genlmsghdr).
+pub use neli;
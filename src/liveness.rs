@@ -0,0 +1,255 @@
+use crate::client::RobinClient;
+use crate::error::RobinError;
+use crate::model::Originator;
+use crate::netlink::MeshTransport;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use macaddr::MacAddr6;
+use tokio::time::{self, MissedTickBehavior};
+
+/// A change in the liveness of an originator, reported by [`LivenessMonitor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LivenessEvent {
+    /// The originator has not been seen for longer than the threshold.
+    Down {
+        /// MAC address of the originator.
+        originator: MacAddr6,
+        /// Time since the originator was last seen.
+        silent_for: Duration,
+    },
+    /// An originator reported down has been seen again.
+    Up {
+        /// MAC address of the originator.
+        originator: MacAddr6,
+        /// Time the originator had not been seen for.
+        silent_for: Duration,
+    },
+}
+
+impl LivenessEvent {
+    /// Returns the MAC address of the originator.
+    pub fn originator(&self) -> MacAddr6 {
+        match *self {
+            LivenessEvent::Down { originator, .. } | LivenessEvent::Up { originator, .. } => {
+                originator
+            }
+        }
+    }
+
+    /// Returns `true` for [`LivenessEvent::Down`].
+    pub fn is_down(&self) -> bool {
+        matches!(self, LivenessEvent::Down { .. })
+    }
+}
+
+impl fmt::Display for LivenessEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LivenessEvent::Down {
+                originator,
+                silent_for,
+            } => write!(
+                f,
+                "{} is down, last seen {:.1}s ago",
+                originator,
+                silent_for.as_secs_f64()
+            ),
+            LivenessEvent::Up {
+                originator,
+                silent_for,
+            } => write!(
+                f,
+                "{} is up again after {:.1}s",
+                originator,
+                silent_for.as_secs_f64()
+            ),
+        }
+    }
+}
+
+/// What the monitor knows about an originator.
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    /// When the originator was last seen, estimated from its `last_seen` age.
+    seen_at: Instant,
+    /// Whether it was reported down.
+    down: bool,
+}
+
+/// Tracks when each originator was last seen and reports the ones going silent
+/// for longer than a threshold, and coming back.
+///
+/// The monitor is fed with originator tables as they are read, e.g. by polling
+/// [`RobinClient::originators`](crate::RobinClient::originators) or with
+/// [`monitor`]. An originator is down once its last-seen age exceeds the threshold,
+/// also after the kernel purged it from the table; it is up again once a table
+/// shows it seen within the threshold.
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::Originator;
+/// use batman_robin::liveness::{LivenessEvent, LivenessMonitor};
+/// use std::time::{Duration, Instant};
+///
+/// let mac = "02:00:00:00:00:01".parse().unwrap();
+/// let originator = |secs| {
+///     let mut originator = Originator::new(mac, mac, "wlan0");
+///     originator.last_seen = Duration::from_secs(secs).into();
+///     originator
+/// };
+///
+/// let mut monitor = LivenessMonitor::new(Duration::from_secs(30));
+/// let start = Instant::now();
+/// assert!(monitor.update_at(start, &[originator(1)]).is_empty());
+///
+/// // Purged from the table, silent for 41s.
+/// let events = monitor.update_at(start + Duration::from_secs(40), &[]);
+/// assert_eq!(
+///     events,
+///     [LivenessEvent::Down { originator: mac, silent_for: Duration::from_secs(41) }]
+/// );
+/// assert!(monitor.is_down(mac));
+///
+/// let events = monitor.update_at(start + Duration::from_secs(60), &[originator(2)]);
+/// assert_eq!(
+///     events,
+///     [LivenessEvent::Up { originator: mac, silent_for: Duration::from_secs(59) }]
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct LivenessMonitor {
+    threshold: Duration,
+    nodes: HashMap<MacAddr6, Node>,
+}
+
+impl LivenessMonitor {
+    /// Creates a monitor reporting originators not seen for longer than `threshold`.
+    ///
+    /// The threshold should be well above the originator interval (1s by default),
+    /// e.g. 30 seconds, so single lost packets do not raise events.
+    pub fn new(threshold: Duration) -> Self {
+        Self {
+            threshold,
+            nodes: HashMap::new(),
+        }
+    }
+
+    /// Returns the staleness threshold.
+    pub fn threshold(&self) -> Duration {
+        self.threshold
+    }
+
+    /// Updates the monitor with an originator table read now.
+    pub fn update(&mut self, originators: &[Originator]) -> Vec<LivenessEvent> {
+        self.update_at(Instant::now(), originators)
+    }
+
+    /// Updates the monitor with an originator table read at `at`, returning the
+    /// originators that went down or came back up, ordered by MAC address.
+    ///
+    /// Originators first seen already silent for longer than the threshold are
+    /// reported down as well.
+    pub fn update_at(&mut self, at: Instant, originators: &[Originator]) -> Vec<LivenessEvent> {
+        let mut events = Vec::new();
+        for o in originators {
+            let seen_at = at.checked_sub(o.last_seen.into()).unwrap_or(at);
+            let node = self.nodes.entry(o.originator).or_insert(Node {
+                seen_at,
+                down: false,
+            });
+            // One entry per router; the most recent one counts.
+            if seen_at <= node.seen_at {
+                continue;
+            }
+            let silent_for = seen_at.duration_since(node.seen_at);
+            node.seen_at = seen_at;
+            if node.down && at.saturating_duration_since(seen_at) <= self.threshold {
+                node.down = false;
+                events.push(LivenessEvent::Up {
+                    originator: o.originator,
+                    silent_for,
+                });
+            }
+        }
+
+        for (&originator, node) in &mut self.nodes {
+            let silent_for = at.saturating_duration_since(node.seen_at);
+            if !node.down && silent_for > self.threshold {
+                node.down = true;
+                events.push(LivenessEvent::Down {
+                    originator,
+                    silent_for,
+                });
+            }
+        }
+
+        events.sort_by_key(LivenessEvent::originator);
+        events
+    }
+
+    /// Returns `true` if `originator` is currently reported down.
+    pub fn is_down(&self, originator: MacAddr6) -> bool {
+        self.nodes.get(&originator).is_some_and(|node| node.down)
+    }
+
+    /// Returns the originators currently reported down.
+    pub fn down(&self) -> impl Iterator<Item = MacAddr6> + '_ {
+        self.nodes
+            .iter()
+            .filter(|(_, node)| node.down)
+            .map(|(&originator, _)| originator)
+    }
+
+    /// Forgets `originator`, e.g. a node decommissioned for good, so it is no
+    /// longer reported.
+    pub fn forget(&mut self, originator: MacAddr6) {
+        self.nodes.remove(&originator);
+    }
+}
+
+/// Reads the originator table of `mesh_if` every `interval`, starting now, and
+/// passes the events of `liveness` to `on_event`.
+///
+/// Runs until the originator table cannot be read; spawn it as a task to monitor
+/// in the background.
+///
+/// # Example
+///
+/// ```no_run
+/// use batman_robin::RobinClient;
+/// use batman_robin::liveness::{LivenessMonitor, monitor};
+/// use std::time::Duration;
+///
+/// # async fn example() -> Result<(), batman_robin::RobinError> {
+/// let client = RobinClient::new();
+/// let mut liveness = LivenessMonitor::new(Duration::from_secs(30));
+/// monitor(&client, "bat0", Duration::from_secs(5), &mut liveness, |event| {
+///     eprintln!("{}", event);
+/// })
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn monitor<T: MeshTransport>(
+    client: &RobinClient<T>,
+    mesh_if: &str,
+    interval: Duration,
+    liveness: &mut LivenessMonitor,
+    mut on_event: impl FnMut(LivenessEvent),
+) -> Result<(), RobinError> {
+    let mut ticks = time::interval(interval);
+    ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        ticks.tick().await;
+        let originators = client.originators(mesh_if).await?;
+        liveness
+            .update(&originators)
+            .into_iter()
+            .for_each(&mut on_event);
+    }
+}
@@ -0,0 +1,211 @@
+use crate::error::RobinError;
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::os::fd::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// A log file rotated by size and age, keeping a bounded history.
+///
+/// Lines are appended to `path`. Once it holds `max_bytes` or was started
+/// `max_age` ago, it is renamed to `path.1`, older files move up to `path.2`,
+/// `path.3` and so on, the ones beyond `keep` are deleted, and a new `path` is
+/// started. This keeps the history of long-running modes bounded, e.g. on
+/// embedded devices with small flash.
+///
+/// Output written with `println!` ends up in the file after
+/// [`capture_stdout`](LogFile::capture_stdout).
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::logfile::LogFile;
+/// use std::io::Write;
+///
+/// let dir = std::env::temp_dir().join(format!("robin-logfile-{}", std::process::id()));
+/// std::fs::create_dir_all(&dir).unwrap();
+/// let path = dir.join("monitor.log");
+///
+/// let mut log = LogFile::open(&path)?.max_bytes(10).keep(2);
+/// for i in 0..4 {
+///     writeln!(log, "event number {}", i).unwrap();
+/// }
+///
+/// // Every line filled a file; the current one and two rotated ones remain.
+/// assert_eq!(std::fs::read_to_string(&path).unwrap(), "event number 3\n");
+/// assert_eq!(std::fs::read_to_string(dir.join("monitor.log.2")).unwrap(), "event number 1\n");
+/// assert!(!dir.join("monitor.log.3").exists());
+/// # std::fs::remove_dir_all(&dir).unwrap();
+/// # Ok::<(), batman_robin::RobinError>(())
+/// ```
+#[derive(Debug)]
+pub struct LogFile {
+    path: PathBuf,
+    max_bytes: u64,
+    max_age: Option<Duration>,
+    keep: usize,
+    file: File,
+    started: Instant,
+    stdout: bool,
+    /// Whether the last write ended a line, so rotating does not split one.
+    line_start: bool,
+}
+
+impl LogFile {
+    /// Opens `path` for appending, creating it if needed.
+    ///
+    /// The file is rotated at 1 MiB, never by age, and 3 rotated files are kept.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, RobinError> {
+        let path = path.into();
+        let file = open_append(&path)?;
+        Ok(Self {
+            path,
+            max_bytes: 1024 * 1024,
+            max_age: None,
+            keep: 3,
+            file,
+            started: Instant::now(),
+            stdout: false,
+            line_start: true,
+        })
+    }
+
+    /// Rotates the file once it holds at least `bytes` bytes.
+    pub fn max_bytes(mut self, bytes: u64) -> Self {
+        self.max_bytes = bytes;
+        self
+    }
+
+    /// Rotates the file once it was started `age` ago, e.g. daily.
+    pub fn max_age(mut self, age: Duration) -> Self {
+        self.max_age = Some(age);
+        self
+    }
+
+    /// Keeps `files` rotated files besides the current one, deleting older ones.
+    pub fn keep(mut self, files: usize) -> Self {
+        self.keep = files;
+        self
+    }
+
+    /// Returns the path of the current file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Redirects the standard output of the process into the file, now and after
+    /// every rotation.
+    ///
+    /// Rotation only happens on writes to the `LogFile` itself or on
+    /// [`rotate_if_due`](LogFile::rotate_if_due), so call the latter regularly,
+    /// e.g. after every refresh.
+    pub fn capture_stdout(&mut self) -> Result<(), RobinError> {
+        self.stdout = true;
+        self.redirect_stdout()
+    }
+
+    /// Rotates the file if it is full or old enough.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(true)` if the file was rotated, or a `RobinError::Io` if rotating failed.
+    pub fn rotate_if_due(&mut self) -> Result<bool, RobinError> {
+        if self.stdout {
+            // Buffered output belongs to the file it was written for.
+            let _ = io::stdout().flush();
+        }
+
+        let len = self.file.metadata().map(|m| m.len()).unwrap_or_default();
+        let aged = self
+            .max_age
+            .is_some_and(|age| self.started.elapsed() >= age && len > 0);
+        if len < self.max_bytes && !aged {
+            return Ok(false);
+        }
+
+        self.rotate()?;
+        Ok(true)
+    }
+
+    /// Shifts the rotated files, moves the current file to `path.1` and starts a
+    /// new one.
+    fn rotate(&mut self) -> Result<(), RobinError> {
+        let rotated = |n: usize| {
+            let mut name = self.path.clone().into_os_string();
+            name.push(format!(".{}", n));
+            PathBuf::from(name)
+        };
+        let io_error = |context: String| move |source| RobinError::Io { context, source };
+
+        if self.keep == 0 {
+            fs::remove_file(&self.path).map_err(io_error(format!(
+                "Failed to delete {}",
+                self.path.display()
+            )))?;
+        } else {
+            let _ = fs::remove_file(rotated(self.keep));
+            for n in (1..self.keep).rev() {
+                let from = rotated(n);
+                if from.exists() {
+                    fs::rename(&from, rotated(n + 1))
+                        .map_err(io_error(format!("Failed to rotate {}", from.display())))?;
+                }
+            }
+            fs::rename(&self.path, rotated(1)).map_err(io_error(format!(
+                "Failed to rotate {}",
+                self.path.display()
+            )))?;
+        }
+
+        self.file = open_append(&self.path)?;
+        self.started = Instant::now();
+        if self.stdout {
+            self.redirect_stdout()?;
+        }
+        Ok(())
+    }
+
+    /// Points the standard output file descriptor at the current file.
+    fn redirect_stdout(&self) -> Result<(), RobinError> {
+        // SAFETY: both descriptors are open; dup2 atomically replaces stdout.
+        let ret = unsafe { libc::dup2(self.file.as_raw_fd(), libc::STDOUT_FILENO) };
+        if ret < 0 {
+            return Err(RobinError::Io {
+                context: format!("Failed to redirect stdout to {}", self.path.display()),
+                source: io::Error::last_os_error(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Rotates the file before a write starting a line if it is due.
+impl Write for LogFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.line_start {
+            self.rotate_if_due().map_err(io::Error::other)?;
+        }
+        let written = self.file.write(buf)?;
+        if written > 0 {
+            self.line_start = buf[written - 1] == b'\n';
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Opens `path` for appending, creating it if needed.
+fn open_append(path: &Path) -> Result<File, RobinError> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| RobinError::Io {
+            context: format!("Failed to open {}", path.display()),
+            source: e,
+        })
+}
@@ -1,3 +1,9 @@
+use crate::error::RobinError;
+use crate::model::Command;
+use crate::netlink;
+
+use std::str::FromStr;
+
 /// BATMAN-adv Netlink attributes (from `linux/uapi/batman_adv.h`).
 ///
 /// These attributes are used when communicating with the kernel via
@@ -5,7 +11,7 @@
 /// settings, metrics, and state information for mesh interfaces,
 /// translation tables, neighbors, gateways, and protocol features.
 #[repr(u16)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Attribute {
     /// Unspecified / placeholder attribute.
     BatadvAttrUnspec = 0,
@@ -73,7 +79,7 @@ pub enum Attribute {
     /// Flags for translation table entry (see `ClientFlags`).
     BatadvAttrTtFlags = 21,
 
-    /// Marks the "best" client in the translation table.
+    /// Flag marking the best (selected) entry, e.g. the best originator route.
     BatadvAttrFlagBest = 22,
 
     /// Last seen timestamp in milliseconds.
@@ -97,19 +103,19 @@ pub enum Attribute {
     /// Gateway MAC address for router.
     BatadvAttrRouter = 29,
 
-    /// Backbone link advertisement (BLA) own attribute.
+    /// Flag marking a bridge loop avoidance (BLA) entry as owned by this node.
     BatadvAttrBlaOwn = 30,
 
-    /// BLA MAC address.
+    /// BLA claimed client or backbone MAC address.
     BatadvAttrBlaAddress = 31,
 
     /// BLA VLAN ID.
     BatadvAttrBlaVid = 32,
 
-    /// BLA backbone flag.
+    /// BLA backbone gateway MAC address.
     BatadvAttrBlaBackbone = 33,
 
-    /// BLA CRC.
+    /// BLA CRC16 checksum of the claims of a backbone.
     BatadvAttrBlaCrc = 34,
 
     /// DAT IPv4 address.
@@ -136,10 +142,10 @@ pub enum Attribute {
     /// AP isolation enabled.
     BatadvAttrApIsolationEnabled = 42,
 
-    /// Isolation mark.
+    /// Firewall mark used to classify isolated clients.
     BatadvAttrIsolationMark = 43,
 
-    /// Isolation mask.
+    /// Mask applied to the firewall mark for isolated clients.
     BatadvAttrIsolationMask = 44,
 
     /// Bonding enabled flag.
@@ -196,3 +202,285 @@ impl From<Attribute> for u16 {
         a as u16
     }
 }
+
+impl Attribute {
+    /// Highest attribute known to this crate.
+    pub const MAX: Attribute = Attribute::BatadvAttrMulticastFanout;
+
+    /// All attributes, indexed by their numeric value.
+    pub const ALL: [Attribute; 61] = [
+        Attribute::BatadvAttrUnspec,
+        Attribute::BatadvAttrVersion,
+        Attribute::BatadvAttrAlgoName,
+        Attribute::BatadvAttrMeshIfindex,
+        Attribute::BatadvAttrMeshIfname,
+        Attribute::BatadvAttrMeshAddress,
+        Attribute::BatadvAttrHardIfindex,
+        Attribute::BatadvAttrHardIfname,
+        Attribute::BatadvAttrHardAddress,
+        Attribute::BatadvAttrOrigAddress,
+        Attribute::BatadvAttrTpMeterResult,
+        Attribute::BatadvAttrTpMeterTestTime,
+        Attribute::BatadvAttrTpMeterBytes,
+        Attribute::BatadvAttrTpMeterCookie,
+        Attribute::BatadvAttrPad,
+        Attribute::BatadvAttrActive,
+        Attribute::BatadvAttrTtAddress,
+        Attribute::BatadvAttrTtTtvn,
+        Attribute::BatadvAttrTtLastTtvn,
+        Attribute::BatadvAttrTtCrc32,
+        Attribute::BatadvAttrTtVid,
+        Attribute::BatadvAttrTtFlags,
+        Attribute::BatadvAttrFlagBest,
+        Attribute::BatadvAttrLastSeenMsecs,
+        Attribute::BatadvAttrNeighAddress,
+        Attribute::BatadvAttrTq,
+        Attribute::BatadvAttrThroughput,
+        Attribute::BatadvAttrBandwidthUp,
+        Attribute::BatadvAttrBandwidthDown,
+        Attribute::BatadvAttrRouter,
+        Attribute::BatadvAttrBlaOwn,
+        Attribute::BatadvAttrBlaAddress,
+        Attribute::BatadvAttrBlaVid,
+        Attribute::BatadvAttrBlaBackbone,
+        Attribute::BatadvAttrBlaCrc,
+        Attribute::BatadvAttrDatCacheIp4Address,
+        Attribute::BatadvAttrDatCacheHwAddress,
+        Attribute::BatadvAttrDatCacheVid,
+        Attribute::BatadvAttrMcastFlags,
+        Attribute::BatadvAttrMcastFlagsPriv,
+        Attribute::BatadvAttrVlanId,
+        Attribute::BatadvAttrAggregatedOgmsEnabled,
+        Attribute::BatadvAttrApIsolationEnabled,
+        Attribute::BatadvAttrIsolationMark,
+        Attribute::BatadvAttrIsolationMask,
+        Attribute::BatadvAttrBondingEnabled,
+        Attribute::BatadvAttrBridgeLoopAvoidanceEnabled,
+        Attribute::BatadvAttrDistributedArpTableEnabled,
+        Attribute::BatadvAttrFragmentationEnabled,
+        Attribute::BatadvAttrGwBandwidthDown,
+        Attribute::BatadvAttrGwBandwidthUp,
+        Attribute::BatadvAttrGwMode,
+        Attribute::BatadvAttrGwSelClass,
+        Attribute::BatadvAttrHopPenalty,
+        Attribute::BatadvAttrLogLevel,
+        Attribute::BatadvAttrMulticastForceFloodEnabled,
+        Attribute::BatadvAttrNetworkCodingEnabled,
+        Attribute::BatadvAttrOrigInterval,
+        Attribute::BatadvAttrElpInterval,
+        Attribute::BatadvAttrThroughputOverride,
+        Attribute::BatadvAttrMulticastFanout,
+    ];
+
+    /// Names of all attributes in `linux/uapi/batman_adv.h`, indexed by their numeric value.
+    pub const NAMES: [&'static str; 61] = [
+        "BATADV_ATTR_UNSPEC",
+        "BATADV_ATTR_VERSION",
+        "BATADV_ATTR_ALGO_NAME",
+        "BATADV_ATTR_MESH_IFINDEX",
+        "BATADV_ATTR_MESH_IFNAME",
+        "BATADV_ATTR_MESH_ADDRESS",
+        "BATADV_ATTR_HARD_IFINDEX",
+        "BATADV_ATTR_HARD_IFNAME",
+        "BATADV_ATTR_HARD_ADDRESS",
+        "BATADV_ATTR_ORIG_ADDRESS",
+        "BATADV_ATTR_TP_METER_RESULT",
+        "BATADV_ATTR_TP_METER_TEST_TIME",
+        "BATADV_ATTR_TP_METER_BYTES",
+        "BATADV_ATTR_TP_METER_COOKIE",
+        "BATADV_ATTR_PAD",
+        "BATADV_ATTR_ACTIVE",
+        "BATADV_ATTR_TT_ADDRESS",
+        "BATADV_ATTR_TT_TTVN",
+        "BATADV_ATTR_TT_LAST_TTVN",
+        "BATADV_ATTR_TT_CRC32",
+        "BATADV_ATTR_TT_VID",
+        "BATADV_ATTR_TT_FLAGS",
+        "BATADV_ATTR_FLAG_BEST",
+        "BATADV_ATTR_LAST_SEEN_MSECS",
+        "BATADV_ATTR_NEIGH_ADDRESS",
+        "BATADV_ATTR_TQ",
+        "BATADV_ATTR_THROUGHPUT",
+        "BATADV_ATTR_BANDWIDTH_UP",
+        "BATADV_ATTR_BANDWIDTH_DOWN",
+        "BATADV_ATTR_ROUTER",
+        "BATADV_ATTR_BLA_OWN",
+        "BATADV_ATTR_BLA_ADDRESS",
+        "BATADV_ATTR_BLA_VID",
+        "BATADV_ATTR_BLA_BACKBONE",
+        "BATADV_ATTR_BLA_CRC",
+        "BATADV_ATTR_DAT_CACHE_IP4ADDRESS",
+        "BATADV_ATTR_DAT_CACHE_HWADDRESS",
+        "BATADV_ATTR_DAT_CACHE_VID",
+        "BATADV_ATTR_MCAST_FLAGS",
+        "BATADV_ATTR_MCAST_FLAGS_PRIV",
+        "BATADV_ATTR_VLANID",
+        "BATADV_ATTR_AGGREGATED_OGMS_ENABLED",
+        "BATADV_ATTR_AP_ISOLATION_ENABLED",
+        "BATADV_ATTR_ISOLATION_MARK",
+        "BATADV_ATTR_ISOLATION_MASK",
+        "BATADV_ATTR_BONDING_ENABLED",
+        "BATADV_ATTR_BRIDGE_LOOP_AVOIDANCE_ENABLED",
+        "BATADV_ATTR_DISTRIBUTED_ARP_TABLE_ENABLED",
+        "BATADV_ATTR_FRAGMENTATION_ENABLED",
+        "BATADV_ATTR_GW_BANDWIDTH_DOWN",
+        "BATADV_ATTR_GW_BANDWIDTH_UP",
+        "BATADV_ATTR_GW_MODE",
+        "BATADV_ATTR_GW_SEL_CLASS",
+        "BATADV_ATTR_HOP_PENALTY",
+        "BATADV_ATTR_LOG_LEVEL",
+        "BATADV_ATTR_MULTICAST_FORCEFLOOD_ENABLED",
+        "BATADV_ATTR_NETWORK_CODING_ENABLED",
+        "BATADV_ATTR_ORIG_INTERVAL",
+        "BATADV_ATTR_ELP_INTERVAL",
+        "BATADV_ATTR_THROUGHPUT_OVERRIDE",
+        "BATADV_ATTR_MULTICAST_FANOUT",
+    ];
+
+    /// Returns the name of the attribute in `linux/uapi/batman_adv.h`, e.g.
+    /// `"BATADV_ATTR_MESH_IFINDEX"`.
+    pub fn name(self) -> &'static str {
+        Self::NAMES[self as usize]
+    }
+
+    /// Returns the payload type the kernel declares for the attribute: `"u8"`,
+    /// `"u16"`, `"u32"`, `"u64"`, `"flag"`, `"mac"`, `"string"` or `"bytes"`.
+    pub fn payload_type(self) -> &'static str {
+        netlink::get_attr_spec_map()[&self].name()
+    }
+
+    /// Returns the commands whose replies carry the attribute, see
+    /// [`Command::reply_attributes`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use batman_robin::{Attribute, Command};
+    ///
+    /// let commands = Attribute::BatadvAttrTq.returned_by();
+    /// assert!(commands.contains(&Command::BatadvCmdGetOriginators));
+    /// assert!(!commands.contains(&Command::BatadvCmdGetNeighbors));
+    /// assert_eq!(Attribute::BatadvAttrTq.payload_type(), "u8");
+    /// ```
+    pub fn returned_by(self) -> Vec<Command> {
+        Command::ALL
+            .into_iter()
+            .filter(|cmd| cmd.reply_attributes().contains(&self))
+            .collect()
+    }
+}
+
+/// Parses the name of an attribute in `linux/uapi/batman_adv.h`,
+/// case-insensitively and with or without the `BATADV_ATTR_` prefix.
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::Attribute;
+///
+/// assert_eq!("MESH_IFINDEX".parse::<Attribute>().ok(), Some(Attribute::BatadvAttrMeshIfindex));
+/// assert_eq!("mesh_ifindex".parse::<Attribute>().ok(), Some(Attribute::BatadvAttrMeshIfindex));
+/// assert_eq!(Attribute::BatadvAttrMeshIfindex.name(), "BATADV_ATTR_MESH_IFINDEX");
+/// assert!("NO_SUCH_ATTRIBUTE".parse::<Attribute>().is_err());
+/// ```
+impl FromStr for Attribute {
+    type Err = RobinError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.to_ascii_uppercase();
+        let name = name.strip_prefix("BATADV_ATTR_").unwrap_or(&name);
+        Self::NAMES
+            .iter()
+            .position(|n| n.strip_prefix("BATADV_ATTR_") == Some(name))
+            .map(|i| Self::ALL[i])
+            .ok_or_else(|| RobinError::Parse(format!("Error - unknown attribute '{}'", s)))
+    }
+}
+
+/// # Example
+///
+/// ```
+/// use batman_robin::Attribute;
+///
+/// assert_eq!(Attribute::try_from(25), Ok(Attribute::BatadvAttrTq));
+/// assert_eq!(Attribute::try_from(1000), Err(1000));
+/// ```
+impl TryFrom<u16> for Attribute {
+    type Error = u16;
+
+    /// Converts a raw attribute number, returning it back if it is unknown.
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        Attribute::ALL.get(value as usize).copied().ok_or(value)
+    }
+}
+
+// Compile-time check of the numeric values against `linux/uapi/batman_adv.h`.
+const _: () = {
+    assert!(Attribute::BatadvAttrUnspec as u16 == 0); // BATADV_ATTR_UNSPEC
+    assert!(Attribute::BatadvAttrVersion as u16 == 1); // BATADV_ATTR_VERSION
+    assert!(Attribute::BatadvAttrAlgoName as u16 == 2); // BATADV_ATTR_ALGO_NAME
+    assert!(Attribute::BatadvAttrMeshIfindex as u16 == 3); // BATADV_ATTR_MESH_IFINDEX
+    assert!(Attribute::BatadvAttrMeshIfname as u16 == 4); // BATADV_ATTR_MESH_IFNAME
+    assert!(Attribute::BatadvAttrMeshAddress as u16 == 5); // BATADV_ATTR_MESH_ADDRESS
+    assert!(Attribute::BatadvAttrHardIfindex as u16 == 6); // BATADV_ATTR_HARD_IFINDEX
+    assert!(Attribute::BatadvAttrHardIfname as u16 == 7); // BATADV_ATTR_HARD_IFNAME
+    assert!(Attribute::BatadvAttrHardAddress as u16 == 8); // BATADV_ATTR_HARD_ADDRESS
+    assert!(Attribute::BatadvAttrOrigAddress as u16 == 9); // BATADV_ATTR_ORIG_ADDRESS
+    assert!(Attribute::BatadvAttrTpMeterResult as u16 == 10); // BATADV_ATTR_TP_METER_RESULT
+    assert!(Attribute::BatadvAttrTpMeterTestTime as u16 == 11); // BATADV_ATTR_TP_METER_TEST_TIME
+    assert!(Attribute::BatadvAttrTpMeterBytes as u16 == 12); // BATADV_ATTR_TP_METER_BYTES
+    assert!(Attribute::BatadvAttrTpMeterCookie as u16 == 13); // BATADV_ATTR_TP_METER_COOKIE
+    assert!(Attribute::BatadvAttrPad as u16 == 14); // BATADV_ATTR_PAD
+    assert!(Attribute::BatadvAttrActive as u16 == 15); // BATADV_ATTR_ACTIVE
+    assert!(Attribute::BatadvAttrTtAddress as u16 == 16); // BATADV_ATTR_TT_ADDRESS
+    assert!(Attribute::BatadvAttrTtTtvn as u16 == 17); // BATADV_ATTR_TT_TTVN
+    assert!(Attribute::BatadvAttrTtLastTtvn as u16 == 18); // BATADV_ATTR_TT_LAST_TTVN
+    assert!(Attribute::BatadvAttrTtCrc32 as u16 == 19); // BATADV_ATTR_TT_CRC32
+    assert!(Attribute::BatadvAttrTtVid as u16 == 20); // BATADV_ATTR_TT_VID
+    assert!(Attribute::BatadvAttrTtFlags as u16 == 21); // BATADV_ATTR_TT_FLAGS
+    assert!(Attribute::BatadvAttrFlagBest as u16 == 22); // BATADV_ATTR_FLAG_BEST
+    assert!(Attribute::BatadvAttrLastSeenMsecs as u16 == 23); // BATADV_ATTR_LAST_SEEN_MSECS
+    assert!(Attribute::BatadvAttrNeighAddress as u16 == 24); // BATADV_ATTR_NEIGH_ADDRESS
+    assert!(Attribute::BatadvAttrTq as u16 == 25); // BATADV_ATTR_TQ
+    assert!(Attribute::BatadvAttrThroughput as u16 == 26); // BATADV_ATTR_THROUGHPUT
+    assert!(Attribute::BatadvAttrBandwidthUp as u16 == 27); // BATADV_ATTR_BANDWIDTH_UP
+    assert!(Attribute::BatadvAttrBandwidthDown as u16 == 28); // BATADV_ATTR_BANDWIDTH_DOWN
+    assert!(Attribute::BatadvAttrRouter as u16 == 29); // BATADV_ATTR_ROUTER
+    assert!(Attribute::BatadvAttrBlaOwn as u16 == 30); // BATADV_ATTR_BLA_OWN
+    assert!(Attribute::BatadvAttrBlaAddress as u16 == 31); // BATADV_ATTR_BLA_ADDRESS
+    assert!(Attribute::BatadvAttrBlaVid as u16 == 32); // BATADV_ATTR_BLA_VID
+    assert!(Attribute::BatadvAttrBlaBackbone as u16 == 33); // BATADV_ATTR_BLA_BACKBONE
+    assert!(Attribute::BatadvAttrBlaCrc as u16 == 34); // BATADV_ATTR_BLA_CRC
+    assert!(Attribute::BatadvAttrDatCacheIp4Address as u16 == 35); // BATADV_ATTR_DAT_CACHE_IP4ADDRESS
+    assert!(Attribute::BatadvAttrDatCacheHwAddress as u16 == 36); // BATADV_ATTR_DAT_CACHE_HWADDRESS
+    assert!(Attribute::BatadvAttrDatCacheVid as u16 == 37); // BATADV_ATTR_DAT_CACHE_VID
+    assert!(Attribute::BatadvAttrMcastFlags as u16 == 38); // BATADV_ATTR_MCAST_FLAGS
+    assert!(Attribute::BatadvAttrMcastFlagsPriv as u16 == 39); // BATADV_ATTR_MCAST_FLAGS_PRIV
+    assert!(Attribute::BatadvAttrVlanId as u16 == 40); // BATADV_ATTR_VLANID
+    assert!(Attribute::BatadvAttrAggregatedOgmsEnabled as u16 == 41); // BATADV_ATTR_AGGREGATED_OGMS_ENABLED
+    assert!(Attribute::BatadvAttrApIsolationEnabled as u16 == 42); // BATADV_ATTR_AP_ISOLATION_ENABLED
+    assert!(Attribute::BatadvAttrIsolationMark as u16 == 43); // BATADV_ATTR_ISOLATION_MARK
+    assert!(Attribute::BatadvAttrIsolationMask as u16 == 44); // BATADV_ATTR_ISOLATION_MASK
+    assert!(Attribute::BatadvAttrBondingEnabled as u16 == 45); // BATADV_ATTR_BONDING_ENABLED
+    assert!(Attribute::BatadvAttrBridgeLoopAvoidanceEnabled as u16 == 46); // BATADV_ATTR_BRIDGE_LOOP_AVOIDANCE_ENABLED
+    assert!(Attribute::BatadvAttrDistributedArpTableEnabled as u16 == 47); // BATADV_ATTR_DISTRIBUTED_ARP_TABLE_ENABLED
+    assert!(Attribute::BatadvAttrFragmentationEnabled as u16 == 48); // BATADV_ATTR_FRAGMENTATION_ENABLED
+    assert!(Attribute::BatadvAttrGwBandwidthDown as u16 == 49); // BATADV_ATTR_GW_BANDWIDTH_DOWN
+    assert!(Attribute::BatadvAttrGwBandwidthUp as u16 == 50); // BATADV_ATTR_GW_BANDWIDTH_UP
+    assert!(Attribute::BatadvAttrGwMode as u16 == 51); // BATADV_ATTR_GW_MODE
+    assert!(Attribute::BatadvAttrGwSelClass as u16 == 52); // BATADV_ATTR_GW_SEL_CLASS
+    assert!(Attribute::BatadvAttrHopPenalty as u16 == 53); // BATADV_ATTR_HOP_PENALTY
+    assert!(Attribute::BatadvAttrLogLevel as u16 == 54); // BATADV_ATTR_LOG_LEVEL
+    assert!(Attribute::BatadvAttrMulticastForceFloodEnabled as u16 == 55); // BATADV_ATTR_MULTICAST_FORCEFLOOD_ENABLED
+    assert!(Attribute::BatadvAttrNetworkCodingEnabled as u16 == 56); // BATADV_ATTR_NETWORK_CODING_ENABLED
+    assert!(Attribute::BatadvAttrOrigInterval as u16 == 57); // BATADV_ATTR_ORIG_INTERVAL
+    assert!(Attribute::BatadvAttrElpInterval as u16 == 58); // BATADV_ATTR_ELP_INTERVAL
+    assert!(Attribute::BatadvAttrThroughputOverride as u16 == 59); // BATADV_ATTR_THROUGHPUT_OVERRIDE
+    assert!(Attribute::BatadvAttrMulticastFanout as u16 == 60); // BATADV_ATTR_MULTICAST_FANOUT
+    assert!(Attribute::ALL.len() == Attribute::MAX as usize + 1);
+    let mut i = 0;
+    while i < Attribute::ALL.len() {
+        assert!(Attribute::ALL[i] as usize == i);
+        i += 1;
+    }
+};
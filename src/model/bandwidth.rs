@@ -0,0 +1,105 @@
+use crate::error::RobinError;
+
+use std::fmt;
+use std::str::FromStr;
+
+/// A bandwidth or throughput, with kbit/s precision.
+///
+/// The kernel reports and expects gateway bandwidths, gateway throughputs and the
+/// throughput override in units of 100 kbit/s; this type does the conversion so
+/// callers deal in kbit/s only.
+///
+/// Parsing accepts a `kbit` or `mbit` suffix in any case, and plain numbers in
+/// kbit/s. Formatting prints MBit with one decimal, like batctl; the alternate form
+/// (`{:#}`) leaves out the unit, e.g. to print a `down/up` pair:
+///
+/// ```
+/// use batman_robin::Bandwidth;
+///
+/// let down: Bandwidth = "10mbit".parse()?;
+/// let up: Bandwidth = "2500kbit".parse()?;
+/// assert_eq!(down, Bandwidth::from_mbps(10));
+/// assert_eq!(up.kbps(), 2500);
+/// assert_eq!("1.5MBit".parse::<Bandwidth>()?.kbps(), 1500);
+/// assert_eq!("800".parse::<Bandwidth>()?.kbps(), 800);
+/// assert!("fast".parse::<Bandwidth>().is_err());
+///
+/// assert_eq!(format!("{:#}/{}", down, up), "10.0/2.5 MBit");
+/// # Ok::<(), batman_robin::RobinError>(())
+/// ```
+///
+/// With the `config` feature, it (de)serializes as a plain number in kbit/s.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "config",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Bandwidth(u32);
+
+impl Bandwidth {
+    /// No bandwidth, e.g. an unset throughput override.
+    pub const ZERO: Self = Self(0);
+
+    /// Creates a bandwidth of `kbps` kbit/s.
+    pub const fn from_kbps(kbps: u32) -> Self {
+        Self(kbps)
+    }
+
+    /// Creates a bandwidth of `mbps` MBit/s.
+    pub const fn from_mbps(mbps: u32) -> Self {
+        Self(mbps.saturating_mul(1000))
+    }
+
+    /// Creates a bandwidth from a kernel value in units of 100 kbit/s.
+    pub(crate) const fn from_netlink(units: u32) -> Self {
+        Self(units.saturating_mul(100))
+    }
+
+    /// Returns the bandwidth in kbit/s.
+    pub const fn kbps(self) -> u32 {
+        self.0
+    }
+
+    /// Returns the bandwidth in MBit/s.
+    pub fn mbps(self) -> f64 {
+        f64::from(self.0) / 1000.0
+    }
+
+    /// Returns the kernel value in units of 100 kbit/s, rounded down.
+    pub(crate) const fn to_netlink(self) -> u32 {
+        self.0 / 100
+    }
+}
+
+impl FromStr for Bandwidth {
+    type Err = RobinError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || RobinError::Parse(format!("Invalid bandwidth '{}'", s));
+        let lower = s.trim().to_lowercase();
+
+        if let Some(mbit) = lower.strip_suffix("mbit") {
+            // MBit may be fractional, e.g. "2.5mbit".
+            let mbit: f64 = mbit.trim().parse().map_err(|_| invalid())?;
+            let kbps = (mbit * 1000.0).round();
+            if !(0.0..=f64::from(u32::MAX)).contains(&kbps) {
+                return Err(invalid());
+            }
+            return Ok(Self(kbps as u32));
+        }
+
+        let kbit = lower.strip_suffix("kbit").unwrap_or(&lower);
+        kbit.trim().parse().map(Self).map_err(|_| invalid())
+    }
+}
+
+impl fmt::Display for Bandwidth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.0 / 1000, (self.0 % 1000) / 100)?;
+        if !f.alternate() {
+            f.write_str(" MBit")?;
+        }
+        Ok(())
+    }
+}
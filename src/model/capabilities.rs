@@ -0,0 +1,79 @@
+use crate::error::RobinError;
+use crate::model::{Attribute, Command};
+
+use std::collections::BTreeSet;
+
+/// What the running batman-adv supports, as reported by
+/// [`RobinClient::capabilities`](crate::RobinClient::capabilities).
+///
+/// Read from the `batadv` Generic Netlink family: its command list
+/// (`CTRL_ATTR_OPS`) and highest attribute (`CTRL_ATTR_MAXATTR`). Older releases
+/// lack e.g. the VLAN or hard interface settings commands and newer attributes.
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::{Attribute, Capabilities, Command};
+///
+/// let caps = Capabilities::new(
+///     1,
+///     [Command::BatadvCmdGetMeshInfo, Command::BatadvCmdGetOriginators],
+///     Attribute::BatadvAttrHopPenalty as u16,
+/// );
+/// assert!(caps.supports(Command::BatadvCmdGetOriginators));
+/// assert!(!caps.supports(Command::BatadvCmdGetVlan));
+/// assert!(caps.require(Command::BatadvCmdGetVlan).is_err());
+/// assert!(!caps.supports_attr(Attribute::BatadvAttrMulticastFanout));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Capabilities {
+    /// Version of the `batadv` family.
+    pub family_version: u32,
+    /// Commands the kernel implements, among those known to this crate.
+    pub commands: BTreeSet<Command>,
+    /// Highest attribute type the kernel accepts.
+    pub max_attr: u16,
+}
+
+impl Capabilities {
+    /// Creates the capabilities of a kernel implementing `commands` and accepting
+    /// attributes up to `max_attr`.
+    pub fn new(
+        family_version: u32,
+        commands: impl IntoIterator<Item = Command>,
+        max_attr: u16,
+    ) -> Self {
+        Self {
+            family_version,
+            commands: commands.into_iter().collect(),
+            max_attr,
+        }
+    }
+
+    /// Assumes everything known to this crate is supported, e.g. for transports
+    /// that cannot probe the kernel.
+    pub fn all() -> Self {
+        Self::new(1, Command::ALL, Attribute::MAX as u16)
+    }
+
+    /// Returns `true` if the kernel implements `cmd`.
+    pub fn supports(&self, cmd: Command) -> bool {
+        self.commands.contains(&cmd)
+    }
+
+    /// Returns `true` if the kernel accepts `attr`.
+    pub fn supports_attr(&self, attr: Attribute) -> bool {
+        attr as u16 <= self.max_attr
+    }
+
+    /// Returns [`RobinError::Unsupported`] unless the kernel implements `cmd`.
+    pub fn require(&self, cmd: Command) -> Result<(), RobinError> {
+        match self.supports(cmd) {
+            true => Ok(()),
+            false => Err(RobinError::Unsupported {
+                feature: format!("{:?}", cmd),
+            }),
+        }
+    }
+}
@@ -16,6 +16,10 @@ bitflags! {
         const ROAM     = 1 << 1;
 
         /// Client is connected via Wi-Fi.
+        ///
+        /// The kernel sets it for clients learned on a wireless interface of the
+        /// bridge; see [`RobinClient::is_wireless`](crate::RobinClient::is_wireless)
+        /// for the hard interfaces of the mesh.
         const WIFI     = 1 << 4;
 
         /// Client is isolated (AP isolation is enabled).
@@ -0,0 +1,120 @@
+use crate::debugfs::VLAN_HAS_TAG;
+use crate::error::RobinError;
+use crate::model::{ClientFlags, Originator, TransglobalEntry, TranslocalEntry};
+
+use std::fmt;
+
+use macaddr::MacAddr6;
+
+/// How this node reaches a client, returned by
+/// [`RobinClient::path_to_client`](crate::RobinClient::path_to_client).
+///
+/// The path chains the translation table lookup (the originator serving the client)
+/// with the best route towards that originator (the next hop and outgoing
+/// interface).
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::{ClientPath, Originator, TransglobalEntry};
+///
+/// let client = "02:00:00:00:00:aa".parse().unwrap();
+/// let orig = "02:00:00:00:00:01".parse().unwrap();
+/// let next_hop = "02:00:00:00:00:02".parse().unwrap();
+///
+/// let mut entry = TransglobalEntry::new(client, orig);
+/// entry.is_best = true;
+/// let mut route = Originator::new(orig, next_hop, "wlan0");
+/// route.is_best = true;
+///
+/// let path = ClientPath::resolve(client, &[], &[entry], &[route]).unwrap();
+/// let route = path.route.as_ref().unwrap();
+/// assert_eq!(route.next_hop, next_hop);
+/// assert_eq!(route.outgoing_if, "wlan0");
+///
+/// // A client in no translation table has no path.
+/// assert!(ClientPath::resolve(next_hop, &[], &[], &[]).is_err());
+/// ```
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ClientPath {
+    /// MAC address of the client.
+    pub client: MacAddr6,
+
+    /// VLAN id of the translation table entry, as reported in `BATADV_ATTR_TT_VID`.
+    pub vid: u16,
+
+    /// The best route towards the originator serving the client, whose
+    /// `originator`, `next_hop` and `outgoing_if` make up the path; `None` if the
+    /// client is attached to this node.
+    pub route: Option<Originator>,
+}
+
+impl ClientPath {
+    /// Resolves the path to `client` from the local and global translation tables
+    /// and the originator table.
+    ///
+    /// A client in the local table is attached to this node. Otherwise the
+    /// originator serving it is the one of its best global entry, or else of an
+    /// entry not flagged `ROAM`.
+    ///
+    /// # Returns
+    /// - `Ok(ClientPath)` on success.
+    /// - `Err(RobinError::NotFound)` if no translation table knows `client`, or if
+    ///   there is no best route towards the originator serving it.
+    pub fn resolve(
+        client: MacAddr6,
+        local: &[TranslocalEntry],
+        global: &[TransglobalEntry],
+        originators: &[Originator],
+    ) -> Result<Self, RobinError> {
+        if let Some(entry) = local.iter().find(|e| e.client == client) {
+            return Ok(Self {
+                client,
+                vid: entry.vid,
+                route: None,
+            });
+        }
+
+        let rank = |e: &&TransglobalEntry| (e.is_best, !e.flags.contains(ClientFlags::ROAM));
+        let entry = global
+            .iter()
+            .filter(|e| e.client == client)
+            .max_by_key(rank)
+            .ok_or_else(|| {
+                RobinError::NotFound(format!(
+                    "Client {} is not in the translation tables",
+                    client
+                ))
+            })?;
+        let route = originators
+            .iter()
+            .find(|o| o.originator == entry.orig && o.is_best)
+            .ok_or_else(|| {
+                RobinError::NotFound(format!("No route to originator {}", entry.orig))
+            })?;
+
+        Ok(Self {
+            client,
+            vid: entry.vid,
+            route: Some(route.clone()),
+        })
+    }
+}
+
+impl fmt::Display for ClientPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "client {}", self.client)?;
+        if self.vid & VLAN_HAS_TAG != 0 {
+            write!(f, " on VLAN {}", self.vid & 0x0fff)?;
+        }
+        match &self.route {
+            Some(route) => write!(
+                f,
+                " is served by {} via {} on {}",
+                route.originator, route.next_hop, route.outgoing_if
+            ),
+            None => write!(f, " is attached to this node"),
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use crate::model::{ClientFlags, Originator, TransglobalEntry};
+
+use std::collections::BTreeMap;
+
+use macaddr::MacAddr6;
+
+/// Number of clients in the mesh, per originator and per VLAN, returned by
+/// [`RobinClient::client_summary`](crate::RobinClient::client_summary).
+///
+/// Each client of the global translation table is counted once, for the originator
+/// announcing it: the best entry, or else one not flagged `ROAM`. The counts per
+/// originator and per VLAN therefore add up to `clients`. Originators of the
+/// originator table announcing no client are counted with zero clients. The
+/// clients of this node itself are in the local translation table and not counted.
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::{ClientFlags, ClientSummary, TransglobalEntry};
+///
+/// let entry = |client: &str, orig: &str, flags| {
+///     let mut entry = TransglobalEntry::new(client.parse().unwrap(), orig.parse().unwrap());
+///     entry.flags = flags;
+///     entry
+/// };
+/// let summary = ClientSummary::new(
+///     &[
+///         entry("02:00:00:00:00:aa", "02:00:00:00:00:01", ClientFlags::empty()),
+///         entry("02:00:00:00:00:bb", "02:00:00:00:00:01", ClientFlags::empty()),
+///         // Roaming from 02:00:00:00:00:01 to 02:00:00:00:00:02.
+///         entry("02:00:00:00:00:cc", "02:00:00:00:00:01", ClientFlags::ROAM),
+///         entry("02:00:00:00:00:cc", "02:00:00:00:00:02", ClientFlags::empty()),
+///     ],
+///     &[],
+/// );
+///
+/// assert_eq!(summary.clients, 3);
+/// assert_eq!(summary.per_originator[&"02:00:00:00:00:01".parse().unwrap()], 2);
+/// assert_eq!(summary.per_originator[&"02:00:00:00:00:02".parse().unwrap()], 1);
+/// assert_eq!(summary.per_vid[&0], 3);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClientSummary {
+    /// Number of distinct clients, by MAC address and VID.
+    pub clients: usize,
+
+    /// Number of clients announced by each originator.
+    pub per_originator: BTreeMap<MacAddr6, usize>,
+
+    /// Number of clients per raw VID (`BATADV_ATTR_TT_VID`).
+    pub per_vid: BTreeMap<u16, usize>,
+}
+
+impl ClientSummary {
+    /// Summarizes the clients of the global translation table `entries`, listing
+    /// every originator of `originators`.
+    pub fn new(entries: &[TransglobalEntry], originators: &[Originator]) -> Self {
+        // The entry of each client announcing it, ranked by (best, not roaming).
+        let mut owners: BTreeMap<(MacAddr6, u16), &TransglobalEntry> = BTreeMap::new();
+        let rank = |e: &TransglobalEntry| (e.is_best, !e.flags.contains(ClientFlags::ROAM));
+        for e in entries {
+            owners
+                .entry((e.client, e.vid))
+                .and_modify(|owner| {
+                    if rank(e) > rank(owner) {
+                        *owner = e;
+                    }
+                })
+                .or_insert(e);
+        }
+
+        let mut summary = Self {
+            clients: owners.len(),
+            per_originator: originators.iter().map(|o| (o.originator, 0)).collect(),
+            ..Self::default()
+        };
+        for ((_, vid), owner) in owners {
+            *summary.per_originator.entry(owner.orig).or_default() += 1;
+            *summary.per_vid.entry(vid).or_default() += 1;
+        }
+        summary
+    }
+}
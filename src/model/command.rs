@@ -1,9 +1,14 @@
+use crate::error::RobinError;
+use crate::model::Attribute;
+
+use std::str::FromStr;
+
 /// BATMAN-adv supported generic netlink commands.
 ///
 /// These commands correspond to the BATMAN-adv netlink operations
 /// defined in `linux/uapi/batman_adv.h`.
 #[repr(u8)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Command {
     /// Unspecified / no operation.
     BatadvCmdUnspec = 0,
@@ -68,3 +73,271 @@ impl From<Command> for u8 {
         c as u8
     }
 }
+
+impl Command {
+    /// Highest command known to this crate.
+    pub const MAX: Command = Command::BatadvCmdSetVlan;
+
+    /// All commands, indexed by their numeric value.
+    pub const ALL: [Command; 19] = [
+        Command::BatadvCmdUnspec,
+        Command::BatadvCmdGetMeshInfo,
+        Command::BatadvCmdTpMeter,
+        Command::BatadvCmdTpMeterCancel,
+        Command::BatadvCmdGetRoutingAlgos,
+        Command::BatadvCmdGetHardif,
+        Command::BatadvCmdGetTranstableLocal,
+        Command::BatadvCmdGetTranstableGlobal,
+        Command::BatadvCmdGetOriginators,
+        Command::BatadvCmdGetNeighbors,
+        Command::BatadvCmdGetGateways,
+        Command::BatadvCmdGetBlaClaim,
+        Command::BatadvCmdGetBlaBackbone,
+        Command::BatadvCmdGetDatCache,
+        Command::BatadvCmdGetMcastFlags,
+        Command::BatadvCmdSetMesh,
+        Command::BatadvCmdSetHardif,
+        Command::BatadvCmdGetVlan,
+        Command::BatadvCmdSetVlan,
+    ];
+
+    /// Names of all commands in `linux/uapi/batman_adv.h`, indexed by their numeric value.
+    pub const NAMES: [&'static str; 19] = [
+        "BATADV_CMD_UNSPEC",
+        "BATADV_CMD_GET_MESH_INFO",
+        "BATADV_CMD_TP_METER",
+        "BATADV_CMD_TP_METER_CANCEL",
+        "BATADV_CMD_GET_ROUTING_ALGOS",
+        "BATADV_CMD_GET_HARDIF",
+        "BATADV_CMD_GET_TRANSTABLE_LOCAL",
+        "BATADV_CMD_GET_TRANSTABLE_GLOBAL",
+        "BATADV_CMD_GET_ORIGINATORS",
+        "BATADV_CMD_GET_NEIGHBORS",
+        "BATADV_CMD_GET_GATEWAYS",
+        "BATADV_CMD_GET_BLA_CLAIM",
+        "BATADV_CMD_GET_BLA_BACKBONE",
+        "BATADV_CMD_GET_DAT_CACHE",
+        "BATADV_CMD_GET_MCAST_FLAGS",
+        "BATADV_CMD_SET_MESH",
+        "BATADV_CMD_SET_HARDIF",
+        "BATADV_CMD_GET_VLAN",
+        "BATADV_CMD_SET_VLAN",
+    ];
+
+    /// Returns the name of the command in `linux/uapi/batman_adv.h`, e.g.
+    /// `"BATADV_CMD_GET_ORIGINATORS"`.
+    pub fn name(self) -> &'static str {
+        Self::NAMES[self as usize]
+    }
+
+    /// Returns the attributes the kernel may send in replies to the command.
+    ///
+    /// Which of them a reply actually carries depends on the routing algorithm
+    /// (e.g. `TQ` for BATMAN_IV, `THROUGHPUT` for BATMAN_V) and on the features
+    /// compiled into batman-adv. Commands changing settings only reply with an
+    /// acknowledgement.
+    pub fn reply_attributes(self) -> &'static [Attribute] {
+        use Attribute::*;
+
+        match self {
+            Command::BatadvCmdGetMeshInfo => &[
+                BatadvAttrVersion,
+                BatadvAttrAlgoName,
+                BatadvAttrMeshIfindex,
+                BatadvAttrMeshIfname,
+                BatadvAttrMeshAddress,
+                BatadvAttrHardIfindex,
+                BatadvAttrHardIfname,
+                BatadvAttrHardAddress,
+                BatadvAttrTtTtvn,
+                BatadvAttrBlaCrc,
+                BatadvAttrMcastFlags,
+                BatadvAttrMcastFlagsPriv,
+                BatadvAttrAggregatedOgmsEnabled,
+                BatadvAttrApIsolationEnabled,
+                BatadvAttrIsolationMark,
+                BatadvAttrIsolationMask,
+                BatadvAttrBondingEnabled,
+                BatadvAttrBridgeLoopAvoidanceEnabled,
+                BatadvAttrDistributedArpTableEnabled,
+                BatadvAttrFragmentationEnabled,
+                BatadvAttrGwBandwidthDown,
+                BatadvAttrGwBandwidthUp,
+                BatadvAttrGwMode,
+                BatadvAttrGwSelClass,
+                BatadvAttrHopPenalty,
+                BatadvAttrLogLevel,
+                BatadvAttrMulticastForceFloodEnabled,
+                BatadvAttrMulticastFanout,
+                BatadvAttrNetworkCodingEnabled,
+                BatadvAttrOrigInterval,
+            ],
+            Command::BatadvCmdTpMeter => &[
+                BatadvAttrOrigAddress,
+                BatadvAttrTpMeterResult,
+                BatadvAttrTpMeterTestTime,
+                BatadvAttrTpMeterBytes,
+                BatadvAttrTpMeterCookie,
+            ],
+            Command::BatadvCmdGetRoutingAlgos => &[BatadvAttrAlgoName],
+            Command::BatadvCmdGetHardif => &[
+                BatadvAttrMeshIfindex,
+                BatadvAttrHardIfindex,
+                BatadvAttrHardIfname,
+                BatadvAttrHardAddress,
+                BatadvAttrActive,
+                BatadvAttrHopPenalty,
+                BatadvAttrElpInterval,
+                BatadvAttrThroughputOverride,
+            ],
+            Command::BatadvCmdGetTranstableLocal => &[
+                BatadvAttrTtAddress,
+                BatadvAttrTtCrc32,
+                BatadvAttrTtVid,
+                BatadvAttrTtFlags,
+                BatadvAttrLastSeenMsecs,
+            ],
+            Command::BatadvCmdGetTranstableGlobal => &[
+                BatadvAttrTtAddress,
+                BatadvAttrOrigAddress,
+                BatadvAttrTtTtvn,
+                BatadvAttrTtLastTtvn,
+                BatadvAttrTtCrc32,
+                BatadvAttrTtVid,
+                BatadvAttrTtFlags,
+                BatadvAttrFlagBest,
+            ],
+            Command::BatadvCmdGetOriginators => &[
+                BatadvAttrOrigAddress,
+                BatadvAttrNeighAddress,
+                BatadvAttrHardIfindex,
+                BatadvAttrLastSeenMsecs,
+                BatadvAttrTq,
+                BatadvAttrThroughput,
+                BatadvAttrFlagBest,
+            ],
+            Command::BatadvCmdGetNeighbors => &[
+                BatadvAttrNeighAddress,
+                BatadvAttrHardIfindex,
+                BatadvAttrLastSeenMsecs,
+                BatadvAttrThroughput,
+            ],
+            Command::BatadvCmdGetGateways => &[
+                BatadvAttrOrigAddress,
+                BatadvAttrRouter,
+                BatadvAttrHardIfindex,
+                BatadvAttrHardIfname,
+                BatadvAttrTq,
+                BatadvAttrThroughput,
+                BatadvAttrBandwidthDown,
+                BatadvAttrBandwidthUp,
+                BatadvAttrFlagBest,
+            ],
+            Command::BatadvCmdGetBlaClaim => &[
+                BatadvAttrBlaOwn,
+                BatadvAttrBlaAddress,
+                BatadvAttrBlaVid,
+                BatadvAttrBlaBackbone,
+                BatadvAttrBlaCrc,
+            ],
+            Command::BatadvCmdGetBlaBackbone => &[
+                BatadvAttrBlaOwn,
+                BatadvAttrBlaBackbone,
+                BatadvAttrBlaVid,
+                BatadvAttrBlaCrc,
+                BatadvAttrLastSeenMsecs,
+            ],
+            Command::BatadvCmdGetDatCache => &[
+                BatadvAttrDatCacheIp4Address,
+                BatadvAttrDatCacheHwAddress,
+                BatadvAttrDatCacheVid,
+                BatadvAttrLastSeenMsecs,
+            ],
+            Command::BatadvCmdGetMcastFlags => &[BatadvAttrOrigAddress, BatadvAttrMcastFlags],
+            Command::BatadvCmdGetVlan => &[
+                BatadvAttrMeshIfindex,
+                BatadvAttrVlanId,
+                BatadvAttrApIsolationEnabled,
+            ],
+            Command::BatadvCmdUnspec
+            | Command::BatadvCmdTpMeterCancel
+            | Command::BatadvCmdSetMesh
+            | Command::BatadvCmdSetHardif
+            | Command::BatadvCmdSetVlan => &[],
+        }
+    }
+}
+
+/// Parses the name of a command in `linux/uapi/batman_adv.h`,
+/// case-insensitively and with or without the `BATADV_CMD_` prefix.
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::Command;
+///
+/// assert_eq!("GET_ORIGINATORS".parse::<Command>().ok(), Some(Command::BatadvCmdGetOriginators));
+/// assert_eq!("get_originators".parse::<Command>().ok(), Some(Command::BatadvCmdGetOriginators));
+/// assert_eq!(Command::BatadvCmdGetOriginators.name(), "BATADV_CMD_GET_ORIGINATORS");
+/// assert!("NO_SUCH_COMMAND".parse::<Command>().is_err());
+/// ```
+impl FromStr for Command {
+    type Err = RobinError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.to_ascii_uppercase();
+        let name = name.strip_prefix("BATADV_CMD_").unwrap_or(&name);
+        Self::NAMES
+            .iter()
+            .position(|n| n.strip_prefix("BATADV_CMD_") == Some(name))
+            .map(|i| Self::ALL[i])
+            .ok_or_else(|| RobinError::Parse(format!("Error - unknown command '{}'", s)))
+    }
+}
+
+/// # Example
+///
+/// ```
+/// use batman_robin::Command;
+///
+/// assert_eq!(Command::try_from(8), Ok(Command::BatadvCmdGetOriginators));
+/// assert_eq!(u8::from(Command::BatadvCmdGetOriginators), 8);
+/// assert_eq!(Command::try_from(200), Err(200));
+/// ```
+impl TryFrom<u8> for Command {
+    type Error = u8;
+
+    /// Converts a raw command number, returning it back if it is unknown.
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Command::ALL.get(value as usize).copied().ok_or(value)
+    }
+}
+
+// Compile-time check of the numeric values against `linux/uapi/batman_adv.h`.
+const _: () = {
+    assert!(Command::BatadvCmdUnspec as u8 == 0); // BATADV_CMD_UNSPEC
+    assert!(Command::BatadvCmdGetMeshInfo as u8 == 1); // BATADV_CMD_GET_MESH_INFO
+    assert!(Command::BatadvCmdTpMeter as u8 == 2); // BATADV_CMD_TP_METER
+    assert!(Command::BatadvCmdTpMeterCancel as u8 == 3); // BATADV_CMD_TP_METER_CANCEL
+    assert!(Command::BatadvCmdGetRoutingAlgos as u8 == 4); // BATADV_CMD_GET_ROUTING_ALGOS
+    assert!(Command::BatadvCmdGetHardif as u8 == 5); // BATADV_CMD_GET_HARDIF
+    assert!(Command::BatadvCmdGetTranstableLocal as u8 == 6); // BATADV_CMD_GET_TRANSTABLE_LOCAL
+    assert!(Command::BatadvCmdGetTranstableGlobal as u8 == 7); // BATADV_CMD_GET_TRANSTABLE_GLOBAL
+    assert!(Command::BatadvCmdGetOriginators as u8 == 8); // BATADV_CMD_GET_ORIGINATORS
+    assert!(Command::BatadvCmdGetNeighbors as u8 == 9); // BATADV_CMD_GET_NEIGHBORS
+    assert!(Command::BatadvCmdGetGateways as u8 == 10); // BATADV_CMD_GET_GATEWAYS
+    assert!(Command::BatadvCmdGetBlaClaim as u8 == 11); // BATADV_CMD_GET_BLA_CLAIM
+    assert!(Command::BatadvCmdGetBlaBackbone as u8 == 12); // BATADV_CMD_GET_BLA_BACKBONE
+    assert!(Command::BatadvCmdGetDatCache as u8 == 13); // BATADV_CMD_GET_DAT_CACHE
+    assert!(Command::BatadvCmdGetMcastFlags as u8 == 14); // BATADV_CMD_GET_MCAST_FLAGS
+    assert!(Command::BatadvCmdSetMesh as u8 == 15); // BATADV_CMD_SET_MESH
+    assert!(Command::BatadvCmdSetHardif as u8 == 16); // BATADV_CMD_SET_HARDIF
+    assert!(Command::BatadvCmdGetVlan as u8 == 17); // BATADV_CMD_GET_VLAN
+    assert!(Command::BatadvCmdSetVlan as u8 == 18); // BATADV_CMD_SET_VLAN
+    assert!(Command::ALL.len() == Command::MAX as usize + 1);
+    let mut i = 0;
+    while i < Command::ALL.len() {
+        assert!(Command::ALL[i] as usize == i);
+        i += 1;
+    }
+};
@@ -0,0 +1,45 @@
+use crate::model::LastSeen;
+
+use batman_robin_derive::FromBatadvAttrs;
+use macaddr::MacAddr6;
+use std::net::Ipv4Addr;
+
+/// A single entry in the batman-adv distributed ARP table (DAT) cache.
+///
+/// The DAT cache maps IPv4 addresses to the MAC addresses learned from ARP traffic
+/// crossing the mesh.
+#[derive(Debug, Clone, FromBatadvAttrs)]
+#[non_exhaustive]
+pub struct DatCacheEntry {
+    /// IPv4 address of the entry.
+    /// Corresponds to `BATADV_ATTR_DAT_CACHE_IP4ADDRESS`.
+    #[batadv(BatadvAttrDatCacheIp4Address)]
+    pub ip: Ipv4Addr,
+
+    /// MAC address the IPv4 address resolves to.
+    /// Corresponds to `BATADV_ATTR_DAT_CACHE_HWADDRESS`.
+    #[batadv(BatadvAttrDatCacheHwAddress)]
+    pub mac: MacAddr6,
+
+    /// VLAN ID of the entry.
+    /// Corresponds to `BATADV_ATTR_DAT_CACHE_VID`.
+    #[batadv(BatadvAttrDatCacheVid)]
+    pub vid: u16,
+
+    /// Time since the entry was last refreshed.
+    /// Corresponds to `BATADV_ATTR_LAST_SEEN_MSECS`.
+    #[batadv(BatadvAttrLastSeenMsecs, default)]
+    pub last_seen: LastSeen,
+}
+
+impl DatCacheEntry {
+    /// Creates an entry resolving `ip` to `mac`, untagged, last seen just now.
+    pub fn new(ip: Ipv4Addr, mac: MacAddr6) -> Self {
+        Self {
+            ip,
+            mac,
+            vid: 0,
+            last_seen: LastSeen::default(),
+        }
+    }
+}
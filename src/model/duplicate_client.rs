@@ -0,0 +1,87 @@
+use crate::debugfs::VLAN_HAS_TAG;
+use crate::model::{ClientFlags, TransglobalEntry};
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use macaddr::MacAddr6;
+
+/// A client MAC address announced by several originators within the same VLAN,
+/// reported by [`RobinClient::find_duplicate_clients`](crate::RobinClient::find_duplicate_clients).
+///
+/// Two devices sharing an address, e.g. cloned VMs or a misconfigured bridge, make
+/// the mesh deliver their traffic to one node or the other, so their connectivity
+/// flaps.
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::{ClientFlags, DuplicateClient, TransglobalEntry};
+///
+/// let entry = |client: &str, orig: &str, flags| {
+///     let mut entry = TransglobalEntry::new(client.parse().unwrap(), orig.parse().unwrap());
+///     entry.flags = flags;
+///     entry
+/// };
+/// let duplicates = DuplicateClient::find(&[
+///     entry("02:00:00:00:00:aa", "02:00:00:00:00:01", ClientFlags::empty()),
+///     entry("02:00:00:00:00:aa", "02:00:00:00:00:02", ClientFlags::empty()),
+///     // Roaming away from 02:00:00:00:00:03.
+///     entry("02:00:00:00:00:bb", "02:00:00:00:00:03", ClientFlags::ROAM),
+///     entry("02:00:00:00:00:bb", "02:00:00:00:00:04", ClientFlags::empty()),
+/// ]);
+///
+/// assert_eq!(duplicates.len(), 1);
+/// assert_eq!(duplicates[0].client.to_string(), "02:00:00:00:00:AA");
+/// assert_eq!(duplicates[0].originators.len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateClient {
+    /// MAC address of the client.
+    pub client: MacAddr6,
+    /// VLAN id of the entries, as reported in `BATADV_ATTR_TT_VID`.
+    pub vid: u16,
+    /// The originators announcing the client, ordered by MAC address.
+    pub originators: Vec<MacAddr6>,
+}
+
+impl DuplicateClient {
+    /// Finds the clients of the global translation table announced by more than one
+    /// originator within the same VLAN, ordered by VID and client.
+    ///
+    /// Entries flagged `ROAM` or `TEMP` are left out: they belong to a client
+    /// moving between nodes, which both announce it until the roaming completes.
+    pub fn find(entries: &[TransglobalEntry]) -> Vec<Self> {
+        let mut announced: BTreeMap<(u16, MacAddr6), BTreeSet<MacAddr6>> = BTreeMap::new();
+        for e in entries {
+            if e.flags.intersects(ClientFlags::ROAM | ClientFlags::TEMP) {
+                continue;
+            }
+            announced
+                .entry((e.vid, e.client))
+                .or_default()
+                .insert(e.orig);
+        }
+
+        announced
+            .into_iter()
+            .filter(|(_, originators)| originators.len() > 1)
+            .map(|((vid, client), originators)| Self {
+                client,
+                vid,
+                originators: originators.into_iter().collect(),
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for DuplicateClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let originators: Vec<String> = self.originators.iter().map(|o| o.to_string()).collect();
+        write!(f, "client {}", self.client)?;
+        if self.vid & VLAN_HAS_TAG != 0 {
+            write!(f, " on VLAN {}", self.vid & 0x0fff)?;
+        }
+        write!(f, " is announced by {}", originators.join(", "))
+    }
+}
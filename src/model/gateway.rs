@@ -1,3 +1,7 @@
+use crate::error::RobinError;
+use crate::model::Bandwidth;
+
+use batman_robin_derive::FromBatadvAttrs;
 use macaddr::MacAddr6;
 
 /// Represents a gateway in the batman-adv mesh.
@@ -5,38 +9,65 @@ use macaddr::MacAddr6;
 /// This struct contains information about a gateway node, including its MAC address,
 /// the router it is associated with, interface used, bandwidth, throughput, and
 /// quality metrics.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, FromBatadvAttrs)]
+#[non_exhaustive]
 pub struct Gateway {
     /// MAC address of the gateway (BATADV_ATTR_ORIG_ADDRESS).
+    #[batadv(BatadvAttrOrigAddress)]
     pub mac_addr: MacAddr6,
 
     /// MAC address of the associated router (BATADV_ATTR_ROUTER).
+    #[batadv(BatadvAttrRouter)]
     pub router: MacAddr6,
 
     /// Outgoing interface used to reach this gateway.
     /// Usually from BATADV_ATTR_HARD_IFNAME; if not available, falls back to interface index.
+    #[batadv(hard_ifname)]
     pub outgoing_if: String,
 
-    /// Optional downstream bandwidth in kbps (BATADV_ATTR_BANDWIDTH_DOWN).
-    pub bandwidth_down: Option<u32>,
+    /// Optional announced downstream bandwidth (BATADV_ATTR_BANDWIDTH_DOWN).
+    #[batadv(BatadvAttrBandwidthDown)]
+    pub bandwidth_down: Option<Bandwidth>,
 
-    /// Optional upstream bandwidth in kbps (BATADV_ATTR_BANDWIDTH_UP).
-    pub bandwidth_up: Option<u32>,
+    /// Optional announced upstream bandwidth (BATADV_ATTR_BANDWIDTH_UP).
+    #[batadv(BatadvAttrBandwidthUp)]
+    pub bandwidth_up: Option<Bandwidth>,
 
-    /// Optional throughput in kbps (BATADV_ATTR_THROUGHPUT).
-    pub throughput: Option<u32>,
+    /// Optional throughput towards the gateway (BATADV_ATTR_THROUGHPUT).
+    #[batadv(BatadvAttrThroughput)]
+    pub throughput: Option<Bandwidth>,
 
     /// Optional transmission quality (TQ) of the gateway (BATADV_ATTR_TQ).
+    #[batadv(BatadvAttrTq)]
     pub tq: Option<u8>,
 
     /// Whether this gateway is considered the best among alternatives (BATADV_ATTR_FLAG_BEST).
+    #[batadv(BatadvAttrFlagBest, flag)]
     pub is_best: bool,
 }
 
+impl Gateway {
+    /// Creates a gateway reached through `router` on `outgoing_if`, with no
+    /// announced bandwidth or metrics and not selected as best.
+    pub fn new(mac_addr: MacAddr6, router: MacAddr6, outgoing_if: impl Into<String>) -> Self {
+        Self {
+            mac_addr,
+            router,
+            outgoing_if: outgoing_if.into(),
+            bandwidth_down: None,
+            bandwidth_up: None,
+            throughput: None,
+            tq: None,
+            is_best: false,
+        }
+    }
+}
+
 /// Contains configuration information about a mesh gateway.
 ///
 /// This struct is used when querying or setting the gateway mode and associated parameters.
 #[derive(Debug)]
+#[non_exhaustive]
 pub struct GatewayInfo {
     /// Current gateway mode (BATADV_ATTR_GW_MODE).
     pub mode: GwMode,
@@ -44,18 +75,38 @@ pub struct GatewayInfo {
     /// Selection class for the gateway (BATADV_ATTR_GW_SEL_CLASS).
     pub sel_class: u32,
 
-    /// Downstream bandwidth in kbps (BATADV_ATTR_GW_BANDWIDTH_DOWN).
-    pub bandwidth_down: u32,
+    /// Announced downstream bandwidth (BATADV_ATTR_GW_BANDWIDTH_DOWN).
+    pub bandwidth_down: Bandwidth,
 
-    /// Upstream bandwidth in kbps (BATADV_ATTR_GW_BANDWIDTH_UP).
-    pub bandwidth_up: u32,
+    /// Announced upstream bandwidth (BATADV_ATTR_GW_BANDWIDTH_UP).
+    pub bandwidth_up: Bandwidth,
 
-    /// Routing algorithm in use (BATADV_ATTR_ALGO_NAME).
-    pub algo: String,
+    /// Routing algorithm in use (BATADV_ATTR_ALGO_NAME), `None` if the kernel does
+    /// not report it.
+    pub algo: Option<String>,
+}
+
+impl GatewayInfo {
+    /// Creates the gateway settings of a node in `mode`, with a selection class and
+    /// bandwidths of zero and no routing algorithm.
+    pub fn new(mode: GwMode) -> Self {
+        Self {
+            mode,
+            sel_class: 0,
+            bandwidth_down: Bandwidth::ZERO,
+            bandwidth_up: Bandwidth::ZERO,
+            algo: None,
+        }
+    }
 }
 
 /// Represents the mode of a batman-adv gateway.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "config",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
 pub enum GwMode {
     /// Gateway mode is turned off.
     Off,
@@ -66,6 +117,76 @@ pub enum GwMode {
     /// Node is operating as a gateway server.
     Server,
 
-    /// Unknown or unsupported mode.
-    Unknown,
+    /// Unknown or unsupported mode, with the raw BATADV_ATTR_GW_MODE value.
+    #[cfg_attr(feature = "config", serde(skip))]
+    Unknown(u8),
+}
+
+/// Gateway parameters parsed by [`parse_gw_param`]: the downstream and upstream
+/// bandwidths and the selection class, as taken by
+/// [`RobinClient::set_gw_mode`](crate::RobinClient::set_gw_mode).
+pub type GwParseResult = (Option<Bandwidth>, Option<Bandwidth>, Option<u32>);
+
+/// Parses a gateway parameter string according to the gateway mode and routing
+/// algorithm, like the parameter of `batctl gw_mode`.
+///
+/// # Arguments
+/// - `mode`: The `GwMode` to interpret the parameter for.
+/// - `param`: The parameter string, e.g., selection class or bandwidth (`"1000/500"`).
+/// - `algo`: The routing algorithm of the mesh interface, e.g. `"BATMAN_V"`.
+///
+/// # Returns
+/// - `Ok((Option<down>, Option<up>, Option<sel_class>))`
+///   - `down` and `up` are the bandwidths for `Server` mode; `up` defaults to a fifth of `down`.
+///   - `sel_class` is used for `Client` mode.
+///   - `None` values for `Off` mode.
+/// - `Err(RobinError::Parse)` if parsing fails or mode is `Unknown`.
+///
+/// # Notes
+/// - For server mode, the `param` can be `"down/up"`, each parsed as a [`Bandwidth`], i.e.
+///   in kbit unless suffixed with `"kbit"` or `"MBit"`.
+/// - For client mode, `param` is parsed as a selection class integer, except for
+///   `BATMAN_V`, where the selection class is a throughput parsed as a [`Bandwidth`]
+///   and sent in units of 100 kbit/s.
+///
+/// # Example
+/// ```
+/// use batman_robin::{Bandwidth, GwMode, parse_gw_param};
+///
+/// let (down, up, _) = parse_gw_param(GwMode::Server, "10mbit/2mbit", "BATMAN_IV")?;
+/// assert_eq!(down, Some(Bandwidth::from_mbps(10)));
+/// assert_eq!(up, Some(Bandwidth::from_mbps(2)));
+///
+/// assert_eq!(parse_gw_param(GwMode::Client, "20", "BATMAN_IV")?, (None, None, Some(20)));
+/// assert_eq!(parse_gw_param(GwMode::Client, "1.5mbit", "BATMAN_V")?, (None, None, Some(15)));
+/// assert!(parse_gw_param(GwMode::Unknown(7), "", "BATMAN_IV").is_err());
+/// # Ok::<(), batman_robin::RobinError>(())
+/// ```
+pub fn parse_gw_param(mode: GwMode, param: &str, algo: &str) -> Result<GwParseResult, RobinError> {
+    match mode {
+        GwMode::Off => Ok((None, None, None)),
+        GwMode::Client if algo == "BATMAN_V" => {
+            let sel_class: Bandwidth = param.parse()?;
+            Ok((None, None, Some(sel_class.to_netlink())))
+        }
+        GwMode::Client => {
+            let sel_class = param.parse::<u32>().map_err(|e| {
+                RobinError::Parse(format!("Invalid sel_class '{}': {:?}", param, e))
+            })?;
+            Ok((None, None, Some(sel_class)))
+        }
+        GwMode::Server => {
+            let mut parts = param.split('/');
+            let down: Bandwidth = parts.next().unwrap_or_default().parse()?;
+            let up = match parts.next() {
+                Some(up) => up.parse()?,
+                None => Bandwidth::from_kbps(down.kbps() / 5),
+            };
+
+            Ok((Some(down), Some(up), None))
+        }
+        GwMode::Unknown(_) => Err(RobinError::Parse(
+            "Cannot set unknown gateway mode".to_string(),
+        )),
+    }
 }
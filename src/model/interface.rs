@@ -1,12 +1,254 @@
+use crate::model::Bandwidth;
+
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+use macaddr::MacAddr6;
+
 /// Represents a network interface in the batman-adv mesh.
 ///
-/// This struct provides the interface name and whether it is currently active
-/// within the mesh.
+/// This struct provides the interface name, whether it is currently active
+/// within the mesh, and its link state, so an interface batman-adv considers
+/// inactive can be told apart from one whose cable is unplugged.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct Interface {
     /// Name of the interface, e.g., "eth0" or "bat0".
     pub ifname: String,
 
     /// Indicates whether this interface is currently active in the mesh.
     pub active: bool,
+
+    /// Operational state of the link (`IFLA_OPERSTATE`).
+    /// `OperState::Unknown` if the transport cannot report link states.
+    pub operstate: OperState,
+
+    /// Whether the link has a carrier, e.g. a plugged cable (`IFLA_CARRIER`).
+    /// `None` if the transport cannot report link states.
+    pub carrier: Option<bool>,
+
+    /// Traffic counters of the link (`IFLA_STATS64`).
+    /// `None` if the transport cannot report link statistics.
+    pub stats: Option<LinkStats>,
+}
+
+impl Interface {
+    /// Creates an inactive hard interface named `ifname` in an unknown state.
+    pub fn new(ifname: impl Into<String>) -> Self {
+        Self {
+            ifname: ifname.into(),
+            active: false,
+            operstate: OperState::Unknown,
+            carrier: None,
+            stats: None,
+        }
+    }
+}
+
+/// Operational state of a network interface, as defined in RFC 2863.
+///
+/// Corresponds to the `IF_OPER_*` values of `IFLA_OPERSTATE`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OperState {
+    /// The state is not known, e.g. for interfaces without carrier detection.
+    Unknown,
+    /// A component of the interface is missing.
+    NotPresent,
+    /// The interface is down.
+    Down,
+    /// The interface is down because a lower layer interface is down.
+    LowerLayerDown,
+    /// The interface is in test mode.
+    Testing,
+    /// The interface is waiting for an external event, e.g. Wi-Fi authentication.
+    Dormant,
+    /// The interface is up and can pass packets.
+    Up,
+}
+
+impl From<u8> for OperState {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => OperState::NotPresent,
+            2 => OperState::Down,
+            3 => OperState::LowerLayerDown,
+            4 => OperState::Testing,
+            5 => OperState::Dormant,
+            6 => OperState::Up,
+            _ => OperState::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for OperState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            OperState::Unknown => "UNKNOWN",
+            OperState::NotPresent => "NOTPRESENT",
+            OperState::Down => "DOWN",
+            OperState::LowerLayerDown => "LOWERLAYERDOWN",
+            OperState::Testing => "TESTING",
+            OperState::Dormant => "DORMANT",
+            OperState::Up => "UP",
+        })
+    }
+}
+
+/// Link state of a network interface, as reported by rtnetlink.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct LinkState {
+    /// Operational state (`IFLA_OPERSTATE`).
+    pub operstate: OperState,
+
+    /// Whether the link has a carrier (`IFLA_CARRIER`).
+    pub carrier: bool,
+}
+
+/// Traffic counters of a network interface, as reported by rtnetlink in
+/// `IFLA_STATS64`.
+///
+/// The counters are totals since the interface was created; sample them twice to
+/// get rates.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct LinkStats {
+    /// Bytes received (`rx_bytes`).
+    pub rx_bytes: u64,
+
+    /// Packets received (`rx_packets`).
+    pub rx_packets: u64,
+
+    /// Bytes sent (`tx_bytes`).
+    pub tx_bytes: u64,
+
+    /// Packets sent (`tx_packets`).
+    pub tx_packets: u64,
+}
+
+/// Settings and state of a single hard interface of a batman-adv mesh.
+///
+/// Returned by [`RobinClient::hardif_info`](crate::RobinClient::hardif_info), which
+/// issues `BATADV_CMD_GET_HARDIF` for one interface.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct HardifInfo {
+    /// Name of the hard interface.
+    /// Corresponds to `BATADV_ATTR_HARD_IFNAME`.
+    pub ifname: String,
+
+    /// Index of the hard interface.
+    /// Corresponds to `BATADV_ATTR_HARD_IFINDEX`.
+    pub ifindex: u32,
+
+    /// MAC address of the hard interface.
+    /// Corresponds to `BATADV_ATTR_HARD_ADDRESS`.
+    pub mac: Option<MacAddr6>,
+
+    /// Indicates whether this interface is currently active in the mesh.
+    /// Corresponds to `BATADV_ATTR_ACTIVE`.
+    pub active: bool,
+
+    /// Hop penalty applied to packets forwarded over this interface.
+    /// Corresponds to `BATADV_ATTR_HOP_PENALTY`.
+    pub hop_penalty: Option<u8>,
+
+    /// Interval between ELP messages in milliseconds.
+    /// Corresponds to `BATADV_ATTR_ELP_INTERVAL`. Only available in BATMAN_V mode.
+    pub elp_interval_ms: Option<u32>,
+
+    /// Throughput assumed for neighbors on this interface, zero if it is measured
+    /// instead.
+    /// Corresponds to `BATADV_ATTR_THROUGHPUT_OVERRIDE`. Only available in BATMAN_V mode.
+    pub throughput_override: Option<Bandwidth>,
+}
+
+impl HardifInfo {
+    /// Creates the information of an inactive hard interface `ifname` with index
+    /// `ifindex`, without address or tunables.
+    pub fn new(ifname: impl Into<String>, ifindex: u32) -> Self {
+        Self {
+            ifname: ifname.into(),
+            ifindex,
+            mac: None,
+            active: false,
+            hop_penalty: None,
+            elp_interval_ms: None,
+            throughput_override: None,
+        }
+    }
+}
+
+/// Reference to a network interface, by name or by index.
+///
+/// Indexes stay valid when interfaces are renamed, e.g. by predictable-naming races
+/// or moves between network namespaces, while names are easier to read. Operations
+/// taking an `impl Into<IfaceRef>` accept both:
+///
+/// ```
+/// use batman_robin::IfaceRef;
+///
+/// assert_eq!(IfaceRef::from("wlan0"), IfaceRef::Name("wlan0".to_string()));
+/// assert_eq!(IfaceRef::from(3), IfaceRef::Index(3));
+///
+/// // Parsing treats numbers as indexes, e.g. for command-line arguments.
+/// assert_eq!("3".parse::<IfaceRef>(), Ok(IfaceRef::Index(3)));
+/// assert_eq!("eth0".parse::<IfaceRef>(), Ok(IfaceRef::Name("eth0".to_string())));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum IfaceRef {
+    /// The interface name, e.g. `"wlan0"`.
+    Name(String),
+    /// The interface index (`ifindex`).
+    Index(u32),
+}
+
+impl From<&str> for IfaceRef {
+    fn from(name: &str) -> Self {
+        IfaceRef::Name(name.to_string())
+    }
+}
+
+impl From<&String> for IfaceRef {
+    fn from(name: &String) -> Self {
+        IfaceRef::Name(name.clone())
+    }
+}
+
+impl From<String> for IfaceRef {
+    fn from(name: String) -> Self {
+        IfaceRef::Name(name)
+    }
+}
+
+impl From<u32> for IfaceRef {
+    fn from(index: u32) -> Self {
+        IfaceRef::Index(index)
+    }
+}
+
+impl From<&IfaceRef> for IfaceRef {
+    fn from(iface: &IfaceRef) -> Self {
+        iface.clone()
+    }
+}
+
+impl FromStr for IfaceRef {
+    type Err = Infallible;
+
+    /// Parses a decimal number as an index and anything else as a name.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.parse() {
+            Ok(index) => IfaceRef::Index(index),
+            Err(_) => IfaceRef::Name(s.to_string()),
+        })
+    }
+}
+
+impl fmt::Display for IfaceRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IfaceRef::Name(name) => f.write_str(name),
+            IfaceRef::Index(index) => write!(f, "#{}", index),
+        }
+    }
 }
@@ -0,0 +1,126 @@
+use crate::model::{DatCacheEntry, Neighbor, Originator, TranslocalEntry};
+
+use std::fmt;
+use std::time::Duration;
+
+/// Time since a table entry was last seen, as reported by the kernel in milliseconds.
+///
+/// Formats for humans, with tenths of a second below a minute and whole seconds or
+/// minutes above:
+///
+/// ```
+/// use batman_robin::LastSeen;
+/// use std::time::Duration;
+///
+/// assert_eq!(LastSeen::from_millis(4_230).to_string(), "4.2s");
+/// assert_eq!(LastSeen::from_millis(92_000).to_string(), "1m 32s");
+/// assert_eq!(LastSeen::from_millis(7_380_000).to_string(), "2h 3m");
+///
+/// let last_seen = LastSeen::from_millis(4_230);
+/// assert!(last_seen.is_older_than(Duration::from_secs(4)));
+/// assert!(last_seen.is_within(Duration::from_secs(5)));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LastSeen(Duration);
+
+impl LastSeen {
+    /// Creates a last-seen time of `ms` milliseconds, e.g. `BATADV_ATTR_LAST_SEEN_MSECS`.
+    pub const fn from_millis(ms: u32) -> Self {
+        Self(Duration::from_millis(ms as u64))
+    }
+
+    /// Returns the time since the entry was last seen.
+    pub const fn as_duration(self) -> Duration {
+        self.0
+    }
+
+    /// Returns the time since the entry was last seen in milliseconds, saturating at
+    /// `u32::MAX`.
+    pub fn as_millis(self) -> u32 {
+        u32::try_from(self.0.as_millis()).unwrap_or(u32::MAX)
+    }
+
+    /// Returns `true` if the entry was seen more than `threshold` ago.
+    pub fn is_older_than(self, threshold: Duration) -> bool {
+        self.0 > threshold
+    }
+
+    /// Returns `true` if the entry was seen at most `max_age` ago.
+    pub fn is_within(self, max_age: Duration) -> bool {
+        self.0 <= max_age
+    }
+}
+
+impl From<Duration> for LastSeen {
+    fn from(duration: Duration) -> Self {
+        Self(duration)
+    }
+}
+
+impl From<LastSeen> for Duration {
+    fn from(last_seen: LastSeen) -> Self {
+        last_seen.0
+    }
+}
+
+impl fmt::Display for LastSeen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self.0.as_secs();
+        match secs {
+            0..60 => write!(f, "{}.{}s", secs, self.0.subsec_millis() / 100),
+            60..3600 => write!(f, "{}m {}s", secs / 60, secs % 60),
+            _ => write!(f, "{}h {}m", secs / 3600, secs % 3600 / 60),
+        }
+    }
+}
+
+/// Table entries carrying the time since they were last seen.
+///
+/// On sparse meshes, stale entries waiting to be purged can dominate a table;
+/// [`seen_within`](LastSeenEntry::seen_within) filters them out.
+///
+/// # Example
+///
+/// ```no_run
+/// # use batman_robin::{LastSeenEntry, RobinClient};
+/// # use std::time::Duration;
+/// # async fn example() -> Result<(), batman_robin::RobinError> {
+/// # let client = RobinClient::new();
+/// let mut originators = client.originators("bat0").await?;
+/// originators.retain(|o| o.seen_within(Duration::from_secs(10)));
+/// # Ok(())
+/// # }
+/// ```
+pub trait LastSeenEntry {
+    /// Time since the entry was last seen.
+    fn last_seen(&self) -> LastSeen;
+
+    /// Returns `true` if the entry was seen at most `max_age` ago.
+    fn seen_within(&self, max_age: Duration) -> bool {
+        self.last_seen().is_within(max_age)
+    }
+}
+
+impl LastSeenEntry for Originator {
+    fn last_seen(&self) -> LastSeen {
+        self.last_seen
+    }
+}
+
+impl LastSeenEntry for Neighbor {
+    fn last_seen(&self) -> LastSeen {
+        self.last_seen
+    }
+}
+
+impl LastSeenEntry for TranslocalEntry {
+    fn last_seen(&self) -> LastSeen {
+        self.last_seen
+    }
+}
+
+impl LastSeenEntry for DatCacheEntry {
+    fn last_seen(&self) -> LastSeen {
+        self.last_seen
+    }
+}
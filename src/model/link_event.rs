@@ -0,0 +1,78 @@
+use crate::model::LinkState;
+
+/// A change to a mesh interface or one of its hard interfaces, reported by
+/// [`RobinClient::link_events`](crate::RobinClient::link_events).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LinkEvent {
+    /// A mesh interface was created.
+    MeshAdded {
+        /// Index of the mesh interface.
+        ifindex: u32,
+        /// Name of the mesh interface.
+        ifname: String,
+    },
+
+    /// A mesh interface was destroyed. Its hard interfaces are reported as removed
+    /// first.
+    MeshRemoved {
+        /// Index of the mesh interface.
+        ifindex: u32,
+        /// Name of the mesh interface.
+        ifname: String,
+    },
+
+    /// An interface was added to a mesh interface.
+    HardifAdded {
+        /// Index of the hard interface.
+        ifindex: u32,
+        /// Name of the hard interface.
+        ifname: String,
+        /// Name of the mesh interface it was added to.
+        mesh_if: String,
+    },
+
+    /// An interface was removed from a mesh interface, or destroyed.
+    HardifRemoved {
+        /// Index of the hard interface.
+        ifindex: u32,
+        /// Name of the hard interface.
+        ifname: String,
+        /// Name of the mesh interface it was removed from.
+        mesh_if: String,
+    },
+
+    /// A mesh or hard interface was renamed.
+    Renamed {
+        /// Index of the interface.
+        ifindex: u32,
+        /// Previous name of the interface.
+        old: String,
+        /// New name of the interface.
+        new: String,
+    },
+
+    /// The operational state or carrier of a mesh or hard interface changed.
+    StateChanged {
+        /// Index of the interface.
+        ifindex: u32,
+        /// Name of the interface.
+        ifname: String,
+        /// The new link state.
+        state: LinkState,
+    },
+}
+
+impl LinkEvent {
+    /// Returns the index of the interface the event is about.
+    pub fn ifindex(&self) -> u32 {
+        match self {
+            LinkEvent::MeshAdded { ifindex, .. }
+            | LinkEvent::MeshRemoved { ifindex, .. }
+            | LinkEvent::HardifAdded { ifindex, .. }
+            | LinkEvent::HardifRemoved { ifindex, .. }
+            | LinkEvent::Renamed { ifindex, .. }
+            | LinkEvent::StateChanged { ifindex, .. } => *ifindex,
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use std::fmt;
+
+/// A likely misconfiguration of a mesh interface, reported by
+/// [`RobinClient::check`](crate::RobinClient::check).
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::MeshWarning;
+///
+/// let warning = MeshWarning::BridgedWithoutBla {
+///     mesh_if: "bat0".into(),
+///     ifname: "bat0".into(),
+///     bridge: "br-lan".into(),
+/// };
+/// assert_eq!(warning.code(), "bridged_without_bla");
+/// assert_eq!(warning.advice(), "robctl -m bat0 bridge_loop_avoidance 1");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MeshWarning {
+    /// The mesh interface, or one of its hard interfaces, is part of a bridge while
+    /// bridge loop avoidance is disabled. Nodes bridging the mesh into the same LAN
+    /// then forward each other's broadcasts back and forth.
+    BridgedWithoutBla {
+        /// Name of the mesh interface.
+        mesh_if: String,
+        /// Name of the bridged interface: the mesh interface or a hard interface.
+        ifname: String,
+        /// Name of the bridge.
+        bridge: String,
+    },
+}
+
+impl MeshWarning {
+    /// Returns a stable identifier of the kind of warning, e.g. for monitoring.
+    pub fn code(&self) -> &'static str {
+        match self {
+            MeshWarning::BridgedWithoutBla { .. } => "bridged_without_bla",
+        }
+    }
+
+    /// Returns the `robctl` command fixing the problem.
+    pub fn advice(&self) -> String {
+        match self {
+            MeshWarning::BridgedWithoutBla { mesh_if, .. } => {
+                format!("robctl -m {} bridge_loop_avoidance 1", mesh_if)
+            }
+        }
+    }
+}
+
+impl fmt::Display for MeshWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MeshWarning::BridgedWithoutBla {
+                mesh_if,
+                ifname,
+                bridge,
+            } if ifname == bridge => write!(
+                f,
+                "{} is a bridge attached to {} but bridge loop avoidance is disabled",
+                ifname, mesh_if
+            ),
+            MeshWarning::BridgedWithoutBla {
+                mesh_if,
+                ifname,
+                bridge,
+            } => write!(
+                f,
+                "{} is part of bridge {} but bridge loop avoidance is disabled on {}",
+                ifname, bridge, mesh_if
+            ),
+        }
+    }
+}
@@ -1,27 +1,55 @@
 //! Data models and abstractions for Robin.
 //!
 //! This module defines the core types used for representing batman-adv
-//! state, attributes, clients, gateways, interfaces, neighbors, originators,
-//! translation tables, and utility functions.
+//! state, attributes, bandwidths, kernel capabilities, clients, client paths, client summaries, DAT cache entries, duplicate clients, gateways, interfaces, link events, mesh warnings, neighbors, originators, wireless stations,
+//! translation tables, per-VLAN client listings, and utility functions.
 //!
 //! Each submodule focuses on a specific area of the mesh network model.
 
 mod attribute;
+mod bandwidth;
+mod capabilities;
 mod client_flag;
+mod client_path;
+mod client_summary;
 mod command;
+mod dat_cache;
+mod duplicate_client;
 mod gateway;
 mod interface;
+mod last_seen;
+mod link_event;
+mod mesh_warning;
 mod neighbor;
 mod originator;
+mod set_outcome;
+mod settings;
+mod station;
 mod transtable;
+mod tt_stats;
 mod utils;
+mod vlan_clients;
 
 pub use attribute::*;
+pub use bandwidth::*;
+pub use capabilities::*;
 pub use client_flag::*;
+pub use client_path::*;
+pub use client_summary::*;
 pub use command::*;
+pub use dat_cache::*;
+pub use duplicate_client::*;
 pub use gateway::*;
 pub use interface::*;
+pub use last_seen::*;
+pub use link_event::*;
+pub use mesh_warning::*;
 pub use neighbor::*;
 pub use originator::*;
+pub use set_outcome::*;
+pub use settings::*;
+pub use station::*;
 pub use transtable::*;
+pub use tt_stats::*;
 pub use utils::*;
+pub use vlan_clients::*;
@@ -1,3 +1,6 @@
+use crate::model::LastSeen;
+
+use batman_robin_derive::FromBatadvAttrs;
 use macaddr::MacAddr6;
 
 /// Represents a neighboring node in the batman-adv mesh network.
@@ -5,23 +8,62 @@ use macaddr::MacAddr6;
 /// A neighbor is a directly reachable node within the mesh. This struct provides
 /// information about its MAC address, the interface used to reach it, and metrics such as
 /// last seen time and optional throughput.
-#[derive(Debug, Clone)]
+///
+/// A neighbor reachable over several hard interfaces has one entry per link; the
+/// hard interface fields tell them apart and `is_best` marks the one carrying traffic.
+#[derive(Debug, Clone, FromBatadvAttrs)]
+#[non_exhaustive]
 pub struct Neighbor {
     /// MAC address of the neighbor.
     /// Corresponds to `BATADV_ATTR_NEIGH_ADDRESS`.
+    #[batadv(BatadvAttrNeighAddress)]
     pub neigh: MacAddr6,
 
     /// Outgoing interface name used to reach the neighbor.
     /// Falls back from `HARD_IFINDEX` if `HARD_IFNAME` is unavailable.
     /// Corresponds to `BATADV_ATTR_HARD_IFNAME`.
+    #[batadv(hard_ifname)]
     pub outgoing_if: String,
 
-    /// Time since the neighbor was last seen, in milliseconds.
+    /// Index of the local hard interface the neighbor is reached over.
+    /// Corresponds to `BATADV_ATTR_HARD_IFINDEX`.
+    #[batadv(BatadvAttrHardIfindex)]
+    pub hard_ifindex: Option<u32>,
+
+    /// MAC address of the local hard interface the neighbor is reached over.
+    /// Corresponds to `BATADV_ATTR_HARD_ADDRESS`, if the kernel reports it.
+    #[batadv(BatadvAttrHardAddress)]
+    pub hard_addr: Option<MacAddr6>,
+
+    /// Time since the neighbor was last seen.
     /// Corresponds to `BATADV_ATTR_LAST_SEEN_MSECS`.
-    pub last_seen_ms: u32,
+    #[batadv(BatadvAttrLastSeenMsecs)]
+    pub last_seen: LastSeen,
 
     /// Optional throughput towards this neighbor in kilobits per second.
     /// Corresponds to `BATADV_ATTR_THROUGHPUT`.
     /// Only available in BATMAN_V mode.
+    #[batadv(BatadvAttrThroughput)]
     pub throughput_kbps: Option<u32>,
+
+    /// Whether this link is the one selected as router towards the neighbor.
+    /// Corresponds to `BATADV_ATTR_FLAG_BEST`.
+    #[batadv(BatadvAttrFlagBest, flag)]
+    pub is_best: bool,
+}
+
+impl Neighbor {
+    /// Creates a neighbor reached on `outgoing_if`, last seen just now, with no
+    /// metrics and not selected as best.
+    pub fn new(neigh: MacAddr6, outgoing_if: impl Into<String>) -> Self {
+        Self {
+            neigh,
+            outgoing_if: outgoing_if.into(),
+            hard_ifindex: None,
+            hard_addr: None,
+            last_seen: LastSeen::default(),
+            throughput_kbps: None,
+            is_best: false,
+        }
+    }
 }
@@ -1,3 +1,6 @@
+use crate::model::LastSeen;
+
+use batman_robin_derive::FromBatadvAttrs;
 use macaddr::MacAddr6;
 
 /// Represents an originator node in the batman-adv mesh network.
@@ -5,33 +8,72 @@ use macaddr::MacAddr6;
 /// An originator is a node that advertises itself or forwards packets for other nodes.
 /// This struct contains information about the originator's MAC address, routing metrics,
 /// and the interface used to reach it.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, FromBatadvAttrs)]
+#[non_exhaustive]
 pub struct Originator {
     /// MAC address of the originator node.
     /// Corresponds to `BATADV_ATTR_ORIG_ADDRESS`.
+    #[batadv(BatadvAttrOrigAddress)]
     pub originator: MacAddr6,
 
     /// MAC address of the next hop towards the originator.
     /// Corresponds to `BATADV_ATTR_NEIGH_ADDRESS`.
+    #[batadv(BatadvAttrNeighAddress)]
     pub next_hop: MacAddr6,
 
     /// Outgoing interface name or index used to reach the originator.
     /// Corresponds to `BATADV_ATTR_HARD_IFNAME` (or the interface index).
+    #[batadv(hard_ifname)]
     pub outgoing_if: String,
 
-    /// Time since the originator was last seen, in milliseconds.
+    /// Time since the originator was last seen.
     /// Corresponds to `BATADV_ATTR_LAST_SEEN_MSECS`.
-    pub last_seen_ms: u32,
+    #[batadv(BatadvAttrLastSeenMsecs)]
+    pub last_seen: LastSeen,
 
     /// Optional TQ (link quality) metric towards this originator.
     /// Corresponds to `BATADV_ATTR_TQ`.
+    #[batadv(BatadvAttrTq)]
     pub tq: Option<u8>,
 
     /// Optional throughput value towards this originator.
     /// Corresponds to `BATADV_ATTR_THROUGHPUT`.
+    #[batadv(BatadvAttrThroughput)]
     pub throughput: Option<u32>,
 
     /// Indicates whether this originator is considered the best next-hop router.
     /// Corresponds to `BATADV_ATTR_ROUTER`.
+    #[batadv(BatadvAttrFlagBest, flag)]
     pub is_best: bool,
 }
+
+impl Originator {
+    /// Creates an originator reached through `next_hop` on `outgoing_if`, last seen
+    /// just now, with no metrics and not selected as best.
+    ///
+    /// New fields may be added to `Originator`; building entries with `new` and
+    /// setting the fields of interest keeps code such as test fixtures compiling.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use batman_robin::Originator;
+    ///
+    /// let mac = "02:00:00:00:00:01".parse().unwrap();
+    /// let mut originator = Originator::new(mac, mac, "wlan0");
+    /// originator.tq = Some(255);
+    /// originator.is_best = true;
+    /// assert_eq!(originator.outgoing_if, "wlan0");
+    /// ```
+    pub fn new(originator: MacAddr6, next_hop: MacAddr6, outgoing_if: impl Into<String>) -> Self {
+        Self {
+            originator,
+            next_hop,
+            outgoing_if: outgoing_if.into(),
+            last_seen: LastSeen::default(),
+            tq: None,
+            throughput: None,
+            is_best: false,
+        }
+    }
+}
@@ -0,0 +1,16 @@
+/// Result of a setter that skips the request when the setting already has the
+/// requested value, e.g. [`RobinClient::ensure_aggregation`](crate::RobinClient::ensure_aggregation).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetOutcome {
+    /// The setting was changed.
+    Changed,
+    /// The setting already had the requested value; nothing was sent to the kernel.
+    Unchanged,
+}
+
+impl SetOutcome {
+    /// Returns `true` if the setting was changed.
+    pub fn is_changed(self) -> bool {
+        self == SetOutcome::Changed
+    }
+}
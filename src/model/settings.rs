@@ -0,0 +1,110 @@
+use crate::model::{Bandwidth, GwMode};
+
+/// Mesh-level tunables of a BATMAN-adv mesh interface (`BATADV_CMD_GET_MESH`).
+///
+/// Read with [`RobinClient::get_settings`](crate::RobinClient::get_settings), where
+/// a field is `None` if the kernel does not report it, e.g. because the feature is
+/// not compiled in. Written with
+/// [`RobinClient::apply_settings`](crate::RobinClient::apply_settings), where `None`
+/// leaves the setting untouched.
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::MeshSettings;
+///
+/// // Only these settings are changed, all others are left as they are.
+/// let settings = MeshSettings {
+///     bridge_loop_avoidance: Some(true),
+///     hop_penalty: Some(30),
+///     ..Default::default()
+/// };
+/// assert!(settings.aggregation.is_none());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MeshSettings {
+    /// OGM aggregation (BATADV_ATTR_AGGREGATED_OGMS_ENABLED).
+    pub aggregation: Option<bool>,
+
+    /// AP isolation of the untagged clients (BATADV_ATTR_AP_ISOLATION_ENABLED).
+    pub ap_isolation: Option<bool>,
+
+    /// Bonding of multiple links to the same neighbor (BATADV_ATTR_BONDING_ENABLED).
+    pub bonding: Option<bool>,
+
+    /// Bridge loop avoidance (BATADV_ATTR_BRIDGE_LOOP_AVOIDANCE_ENABLED).
+    pub bridge_loop_avoidance: Option<bool>,
+
+    /// Distributed ARP table (BATADV_ATTR_DISTRIBUTED_ARP_TABLE_ENABLED).
+    pub distributed_arp_table: Option<bool>,
+
+    /// Fragmentation of packets exceeding the MTU (BATADV_ATTR_FRAGMENTATION_ENABLED).
+    pub fragmentation: Option<bool>,
+
+    /// Gateway mode (BATADV_ATTR_GW_MODE).
+    pub gw_mode: Option<GwMode>,
+
+    /// Advertised downstream bandwidth in server mode (BATADV_ATTR_GW_BANDWIDTH_DOWN).
+    pub gw_bandwidth_down: Option<Bandwidth>,
+
+    /// Advertised upstream bandwidth in server mode (BATADV_ATTR_GW_BANDWIDTH_UP).
+    pub gw_bandwidth_up: Option<Bandwidth>,
+
+    /// Gateway selection class in client mode (BATADV_ATTR_GW_SEL_CLASS).
+    pub gw_sel_class: Option<u32>,
+
+    /// Penalty applied to the link quality of forwarded OGMs (BATADV_ATTR_HOP_PENALTY).
+    pub hop_penalty: Option<u8>,
+
+    /// Firewall mark of isolated clients (BATADV_ATTR_ISOLATION_MARK).
+    pub isolation_mark: Option<u32>,
+
+    /// Bits of the firewall mark compared with `isolation_mark` (BATADV_ATTR_ISOLATION_MASK).
+    pub isolation_mask: Option<u32>,
+
+    /// Bitmask of the enabled debug log categories (BATADV_ATTR_LOG_LEVEL).
+    pub log_level: Option<u32>,
+
+    /// Flooding of all multicast traffic (BATADV_ATTR_MULTICAST_FORCEFLOOD_ENABLED).
+    pub multicast_forceflood: Option<bool>,
+
+    /// Maximum number of unicast copies of a multicast packet (BATADV_ATTR_MULTICAST_FANOUT).
+    pub multicast_fanout: Option<u32>,
+
+    /// Network coding (BATADV_ATTR_NETWORK_CODING_ENABLED).
+    pub network_coding: Option<bool>,
+
+    /// Interval between OGMs in milliseconds (BATADV_ATTR_ORIG_INTERVAL).
+    pub orig_interval_ms: Option<u32>,
+}
+
+/// Tunables of a hard interface attached to a mesh (`BATADV_CMD_GET_HARDIF`).
+///
+/// Read with [`RobinClient::get_hardif_settings`](crate::RobinClient::get_hardif_settings)
+/// and written with
+/// [`RobinClient::apply_hardif_settings`](crate::RobinClient::apply_hardif_settings),
+/// with the same meaning of `None` as for [`MeshSettings`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HardifSettings {
+    /// Interval between ELP packets in milliseconds, `BATMAN_V` (BATADV_ATTR_ELP_INTERVAL).
+    pub elp_interval_ms: Option<u32>,
+
+    /// Penalty applied to OGMs forwarded on this interface (BATADV_ATTR_HOP_PENALTY).
+    pub hop_penalty: Option<u8>,
+
+    /// Throughput used instead of the estimated one, zero if estimated, `BATMAN_V`
+    /// (BATADV_ATTR_THROUGHPUT_OVERRIDE).
+    pub throughput_override: Option<Bandwidth>,
+}
+
+/// Tunables of a VLAN on a mesh interface (`BATADV_CMD_GET_VLAN`).
+///
+/// Read with [`RobinClient::get_vlan_settings`](crate::RobinClient::get_vlan_settings)
+/// and written with
+/// [`RobinClient::apply_vlan_settings`](crate::RobinClient::apply_vlan_settings),
+/// with the same meaning of `None` as for [`MeshSettings`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VlanSettings {
+    /// AP isolation of the clients on this VLAN (BATADV_ATTR_AP_ISOLATION_ENABLED).
+    pub ap_isolation: Option<bool>,
+}
@@ -0,0 +1,22 @@
+/// Link state of a wireless station, as reported by `nl80211`.
+///
+/// For a neighbor reached over a wireless hard interface, this is the state of the
+/// radio link to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StationInfo {
+    /// Signal strength of the last received frame in dBm.
+    /// Corresponds to `NL80211_STA_INFO_SIGNAL`.
+    pub signal_dbm: Option<i8>,
+
+    /// Average signal strength in dBm.
+    /// Corresponds to `NL80211_STA_INFO_SIGNAL_AVG`.
+    pub signal_avg_dbm: Option<i8>,
+
+    /// Bitrate of the last transmitted frame in kilobits per second.
+    /// Corresponds to `NL80211_STA_INFO_TX_BITRATE`.
+    pub tx_bitrate_kbps: Option<u32>,
+
+    /// Time since the station was last active, in milliseconds.
+    /// Corresponds to `NL80211_STA_INFO_INACTIVE_TIME`.
+    pub inactive_ms: Option<u32>,
+}
@@ -1,4 +1,5 @@
-use crate::ClientFlags;
+use crate::model::{ClientFlags, LastSeen};
+use batman_robin_derive::FromBatadvAttrs;
 use macaddr::MacAddr6;
 
 /// A single entry in the batman-adv transglobal table (TT).
@@ -6,61 +7,163 @@ use macaddr::MacAddr6;
 /// The transglobal table contains information about clients known across the entire
 /// mesh network, including the client's MAC address, the originator node, and the
 /// route state.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, FromBatadvAttrs)]
+#[non_exhaustive]
 pub struct TransglobalEntry {
     /// MAC address of the client.
     /// Corresponds to `BATADV_ATTR_TT_ADDRESS`.
+    #[batadv(BatadvAttrTtAddress)]
     pub client: MacAddr6,
 
     /// MAC address of the originator announcing this client.
     /// Corresponds to `BATADV_ATTR_ORIG_ADDRESS`.
+    #[batadv(BatadvAttrOrigAddress)]
     pub orig: MacAddr6,
 
     /// VLAN ID associated with this client.
     /// Corresponds to `BATADV_ATTR_TT_VID`.
+    #[batadv(BatadvAttrTtVid)]
     pub vid: u16,
 
     /// Transglobal table version used for this client.
     /// Corresponds to `BATADV_ATTR_TT_TTVN`.
+    #[batadv(BatadvAttrTtTtvn)]
     pub ttvn: u8,
 
     /// Last known transglobal table version.
     /// Corresponds to `BATADV_ATTR_TT_LAST_TTVN`.
+    #[batadv(BatadvAttrTtLastTtvn)]
     pub last_ttvn: u8,
 
     /// Flags associated with the client, wrapped in `ClientFlags`.
     /// Corresponds to `BATADV_ATTR_TT_FLAGS`.
+    #[batadv(BatadvAttrTtFlags)]
     pub flags: ClientFlags,
 
     /// CRC32 checksum for this entry.
     /// Corresponds to `BATADV_ATTR_TT_CRC32`.
+    #[batadv(BatadvAttrTtCrc32)]
     pub crc32: u32,
 
     /// Indicates if this route is considered the best route to this client.
     /// Corresponds to `BATADV_ATTR_FLAG_BEST`.
+    #[batadv(BatadvAttrFlagBest, flag)]
     pub is_best: bool,
 }
 
+impl TransglobalEntry {
+    /// Creates an entry for `client` announced by `orig`, untagged, without flags
+    /// and not selected as best.
+    pub fn new(client: MacAddr6, orig: MacAddr6) -> Self {
+        Self {
+            client,
+            orig,
+            vid: 0,
+            ttvn: 0,
+            last_ttvn: 0,
+            flags: ClientFlags::empty(),
+            crc32: 0,
+            is_best: false,
+        }
+    }
+}
+
 /// A single entry in the batman-adv translocal table (TL).
 ///
 /// The translocal table contains clients directly known by the local node,
 /// including last-seen timestamps and flags.
+#[derive(Debug, Clone, FromBatadvAttrs)]
+#[non_exhaustive]
 pub struct TranslocalEntry {
     /// MAC address of the client.
+    #[batadv(BatadvAttrTtAddress)]
     pub client: MacAddr6,
 
     /// VLAN ID associated with this client.
+    #[batadv(BatadvAttrTtVid)]
     pub vid: u16,
 
     /// Flags associated with the client, wrapped in `ClientFlags`.
+    #[batadv(BatadvAttrTtFlags)]
     pub flags: ClientFlags,
 
     /// CRC32 checksum for this entry.
+    #[batadv(BatadvAttrTtCrc32)]
     pub crc32: u32,
 
-    /// Seconds since the last update for this entry.
-    pub last_seen_secs: u32,
+    /// Time since the last update for this entry.
+    /// Corresponds to `BATADV_ATTR_LAST_SEEN_MSECS`.
+    #[batadv(BatadvAttrLastSeenMsecs, default)]
+    pub last_seen: LastSeen,
+}
+
+impl TranslocalEntry {
+    /// Creates an entry for `client`, untagged, without flags, last seen just now.
+    pub fn new(client: MacAddr6) -> Self {
+        Self {
+            client,
+            vid: 0,
+            flags: ClientFlags::empty(),
+            crc32: 0,
+            last_seen: LastSeen::default(),
+        }
+    }
+}
+
+/// Translation table entries carrying client flags.
+///
+/// The predicates select clients in a given state, e.g. only the roaming ones during
+/// a handover investigation.
+///
+/// # Example
+///
+/// ```no_run
+/// # use batman_robin::{ClientFlagsEntry, RobinClient};
+/// # async fn example() -> Result<(), batman_robin::RobinError> {
+/// # let client = RobinClient::new();
+/// let mut entries = client.transglobal("bat0").await?;
+/// entries.retain(|e| e.is_roaming());
+/// # Ok(())
+/// # }
+/// ```
+pub trait ClientFlagsEntry {
+    /// Flags of the client.
+    fn flags(&self) -> ClientFlags;
+
+    /// Returns `true` if all of `flags` are set.
+    fn has_flags(&self, flags: ClientFlags) -> bool {
+        self.flags().contains(flags)
+    }
+
+    /// Returns `true` if the client is roaming ([`ClientFlags::ROAM`]).
+    fn is_roaming(&self) -> bool {
+        self.has_flags(ClientFlags::ROAM)
+    }
+
+    /// Returns `true` if the client is isolated ([`ClientFlags::ISOLA`]).
+    fn is_isolated(&self) -> bool {
+        self.has_flags(ClientFlags::ISOLA)
+    }
+
+    /// Returns `true` if the entry is temporary ([`ClientFlags::TEMP`]).
+    fn is_temp(&self) -> bool {
+        self.has_flags(ClientFlags::TEMP)
+    }
+
+    /// Returns `true` if the client is connected via Wi-Fi ([`ClientFlags::WIFI`]).
+    fn is_wifi(&self) -> bool {
+        self.has_flags(ClientFlags::WIFI)
+    }
+}
+
+impl ClientFlagsEntry for TransglobalEntry {
+    fn flags(&self) -> ClientFlags {
+        self.flags
+    }
+}
 
-    /// Milliseconds since the last update for this entry.
-    pub last_seen_msecs: u32,
+impl ClientFlagsEntry for TranslocalEntry {
+    fn flags(&self) -> ClientFlags {
+        self.flags
+    }
 }
@@ -0,0 +1,99 @@
+use crate::model::{ClientFlags, TransglobalEntry, TranslocalEntry};
+
+use std::collections::{BTreeMap, HashSet};
+
+use macaddr::MacAddr6;
+
+/// Summary of one translation table.
+///
+/// Large tables make the translation table announcements of a node grow until
+/// they no longer fit the MTU; the counts show where the entries come from.
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::{ClientFlags, TranslocalEntry, TtTableStats};
+///
+/// let entry = |mac: &str, vid, flags| {
+///     let mut entry = TranslocalEntry::new(mac.parse().unwrap());
+///     entry.vid = vid;
+///     entry.flags = flags;
+///     entry
+/// };
+/// let stats = TtTableStats::local(&[
+///     entry("02:00:00:00:00:01", 0, ClientFlags::WIFI),
+///     entry("02:00:00:00:00:02", 0x8005, ClientFlags::WIFI | ClientFlags::ROAM),
+/// ]);
+///
+/// assert_eq!(stats.entries, 2);
+/// assert_eq!(stats.per_vid[&0x8005], 1);
+/// assert_eq!(stats.per_flag["WIFI"], 2);
+/// assert_eq!(stats.per_flag["ROAM"], 1);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TtTableStats {
+    /// Number of entries in the table.
+    pub entries: usize,
+
+    /// Number of distinct clients, by MAC address and VID. Below `entries` in the
+    /// global table when several originators announce the same client.
+    pub clients: usize,
+
+    /// Number of entries per raw VID (`BATADV_ATTR_TT_VID`).
+    pub per_vid: BTreeMap<u16, usize>,
+
+    /// Number of entries with each client flag, by flag name, e.g. `"ROAM"`.
+    /// Flags set on no entry are left out.
+    pub per_flag: BTreeMap<&'static str, usize>,
+
+    /// Number of entries announced by each originator; empty for the local table.
+    pub per_originator: BTreeMap<MacAddr6, usize>,
+}
+
+impl TtTableStats {
+    /// Summarizes the local translation table.
+    pub fn local(entries: &[TranslocalEntry]) -> Self {
+        let mut stats = Self::default();
+        for e in entries {
+            stats.add(e.vid, e.flags);
+        }
+        stats.clients = distinct_clients(entries.iter().map(|e| (e.client, e.vid)));
+        stats
+    }
+
+    /// Summarizes the global translation table.
+    pub fn global(entries: &[TransglobalEntry]) -> Self {
+        let mut stats = Self::default();
+        for e in entries {
+            stats.add(e.vid, e.flags);
+            *stats.per_originator.entry(e.orig).or_default() += 1;
+        }
+        stats.clients = distinct_clients(entries.iter().map(|e| (e.client, e.vid)));
+        stats
+    }
+
+    /// Counts an entry of `vid` with `flags`.
+    fn add(&mut self, vid: u16, flags: ClientFlags) {
+        self.entries += 1;
+        *self.per_vid.entry(vid).or_default() += 1;
+        for (name, _) in flags.iter_names() {
+            *self.per_flag.entry(name).or_default() += 1;
+        }
+    }
+}
+
+/// Summary of the local and global translation tables of a mesh interface,
+/// returned by [`RobinClient::tt_stats`](crate::RobinClient::tt_stats).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TtStats {
+    /// Summary of the local translation table.
+    pub local: TtTableStats,
+
+    /// Summary of the global translation table.
+    pub global: TtTableStats,
+}
+
+/// Counts the distinct `(client, vid)` pairs.
+fn distinct_clients(clients: impl Iterator<Item = (MacAddr6, u16)>) -> usize {
+    clients.collect::<HashSet<_>>().len()
+}
@@ -1,8 +1,24 @@
+use crate::error::RobinError;
+use crate::model::Attribute;
+use crate::netlink;
+
+use macaddr::MacAddr6;
+
 /// Represents the possible types of values that can be sent as netlink attributes
 /// to the BATMAN-adv kernel module.
 ///
 /// This enum is used when constructing generic netlink messages for BATMAN-adv.
 /// Each variant corresponds to a different type of payload that the kernel expects.
+///
+/// Plain Rust values convert into the matching variant:
+///
+/// ```
+/// use batman_robin::AttrValueForSend;
+///
+/// assert!(matches!(AttrValueForSend::from(1u8), AttrValueForSend::U8(1)));
+/// assert!(matches!(AttrValueForSend::from(7u64), AttrValueForSend::U64(7)));
+/// assert!(matches!(AttrValueForSend::from("bat0"), AttrValueForSend::String(_)));
+/// ```
 #[derive(Debug, Clone)]
 pub enum AttrValueForSend {
     /// 8-bit unsigned integer attribute.
@@ -14,9 +30,430 @@ pub enum AttrValueForSend {
     /// 32-bit unsigned integer attribute.
     U32(u32),
 
+    /// 64-bit unsigned integer attribute (e.g. throughput meter byte counts).
+    U64(u64),
+
+    /// 32-bit signed integer attribute.
+    I32(i32),
+
+    /// Presence-only attribute with an empty payload.
+    Flag,
+
+    /// Raw bytes attribute.
+    Bytes(Vec<u8>),
+
+    /// UTF-8 string attribute.
+    String(String),
+}
+
+impl From<u8> for AttrValueForSend {
+    fn from(v: u8) -> Self {
+        AttrValueForSend::U8(v)
+    }
+}
+
+impl From<u16> for AttrValueForSend {
+    fn from(v: u16) -> Self {
+        AttrValueForSend::U16(v)
+    }
+}
+
+impl From<u32> for AttrValueForSend {
+    fn from(v: u32) -> Self {
+        AttrValueForSend::U32(v)
+    }
+}
+
+impl From<u64> for AttrValueForSend {
+    fn from(v: u64) -> Self {
+        AttrValueForSend::U64(v)
+    }
+}
+
+impl From<i32> for AttrValueForSend {
+    fn from(v: i32) -> Self {
+        AttrValueForSend::I32(v)
+    }
+}
+
+impl From<Vec<u8>> for AttrValueForSend {
+    fn from(v: Vec<u8>) -> Self {
+        AttrValueForSend::Bytes(v)
+    }
+}
+
+impl From<String> for AttrValueForSend {
+    fn from(v: String) -> Self {
+        AttrValueForSend::String(v)
+    }
+}
+
+impl From<&str> for AttrValueForSend {
+    fn from(v: &str) -> Self {
+        AttrValueForSend::String(v.to_string())
+    }
+}
+
+/// Represents a netlink attribute value received from the BATMAN-adv kernel module.
+///
+/// This is the receive-side counterpart of [`AttrValueForSend`]. The accessors
+/// widen integers where this is lossless, so callers do not need to know the
+/// exact width the kernel used for an attribute.
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::AttrValue;
+///
+/// let bytes = AttrValue::U64(1_000_000);
+/// assert_eq!(bytes.as_u64(), Some(1_000_000));
+/// assert_eq!(bytes.as_u32(), Some(1_000_000));
+///
+/// assert_eq!(AttrValue::U8(25).as_u64(), Some(25));
+/// assert!(AttrValue::Flag.as_flag());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttrValue {
+    /// 8-bit unsigned integer attribute.
+    U8(u8),
+
+    /// 16-bit unsigned integer attribute.
+    U16(u16),
+
+    /// 32-bit unsigned integer attribute.
+    U32(u32),
+
+    /// 64-bit unsigned integer attribute.
+    U64(u64),
+
+    /// 32-bit signed integer attribute.
+    I32(i32),
+
+    /// Presence-only attribute with an empty payload.
+    Flag,
+
+    /// MAC address attribute (e.g. `ORIG_ADDRESS`, `NEIGH_ADDRESS`, `TT_ADDRESS`).
+    Mac(MacAddr6),
+
     /// Raw bytes attribute.
     Bytes(Vec<u8>),
 
     /// UTF-8 string attribute.
     String(String),
+
+    /// Attribute set nested in an attribute flagged `NLA_F_NESTED`.
+    Nested(AttrObject),
+}
+
+impl AttrValue {
+    /// Decodes the raw payload of `attr` as received from the kernel.
+    ///
+    /// The payload is decoded as the type the kernel declares for `attr` (MAC
+    /// addresses become `AttrValue::Mac`) and its length is validated against it.
+    ///
+    /// # Returns
+    /// - `Ok(AttrValue)` if the payload is well-formed.
+    /// - `Err(RobinError::InvalidAttribute)` if it has the wrong length or encoding.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use batman_robin::{AttrValue, Attribute};
+    ///
+    /// let value = AttrValue::from_payload(Attribute::BatadvAttrOrigAddress, &[2, 0, 0, 0, 0, 1]).unwrap();
+    /// assert_eq!(value.as_mac().map(|m| m.to_string()), Some("02:00:00:00:00:01".into()));
+    ///
+    /// let tq = AttrValue::from_payload(Attribute::BatadvAttrTq, &[255]).unwrap();
+    /// assert_eq!(tq, AttrValue::U8(255));
+    ///
+    /// assert!(AttrValue::from_payload(Attribute::BatadvAttrTq, &[255, 0]).is_err());
+    ///
+    /// // Values encoded by `GenlAttrBuilder` decode back unchanged, on any host.
+    /// use batman_robin::{AttrValueForSend, GenlAttrBuilder};
+    ///
+    /// let mut attrs = GenlAttrBuilder::new();
+    /// attrs.add(Attribute::BatadvAttrMeshIfindex, AttrValueForSend::U32(0x0102_0304))?;
+    /// attrs.add(Attribute::BatadvAttrTtVid, AttrValueForSend::U16(0x8005))?;
+    /// attrs.add(Attribute::BatadvAttrTpMeterBytes, AttrValueForSend::U64(1 << 40))?;
+    /// let decoded: Vec<AttrValue> = attrs
+    ///     .build()
+    ///     .iter()
+    ///     .zip([
+    ///         Attribute::BatadvAttrMeshIfindex,
+    ///         Attribute::BatadvAttrTtVid,
+    ///         Attribute::BatadvAttrTpMeterBytes,
+    ///     ])
+    ///     .map(|(nla, attr)| AttrValue::from_payload(attr, nla.nla_payload().as_ref()))
+    ///     .collect::<Result<_, _>>()?;
+    /// assert_eq!(
+    ///     decoded,
+    ///     [AttrValue::U32(0x0102_0304), AttrValue::U16(0x8005), AttrValue::U64(1 << 40)]
+    /// );
+    /// # Ok::<(), batman_robin::RobinError>(())
+    /// ```
+    pub fn from_payload(attr: Attribute, payload: &[u8]) -> Result<Self, RobinError> {
+        netlink::auto_cast_value_with_spec(attr, payload)
+    }
+
+    /// Returns the value as a MAC address if it is a `Mac`.
+    pub fn as_mac(&self) -> Option<MacAddr6> {
+        match *self {
+            AttrValue::Mac(mac) => Some(mac),
+            _ => None,
+        }
+    }
+
+    /// Returns the nested attribute set if the value is `Nested`.
+    pub fn as_nested(&self) -> Option<&AttrObject> {
+        match self {
+            AttrValue::Nested(obj) => Some(obj),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as `u64` if it is an unsigned integer, or a non-negative `I32`.
+    pub fn as_u64(&self) -> Option<u64> {
+        match *self {
+            AttrValue::U8(v) => Some(v.into()),
+            AttrValue::U16(v) => Some(v.into()),
+            AttrValue::U32(v) => Some(v.into()),
+            AttrValue::U64(v) => Some(v),
+            AttrValue::I32(v) => u64::try_from(v).ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as `u32` if it is an integer that fits.
+    pub fn as_u32(&self) -> Option<u32> {
+        self.as_u64().and_then(|v| u32::try_from(v).ok())
+    }
+
+    /// Returns the value as `u16` if it is an integer that fits.
+    pub fn as_u16(&self) -> Option<u16> {
+        self.as_u64().and_then(|v| u16::try_from(v).ok())
+    }
+
+    /// Returns the value as `u8` if it is an integer that fits.
+    pub fn as_u8(&self) -> Option<u8> {
+        self.as_u64().and_then(|v| u8::try_from(v).ok())
+    }
+
+    /// Returns the value as `i32` if it is an integer that fits.
+    pub fn as_i32(&self) -> Option<i32> {
+        match *self {
+            AttrValue::I32(v) => Some(v),
+            _ => self.as_u64().and_then(|v| i32::try_from(v).ok()),
+        }
+    }
+
+    /// Returns `true` for a `Flag`, or for a non-zero `U8` as used by boolean settings.
+    pub fn as_flag(&self) -> bool {
+        matches!(self, AttrValue::Flag | AttrValue::U8(1..))
+    }
+
+    /// Returns the value as a string slice if it is a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            AttrValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the raw bytes of a `Bytes`, `Mac` or `String` value.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            AttrValue::Bytes(b) => Some(b),
+            AttrValue::Mac(mac) => Some(mac.as_bytes()),
+            AttrValue::String(s) => Some(s.as_bytes()),
+            _ => None,
+        }
+    }
+}
+
+/// The attributes of a single message received from the BATMAN-adv kernel module.
+///
+/// Attributes are kept in the order the kernel sent them. Attribute types not known
+/// to this crate (e.g. added by a newer kernel) are kept undecoded and are
+/// available through [`AttrObject::unknown`].
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::{AttrObject, AttrValue, Attribute};
+///
+/// let mut obj = AttrObject::new();
+/// obj.insert(Attribute::BatadvAttrTq, AttrValue::U8(255));
+///
+/// assert_eq!(obj.get(Attribute::BatadvAttrTq).and_then(AttrValue::as_u8), Some(255));
+/// assert!(!obj.contains(Attribute::BatadvAttrFlagBest));
+/// ```
+///
+/// The typed accessors fail with the attribute at fault, so parsers can use `?`:
+///
+/// ```
+/// use batman_robin::{AttrObject, AttrValue, Attribute, RobinError};
+///
+/// let mut obj = AttrObject::new();
+/// obj.insert(Attribute::BatadvAttrLastSeenMsecs, AttrValue::U32(420));
+/// obj.insert(Attribute::BatadvAttrHardIfname, AttrValue::String("wlan0".into()));
+///
+/// assert_eq!(obj.get_u32(Attribute::BatadvAttrLastSeenMsecs)?, 420);
+/// assert_eq!(obj.get_string(Attribute::BatadvAttrHardIfname)?, "wlan0");
+/// assert!(matches!(
+///     obj.get_mac(Attribute::BatadvAttrOrigAddress),
+///     Err(RobinError::MissingAttribute { .. })
+/// ));
+/// assert!(matches!(
+///     obj.get_u8(Attribute::BatadvAttrLastSeenMsecs),
+///     Err(RobinError::InvalidAttribute { .. })
+/// ));
+/// assert!(!obj.get_flag(Attribute::BatadvAttrFlagBest));
+/// # Ok::<(), RobinError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AttrObject {
+    values: Vec<(Attribute, AttrValue)>,
+    unknown: Vec<(u16, Vec<u8>)>,
+}
+
+impl AttrObject {
+    /// Creates an empty `AttrObject`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the value of `attr`, if present. If the kernel sent `attr` several
+    /// times, this is the first value.
+    pub fn get(&self, attr: Attribute) -> Option<&AttrValue> {
+        self.get_all(attr).next()
+    }
+
+    /// Iterates over all values of `attr`, in the order they were received.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use batman_robin::{AttrObject, AttrValue, Attribute};
+    ///
+    /// let mut obj = AttrObject::new();
+    /// obj.insert(Attribute::BatadvAttrVlanId, AttrValue::U16(0x8001));
+    /// obj.insert(Attribute::BatadvAttrVlanId, AttrValue::U16(0x8002));
+    ///
+    /// let vids: Vec<_> = obj
+    ///     .get_all(Attribute::BatadvAttrVlanId)
+    ///     .filter_map(AttrValue::as_u16)
+    ///     .collect();
+    /// assert_eq!(vids, [0x8001, 0x8002]);
+    /// assert_eq!(obj.get_u16(Attribute::BatadvAttrVlanId)?, 0x8001);
+    /// # Ok::<(), batman_robin::RobinError>(())
+    /// ```
+    pub fn get_all(&self, attr: Attribute) -> impl Iterator<Item = &AttrValue> {
+        self.values
+            .iter()
+            .filter(move |(a, _)| *a == attr)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns the value of `attr`, failing with `RobinError::MissingAttribute` if it
+    /// is absent.
+    pub fn require(&self, attr: Attribute) -> Result<&AttrValue, RobinError> {
+        self.get(attr).ok_or(RobinError::MissingAttribute { attr })
+    }
+
+    /// Returns `true` if `attr` is present.
+    pub fn contains(&self, attr: Attribute) -> bool {
+        self.get(attr).is_some()
+    }
+
+    /// Returns the value of `attr` as `u8`.
+    ///
+    /// Fails with `RobinError::MissingAttribute` if `attr` is absent, and with
+    /// `RobinError::InvalidAttribute` if it is not an integer that fits; the other
+    /// typed accessors behave alike.
+    pub fn get_u8(&self, attr: Attribute) -> Result<u8, RobinError> {
+        self.require_as(attr, AttrValue::as_u8)
+    }
+
+    /// Returns the value of `attr` as `u16`.
+    pub fn get_u16(&self, attr: Attribute) -> Result<u16, RobinError> {
+        self.require_as(attr, AttrValue::as_u16)
+    }
+
+    /// Returns the value of `attr` as `u32`.
+    pub fn get_u32(&self, attr: Attribute) -> Result<u32, RobinError> {
+        self.require_as(attr, AttrValue::as_u32)
+    }
+
+    /// Returns the value of `attr` as `u64`.
+    pub fn get_u64(&self, attr: Attribute) -> Result<u64, RobinError> {
+        self.require_as(attr, AttrValue::as_u64)
+    }
+
+    /// Returns the value of `attr` as a MAC address.
+    pub fn get_mac(&self, attr: Attribute) -> Result<MacAddr6, RobinError> {
+        self.require_as(attr, AttrValue::as_mac)
+    }
+
+    /// Returns the value of `attr` as a string slice.
+    pub fn get_string(&self, attr: Attribute) -> Result<&str, RobinError> {
+        self.require_as(attr, AttrValue::as_str)
+    }
+
+    /// Returns `true` if the flag `attr` is present, or if the boolean setting
+    /// `attr` is enabled. An absent attribute reads as `false`.
+    pub fn get_flag(&self, attr: Attribute) -> bool {
+        self.get(attr).is_some_and(AttrValue::as_flag)
+    }
+
+    /// Returns the value of `attr` converted by `convert`, failing like the typed
+    /// accessors.
+    fn require_as<'a, T>(
+        &'a self,
+        attr: Attribute,
+        convert: impl FnOnce(&'a AttrValue) -> Option<T>,
+    ) -> Result<T, RobinError> {
+        convert(self.require(attr)?).ok_or(RobinError::InvalidAttribute { attr })
+    }
+
+    /// Appends `attr` with the given value.
+    pub fn insert(&mut self, attr: Attribute, value: AttrValue) {
+        self.values.push((attr, value));
+    }
+
+    /// Iterates over the decoded attributes in the order they were received.
+    pub fn iter(&self) -> impl Iterator<Item = (Attribute, &AttrValue)> {
+        self.into_iter()
+    }
+
+    /// Returns the number of decoded attributes, counting repeated ones.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if no attribute was decoded.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the attributes whose type is not known to this crate, with their raw payload.
+    pub fn unknown(&self) -> &[(u16, Vec<u8>)] {
+        &self.unknown
+    }
+
+    /// Records an attribute whose type is not known to this crate.
+    pub(crate) fn insert_unknown(&mut self, attr: u16, payload: Vec<u8>) {
+        self.unknown.push((attr, payload));
+    }
+}
+
+impl<'a> IntoIterator for &'a AttrObject {
+    type Item = (Attribute, &'a AttrValue);
+    type IntoIter = std::iter::Map<
+        std::slice::Iter<'a, (Attribute, AttrValue)>,
+        fn(&'a (Attribute, AttrValue)) -> (Attribute, &'a AttrValue),
+    >;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.iter().map(|(attr, value)| (*attr, value))
+    }
 }
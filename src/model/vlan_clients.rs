@@ -0,0 +1,79 @@
+use crate::debugfs::VLAN_HAS_TAG;
+use crate::model::{TransglobalEntry, TranslocalEntry};
+
+use std::collections::{BTreeMap, HashSet};
+
+use macaddr::MacAddr6;
+
+/// The clients of one VLAN, from the local and global translation tables,
+/// returned by [`RobinClient::clients_for_vid`](crate::RobinClient::clients_for_vid).
+///
+/// On meshes where VLANs separate tenants, this lists the clients of one tenant
+/// and the nodes they are attached to.
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::{TransglobalEntry, TranslocalEntry, VlanClients};
+///
+/// let global = |client: &str, orig: &str, vid| {
+///     let mut entry = TransglobalEntry::new(client.parse().unwrap(), orig.parse().unwrap());
+///     entry.vid = vid;
+///     entry
+/// };
+/// let mut local = TranslocalEntry::new("02:00:00:00:00:aa".parse().unwrap());
+/// local.vid = 0x8005;
+///
+/// let clients = VlanClients::new(
+///     5,
+///     &[local],
+///     &[
+///         global("02:00:00:00:00:bb", "02:00:00:00:00:01", 0x8005),
+///         global("02:00:00:00:00:cc", "02:00:00:00:00:01", 0x8005),
+///         global("02:00:00:00:00:dd", "02:00:00:00:00:02", 0x8007),
+///     ],
+/// );
+///
+/// assert_eq!(clients.local.len(), 1);
+/// assert_eq!(clients.per_originator.len(), 1);
+/// assert_eq!(clients.per_originator[&"02:00:00:00:00:01".parse().unwrap()].len(), 2);
+/// assert_eq!(clients.clients(), 3);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct VlanClients {
+    /// VLAN id, without the `BATADV_VLAN_HAS_TAG` flag.
+    pub vid: u16,
+
+    /// Entries of the local translation table on the VLAN, i.e. the clients of this
+    /// node, including the mesh interface itself.
+    pub local: Vec<TranslocalEntry>,
+
+    /// Entries of the global translation table on the VLAN, grouped by the
+    /// originator announcing them. A roaming client is listed under both the old
+    /// and the new originator.
+    pub per_originator: BTreeMap<MacAddr6, Vec<TransglobalEntry>>,
+}
+
+impl VlanClients {
+    /// Keeps the entries of `local` and `global` on VLAN `vid` (0 to 4095).
+    pub fn new(vid: u16, local: &[TranslocalEntry], global: &[TransglobalEntry]) -> Self {
+        let raw = vid | VLAN_HAS_TAG;
+        let mut per_originator: BTreeMap<MacAddr6, Vec<TransglobalEntry>> = BTreeMap::new();
+        for e in global.iter().filter(|e| e.vid == raw) {
+            per_originator.entry(e.orig).or_default().push(e.clone());
+        }
+
+        Self {
+            vid,
+            local: local.iter().filter(|e| e.vid == raw).cloned().collect(),
+            per_originator,
+        }
+    }
+
+    /// Returns the number of distinct clients on the VLAN, local and global.
+    pub fn clients(&self) -> usize {
+        let global = self.per_originator.values().flatten().map(|e| e.client);
+        let local = self.local.iter().map(|e| e.client);
+        local.chain(global).collect::<HashSet<_>>().len()
+    }
+}
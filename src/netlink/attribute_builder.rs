@@ -11,7 +11,25 @@ use crate::model::{AttrValueForSend, Attribute};
 ///
 /// This is a convenience wrapper over `neli`’s `GenlBuffer`, `NlattrBuilder`,
 /// and `AttrTypeBuilder`, handling conversion from Rust types to netlink payloads.
-pub(crate) struct GenlAttrBuilder {
+///
+/// It is also the way to pass attributes to [`RobinClient::raw_dump`] and
+/// [`RobinClient::raw_set`].
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::{Attribute, GenlAttrBuilder};
+///
+/// let mut attrs = GenlAttrBuilder::new();
+/// attrs.add(Attribute::BatadvAttrMeshIfindex, 4u32)?;
+/// attrs.add(Attribute::BatadvAttrHopPenalty, 30u8)?;
+/// # Ok::<(), batman_robin::RobinError>(())
+/// ```
+///
+/// [`RobinClient::raw_dump`]: crate::RobinClient::raw_dump
+/// [`RobinClient::raw_set`]: crate::RobinClient::raw_set
+#[derive(Clone, Debug, Default)]
+pub struct GenlAttrBuilder {
     buf: GenlBuffer<u16, Buffer>,
 }
 
@@ -20,7 +38,7 @@ impl GenlAttrBuilder {
     ///
     /// # Returns
     /// A `GenlAttrBuilder` ready to have attributes added.
-    pub(crate) fn new() -> Self {
+    pub fn new() -> Self {
         Self {
             buf: GenlBuffer::new(),
         }
@@ -28,33 +46,42 @@ impl GenlAttrBuilder {
 
     /// Adds an attribute to the builder.
     ///
+    /// Integers are encoded in their natural width and host byte order, strings are
+    /// NUL-terminated and `AttrValueForSend::Flag` emits a presence-only attribute:
+    /// a bare 4-byte header (`nla_len == 4`) without payload, as the kernel expects
+    /// for `NLA_FLAG` attributes used as semaphores in requests.
+    ///
     /// # Parameters
     /// - `attr`: The `Attribute` enum specifying which BATMAN-adv attribute to set.
-    /// - `value`: The value to associate with the attribute, as `AttrValueForSend`.
+    /// - `value`: The value to associate with the attribute, as `AttrValueForSend` or
+    ///   any type converting into it.
     ///
     /// # Returns
     /// - `Ok(())` on success.
     /// - `Err(RobinError)` if building the netlink attribute fails.
-    pub(crate) fn add(
+    pub fn add(
         &mut self,
         attr: Attribute,
-        value: AttrValueForSend,
+        value: impl Into<AttrValueForSend>,
     ) -> Result<(), RobinError> {
         let attr_type = AttrTypeBuilder::default()
             .nla_type(attr.into())
             .build()
             .map_err(|e| RobinError::Netlink(format!("Failed to build AttrType: {:?}", e)))?;
 
-        let attr_payload = match value {
+        let attr_payload = match value.into() {
             AttrValueForSend::String(s) => {
                 let mut b = s.into_bytes();
                 b.push(0);
                 b
             }
             AttrValueForSend::Bytes(b) => b,
-            AttrValueForSend::U32(v) => v.to_le_bytes().to_vec(),
-            AttrValueForSend::U16(v) => v.to_le_bytes().to_vec(),
+            AttrValueForSend::U64(v) => v.to_ne_bytes().to_vec(),
+            AttrValueForSend::I32(v) => v.to_ne_bytes().to_vec(),
+            AttrValueForSend::U32(v) => v.to_ne_bytes().to_vec(),
+            AttrValueForSend::U16(v) => v.to_ne_bytes().to_vec(),
             AttrValueForSend::U8(v) => vec![v],
+            AttrValueForSend::Flag => Vec::new(),
         };
 
         let attr = NlattrBuilder::default()
@@ -71,8 +98,9 @@ impl GenlAttrBuilder {
     ///
     /// # Returns
     /// A `GenlBuffer<u16, Buffer>` containing all added attributes, ready
-    /// to be sent in a Generic Netlink message.
-    pub(crate) fn build(self) -> GenlBuffer<u16, Buffer> {
+    /// to be sent in a Generic Netlink message, e.g. one built with
+    /// [`build_genl_msg`](crate::build_genl_msg).
+    pub fn build(self) -> GenlBuffer<u16, Buffer> {
         self.buf
     }
 }
@@ -0,0 +1,168 @@
+use crate::error::RobinError;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use neli::consts::genl::{CtrlAttr, CtrlAttrMcastGrp, CtrlCmd, Index};
+use neli::consts::nl::{GenlId, NlmF};
+use neli::consts::socket::NlFamily;
+use neli::err::RouterError;
+use neli::genl::{AttrTypeBuilder, Genlmsghdr, GenlmsghdrBuilder, NlattrBuilder, NoUserHeader};
+use neli::nl::NlPayload;
+use neli::router::asynchronous::NlRouter;
+use neli::types::GenlBuffer;
+use neli::utils::Groups;
+
+/// `CTRL_ATTR_OP_ID`, the command of an entry of `CTRL_ATTR_OPS`.
+const CTRL_ATTR_OP_ID: u16 = libc::CTRL_ATTR_OP_ID as u16;
+
+/// A Generic Netlink family registered with the kernel, as returned by
+/// [`resolve_family`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct GenlFamily {
+    /// Name of the family, e.g. `nl80211`.
+    pub name: String,
+    /// Family id, the `nlmsg_type` of its messages.
+    pub id: u16,
+    /// Version of the family's interface.
+    pub version: u32,
+    /// Highest attribute type the family accepts (`CTRL_ATTR_MAXATTR`).
+    pub max_attr: u32,
+    /// Commands the family implements (`CTRL_ATTR_OPS`).
+    pub ops: BTreeSet<u32>,
+    /// Ids of the multicast groups of the family, by name.
+    pub mcast_groups: BTreeMap<String, u32>,
+}
+
+impl GenlFamily {
+    /// Returns the id of the multicast group `name`, if the family has one.
+    pub fn mcast_group(&self, name: &str) -> Option<u32> {
+        self.mcast_groups.get(name).copied()
+    }
+}
+
+/// Looks up the Generic Netlink family `name` (`CTRL_CMD_GETFAMILY`) on a new socket.
+///
+/// Works for any family, not only `batadv`, e.g. to talk to `nl80211` or `ethtool`
+/// next to the mesh: the returned id goes into the `nlmsg_type` of requests, the
+/// multicast group ids into [`Groups`] when subscribing to notifications.
+///
+/// # Errors
+/// - [`RobinError::FamilyNotFound`] if the kernel has no such family, e.g. because
+///   the module providing it is not loaded.
+/// - Other errors if the socket cannot be opened or fails.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn example() -> Result<(), batman_robin::RobinError> {
+/// let nl80211 = batman_robin::resolve_family("nl80211").await?;
+/// println!("nl80211 is family {}", nl80211.id);
+/// if let Some(group) = nl80211.mcast_group("mlme") {
+///     println!("MLME events on group {group}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn resolve_family(name: &str) -> Result<GenlFamily, RobinError> {
+    let (sock, _mcast) = NlRouter::connect(NlFamily::Generic, None, Groups::empty()).await?;
+    query_family(&sock, name).await
+}
+
+/// Looks up the Generic Netlink family `name` on the existing socket `sock`.
+pub(crate) async fn query_family(sock: &NlRouter, name: &str) -> Result<GenlFamily, RobinError> {
+    let not_found = || RobinError::FamilyNotFound {
+        family: name.to_string(),
+    };
+
+    let mut attrs = GenlBuffer::new();
+    attrs.push(
+        NlattrBuilder::default()
+            .nla_type(
+                AttrTypeBuilder::default()
+                    .nla_type(CtrlAttr::FamilyName)
+                    .build()
+                    .map_err(|e| RobinError::Netlink(e.to_string()))?,
+            )
+            .nla_payload(name)
+            .build()
+            .map_err(|e| RobinError::Netlink(e.to_string()))?,
+    );
+    let msg = GenlmsghdrBuilder::<CtrlCmd, CtrlAttr, NoUserHeader>::default()
+        .cmd(CtrlCmd::Getfamily)
+        .version(2)
+        .attrs(attrs)
+        .build()
+        .map_err(|e| RobinError::Netlink(e.to_string()))?;
+
+    let mut recv = sock
+        .send::<_, _, u16, Genlmsghdr<CtrlCmd, CtrlAttr>>(
+            GenlId::Ctrl,
+            NlmF::ACK,
+            NlPayload::Payload(msg),
+        )
+        .await?;
+
+    let mut family = None;
+    while let Some(reply) = recv.next::<u16, Genlmsghdr<CtrlCmd, CtrlAttr>>().await {
+        let reply = match reply {
+            Ok(reply) => reply,
+            // The kernel answers an unknown family with `ENOENT`.
+            Err(RouterError::Nlmsgerr(_)) => return Err(not_found()),
+            Err(e) => return Err(e.into()),
+        };
+        if let Some(payload) = reply.get_payload() {
+            family = family.or_else(|| parse_family(name, payload));
+        }
+    }
+    family.ok_or_else(not_found)
+}
+
+/// Reads the id, version, commands and multicast groups of the family `name` from a
+/// `CTRL_CMD_NEWFAMILY` reply.
+fn parse_family(name: &str, payload: &Genlmsghdr<CtrlCmd, CtrlAttr>) -> Option<GenlFamily> {
+    let attrs = payload.attrs().get_attr_handle();
+    let id = attrs.get_attr_payload_as::<u16>(CtrlAttr::FamilyId).ok()?;
+    let version = attrs
+        .get_attr_payload_as::<u32>(CtrlAttr::Version)
+        .unwrap_or(0);
+    let max_attr = attrs
+        .get_attr_payload_as::<u32>(CtrlAttr::Maxattr)
+        .unwrap_or(0);
+
+    let mut ops = BTreeSet::new();
+    if let Ok(list) = attrs.get_nested_attributes::<Index>(CtrlAttr::Ops) {
+        for op in list.iter() {
+            if let Ok(id) = op
+                .get_attr_handle::<u16>()
+                .and_then(|op| op.get_attr_payload_as::<u32>(CTRL_ATTR_OP_ID))
+            {
+                ops.insert(id);
+            }
+        }
+    }
+
+    let mut mcast_groups = BTreeMap::new();
+    if let Ok(groups) = attrs.get_nested_attributes::<Index>(CtrlAttr::McastGroups) {
+        for group in groups.iter() {
+            let Ok(group) = group.get_attr_handle::<CtrlAttrMcastGrp>() else {
+                continue;
+            };
+            if let (Ok(name), Ok(id)) = (
+                group.get_attr_payload_as_with_len::<String>(CtrlAttrMcastGrp::Name),
+                group.get_attr_payload_as::<u32>(CtrlAttrMcastGrp::Id),
+            ) {
+                mcast_groups.insert(name, id);
+            }
+        }
+    }
+
+    Some(GenlFamily {
+        name: name.to_string(),
+        id,
+        version,
+        max_attr,
+        ops,
+        mcast_groups,
+    })
+}
@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long resolved interface names and indexes are trusted.
+pub(crate) const IF_CACHE_TTL: Duration = Duration::from_secs(5);
+
+/// Small cache mapping interface indexes to names and back.
+///
+/// Parsing a dump resolves the interface of every row; without a cache each of
+/// these lookups costs a full `RTM_GETLINK` round trip. Entries expire after a TTL
+/// and the whole cache is dropped whenever robin itself adds, removes or renames
+/// links, so stale mappings are short-lived.
+///
+/// Each [`RtnlSocket`](crate::netlink::RtnlSocket) has its own cache: indexes and
+/// names are only meaningful within the network namespace of the connection.
+pub(crate) struct IfCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<u32, (String, Instant)>>,
+}
+
+impl IfCache {
+    /// Creates an empty cache whose entries expire after `ttl`.
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the cached index of interface `name`, if still fresh.
+    pub(crate) fn index(&self, name: &str) -> Option<u32> {
+        let entries = self.entries.lock().ok()?;
+        entries
+            .iter()
+            .find(|(_, (n, at))| n == name && at.elapsed() < self.ttl)
+            .map(|(idx, _)| *idx)
+    }
+
+    /// Returns the cached name of interface `index`, if still fresh.
+    pub(crate) fn name(&self, index: u32) -> Option<String> {
+        let entries = self.entries.lock().ok()?;
+        entries
+            .get(&index)
+            .filter(|(_, at)| at.elapsed() < self.ttl)
+            .map(|(name, _)| name.clone())
+    }
+
+    /// Records the mapping between `index` and `name`.
+    pub(crate) fn insert(&self, index: u32, name: &str) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.retain(|_, (n, _)| n != name);
+            entries.insert(index, (name.to_string(), Instant::now()));
+        }
+    }
+
+    /// Drops all cached mappings.
+    pub(crate) fn invalidate(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.clear();
+        }
+    }
+}
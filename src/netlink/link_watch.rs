@@ -0,0 +1,225 @@
+use crate::error::RobinError;
+use crate::model::{LinkEvent, LinkState};
+use crate::netlink::link_state;
+
+use std::collections::{HashMap, VecDeque};
+
+use neli::consts::nl::NlmF;
+use neli::consts::rtnl::{Ifla, IflaInfo, RtAddrFamily, Rtm};
+use neli::consts::socket::NlFamily;
+use neli::genl::{Genlmsghdr, NoUserHeader};
+use neli::nl::NlPayload;
+use neli::router::asynchronous::{NlRouter, NlRouterReceiverHandle};
+use neli::rtnl::{Ifinfomsg, IfinfomsgBuilder};
+use neli::utils::Groups;
+
+/// What the watcher knows about a link.
+#[derive(Debug, Clone)]
+struct Link {
+    name: String,
+    /// Index of the interface the link is enslaved to (`IFLA_MASTER`).
+    master: Option<u32>,
+    /// Whether the link is a batman-adv mesh interface (`IFLA_INFO_KIND`).
+    is_mesh: bool,
+    state: LinkState,
+}
+
+/// Watches the rtnetlink link notifications (`RTNLGRP_LINK`) for changes to mesh
+/// interfaces and their hard interfaces.
+///
+/// The links present when the watcher connects are read first, so hard interfaces
+/// can be told apart from other links from the first notification on.
+pub(crate) struct LinkWatcher {
+    /// Keeps the subscription alive.
+    _sock: NlRouter,
+    notifications: NlRouterReceiverHandle<u16, Genlmsghdr<u8, u16, NoUserHeader>>,
+    links: HashMap<u32, Link>,
+    pending: VecDeque<LinkEvent>,
+}
+
+impl LinkWatcher {
+    /// Joins `RTNLGRP_LINK` on a new `NETLINK_ROUTE` socket and reads the current links.
+    pub(crate) async fn connect() -> Result<Self, RobinError> {
+        let (sock, notifications) = NlRouter::connect(
+            NlFamily::Route,
+            None,
+            Groups::new_groups(&[libc::RTNLGRP_LINK]),
+        )
+        .await?;
+        sock.enable_strict_checking(true).ok();
+
+        let msg = IfinfomsgBuilder::default()
+            .ifi_family(RtAddrFamily::Unspecified)
+            .build()
+            .map_err(|_| RobinError::Netlink("Failed to create Ifinfomsg".to_string()))?;
+        let mut dump = sock
+            .send::<_, _, Rtm, Ifinfomsg>(
+                Rtm::Getlink,
+                NlmF::REQUEST | NlmF::DUMP,
+                NlPayload::Payload(msg),
+            )
+            .await?;
+
+        let mut links = HashMap::new();
+        while let Some(msg) = dump.next::<Rtm, Ifinfomsg>().await {
+            if let Some((index, link)) = msg?.get_payload().and_then(parse_link) {
+                links.insert(index, link);
+            }
+        }
+
+        Ok(Self {
+            _sock: sock,
+            notifications,
+            links,
+            pending: VecDeque::new(),
+        })
+    }
+
+    /// Waits for the next change to a mesh interface or one of its hard interfaces.
+    ///
+    /// Returns `None` once the socket has failed; the error is returned first.
+    pub(crate) async fn next(&mut self) -> Option<Result<LinkEvent, RobinError>> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(Ok(event));
+            }
+
+            let msg = match self.notifications.next::<Rtm, Ifinfomsg>().await? {
+                Ok(msg) => msg,
+                Err(e) => return Some(Err(e.into())),
+            };
+            let Some(payload) = msg.get_payload() else {
+                continue;
+            };
+            let index = payload.ifi_index().cast_unsigned();
+            match msg.nl_type() {
+                Rtm::Newlink => {
+                    if let Some((_, link)) = parse_link(payload) {
+                        self.new_link(index, link);
+                    }
+                }
+                Rtm::Dellink => self.del_link(index),
+                _ => {}
+            }
+        }
+    }
+
+    /// Records the new or changed link `index` and queues the resulting events.
+    fn new_link(&mut self, index: u32, link: Link) {
+        let old = self.links.insert(index, link.clone());
+        let mesh = self.mesh_name(link.master);
+        if !link.is_mesh && mesh.is_none() && old.as_ref().is_none_or(|old| old.master.is_none()) {
+            return;
+        }
+
+        let Some(old) = old else {
+            if link.is_mesh {
+                self.pending.push_back(LinkEvent::MeshAdded {
+                    ifindex: index,
+                    ifname: link.name,
+                });
+            } else if let Some(mesh_if) = mesh {
+                self.pending.push_back(LinkEvent::HardifAdded {
+                    ifindex: index,
+                    ifname: link.name,
+                    mesh_if,
+                });
+            }
+            return;
+        };
+
+        if link.is_mesh && !old.is_mesh {
+            self.pending.push_back(LinkEvent::MeshAdded {
+                ifindex: index,
+                ifname: link.name.clone(),
+            });
+        }
+        if old.name != link.name && (link.is_mesh || mesh.is_some()) {
+            self.pending.push_back(LinkEvent::Renamed {
+                ifindex: index,
+                old: old.name.clone(),
+                new: link.name.clone(),
+            });
+        }
+        if old.master != link.master {
+            if let Some(mesh_if) = self.mesh_name(old.master) {
+                self.pending.push_back(LinkEvent::HardifRemoved {
+                    ifindex: index,
+                    ifname: link.name.clone(),
+                    mesh_if,
+                });
+            }
+            if let Some(mesh_if) = mesh.clone() {
+                self.pending.push_back(LinkEvent::HardifAdded {
+                    ifindex: index,
+                    ifname: link.name.clone(),
+                    mesh_if,
+                });
+            }
+        }
+        if old.state != link.state && (link.is_mesh || mesh.is_some()) {
+            self.pending.push_back(LinkEvent::StateChanged {
+                ifindex: index,
+                ifname: link.name,
+                state: link.state,
+            });
+        }
+    }
+
+    /// Forgets the deleted link `index` and queues the resulting event.
+    fn del_link(&mut self, index: u32) {
+        let Some(old) = self.links.remove(&index) else {
+            return;
+        };
+
+        if old.is_mesh {
+            self.pending.push_back(LinkEvent::MeshRemoved {
+                ifindex: index,
+                ifname: old.name,
+            });
+        } else if let Some(mesh_if) = self.mesh_name(old.master) {
+            self.pending.push_back(LinkEvent::HardifRemoved {
+                ifindex: index,
+                ifname: old.name,
+                mesh_if,
+            });
+        }
+    }
+
+    /// Returns the name of `master` if it is a mesh interface.
+    fn mesh_name(&self, master: Option<u32>) -> Option<String> {
+        self.links
+            .get(&master?)
+            .filter(|link| link.is_mesh)
+            .map(|link| link.name.clone())
+    }
+}
+
+/// Returns the index of the link described by `msg` and what the watcher needs to
+/// know about it, or `None` if it carries no name.
+fn parse_link(msg: &Ifinfomsg) -> Option<(u32, Link)> {
+    let attrs = msg.rtattrs().get_attr_handle();
+    let name = attrs
+        .get_attr_payload_as_with_len::<String>(Ifla::Ifname)
+        .ok()?;
+    let master = attrs
+        .get_attr_payload_as::<u32>(Ifla::Master)
+        .ok()
+        .filter(|&master| master != 0);
+    let is_mesh = attrs
+        .get_nested_attributes::<IflaInfo>(Ifla::Linkinfo)
+        .ok()
+        .and_then(|info| {
+            info.get_attr_payload_as_with_len::<String>(IflaInfo::Kind)
+                .ok()
+        })
+        .is_some_and(|kind| kind.trim_end_matches('\0') == "batadv");
+
+    let link = Link {
+        name,
+        master,
+        is_mesh,
+        state: link_state(msg),
+    };
+    Some((msg.ifi_index().cast_unsigned(), link))
+}
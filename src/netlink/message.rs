@@ -1,8 +1,9 @@
 use crate::error::RobinError;
-use crate::model::Command;
+use crate::model::{AttrObject, AttrValue, Attribute, Command};
+use crate::netlink;
 
-use neli::consts::nl::NlmF;
-use neli::genl::{Genlmsghdr, GenlmsghdrBuilder};
+use neli::consts::nl::{NlmF, Nlmsg};
+use neli::genl::{Genlmsghdr, GenlmsghdrBuilder, Nlattr};
 use neli::nl::{NlPayload, Nlmsghdr, NlmsghdrBuilder};
 use neli::types::{Buffer, GenlBuffer};
 
@@ -74,3 +75,86 @@ pub fn build_nl_msg(
 
     Ok(nl_msg)
 }
+
+/// Decodes a message received from BATMAN-adv into an [`AttrObject`].
+///
+/// # Returns
+/// - `Ok(Some(AttrObject))` with the attributes of a reply.
+/// - `Ok(None)` for the messages ending a reply: `NLMSG_DONE`, or an error message
+///   with errno 0 (an ACK).
+/// - `Err(RobinError)` if the kernel rejected the request, or the reply is malformed.
+pub fn parse_genl_msg(
+    msg: &Nlmsghdr<u16, Genlmsghdr<u8, u16>>,
+) -> Result<Option<AttrObject>, RobinError> {
+    match genl_payload(msg)? {
+        Some(payload) => parse_attr_set(payload.attrs().as_ref()).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Returns the generic netlink payload of a message received from BATMAN-adv,
+/// leaving its attributes undecoded.
+///
+/// # Returns
+/// - `Ok(Some(payload))` for a reply.
+/// - `Ok(None)` for the messages ending a reply, as for [`parse_genl_msg`].
+/// - `Err(RobinError)` if the kernel rejected the request, or the reply has no payload.
+pub(crate) fn genl_payload(
+    msg: &Nlmsghdr<u16, Genlmsghdr<u8, u16>>,
+) -> Result<Option<&Genlmsghdr<u8, u16>>, RobinError> {
+    match *msg.nl_type() {
+        x if x == u16::from(Nlmsg::Done) => return Ok(None),
+        x if x == u16::from(Nlmsg::Error) => {
+            return match msg.nl_payload() {
+                NlPayload::Err(err) if *err.error() == 0 => Ok(None),
+                NlPayload::Err(err) => Err(RobinError::from_nlmsgerr(err)),
+                NlPayload::Ack(_) => Ok(None),
+                _ => Err(RobinError::Netlink(
+                    "Unknown netlink error payload".to_string(),
+                )),
+            };
+        }
+        _ => {}
+    }
+
+    msg.get_payload()
+        .map(Some)
+        .ok_or_else(|| RobinError::Parse("Message without payload".into()))
+}
+
+/// Decodes a set of attributes into an [`AttrObject`].
+///
+/// Every attribute is decoded and validated according to the spec map; attributes
+/// flagged `NLA_F_NESTED` are decoded recursively into `AttrValue::Nested`, and
+/// attribute types unknown to the crate are kept as raw bytes.
+///
+/// # Returns
+/// - `Ok(AttrObject)` with all attributes of the set.
+/// - `Err(RobinError::InvalidAttribute)` if an attribute has a malformed payload.
+pub(crate) fn parse_attr_set(attrs: &[Nlattr<u16, Buffer>]) -> Result<AttrObject, RobinError> {
+    let mut obj = AttrObject::new();
+
+    for attr in attrs {
+        let nla_type = *attr.nla_type().nla_type();
+
+        let Ok(kind) = Attribute::try_from(nla_type) else {
+            #[cfg(feature = "tracing")]
+            tracing::trace!(attr = nla_type, "unknown attribute kept undecoded");
+            obj.insert_unknown(nla_type, attr.nla_payload().as_ref().to_vec());
+            continue;
+        };
+
+        let value = if *attr.nla_type().nla_nested() {
+            let nested = attr
+                .get_attr_handle::<u16>()
+                .map_err(|_| RobinError::InvalidAttribute { attr: kind })?;
+            AttrValue::Nested(parse_attr_set(nested.get_attrs())?)
+        } else {
+            netlink::auto_cast_value_with_spec(kind, attr.nla_payload().as_ref())?
+        };
+
+        obj.insert(kind, value);
+    }
+
+    Ok(obj)
+}
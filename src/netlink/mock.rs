@@ -0,0 +1,337 @@
+use crate::error::RobinError;
+use crate::model::Command;
+use crate::netlink::{self, BatadvMsg, BatadvRouterError, GenlAttrBuilder};
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::Cursor;
+use std::path::Path;
+use std::sync::Mutex;
+
+use neli::consts::nl::{NlmF, Nlmsg};
+use neli::consts::rtnl::Ifla;
+use neli::err::{NlmsgerrBuilder, NlmsghdrErrBuilder, RouterError};
+use neli::genl::Genlmsghdr;
+use neli::nl::{NlPayload, NlmsghdrBuilder};
+use neli::rtnl::Ifinfomsg;
+use neli::types::NlBuffer;
+use neli::{FromBytesWithInput, ToBytes};
+
+/// Generic Netlink family ID the mock `batadv` family is registered under.
+const MOCK_FAMILY_ID: u16 = 0x20;
+
+/// A reply queued on a [`MockBackend`].
+enum MockReply {
+    /// One message per entry, as the kernel sends them for a dump.
+    Entries(Command, Vec<GenlAttrBuilder>),
+    /// Rejection of the request with the given errno.
+    Error(i32),
+    /// Netlink messages as read from a netlink socket.
+    Bytes(Vec<u8>),
+}
+
+/// In-memory stand-in for the kernel, serving canned replies to BATMAN-adv requests.
+///
+/// A client built with [`RobinClientBuilder::mock_backend`] sends every `batadv`
+/// request to the mock instead of the kernel, so commands can be tested without the
+/// batman-adv kernel module or root privileges. Each request takes the next queued
+/// reply, in order; the requests are recorded and can be checked byte for byte.
+///
+/// Interface names are resolved from the links registered with
+/// [`MockBackend::with_link`]. Link changes (adding, removing or enslaving
+/// interfaces) are not supported.
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::{AttrValueForSend, Attribute, Command, GenlAttrBuilder, MockBackend, RobinClient};
+/// use std::sync::Arc;
+///
+/// let mut entry = GenlAttrBuilder::new();
+/// entry.add(Attribute::BatadvAttrOrigAddress, vec![2, 0, 0, 0, 0, 1])?;
+/// entry.add(Attribute::BatadvAttrNeighAddress, vec![2, 0, 0, 0, 0, 2])?;
+/// entry.add(Attribute::BatadvAttrHardIfname, "eth0")?;
+/// entry.add(Attribute::BatadvAttrLastSeenMsecs, 120u32)?;
+/// entry.add(Attribute::BatadvAttrTq, 255u8)?;
+/// entry.add(Attribute::BatadvAttrFlagBest, AttrValueForSend::Flag)?;
+///
+/// let mock = Arc::new(
+///     MockBackend::new()
+///         .with_link(7, "bat0")
+///         .with_reply(Command::BatadvCmdGetOriginators, vec![entry]),
+/// );
+/// let client = RobinClient::builder().mock_backend(mock.clone()).build();
+///
+/// let runtime = tokio::runtime::Runtime::new().unwrap();
+/// let originators = runtime.block_on(client.originators("bat0"))?;
+///
+/// assert_eq!(originators.len(), 1);
+/// assert_eq!(originators[0].originator.to_string(), "02:00:00:00:00:01");
+/// assert_eq!(originators[0].outgoing_if, "eth0");
+/// assert_eq!(originators[0].tq, Some(255));
+/// assert!(originators[0].is_best);
+///
+/// // GET_ORIGINATORS (command 8, version 1) with BATADV_ATTR_MESH_IFINDEX (3) = 7,
+/// // in host byte order like netlink itself
+/// let request = [
+///     &[8, 1][..],
+///     &0u16.to_ne_bytes(),
+///     &8u16.to_ne_bytes(),
+///     &3u16.to_ne_bytes(),
+///     &7u32.to_ne_bytes(),
+/// ]
+/// .concat();
+/// assert_eq!(mock.requests(), vec![request]);
+/// # Ok::<(), batman_robin::RobinError>(())
+/// ```
+///
+/// Kernel errors are reported like real ones:
+///
+/// ```
+/// use batman_robin::{MockBackend, RobinClient};
+/// use std::sync::Arc;
+///
+/// let mock = MockBackend::new()
+///     .with_link(7, "bat0")
+///     .with_error(libc::EOPNOTSUPP);
+/// let client = RobinClient::builder().mock_backend(Arc::new(mock)).build();
+///
+/// let runtime = tokio::runtime::Runtime::new().unwrap();
+/// let err = runtime.block_on(client.gateways("bat0")).unwrap_err();
+/// assert_eq!(err.errno(), Some(libc::EOPNOTSUPP));
+/// ```
+///
+/// [`RobinClientBuilder::mock_backend`]: crate::client::RobinClientBuilder::mock_backend
+#[derive(Default)]
+pub struct MockBackend {
+    links: Vec<(u32, String)>,
+    replies: Mutex<VecDeque<MockReply>>,
+    requests: Mutex<Vec<Vec<u8>>>,
+}
+
+impl fmt::Debug for MockBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MockBackend")
+            .field("links", &self.links)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MockBackend {
+    /// Creates a mock without links or replies.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a network interface, e.g. the mesh interface named in requests.
+    pub fn with_link(mut self, index: u32, name: &str) -> Self {
+        self.links.push((index, name.to_string()));
+        self
+    }
+
+    /// Queues a reply of `cmd` messages, one per entry.
+    ///
+    /// Replies to dump requests are terminated with `NLMSG_DONE`.
+    pub fn with_reply(self, cmd: Command, entries: Vec<GenlAttrBuilder>) -> Self {
+        self.push(MockReply::Entries(cmd, entries))
+    }
+
+    /// Queues a rejection of the next request with `errno`.
+    pub fn with_error(self, errno: i32) -> Self {
+        self.push(MockReply::Error(errno))
+    }
+
+    /// Queues a reply given as the raw netlink messages read from a `batadv` socket.
+    pub fn with_reply_bytes(self, bytes: Vec<u8>) -> Self {
+        self.push(MockReply::Bytes(bytes))
+    }
+
+    /// Creates a mock replaying a recording made with
+    /// [`RobinClientBuilder::record_to`](crate::client::RobinClientBuilder::record_to).
+    ///
+    /// The recorded replies are queued in the order of the recorded requests. Links
+    /// are not part of a recording and must be registered with [`MockBackend::with_link`].
+    ///
+    /// # Returns
+    /// - `Ok(Self)` with the recorded replies queued.
+    /// - `Err(RobinError)` if the recording cannot be read or is malformed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use batman_robin::{Attribute, Command, GenlAttrBuilder, MockBackend, RobinClient};
+    /// use std::sync::Arc;
+    ///
+    /// let path = std::env::temp_dir().join(format!("robin-{}.rec", std::process::id()));
+    /// let runtime = tokio::runtime::Runtime::new().unwrap();
+    ///
+    /// let mut entry = GenlAttrBuilder::new();
+    /// entry.add(Attribute::BatadvAttrRouter, vec![2, 0, 0, 0, 0, 1])?;
+    /// entry.add(Attribute::BatadvAttrBandwidthDown, 100u32)?;
+    /// entry.add(Attribute::BatadvAttrBandwidthUp, 20u32)?;
+    /// entry.add(Attribute::BatadvAttrOrigAddress, vec![2, 0, 0, 0, 0, 2])?;
+    /// entry.add(Attribute::BatadvAttrHardIfname, "eth0")?;
+    ///
+    /// // Record a session...
+    /// let mock = MockBackend::new()
+    ///     .with_link(7, "bat0")
+    ///     .with_reply(Command::BatadvCmdGetGateways, vec![entry]);
+    /// let client = RobinClient::builder()
+    ///     .mock_backend(Arc::new(mock))
+    ///     .record_to(&path)
+    ///     .build();
+    /// let recorded = runtime.block_on(client.gateways("bat0"))?;
+    ///
+    /// // ...and replay it.
+    /// let replay = MockBackend::from_recording(&path)?.with_link(7, "bat0");
+    /// let client = RobinClient::builder().mock_backend(Arc::new(replay)).build();
+    /// let replayed = runtime.block_on(client.gateways("bat0"))?;
+    ///
+    /// assert_eq!(format!("{:?}", recorded), format!("{:?}", replayed));
+    /// # std::fs::remove_file(&path).ok();
+    /// # Ok::<(), batman_robin::RobinError>(())
+    /// ```
+    pub fn from_recording(path: impl AsRef<Path>) -> Result<Self, RobinError> {
+        let mut mock = Self::new();
+        for (_request, replies) in netlink::read_recording(path.as_ref())? {
+            mock = mock.with_reply_bytes(replies);
+        }
+        Ok(mock)
+    }
+
+    /// Returns the Generic Netlink messages (`genlmsghdr` and attributes) of all
+    /// requests received so far, in order.
+    pub fn requests(&self) -> Vec<Vec<u8>> {
+        self.requests
+            .lock()
+            .map(|requests| requests.clone())
+            .unwrap_or_default()
+    }
+
+    fn push(self, reply: MockReply) -> Self {
+        if let Ok(mut replies) = self.replies.lock() {
+            replies.push_back(reply);
+        }
+        self
+    }
+
+    /// Returns the family ID of the mock `batadv` family.
+    pub(crate) fn family_id(&self) -> u16 {
+        MOCK_FAMILY_ID
+    }
+
+    /// Records `msg` and returns the next queued reply, in the form the netlink router
+    /// delivers it.
+    pub(crate) fn serve(
+        &self,
+        flags: NlmF,
+        msg: &Genlmsghdr<u8, u16>,
+    ) -> Result<VecDeque<Result<BatadvMsg, BatadvRouterError>>, RobinError> {
+        let mut request = Cursor::new(Vec::new());
+        msg.to_bytes(&mut request)
+            .map_err(|e| RobinError::Netlink(format!("Failed to serialize request: {:?}", e)))?;
+        if let Ok(mut requests) = self.requests.lock() {
+            requests.push(request.into_inner());
+        }
+
+        let reply = self
+            .replies
+            .lock()
+            .ok()
+            .and_then(|mut replies| replies.pop_front())
+            .ok_or_else(|| RobinError::Netlink("No reply queued on mock backend".to_string()))?;
+
+        let messages = match reply {
+            MockReply::Entries(cmd, entries) => {
+                let mut messages = Vec::new();
+                for attrs in entries {
+                    let genl = netlink::build_genl_msg(cmd, attrs.build())?;
+                    messages.push(self.message(
+                        self.family_id(),
+                        NlmF::MULTI,
+                        NlPayload::Payload(genl),
+                    )?);
+                }
+                if flags.contains(NlmF::DUMP) {
+                    messages.push(self.message(
+                        Nlmsg::Done.into(),
+                        NlmF::MULTI,
+                        NlPayload::Empty,
+                    )?);
+                }
+                messages
+            }
+            MockReply::Error(errno) => {
+                let request = NlmsghdrErrBuilder::default()
+                    .nl_type(self.family_id())
+                    .nl_flags(flags)
+                    .nl_seq(0)
+                    .nl_pid(0)
+                    .nl_payload(msg.clone())
+                    .build()
+                    .map_err(|e| RobinError::Netlink(format!("{:?}", e)))?;
+                let err = NlmsgerrBuilder::default()
+                    .error(-errno)
+                    .nlmsg(request)
+                    .build()
+                    .map_err(|e| RobinError::Netlink(format!("{:?}", e)))?;
+                vec![self.message(Nlmsg::Error.into(), NlmF::empty(), NlPayload::Err(err))?]
+            }
+            MockReply::Bytes(bytes) => NlBuffer::<u16, Genlmsghdr<u8, u16>>::from_bytes_with_input(
+                &mut Cursor::new(&bytes[..]),
+                bytes.len(),
+            )
+            .map_err(|e| RobinError::Parse(format!("Malformed mock reply: {:?}", e)))?
+            .into_iter()
+            .collect(),
+        };
+
+        // Like the router, deliver kernel errors as `RouterError::Nlmsgerr` and stop
+        // after the first one.
+        let mut replies = VecDeque::new();
+        for mut message in messages {
+            match message.get_err() {
+                Some(err) => {
+                    replies.push_back(Err(RouterError::Nlmsgerr(err)));
+                    break;
+                }
+                None => replies.push_back(Ok(message)),
+            }
+        }
+        Ok(replies)
+    }
+
+    /// Looks up a registered link by the index or `IFLA_IFNAME` of a link request.
+    pub(crate) fn link(&self, msg: &Ifinfomsg) -> Option<(u32, String)> {
+        let index = msg.ifi_index().cast_unsigned();
+        let name = msg
+            .rtattrs()
+            .get_attr_handle()
+            .get_attr_payload_as_with_len::<String>(Ifla::Ifname)
+            .ok();
+
+        self.links
+            .iter()
+            .find(|(i, n)| match &name {
+                Some(name) => n == name,
+                None => *i == index,
+            })
+            .cloned()
+    }
+
+    fn message(
+        &self,
+        nl_type: u16,
+        flags: NlmF,
+        payload: NlPayload<u16, Genlmsghdr<u8, u16>>,
+    ) -> Result<BatadvMsg, RobinError> {
+        NlmsghdrBuilder::default()
+            .nl_type(nl_type)
+            .nl_flags(flags)
+            .nl_seq(0)
+            .nl_pid(0)
+            .nl_payload(payload)
+            .build()
+            .map_err(|e| RobinError::Netlink(format!("Failed to build NL header: {:?}", e)))
+    }
+}
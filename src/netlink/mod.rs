@@ -1,12 +1,43 @@
 //! Internal netlink utilities for batman-adv.
 //!
 //! This module exposes low-level netlink helpers for building messages, attributes, and sockets.
-//! These are **internal** and only used within the crate (`pub(crate)`).
+//! Most of them are internal (`pub(crate)`); the sockets, message builders and parsers
+//! re-exported at the crate root form the low-level message API.
 
 mod attribute_builder;
+mod family;
+mod ifcache;
+mod link_watch;
 mod message;
+mod mock;
+mod nl80211;
+mod parse;
+mod ratelimit;
+mod recording;
+mod retry;
+mod rtnl;
 mod socket;
+mod spec;
+mod trace;
+mod transport;
 
-pub(crate) use attribute_builder::*;
+pub use attribute_builder::GenlAttrBuilder;
+pub(crate) use family::query_family;
+pub use family::{GenlFamily, resolve_family};
+pub(crate) use ifcache::*;
+pub(crate) use link_watch::*;
 pub(crate) use message::*;
-pub(crate) use socket::*;
+pub use message::{build_genl_msg, parse_genl_msg};
+pub use mock::MockBackend;
+pub(crate) use nl80211::*;
+pub(crate) use parse::*;
+pub use ratelimit::RateLimit;
+pub(crate) use ratelimit::RateLimiter;
+pub(crate) use recording::*;
+pub use retry::RetryPolicy;
+pub use rtnl::RtnlSocket;
+pub(crate) use rtnl::*;
+pub use socket::{BatadvMsg, BatadvResponse, BatadvRouterError, BatadvSocket};
+pub(crate) use spec::*;
+pub(crate) use trace::*;
+pub use transport::{MeshResponse, MeshTransport, NetlinkTransport};
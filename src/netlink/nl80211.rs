@@ -0,0 +1,200 @@
+use crate::error::RobinError;
+use crate::model::StationInfo;
+use crate::netlink::query_family;
+
+use std::fmt;
+use std::sync::Arc;
+
+use macaddr::MacAddr6;
+use neli::consts::nl::{NlmF, Nlmsg};
+use neli::consts::socket::NlFamily;
+use neli::genl::{AttrTypeBuilder, GenlAttrHandle, Genlmsghdr, GenlmsghdrBuilder, NlattrBuilder};
+use neli::nl::{NlPayload, Nlmsghdr};
+use neli::router::asynchronous::NlRouter;
+use neli::types::GenlBuffer;
+use neli::utils::Groups;
+
+// Values from `include/uapi/linux/nl80211.h`.
+const NL80211_CMD_GET_INTERFACE: u8 = 5;
+const NL80211_CMD_GET_STATION: u8 = 17;
+
+const NL80211_ATTR_IFINDEX: u16 = 3;
+const NL80211_ATTR_IFTYPE: u16 = 5;
+const NL80211_ATTR_MAC: u16 = 6;
+const NL80211_ATTR_STA_INFO: u16 = 21;
+
+const NL80211_STA_INFO_INACTIVE_TIME: u16 = 1;
+const NL80211_STA_INFO_SIGNAL: u16 = 7;
+const NL80211_STA_INFO_TX_BITRATE: u16 = 8;
+const NL80211_STA_INFO_SIGNAL_AVG: u16 = 13;
+
+const NL80211_RATE_INFO_BITRATE: u16 = 1;
+const NL80211_RATE_INFO_BITRATE32: u16 = 5;
+
+type Nl80211Msg = Nlmsghdr<u16, Genlmsghdr<u8, u16>>;
+
+/// Async wrapper around a Generic Netlink socket for the `nl80211` family, used to
+/// tell wireless hard interfaces apart and to read the link state of their stations.
+///
+/// Without cfg80211 in the kernel the family does not exist; all interfaces are then
+/// reported as not wireless.
+#[derive(Clone)]
+pub(crate) struct Nl80211Socket {
+    /// The router and the family ID, if the family exists.
+    family: Option<(Arc<NlRouter>, u16)>,
+}
+
+impl fmt::Debug for Nl80211Socket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Nl80211Socket")
+            .field("family_id", &self.family.as_ref().map(|(_, id)| id))
+            .finish()
+    }
+}
+
+impl Nl80211Socket {
+    /// Connects to the Generic Netlink `nl80211` family.
+    pub(crate) async fn connect() -> Result<Self, RobinError> {
+        let (sock, _mcast) = NlRouter::connect(NlFamily::Generic, None, Groups::empty()).await?;
+        let family = query_family(&sock, "nl80211")
+            .await
+            .ok()
+            .map(|family| (Arc::new(sock), family.id));
+
+        Ok(Self { family })
+    }
+
+    /// A socket for which no interface is wireless, e.g. for a mock backend.
+    pub(crate) fn unsupported() -> Self {
+        Self { family: None }
+    }
+
+    /// Returns the `nl80211` interface type (`NL80211_IFTYPE_*`) of `ifindex`, or
+    /// `None` if it is not a wireless interface.
+    pub(crate) async fn iftype(&self, ifindex: u32) -> Result<Option<u32>, RobinError> {
+        let attrs = [(NL80211_ATTR_IFINDEX, ifindex.to_ne_bytes().to_vec())];
+        let Some(msg) = self.request(NL80211_CMD_GET_INTERFACE, attrs).await? else {
+            return Ok(None);
+        };
+
+        let attrs = attrs_of(&msg)?;
+        Ok(attrs.get_attr_payload_as::<u32>(NL80211_ATTR_IFTYPE).ok())
+    }
+
+    /// Returns the link state of station `mac` on the wireless interface `ifindex`, or
+    /// `None` if the interface is not wireless or has no such station.
+    pub(crate) async fn station(
+        &self,
+        ifindex: u32,
+        mac: MacAddr6,
+    ) -> Result<Option<StationInfo>, RobinError> {
+        let attrs = [
+            (NL80211_ATTR_IFINDEX, ifindex.to_ne_bytes().to_vec()),
+            (NL80211_ATTR_MAC, mac.as_bytes().to_vec()),
+        ];
+        let Some(msg) = self.request(NL80211_CMD_GET_STATION, attrs).await? else {
+            return Ok(None);
+        };
+
+        let attrs = attrs_of(&msg)?;
+        let Ok(info) = attrs.get_nested_attributes::<u16>(NL80211_ATTR_STA_INFO) else {
+            return Ok(None);
+        };
+
+        // Signals are `s8` dBm values.
+        let signal = |attr| info.get_attr_payload_as::<u8>(attr).ok().map(|v| v as i8);
+        let tx_bitrate_kbps = info
+            .get_nested_attributes::<u16>(NL80211_STA_INFO_TX_BITRATE)
+            .ok()
+            .and_then(|rate| {
+                rate.get_attr_payload_as::<u32>(NL80211_RATE_INFO_BITRATE32)
+                    .ok()
+                    .or_else(|| {
+                        rate.get_attr_payload_as::<u16>(NL80211_RATE_INFO_BITRATE)
+                            .ok()
+                            .map(u32::from)
+                    })
+            })
+            .map(|units| units * 100);
+
+        Ok(Some(StationInfo {
+            signal_dbm: signal(NL80211_STA_INFO_SIGNAL),
+            signal_avg_dbm: signal(NL80211_STA_INFO_SIGNAL_AVG),
+            tx_bitrate_kbps,
+            inactive_ms: info
+                .get_attr_payload_as::<u32>(NL80211_STA_INFO_INACTIVE_TIME)
+                .ok(),
+        }))
+    }
+
+    /// Sends a `GET` request and returns its reply, or `None` if the object does not
+    /// exist (`ENODEV`, `ENOENT`, `EINVAL` for non-wireless interfaces) or the family
+    /// is missing.
+    async fn request<const N: usize>(
+        &self,
+        cmd: u8,
+        attrs: [(u16, Vec<u8>); N],
+    ) -> Result<Option<Nl80211Msg>, RobinError> {
+        let Some((sock, family_id)) = &self.family else {
+            return Ok(None);
+        };
+
+        let mut buf = GenlBuffer::new();
+        for (nla_type, payload) in attrs {
+            let nla_type = AttrTypeBuilder::default()
+                .nla_type(nla_type)
+                .build()
+                .map_err(|e| RobinError::Netlink(format!("Failed to build AttrType: {:?}", e)))?;
+            let attr = NlattrBuilder::default()
+                .nla_type(nla_type)
+                .nla_payload(payload)
+                .build()
+                .map_err(|e| RobinError::Netlink(format!("Failed to build Nlattr: {:?}", e)))?;
+            buf.push(attr);
+        }
+
+        let msg = GenlmsghdrBuilder::default()
+            .cmd(cmd)
+            .version(1)
+            .attrs(buf)
+            .build()
+            .map_err(|e| RobinError::Netlink(format!("Failed to build GENL header: {:?}", e)))?;
+
+        let mut handle = sock
+            .send::<_, _, u16, Genlmsghdr<u8, u16>>(
+                *family_id,
+                NlmF::REQUEST,
+                NlPayload::Payload(msg),
+            )
+            .await?;
+
+        while let Some(reply) = handle.next::<u16, Genlmsghdr<u8, u16>>().await {
+            let reply: Nl80211Msg = match reply {
+                Ok(reply) => reply,
+                Err(e) => {
+                    let err = RobinError::from(e);
+                    return match err.errno() {
+                        Some(libc::ENODEV | libc::ENOENT | libc::EINVAL | libc::EOPNOTSUPP) => {
+                            Ok(None)
+                        }
+                        _ => Err(err),
+                    };
+                }
+            };
+            if *reply.nl_type() != u16::from(Nlmsg::Error) && reply.get_payload().is_some() {
+                return Ok(Some(reply));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Returns the attributes of an `nl80211` reply.
+fn attrs_of(msg: &Nl80211Msg) -> Result<GenlAttrHandle<'_, u16>, RobinError> {
+    Ok(msg
+        .get_payload()
+        .ok_or_else(|| RobinError::Parse("Message without payload".to_string()))?
+        .attrs()
+        .get_attr_handle())
+}
@@ -0,0 +1,112 @@
+use std::borrow::Cow;
+use std::net::Ipv4Addr;
+
+use macaddr::MacAddr6;
+use neli::genl::GenlAttrHandle;
+
+use crate::error::RobinError;
+use crate::model::{Attribute, Bandwidth, ClientFlags, LastSeen};
+
+/// Returns the payload of `attr`, borrowed from the received message buffer.
+///
+/// Unlike `get_attr_payload_as::<Vec<u8>>`, no copy of the payload is made.
+pub(crate) fn attr_bytes<'a>(
+    attrs: &'a GenlAttrHandle<'_, u16>,
+    attr: Attribute,
+) -> Option<&'a [u8]> {
+    attrs
+        .get_attribute(attr.into())
+        .map(|attr| attr.nla_payload().as_ref())
+}
+
+/// Returns the NUL-terminated string payload of `attr`.
+///
+/// The string is borrowed from the received message buffer and only copied if it
+/// is not valid UTF-8.
+pub(crate) fn attr_str<'a>(
+    attrs: &'a GenlAttrHandle<'_, u16>,
+    attr: Attribute,
+) -> Option<Cow<'a, str>> {
+    attr_bytes(attrs, attr).map(c_str)
+}
+
+/// Decodes a (possibly) NUL-terminated byte string, stopping at the first NUL byte.
+pub(crate) fn c_str(bytes: &[u8]) -> Cow<'_, str> {
+    let nul = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..nul])
+}
+
+/// A value decoded from the raw payload of a `batadv` attribute.
+///
+/// Used by the code generated by `#[derive(FromBatadvAttrs)]`.
+pub(crate) trait AttrPayload: Sized {
+    /// Decodes `payload`, or returns `None` if it has the wrong length.
+    fn from_payload(payload: &[u8]) -> Option<Self>;
+}
+
+macro_rules! int_payload {
+    ($($ty:ty),*) => {$(
+        impl AttrPayload for $ty {
+            fn from_payload(payload: &[u8]) -> Option<Self> {
+                payload.try_into().ok().map(<$ty>::from_ne_bytes)
+            }
+        }
+    )*};
+}
+
+int_payload!(u8, u16, u32, u64);
+
+impl AttrPayload for MacAddr6 {
+    fn from_payload(payload: &[u8]) -> Option<Self> {
+        <[u8; 6]>::try_from(payload).ok().map(MacAddr6::from)
+    }
+}
+
+/// The address is sent in network byte order.
+impl AttrPayload for Ipv4Addr {
+    fn from_payload(payload: &[u8]) -> Option<Self> {
+        <[u8; 4]>::try_from(payload).ok().map(Ipv4Addr::from)
+    }
+}
+
+/// Milliseconds, e.g. `BATADV_ATTR_LAST_SEEN_MSECS`.
+impl AttrPayload for LastSeen {
+    fn from_payload(payload: &[u8]) -> Option<Self> {
+        u32::from_payload(payload).map(LastSeen::from_millis)
+    }
+}
+
+/// Flags unknown to this crate are dropped.
+impl AttrPayload for ClientFlags {
+    fn from_payload(payload: &[u8]) -> Option<Self> {
+        u32::from_payload(payload).map(ClientFlags::from_bits_truncate)
+    }
+}
+
+/// Units of 100 kbit/s.
+impl AttrPayload for Bandwidth {
+    fn from_payload(payload: &[u8]) -> Option<Self> {
+        u32::from_payload(payload).map(Bandwidth::from_netlink)
+    }
+}
+
+/// Decodes the payload of `attr`.
+///
+/// # Returns
+/// - `Err(RobinError::MissingAttribute)` if `attr` is absent.
+/// - `Err(RobinError::InvalidAttribute)` if its payload has the wrong length.
+pub(crate) fn attr_required<T: AttrPayload>(
+    attrs: &GenlAttrHandle<'_, u16>,
+    attr: Attribute,
+) -> Result<T, RobinError> {
+    let payload = attr_bytes(attrs, attr).ok_or(RobinError::MissingAttribute { attr })?;
+    T::from_payload(payload).ok_or(RobinError::InvalidAttribute { attr })
+}
+
+/// Decodes the payload of `attr`, or returns `None` if it is absent or malformed.
+pub(crate) fn attr_optional<T: AttrPayload>(
+    attrs: &GenlAttrHandle<'_, u16>,
+    attr: Attribute,
+) -> Option<T> {
+    attr_bytes(attrs, attr).and_then(T::from_payload)
+}
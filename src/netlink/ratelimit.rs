@@ -0,0 +1,108 @@
+use crate::error::RobinError;
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Limit on the rate of requests changing the mesh configuration.
+///
+/// When set on a client, at most `changes` configuration changes are admitted per
+/// `period`, with bursts of up to `changes` requests; further changes fail with
+/// [`RobinError::Throttled`] instead of reaching the kernel. Queries are never limited.
+/// This keeps a misbehaving automation loop from flooding the kernel with
+/// configuration churn.
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::{Command, MockBackend, RateLimit, RobinClient, RobinError};
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// let mock = Arc::new(
+///     MockBackend::new()
+///         .with_link(7, "bat0")
+///         .with_reply(Command::BatadvCmdSetMesh, Vec::new()),
+/// );
+/// let client = RobinClient::builder()
+///     .mock_backend(mock)
+///     .rate_limit(RateLimit::new(1, Duration::from_secs(60)))
+///     .build();
+///
+/// let runtime = tokio::runtime::Runtime::new().unwrap();
+/// runtime.block_on(client.set_aggregation("bat0", true))?;
+/// let err = runtime.block_on(client.set_aggregation("bat0", false)).unwrap_err();
+/// assert!(matches!(err, RobinError::Throttled { .. }));
+/// # Ok::<(), RobinError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    changes: u32,
+    period: Duration,
+}
+
+impl RateLimit {
+    /// Creates a limit of `changes` configuration changes per `period`.
+    pub fn new(changes: u32, period: Duration) -> Self {
+        Self {
+            changes: changes.max(1),
+            period,
+        }
+    }
+
+    /// Maximum number of changes per period, and the size of a burst.
+    pub fn changes(&self) -> u32 {
+        self.changes
+    }
+
+    /// The period the changes are counted over.
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+}
+
+/// Token bucket enforcing a [`RateLimit`], shared by all clones of a transport.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    limit: RateLimit,
+    /// Available tokens and when they were last refilled.
+    bucket: Mutex<(f64, Instant)>,
+}
+
+impl RateLimiter {
+    /// Creates a limiter starting with a full bucket.
+    pub(crate) fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            bucket: Mutex::new((f64::from(limit.changes), Instant::now())),
+        }
+    }
+
+    /// Takes a token for one change.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the change is admitted.
+    /// - `Err(RobinError::Throttled)` with the time until a token is available otherwise.
+    pub(crate) fn admit(&self) -> Result<(), RobinError> {
+        let capacity = f64::from(self.limit.changes);
+        let per_token = self.limit.period.as_secs_f64() / capacity;
+
+        let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+        let (tokens, refilled) = &mut *bucket;
+        let now = Instant::now();
+        if per_token > 0.0 {
+            let elapsed = now.duration_since(*refilled).as_secs_f64();
+            *tokens = (*tokens + elapsed / per_token).min(capacity);
+        } else {
+            *tokens = capacity;
+        }
+        *refilled = now;
+
+        if *tokens >= 1.0 {
+            *tokens -= 1.0;
+            return Ok(());
+        }
+        Err(RobinError::Throttled {
+            retry_after: Duration::from_secs_f64((1.0 - *tokens) * per_token),
+        })
+    }
+}
@@ -0,0 +1,137 @@
+use crate::error::RobinError;
+use crate::netlink::{BatadvMsg, BatadvRouterError};
+
+use std::fmt;
+use std::fs::File;
+use std::io::{Cursor, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use neli::ToBytes;
+use neli::consts::nl::{NlmF, Nlmsg};
+use neli::err::RouterError;
+use neli::genl::Genlmsghdr;
+use neli::nl::{NlPayload, NlmsghdrBuilder};
+
+/// Record tag of a request: the Generic Netlink message sent (`genlmsghdr` and attributes).
+const TAG_REQUEST: u8 = 0;
+
+/// Record tag of a reply: one netlink message (`nlmsghdr` included) as received.
+const TAG_REPLY: u8 = 1;
+
+/// A recorded request and the concatenated netlink messages received for it.
+pub(crate) type Exchange = (Vec<u8>, Vec<u8>);
+
+/// Writes the `batadv` traffic of a socket to a recording file.
+///
+/// A recording is a sequence of records, each made of a one-byte tag, the length
+/// of the data as a little-endian `u32`, and the data. Every request record is
+/// followed by the records of its replies. Recordings are read back by
+/// [`MockBackend::from_recording`](crate::netlink::MockBackend::from_recording).
+#[derive(Clone)]
+pub(crate) struct Recorder {
+    file: Arc<Mutex<File>>,
+}
+
+impl fmt::Debug for Recorder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recorder").finish_non_exhaustive()
+    }
+}
+
+impl Recorder {
+    /// Creates the recording file at `path`, truncating an existing one.
+    pub(crate) fn create(path: &Path) -> Result<Self, RobinError> {
+        let file = File::create(path).map_err(|e| RobinError::Io {
+            context: format!("Failed to create recording {}", path.display()),
+            source: e,
+        })?;
+
+        Ok(Self {
+            file: Arc::new(Mutex::new(file)),
+        })
+    }
+
+    /// Records a request.
+    pub(crate) fn request(&self, msg: &Genlmsghdr<u8, u16>) {
+        let mut buf = Cursor::new(Vec::new());
+        if msg.to_bytes(&mut buf).is_ok() {
+            self.write(TAG_REQUEST, buf.get_ref());
+        }
+    }
+
+    /// Records a reply.
+    ///
+    /// Kernel errors are recorded as the `NLMSG_ERROR` message they were received
+    /// as; other receive errors carry no message and are skipped.
+    pub(crate) fn reply(&self, msg: &Result<BatadvMsg, BatadvRouterError>) {
+        let mut buf = Cursor::new(Vec::new());
+        let written = match msg {
+            Ok(msg) => msg.to_bytes(&mut buf).is_ok(),
+            Err(RouterError::Nlmsgerr(err)) => NlmsghdrBuilder::default()
+                .nl_type(u16::from(Nlmsg::Error))
+                .nl_flags(NlmF::empty())
+                .nl_seq(0)
+                .nl_pid(0)
+                .nl_payload(NlPayload::<u16, Genlmsghdr<u8, u16>>::Err(err.clone()))
+                .build()
+                .is_ok_and(|msg| msg.to_bytes(&mut buf).is_ok()),
+            Err(_) => false,
+        };
+        if written {
+            self.write(TAG_REPLY, buf.get_ref());
+        }
+    }
+
+    /// Appends one record. Recording is best effort: write errors are ignored so
+    /// they never fail the request being recorded.
+    fn write(&self, tag: u8, data: &[u8]) {
+        let mut record = Vec::with_capacity(5 + data.len());
+        record.push(tag);
+        record.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        record.extend_from_slice(data);
+
+        if let Ok(mut file) = self.file.lock() {
+            file.write_all(&record).ok();
+        }
+    }
+}
+
+/// Reads a recording written by a [`Recorder`].
+///
+/// # Returns
+/// - `Ok` with one [`Exchange`] per request.
+/// - `Err(RobinError)` if the file cannot be read or is truncated.
+pub(crate) fn read_recording(path: &Path) -> Result<Vec<Exchange>, RobinError> {
+    let data = std::fs::read(path).map_err(|e| RobinError::Io {
+        context: format!("Failed to read recording {}", path.display()),
+        source: e,
+    })?;
+
+    let truncated = || RobinError::Parse(format!("Truncated recording {}", path.display()));
+
+    let mut exchanges: Vec<Exchange> = Vec::new();
+    let mut rest = &data[..];
+    while let Some((&tag, tail)) = rest.split_first() {
+        let (len, tail) = tail.split_first_chunk::<4>().ok_or_else(truncated)?;
+        let len = u32::from_le_bytes(*len) as usize;
+        if tail.len() < len {
+            return Err(truncated());
+        }
+        let (record, tail) = tail.split_at(len);
+        rest = tail;
+
+        match (tag, exchanges.last_mut()) {
+            (TAG_REQUEST, _) => exchanges.push((record.to_vec(), Vec::new())),
+            (TAG_REPLY, Some((_, replies))) => replies.extend_from_slice(record),
+            _ => {
+                return Err(RobinError::Parse(format!(
+                    "Unexpected record in recording {}",
+                    path.display()
+                )));
+            }
+        }
+    }
+
+    Ok(exchanges)
+}
@@ -0,0 +1,110 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use neli::err::{RouterError, SocketError};
+
+/// Errno values considered transient: the same request is likely to succeed when retried.
+const TRANSIENT_ERRNOS: [i32; 3] = [libc::EBUSY, libc::EAGAIN, libc::ENOBUFS];
+
+/// Retry policy for transient netlink failures.
+///
+/// When enabled on a client, requests rejected with `EBUSY`, `EAGAIN` or `ENOBUFS`
+/// are sent again after an exponentially growing, jittered delay. Retries only
+/// happen before the first reply has been handed out, so a dump is never replayed
+/// halfway through.
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::RetryPolicy;
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy::new(5)
+///     .with_base_delay(Duration::from_millis(20))
+///     .with_max_delay(Duration::from_millis(500));
+/// assert_eq!(policy.max_retries(), 5);
+/// assert!(policy.delay(10) <= Duration::from_millis(500));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    /// Three retries, starting at 10 ms and capped at one second.
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_secs(1),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a policy retrying up to `max_retries` times with the default delays.
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the delay before the first retry; it doubles on every further attempt.
+    pub fn with_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Sets the upper bound for the delay between two attempts.
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Maximum number of retries after the initial attempt.
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Returns the delay to wait before retry number `attempt` (starting at 0).
+    ///
+    /// The delay is `base_delay * 2^attempt`, capped at `max_delay`, with up to
+    /// 50% random jitter subtracted so concurrent clients do not retry in lockstep.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let exp = self
+            .base_delay
+            .saturating_mul(1u32.checked_shl(attempt).unwrap_or(u32::MAX))
+            .min(self.max_delay);
+
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u32(attempt);
+        let jitter = (hasher.finish() % 1000) as u32;
+
+        exp - exp / 2 * jitter / 1000
+    }
+}
+
+/// Returns `true` if the router error means the socket itself failed, e.g. its
+/// receive buffer overflowed (`ENOBUFS`) or the router stopped, rather than the
+/// kernel rejecting a request.
+pub(crate) fn is_socket_failure<T, P>(err: &RouterError<T, P>) -> bool {
+    matches!(
+        err,
+        RouterError::Socket(_) | RouterError::Io(_) | RouterError::ClosedChannel
+    )
+}
+
+/// Returns `true` if the router error carries one of the transient errno values.
+pub(crate) fn is_transient<T, P>(err: &RouterError<T, P>) -> bool {
+    let errno = match err {
+        RouterError::Nlmsgerr(e) => Some(-*e.error()),
+        RouterError::Socket(SocketError::Io(e)) => e.raw_os_error(),
+        _ => None,
+    };
+
+    errno.is_some_and(|errno| TRANSIENT_ERRNOS.contains(&errno))
+}
@@ -0,0 +1,221 @@
+use crate::error::RobinError;
+use crate::model::{LinkState, LinkStats, OperState};
+use crate::netlink::{IF_CACHE_TTL, IfCache, MockBackend};
+
+use std::fmt;
+use std::sync::Arc;
+
+use neli::consts::nl::NlmF;
+use neli::consts::rtnl::{Ifla, RtAddrFamily, Rtm};
+use neli::consts::socket::NlFamily;
+use neli::nl::NlPayload;
+use neli::router::asynchronous::{NlRouter, NlRouterReceiverHandle};
+use neli::rtnl::{Ifinfomsg, IfinfomsgBuilder};
+use neli::utils::Groups;
+
+/// Async wrapper around an rtnetlink (`NETLINK_ROUTE`) socket.
+///
+/// Used for link lookups and for adding, removing and enslaving interfaces.
+/// Extended ACKs and strict checking are enabled on connect.
+///
+/// Cloning is cheap: clones share the same underlying netlink router, so a single
+/// connection can serve every rtnetlink operation of a client.
+#[derive(Clone)]
+pub struct RtnlSocket {
+    backend: Backend,
+    cache: Arc<IfCache>,
+}
+
+/// Where the requests of an [`RtnlSocket`] are sent.
+#[derive(Clone)]
+enum Backend {
+    /// The kernel, through a `NETLINK_ROUTE` socket.
+    Netlink(Arc<NlRouter>),
+    /// The links registered on a mock, for tests.
+    Mock(Arc<MockBackend>),
+}
+
+impl fmt::Debug for RtnlSocket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RtnlSocket").finish_non_exhaustive()
+    }
+}
+
+impl RtnlSocket {
+    /// Opens a `NETLINK_ROUTE` socket.
+    ///
+    /// # Returns
+    /// - `Ok(Self)` on success.
+    /// - `Err(RobinError)` if the socket could not be opened.
+    pub async fn connect() -> Result<Self, RobinError> {
+        let (sock, _mcast) = NlRouter::connect(NlFamily::Route, None, Groups::empty()).await?;
+
+        sock.enable_ext_ack(true).ok();
+        sock.enable_strict_checking(true).ok();
+
+        Ok(Self {
+            backend: Backend::Netlink(Arc::new(sock)),
+            cache: Arc::new(IfCache::new(IF_CACHE_TTL)),
+        })
+    }
+
+    /// Creates a socket whose link lookups are answered by `mock`.
+    pub(crate) fn mock(mock: Arc<MockBackend>) -> Self {
+        Self {
+            backend: Backend::Mock(mock),
+            cache: Arc::new(IfCache::new(IF_CACHE_TTL)),
+        }
+    }
+
+    /// Returns the ifindex ↔ ifname cache of this connection.
+    pub(crate) fn if_cache(&self) -> &IfCache {
+        &self.cache
+    }
+
+    /// Sends a link message (`RTM_*LINK`) and returns a handle to receive the replies.
+    ///
+    /// # Parameters
+    /// - `msg_type`: The rtnetlink message type, e.g. `Rtm::Getlink`.
+    /// - `flags`: Flags controlling message behavior (`NlmF::REQUEST`, `NlmF::ACK`, etc.).
+    /// - `msg`: The `Ifinfomsg` payload.
+    pub async fn send(
+        &self,
+        msg_type: Rtm,
+        flags: NlmF,
+        msg: Ifinfomsg,
+    ) -> Result<NlRouterReceiverHandle<Rtm, Ifinfomsg>, RobinError> {
+        match &self.backend {
+            Backend::Netlink(sock) => Ok(sock
+                .send::<_, _, Rtm, Ifinfomsg>(msg_type, flags, NlPayload::Payload(msg))
+                .await?),
+            Backend::Mock(_) => Err(RobinError::Netlink(
+                "Link requests are not supported by the mock backend".to_string(),
+            )),
+        }
+    }
+
+    /// Sends a targeted `RTM_GETLINK` request and returns the index and name of the link.
+    ///
+    /// Returns `Ok(None)` if the kernel reports that no such link exists (`ENODEV`).
+    pub(crate) async fn get_link(
+        &self,
+        ifinfomsg: Ifinfomsg,
+    ) -> Result<Option<(u32, String)>, RobinError> {
+        if let Backend::Mock(mock) = &self.backend {
+            return Ok(mock.link(&ifinfomsg));
+        }
+
+        let Some(payload) = self.query_link(ifinfomsg).await? else {
+            return Ok(None);
+        };
+        let attrs = payload.rtattrs().get_attr_handle();
+        let Ok(name) = attrs.get_attr_payload_as_with_len::<String>(Ifla::Ifname) else {
+            return Ok(None);
+        };
+        let index = payload.ifi_index().cast_unsigned();
+        self.cache.insert(index, &name);
+
+        Ok(Some((index, name)))
+    }
+
+    /// Sends a targeted `RTM_GETLINK` request for `ifindex` and returns its
+    /// operational state and carrier.
+    ///
+    /// Returns `Ok(None)` if no such link exists, or for a mock.
+    pub(crate) async fn get_link_state(
+        &self,
+        ifindex: u32,
+    ) -> Result<Option<LinkState>, RobinError> {
+        Ok(self.query_index(ifindex).await?.as_ref().map(link_state))
+    }
+
+    /// Sends a targeted `RTM_GETLINK` request for `ifindex` and returns its
+    /// operational state and carrier, and its traffic counters if the kernel
+    /// reports `IFLA_STATS64`.
+    ///
+    /// Returns `Ok(None)` if no such link exists, or for a mock.
+    pub(crate) async fn get_link_info(
+        &self,
+        ifindex: u32,
+    ) -> Result<Option<(LinkState, Option<LinkStats>)>, RobinError> {
+        Ok(self
+            .query_index(ifindex)
+            .await?
+            .map(|link| (link_state(&link), link_stats(&link))))
+    }
+
+    /// Sends a targeted `RTM_GETLINK` request for `ifindex` and returns the reply.
+    ///
+    /// Returns `Ok(None)` if no such link exists, or for a mock.
+    async fn query_index(&self, ifindex: u32) -> Result<Option<Ifinfomsg>, RobinError> {
+        if let Backend::Mock(_) = &self.backend {
+            return Ok(None);
+        }
+
+        let ifinfomsg = IfinfomsgBuilder::default()
+            .ifi_family(RtAddrFamily::Unspecified)
+            .ifi_index(ifindex.cast_signed())
+            .build()
+            .map_err(|_| RobinError::Netlink("Failed to create Ifinfomsg".to_string()))?;
+
+        self.query_link(ifinfomsg).await
+    }
+
+    /// Sends a targeted `RTM_GETLINK` request and returns the reply.
+    ///
+    /// Returns `Ok(None)` if the kernel reports that no such link exists (`ENODEV`).
+    async fn query_link(&self, ifinfomsg: Ifinfomsg) -> Result<Option<Ifinfomsg>, RobinError> {
+        let mut response = self
+            .send(Rtm::Getlink, NlmF::REQUEST | NlmF::ACK, ifinfomsg)
+            .await?;
+
+        let mut link = None;
+        while let Some(msg) = response.next::<Rtm, Ifinfomsg>().await {
+            let msg = match msg.map_err(RobinError::from) {
+                Ok(msg) => msg,
+                Err(e) if e.errno() == Some(libc::ENODEV) => return Ok(None),
+                Err(e) => return Err(e),
+            };
+
+            if let Some(payload) = msg.get_payload() {
+                link = Some(payload.clone());
+            }
+        }
+
+        Ok(link)
+    }
+}
+
+/// Returns the operational state and carrier of the link described by `link`.
+pub(crate) fn link_state(link: &Ifinfomsg) -> LinkState {
+    let attrs = link.rtattrs().get_attr_handle();
+    LinkState {
+        operstate: attrs
+            .get_attr_payload_as::<u8>(Ifla::Operstate)
+            .map(OperState::from)
+            .unwrap_or(OperState::Unknown),
+        carrier: attrs
+            .get_attr_payload_as::<u8>(Ifla::Carrier)
+            .is_ok_and(|carrier| carrier != 0),
+    }
+}
+
+/// Returns the traffic counters of the link described by `link`, if it carries
+/// `IFLA_STATS64`.
+///
+/// The payload is a `struct rtnl_link_stats64`, starting with the `rx_packets`,
+/// `tx_packets`, `rx_bytes` and `tx_bytes` counters in host byte order.
+pub(crate) fn link_stats(link: &Ifinfomsg) -> Option<LinkStats> {
+    let attrs = link.rtattrs().get_attr_handle();
+    let payload: &[u8] = attrs.get_attribute(Ifla::Stats64)?.rta_payload().as_ref();
+    let counter = |i: usize| {
+        let bytes = payload.get(i * 8..(i + 1) * 8)?;
+        Some(u64::from_ne_bytes(bytes.try_into().ok()?))
+    };
+    Some(LinkStats {
+        rx_packets: counter(0)?,
+        tx_packets: counter(1)?,
+        rx_bytes: counter(2)?,
+        tx_bytes: counter(3)?,
+    })
+}
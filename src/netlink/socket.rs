@@ -1,19 +1,110 @@
 use crate::error::RobinError;
+use crate::kmod;
+use crate::model::{Capabilities, Command};
+use crate::netlink::retry::{RetryPolicy, is_socket_failure, is_transient};
+use crate::netlink::{MockBackend, Recorder, RequestTrace, RtnlSocket, query_family};
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use neli::consts::nl::NlmF;
 use neli::consts::socket::NlFamily;
+use neli::err::RouterError;
 use neli::genl::Genlmsghdr;
-use neli::nl::NlPayload;
+use neli::nl::{NlPayload, Nlmsghdr};
 use neli::router::asynchronous::{NlRouter, NlRouterReceiverHandle};
 use neli::utils::Groups;
 
+/// A netlink message of the `batadv` family, as received from the kernel.
+pub type BatadvMsg = Nlmsghdr<u16, Genlmsghdr<u8, u16>>;
+
+/// Error receiving a [`BatadvMsg`]; converts into [`RobinError`] with `?`.
+pub type BatadvRouterError = RouterError<u16, Genlmsghdr<u8, u16>>;
+
+/// Where the requests of a [`BatadvSocket`] are sent.
+#[derive(Clone)]
+enum Backend {
+    /// The kernel, through a Generic Netlink socket.
+    Netlink(Arc<NlRouter>),
+    /// Canned replies, for tests.
+    Mock(Arc<MockBackend>),
+}
+
 /// Async wrapper around a Generic Netlink socket for interacting with BATMAN-adv.
 ///
 /// Provides methods to connect to the `batadv` family and send messages,
 /// returning an async handle to receive responses.
+///
+/// Cloning is cheap: clones share the same underlying netlink router.
+///
+/// Requests can also be served by a [`MockBackend`] instead of the kernel.
+///
+/// Together with [`build_genl_msg`](crate::build_genl_msg), [`BatadvResponse`] and
+/// [`parse_genl_msg`](crate::parse_genl_msg), this is the low-level API for
+/// requests the crate does not model; it follows semver like the rest of the crate.
+/// The socket of a client is returned by [`NetlinkTransport::socket`](crate::NetlinkTransport::socket),
+/// so custom requests share its connection, retry policy and recording.
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::{
+///     Attribute, Command, GenlAttrBuilder, MockBackend, RobinClient, RobinError,
+///     build_genl_msg, parse_genl_msg,
+/// };
+/// use std::sync::Arc;
+///
+/// let mut entry = GenlAttrBuilder::new();
+/// entry.add(Attribute::BatadvAttrOrigAddress, vec![2, 0, 0, 0, 0, 1])?;
+/// let mock = Arc::new(
+///     MockBackend::new()
+///         .with_link(7, "bat0")
+///         .with_reply(Command::BatadvCmdGetOriginators, vec![entry]),
+/// );
+/// let client = RobinClient::builder().mock_backend(mock).build();
+///
+/// let runtime = tokio::runtime::Runtime::new().unwrap();
+/// let origs = runtime.block_on(async {
+///     let sock = client.transport().socket().await?;
+///
+///     let mut attrs = GenlAttrBuilder::new();
+///     attrs.add(Attribute::BatadvAttrMeshIfindex, client.if_nametoindex("bat0").await?)?;
+///     let msg = build_genl_msg(Command::BatadvCmdGetOriginators, attrs.build())?;
+///
+///     let mut response = sock.dump(msg).await?;
+///     let mut origs = Vec::new();
+///     while let Some(msg) = response.next().await {
+///         if let Some(obj) = parse_genl_msg(&msg?)? {
+///             origs.push(obj.get_mac(Attribute::BatadvAttrOrigAddress)?);
+///         }
+///     }
+///     Ok::<_, RobinError>(origs)
+/// })?;
+/// assert_eq!(origs[0].to_string(), "02:00:00:00:00:01");
+/// # Ok::<(), RobinError>(())
+/// ```
+#[derive(Clone)]
 pub struct BatadvSocket {
-    sock: NlRouter,
+    backend: Backend,
+    rtnl: RtnlSocket,
     family_id: u16,
+    /// What the kernel's `batadv` family implements, read when connecting.
+    capabilities: Arc<Capabilities>,
+    retry: Option<RetryPolicy>,
+    recorder: Option<Recorder>,
+    /// Set once the socket itself failed; shared by all clones.
+    failed: Arc<AtomicBool>,
+}
+
+impl fmt::Debug for BatadvSocket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BatadvSocket")
+            .field("family_id", &self.family_id)
+            .field("retry", &self.retry)
+            .finish_non_exhaustive()
+    }
 }
 
 impl BatadvSocket {
@@ -21,43 +112,276 @@ impl BatadvSocket {
     ///
     /// Resolves the family ID for `batadv` and prepares the socket for sending messages.
     ///
+    /// # Parameters
+    /// - `rtnl`: The rtnetlink socket used for the interface lookups of the requests
+    ///   sent on this socket.
+    /// - `retry`: Optional retry policy for transient failures.
+    ///
     /// # Returns
     /// - `Ok(Self)` on success with an initialized `BatadvSocket`.
     /// - `Err(RobinError)` if the connection or family resolution fails.
-    pub async fn connect() -> Result<Self, RobinError> {
-        let (sock, _mcast) = NlRouter::connect(NlFamily::Generic, None, Groups::empty())
-            .await
-            .map_err(|e| {
-                RobinError::Netlink(format!("Failed to connect with NlRouter: {:?}", e))
-            })?;
-        let family_id = sock
-            .resolve_genl_family("batadv")
+    pub async fn connect(rtnl: RtnlSocket, retry: Option<RetryPolicy>) -> Result<Self, RobinError> {
+        let (sock, _mcast) = NlRouter::connect(NlFamily::Generic, None, Groups::empty()).await?;
+
+        sock.enable_ext_ack(true).ok();
+
+        let family = query_family(&sock, "batadv")
             .await
-            .map_err(|e| RobinError::Netlink(format!("Failed to resolve family: {:?}", e)))?;
+            .map_err(kmod::family_error)?;
+        // A family not reporting its commands or attributes supports them all.
+        let all = Capabilities::all();
+        let capabilities = Capabilities::new(
+            family.version,
+            match family.ops.is_empty() {
+                true => all.commands,
+                false => family
+                    .ops
+                    .iter()
+                    .filter_map(|&op| Command::try_from(u8::try_from(op).ok()?).ok())
+                    .collect(),
+            },
+            match family.max_attr {
+                0 => all.max_attr,
+                max_attr => u16::try_from(max_attr).unwrap_or(u16::MAX),
+            },
+        );
+
+        Ok(Self {
+            backend: Backend::Netlink(Arc::new(sock)),
+            rtnl,
+            family_id: family.id,
+            capabilities: Arc::new(capabilities),
+            retry,
+            recorder: None,
+            failed: Arc::default(),
+        })
+    }
+
+    /// Records all requests sent on this socket and their replies with `recorder`.
+    pub(crate) fn with_recorder(mut self, recorder: Recorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    /// Creates a socket whose requests are answered by `mock`.
+    ///
+    /// Interface lookups are answered by the mock as well.
+    pub fn mock(mock: Arc<MockBackend>) -> Self {
+        Self {
+            rtnl: RtnlSocket::mock(mock.clone()),
+            family_id: mock.family_id(),
+            capabilities: Arc::new(Capabilities::all()),
+            backend: Backend::Mock(mock),
+            retry: None,
+            recorder: None,
+            failed: Arc::default(),
+        }
+    }
 
-        Ok(Self { sock, family_id })
+    /// Returns what the kernel's `batadv` family implements, as read when the socket
+    /// connected. A [mock](Self::mock) supports everything.
+    pub fn capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+
+    /// Returns `true` if requests are answered by a [`MockBackend`].
+    pub(crate) fn is_mock(&self) -> bool {
+        matches!(self.backend, Backend::Mock(_))
+    }
+
+    /// Returns `true` once sending or receiving on the socket failed, e.g. because
+    /// its receive buffer overflowed (`ENOBUFS`). Replies may have been lost, so the
+    /// socket should be replaced.
+    pub(crate) fn has_failed(&self) -> bool {
+        self.failed.load(Ordering::Relaxed)
+    }
+
+    /// Returns the rtnetlink socket shared with this socket.
+    pub fn rtnl(&self) -> &RtnlSocket {
+        &self.rtnl
     }
 
     /// Sends a Generic Netlink message to the `batadv` family.
     ///
+    /// With a retry policy configured, the first reply is awaited before returning:
+    /// if the request failed with a transient error (`EBUSY`, `EAGAIN`, `ENOBUFS`) it
+    /// is sent again after the policy's backoff delay.
+    ///
     /// # Parameters
     /// - `flags`: Flags controlling message behavior (`NlmF::REQUEST`, `NlmF::DUMP`, etc.).
     /// - `msg`: The Generic Netlink message to send (`Genlmsghdr<u8, u16>`).
     ///
     /// # Returns
-    /// - `Ok(NlRouterReceiverHandle)` to asynchronously iterate over responses.
+    /// - `Ok(BatadvResponse)` to asynchronously iterate over responses.
     /// - `Err(RobinError)` if sending the message fails.
     pub async fn send(
-        &mut self,
+        &self,
         flags: NlmF,
         msg: Genlmsghdr<u8, u16>,
-    ) -> Result<NlRouterReceiverHandle<u16, Genlmsghdr<u8, u16>>, RobinError> {
-        let recv = self
-            .sock
-            .send(self.family_id, flags, NlPayload::Payload(msg))
-            .await
-            .map_err(|e| RobinError::Netlink(format!("Failed to send message: {:?}", e)))?;
+    ) -> Result<BatadvResponse, RobinError> {
+        let mut trace = RequestTrace::start(flags, &msg);
+        let mut attempt = 0;
+        loop {
+            let retry = self.retry.filter(|policy| attempt < policy.max_retries());
+
+            if let Some(recorder) = &self.recorder {
+                recorder.request(&msg);
+            }
+
+            let sock = match &self.backend {
+                Backend::Netlink(sock) => sock,
+                Backend::Mock(mock) => {
+                    return Ok(BatadvResponse {
+                        sock: self.clone(),
+                        handle: Handle::Mock(mock.serve(flags, &msg)?),
+                        peeked: None,
+                        trace,
+                    });
+                }
+            };
+
+            let handle = match sock
+                .send(self.family_id, flags, NlPayload::Payload(msg.clone()))
+                .await
+            {
+                Ok(handle) => handle,
+                Err(e) if retry.is_some() && is_transient(&e) => {
+                    trace.retry(attempt, self.backoff(attempt));
+                    tokio::time::sleep(self.backoff(attempt)).await;
+                    attempt += 1;
+                    continue;
+                }
+                Err(e) => {
+                    if is_socket_failure(&e) {
+                        self.failed.store(true, Ordering::Relaxed);
+                    }
+                    return Err(e.into());
+                }
+            };
+
+            let mut response = BatadvResponse {
+                sock: self.clone(),
+                handle: Handle::Netlink(handle),
+                peeked: None,
+                trace,
+            };
+
+            if self.retry.is_none() {
+                return Ok(response);
+            }
+
+            let first = response.recv().await;
+            if retry.is_some() && matches!(&first, Some(Err(e)) if is_transient(e)) {
+                trace = response.trace;
+                trace.retry(attempt, self.backoff(attempt));
+                tokio::time::sleep(self.backoff(attempt)).await;
+                attempt += 1;
+                continue;
+            }
+
+            response.peeked = first;
+            return Ok(response);
+        }
+    }
+
+    /// Sends a dump request (`NlmF::REQUEST | NlmF::DUMP`).
+    ///
+    /// The returned [`BatadvResponse`] keeps the socket alive, so the replies can be
+    /// consumed lazily, e.g. from a `Stream`, without buffering the whole dump first.
+    pub async fn dump(&self, msg: Genlmsghdr<u8, u16>) -> Result<BatadvResponse, RobinError> {
+        self.send(NlmF::REQUEST | NlmF::DUMP, msg).await
+    }
+
+    fn backoff(&self, attempt: u32) -> std::time::Duration {
+        self.retry.unwrap_or_default().delay(attempt)
+    }
+}
+
+/// Replies to a request sent on a [`BatadvSocket`].
+///
+/// Holds a handle on the socket the request was sent on, since the router stops
+/// delivering the remaining parts of a multi-part dump once the socket is dropped.
+pub struct BatadvResponse {
+    sock: BatadvSocket,
+    handle: Handle,
+    peeked: Option<Result<BatadvMsg, BatadvRouterError>>,
+    trace: RequestTrace,
+}
 
-        Ok(recv)
+/// Source of the replies of a [`BatadvResponse`].
+enum Handle {
+    Netlink(NlRouterReceiverHandle<u16, Genlmsghdr<u8, u16>>),
+    Mock(VecDeque<Result<BatadvMsg, BatadvRouterError>>),
+}
+
+impl Handle {
+    async fn next(&mut self) -> Option<Result<BatadvMsg, BatadvRouterError>> {
+        match self {
+            Handle::Netlink(handle) => handle.next().await,
+            Handle::Mock(replies) => replies.pop_front(),
+        }
+    }
+}
+
+impl BatadvResponse {
+    /// Returns the socket the request was sent on.
+    pub fn socket(&self) -> &BatadvSocket {
+        &self.sock
+    }
+
+    /// Receives the next reply.
+    ///
+    /// # Returns
+    /// - `Some(Ok(msg))` for every reply, including the final `NLMSG_DONE` of a dump.
+    /// - `Some(Err(e))` if the message could not be received or parsed, or the kernel
+    ///   rejected the request.
+    /// - `None` once all replies have been received.
+    pub async fn next(&mut self) -> Option<Result<BatadvMsg, BatadvRouterError>> {
+        match self.peeked.take() {
+            Some(msg) => Some(msg),
+            None => self.recv().await,
+        }
+    }
+
+    /// Waits for the first reply and returns `true` if the socket failed before
+    /// delivering it, e.g. overflowed with `ENOBUFS`. Nothing has been received for
+    /// the request then, so it can be sent again on a new socket.
+    pub(crate) async fn failed_before_reply(&mut self) -> bool {
+        if self.peeked.is_none() {
+            self.peeked = self.recv().await;
+        }
+        let failed = matches!(&self.peeked, Some(Err(e)) if is_socket_failure(e));
+        if failed {
+            self.trace.reconnect();
+        }
+        failed
+    }
+
+    /// Receives the next reply from the backend, recording and tracing it.
+    async fn recv(&mut self) -> Option<Result<BatadvMsg, BatadvRouterError>> {
+        let msg = self.handle.next().await;
+        if let Some(msg) = &msg {
+            if matches!(msg, Err(e) if is_socket_failure(e)) {
+                self.sock.failed.store(true, Ordering::Relaxed);
+            }
+            self.trace.reply(msg);
+            if let Some(recorder) = &self.sock.recorder {
+                recorder.reply(msg);
+            }
+        }
+        msg
+    }
+
+    /// Waits for the kernel to acknowledge a request sent with `NlmF::ACK`.
+    ///
+    /// # Returns
+    /// - `Ok(())` once the acknowledgement has been received.
+    /// - `Err(RobinError)` carrying the kernel errno and extended ACK message if the
+    ///   request was rejected.
+    pub async fn ack(mut self) -> Result<(), RobinError> {
+        while let Some(msg) = self.next().await {
+            msg?;
+        }
+        Ok(())
     }
 }
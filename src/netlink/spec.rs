@@ -0,0 +1,184 @@
+use crate::error::RobinError;
+use crate::model::{AttrValue, Attribute};
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use macaddr::MacAddr6;
+
+/// Maximum length of an interface name including the trailing NUL (`IFNAMSIZ`).
+const IFNAMSIZ: usize = 16;
+
+/// Maximum length of a routing algorithm name including the trailing NUL.
+const ALGO_NAME_LEN: usize = 20;
+
+/// Maximum length of the module version string including the trailing NUL.
+const VERSION_LEN: usize = 32;
+
+/// Expected payload type of a BATMAN-adv attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AttrKind {
+    U8,
+    U16,
+    U32,
+    U64,
+    Flag,
+    Mac,
+    /// NUL-terminated string of at most the given length, NUL included.
+    String(usize),
+    Bytes,
+}
+
+impl AttrKind {
+    /// Returns the exact payload length of fixed-size kinds.
+    pub(crate) fn len(self) -> Option<usize> {
+        match self {
+            AttrKind::U8 => Some(1),
+            AttrKind::U16 => Some(2),
+            AttrKind::U32 => Some(4),
+            AttrKind::U64 => Some(8),
+            AttrKind::Flag => Some(0),
+            AttrKind::Mac => Some(6),
+            AttrKind::String(_) | AttrKind::Bytes => None,
+        }
+    }
+
+    /// Returns the name of the kind, e.g. `"u32"` or `"mac"`.
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            AttrKind::U8 => "u8",
+            AttrKind::U16 => "u16",
+            AttrKind::U32 => "u32",
+            AttrKind::U64 => "u64",
+            AttrKind::Flag => "flag",
+            AttrKind::Mac => "mac",
+            AttrKind::String(_) => "string",
+            AttrKind::Bytes => "bytes",
+        }
+    }
+}
+
+/// Payload types of all BATMAN-adv attributes, mirroring the kernel's
+/// `batadv_netlink_policy`.
+static ATTR_SPEC_MAP: LazyLock<HashMap<Attribute, AttrKind>> = LazyLock::new(|| {
+    use AttrKind::*;
+    use Attribute::*;
+
+    HashMap::from([
+        (BatadvAttrUnspec, Bytes),
+        (BatadvAttrVersion, String(VERSION_LEN)),
+        (BatadvAttrAlgoName, String(ALGO_NAME_LEN)),
+        (BatadvAttrMeshIfindex, U32),
+        (BatadvAttrMeshIfname, String(IFNAMSIZ)),
+        (BatadvAttrMeshAddress, Mac),
+        (BatadvAttrHardIfindex, U32),
+        (BatadvAttrHardIfname, String(IFNAMSIZ)),
+        (BatadvAttrHardAddress, Mac),
+        (BatadvAttrOrigAddress, Mac),
+        (BatadvAttrTpMeterResult, U8),
+        (BatadvAttrTpMeterTestTime, U32),
+        (BatadvAttrTpMeterBytes, U64),
+        (BatadvAttrTpMeterCookie, U32),
+        (BatadvAttrPad, Bytes),
+        (BatadvAttrActive, Flag),
+        (BatadvAttrTtAddress, Mac),
+        (BatadvAttrTtTtvn, U8),
+        (BatadvAttrTtLastTtvn, U8),
+        (BatadvAttrTtCrc32, U32),
+        (BatadvAttrTtVid, U16),
+        (BatadvAttrTtFlags, U32),
+        (BatadvAttrFlagBest, Flag),
+        (BatadvAttrLastSeenMsecs, U32),
+        (BatadvAttrNeighAddress, Mac),
+        (BatadvAttrTq, U8),
+        (BatadvAttrThroughput, U32),
+        (BatadvAttrBandwidthUp, U32),
+        (BatadvAttrBandwidthDown, U32),
+        (BatadvAttrRouter, Mac),
+        (BatadvAttrBlaOwn, Flag),
+        (BatadvAttrBlaAddress, Mac),
+        (BatadvAttrBlaVid, U16),
+        (BatadvAttrBlaBackbone, Mac),
+        (BatadvAttrBlaCrc, U16),
+        (BatadvAttrDatCacheIp4Address, U32),
+        (BatadvAttrDatCacheHwAddress, Mac),
+        (BatadvAttrDatCacheVid, U16),
+        (BatadvAttrMcastFlags, U32),
+        (BatadvAttrMcastFlagsPriv, U32),
+        (BatadvAttrVlanId, U16),
+        (BatadvAttrAggregatedOgmsEnabled, U8),
+        (BatadvAttrApIsolationEnabled, U8),
+        (BatadvAttrIsolationMark, U32),
+        (BatadvAttrIsolationMask, U32),
+        (BatadvAttrBondingEnabled, U8),
+        (BatadvAttrBridgeLoopAvoidanceEnabled, U8),
+        (BatadvAttrDistributedArpTableEnabled, U8),
+        (BatadvAttrFragmentationEnabled, U8),
+        (BatadvAttrGwBandwidthDown, U32),
+        (BatadvAttrGwBandwidthUp, U32),
+        (BatadvAttrGwMode, U8),
+        (BatadvAttrGwSelClass, U32),
+        (BatadvAttrHopPenalty, U8),
+        (BatadvAttrLogLevel, U32),
+        (BatadvAttrMulticastForceFloodEnabled, U8),
+        (BatadvAttrNetworkCodingEnabled, U8),
+        (BatadvAttrOrigInterval, U32),
+        (BatadvAttrElpInterval, U32),
+        (BatadvAttrThroughputOverride, U32),
+        (BatadvAttrMulticastFanout, U32),
+    ])
+});
+
+/// Returns the payload types of all BATMAN-adv attributes.
+pub(crate) fn get_attr_spec_map() -> &'static HashMap<Attribute, AttrKind> {
+    &ATTR_SPEC_MAP
+}
+
+/// Decodes and validates the payload of `attr` into an [`AttrValue`].
+///
+/// The payload is decoded as the type declared in the spec map; MAC address
+/// attributes become `AttrValue::Mac`.
+///
+/// # Returns
+/// - `Ok(AttrValue)` if the payload matches the attribute's spec.
+/// - `Err(RobinError::InvalidAttribute)` if its length does not match, or a string
+///   attribute is not valid NUL-terminated UTF-8.
+pub(crate) fn auto_cast_value_with_spec(
+    attr: Attribute,
+    payload: &[u8],
+) -> Result<AttrValue, RobinError> {
+    let kind = get_attr_spec_map()
+        .get(&attr)
+        .copied()
+        .unwrap_or(AttrKind::Bytes);
+
+    cast_value(kind, payload).ok_or(RobinError::InvalidAttribute { attr })
+}
+
+/// Decodes `payload` as `kind`, or returns `None` if it does not match.
+///
+/// Integers are in host byte order, as netlink attributes are.
+fn cast_value(kind: AttrKind, payload: &[u8]) -> Option<AttrValue> {
+    if kind.len().is_some_and(|len| len != payload.len()) {
+        return None;
+    }
+
+    Some(match kind {
+        AttrKind::U8 => AttrValue::U8(payload[0]),
+        AttrKind::U16 => AttrValue::U16(u16::from_ne_bytes(payload.try_into().ok()?)),
+        AttrKind::U32 => AttrValue::U32(u32::from_ne_bytes(payload.try_into().ok()?)),
+        AttrKind::U64 => AttrValue::U64(u64::from_ne_bytes(payload.try_into().ok()?)),
+        AttrKind::Flag => AttrValue::Flag,
+        AttrKind::Mac => AttrValue::Mac(MacAddr6::from(<[u8; 6]>::try_from(payload).ok()?)),
+        AttrKind::String(max) => {
+            let (&0, text) = payload.split_last()? else {
+                return None;
+            };
+            if payload.len() > max || text.contains(&0) {
+                return None;
+            }
+            AttrValue::String(std::str::from_utf8(text).ok()?.to_string())
+        }
+        AttrKind::Bytes => AttrValue::Bytes(payload.to_vec()),
+    })
+}
@@ -0,0 +1,113 @@
+use crate::netlink::{BatadvMsg, BatadvRouterError};
+
+use neli::consts::nl::NlmF;
+use neli::genl::Genlmsghdr;
+
+#[cfg(feature = "tracing")]
+use crate::model::{Attribute, Command};
+#[cfg(feature = "tracing")]
+use neli::err::RouterError;
+#[cfg(feature = "tracing")]
+use std::time::Instant;
+
+/// Trace of a single `batadv` request and its replies.
+///
+/// Every request gets a `batadv` span carrying the command, the request flags and
+/// the number of attributes. Kernel errors and retries are logged as events within
+/// it, and a final event reports the number of replies and the time taken once the
+/// response is dropped.
+#[cfg(feature = "tracing")]
+pub(crate) struct RequestTrace {
+    span: tracing::Span,
+    started: Instant,
+    replies: usize,
+}
+
+#[cfg(feature = "tracing")]
+impl RequestTrace {
+    /// Opens the span of a request about to be sent.
+    pub(crate) fn start(flags: NlmF, msg: &Genlmsghdr<u8, u16>) -> Self {
+        let cmd = name(Command::try_from(*msg.cmd()));
+        let span = tracing::debug_span!(
+            "batadv",
+            cmd = %cmd,
+            flags = ?flags,
+            attrs = msg.attrs().len(),
+        );
+        let attrs: Vec<_> = msg
+            .attrs()
+            .iter()
+            .map(|attr| name(Attribute::try_from(*attr.nla_type().nla_type())))
+            .collect();
+        tracing::trace!(parent: &span, ?attrs, "request sent");
+
+        Self {
+            span,
+            started: Instant::now(),
+            replies: 0,
+        }
+    }
+
+    /// Logs a retry after a transient error.
+    pub(crate) fn retry(&self, attempt: u32, delay: std::time::Duration) {
+        tracing::debug!(parent: &self.span, attempt, ?delay, "transient error, retrying");
+    }
+
+    /// Logs that the socket failed before the first reply and the request is sent
+    /// again on a new one.
+    pub(crate) fn reconnect(&self) {
+        tracing::warn!(parent: &self.span, "socket failed, reconnecting");
+    }
+
+    /// Accounts for a received reply, logging kernel errors.
+    pub(crate) fn reply(&mut self, msg: &Result<BatadvMsg, BatadvRouterError>) {
+        self.replies += 1;
+        match msg {
+            Ok(_) => {}
+            Err(RouterError::Nlmsgerr(err)) => {
+                tracing::debug!(parent: &self.span, errno = -*err.error(), "kernel error");
+            }
+            Err(e) => tracing::debug!(parent: &self.span, error = %e, "receive error"),
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl Drop for RequestTrace {
+    fn drop(&mut self) {
+        tracing::debug!(
+            parent: &self.span,
+            replies = self.replies,
+            elapsed_us = self.started.elapsed().as_micros() as u64,
+            "request finished",
+        );
+    }
+}
+
+/// Names a command or attribute number, e.g. `BatadvCmdGetOriginators`, or
+/// `unknown(42)` if it is not known to this crate.
+#[cfg(feature = "tracing")]
+fn name<T: std::fmt::Debug, R: std::fmt::Display>(id: Result<T, R>) -> String {
+    match id {
+        Ok(known) => format!("{:?}", known),
+        Err(raw) => format!("unknown({})", raw),
+    }
+}
+
+/// Trace of a single `batadv` request and its replies; no-op without the
+/// `tracing` feature.
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct RequestTrace;
+
+#[cfg(not(feature = "tracing"))]
+impl RequestTrace {
+    pub(crate) fn start(_flags: NlmF, _msg: &Genlmsghdr<u8, u16>) -> Self {
+        Self
+    }
+
+    pub(crate) fn retry(&self, _attempt: u32, _delay: std::time::Duration) {}
+
+    pub(crate) fn reconnect(&self) {}
+
+    pub(crate) fn reply(&mut self, _msg: &Result<BatadvMsg, BatadvRouterError>) {}
+}
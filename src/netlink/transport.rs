@@ -0,0 +1,411 @@
+use crate::capability;
+use crate::commands;
+use crate::error::RobinError;
+use crate::model::{Capabilities, LinkState, LinkStats};
+use crate::netlink::{
+    BatadvMsg, BatadvResponse, BatadvRouterError, BatadvSocket, LinkWatcher, MockBackend,
+    Nl80211Socket, RateLimit, RateLimiter, Recorder, RetryPolicy, RtnlSocket,
+};
+
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use neli::consts::nl::NlmF;
+use neli::genl::Genlmsghdr;
+use tokio::sync::{Mutex, OnceCell};
+
+/// Carries BATMAN-adv requests to a mesh node and brings back the replies.
+///
+/// All commands of [`RobinClient`](crate::RobinClient) are written against this trait,
+/// so the kernel connection can be swapped for another backend, e.g. a connection to
+/// a remote node, without touching the command logic. Requests and replies are
+/// Generic Netlink messages of the `batadv` family.
+///
+/// [`NetlinkTransport`] talks to the local kernel and is the default.
+pub trait MeshTransport: Clone + Send + Sync + 'static {
+    /// Replies to a request.
+    type Response: MeshResponse;
+
+    /// Sends a Generic Netlink message to the `batadv` family.
+    fn send(
+        &self,
+        flags: NlmF,
+        msg: Genlmsghdr<u8, u16>,
+    ) -> impl Future<Output = Result<Self::Response, RobinError>> + Send;
+
+    /// Sends a dump request (`NlmF::REQUEST | NlmF::DUMP`).
+    fn dump(
+        &self,
+        msg: Genlmsghdr<u8, u16>,
+    ) -> impl Future<Output = Result<Self::Response, RobinError>> + Send {
+        self.send(NlmF::REQUEST | NlmF::DUMP, msg)
+    }
+
+    /// Resolves the name of a network interface of the mesh node to its index.
+    fn if_nametoindex(&self, ifname: &str) -> impl Future<Output = Result<u32, RobinError>> + Send;
+
+    /// Resolves the index of a network interface of the mesh node to its name.
+    fn if_indextoname(
+        &self,
+        ifindex: u32,
+    ) -> impl Future<Output = Result<String, RobinError>> + Send;
+
+    /// Returns the operational state and carrier of a network interface of the mesh
+    /// node. `None`, the default, means the transport cannot report link states.
+    fn link_state(
+        &self,
+        ifindex: u32,
+    ) -> impl Future<Output = Result<Option<LinkState>, RobinError>> + Send {
+        let _ = ifindex;
+        async { Ok(None) }
+    }
+
+    /// Returns the operational state, carrier and traffic counters of a network
+    /// interface of the mesh node from a single request. `None` means the transport
+    /// cannot report link states; the default uses [`link_state`](Self::link_state)
+    /// and reports no counters.
+    fn link_info(
+        &self,
+        ifindex: u32,
+    ) -> impl Future<Output = Result<Option<(LinkState, Option<LinkStats>)>, RobinError>> + Send
+    {
+        async move { Ok(self.link_state(ifindex).await?.map(|state| (state, None))) }
+    }
+
+    /// Checks the privileges needed to change the mesh configuration.
+    ///
+    /// Called before every request changing the configuration. By default this
+    /// runs [`can_administer`](crate::can_administer) on the local process.
+    fn can_administer(&self) -> Result<(), RobinError> {
+        capability::can_administer()
+    }
+
+    /// Admits a request changing the mesh configuration, after [`can_administer`].
+    ///
+    /// Returning [`RobinError::Throttled`] refuses the change, e.g. to enforce a
+    /// [`RateLimit`]. By default every change is admitted.
+    ///
+    /// [`can_administer`]: MeshTransport::can_administer
+    fn admit_change(&self) -> Result<(), RobinError> {
+        Ok(())
+    }
+
+    /// Returns the batman-adv debugfs directory to read the originator and translation
+    /// tables from when the kernel does not support the netlink dumps, e.g. on
+    /// releases predating netlink support. `None`, the default, disables the fallback.
+    fn debugfs_root(&self) -> Option<&Path> {
+        None
+    }
+
+    /// Returns the commands and attributes the mesh node's batman-adv supports.
+    ///
+    /// By default everything known to this crate is assumed to be supported
+    /// ([`Capabilities::all`]).
+    fn capabilities(&self) -> impl Future<Output = Result<Capabilities, RobinError>> + Send {
+        async { Ok(Capabilities::all()) }
+    }
+
+    /// Returns whether requests select the mesh interface by name
+    /// (`BATADV_ATTR_MESH_IFNAME`) instead of by index. `false`, the default, resolves
+    /// the name with [`if_nametoindex`](MeshTransport::if_nametoindex) first.
+    fn mesh_by_name(&self) -> bool {
+        false
+    }
+}
+
+/// Replies to a request sent on a [`MeshTransport`].
+pub trait MeshResponse: Send + Sized {
+    /// The transport the request was sent on.
+    type Transport: MeshTransport;
+
+    /// Returns the transport the request was sent on, e.g. for interface lookups
+    /// while parsing the replies.
+    fn transport(&self) -> &Self::Transport;
+
+    /// Receives the next reply, or `None` once all replies have been received.
+    ///
+    /// Kernel errors are returned as `RouterError::Nlmsgerr`.
+    fn next(&mut self)
+    -> impl Future<Output = Option<Result<BatadvMsg, BatadvRouterError>>> + Send;
+
+    /// Waits for the request to be acknowledged.
+    ///
+    /// # Returns
+    /// - `Ok(())` once the acknowledgement has been received.
+    /// - `Err(RobinError)` carrying the kernel errno if the request was rejected.
+    fn ack(mut self) -> impl Future<Output = Result<(), RobinError>> + Send {
+        async move {
+            while let Some(msg) = self.next().await {
+                msg?;
+            }
+            Ok(())
+        }
+    }
+}
+
+impl MeshTransport for BatadvSocket {
+    type Response = BatadvResponse;
+
+    async fn send(
+        &self,
+        flags: NlmF,
+        msg: Genlmsghdr<u8, u16>,
+    ) -> Result<BatadvResponse, RobinError> {
+        BatadvSocket::send(self, flags, msg).await
+    }
+
+    async fn if_nametoindex(&self, ifname: &str) -> Result<u32, RobinError> {
+        commands::if_nametoindex(self.rtnl(), ifname).await
+    }
+
+    async fn if_indextoname(&self, ifindex: u32) -> Result<String, RobinError> {
+        commands::if_indextoname(self.rtnl(), ifindex).await
+    }
+
+    async fn link_state(&self, ifindex: u32) -> Result<Option<LinkState>, RobinError> {
+        self.rtnl().get_link_state(ifindex).await
+    }
+
+    async fn link_info(
+        &self,
+        ifindex: u32,
+    ) -> Result<Option<(LinkState, Option<LinkStats>)>, RobinError> {
+        self.rtnl().get_link_info(ifindex).await
+    }
+
+    async fn capabilities(&self) -> Result<Capabilities, RobinError> {
+        Ok(BatadvSocket::capabilities(self).clone())
+    }
+
+    fn can_administer(&self) -> Result<(), RobinError> {
+        match self.is_mock() {
+            true => Ok(()),
+            false => capability::can_administer(),
+        }
+    }
+}
+
+impl MeshResponse for BatadvResponse {
+    type Transport = BatadvSocket;
+
+    fn transport(&self) -> &BatadvSocket {
+        self.socket()
+    }
+
+    async fn next(&mut self) -> Option<Result<BatadvMsg, BatadvRouterError>> {
+        BatadvResponse::next(self).await
+    }
+}
+
+/// Transport to the BATMAN-adv kernel module of the local node.
+///
+/// The netlink sockets are opened on first use and shared by all clones, so repeated
+/// queries, e.g. in a monitoring loop, do not reconnect every time.
+///
+/// If the `batadv` socket fails, e.g. because its receive buffer overflowed
+/// (`ENOBUFS`), it is replaced by a new one on the next request, resolving the family
+/// ID again. A request whose socket failed before the first reply arrived is sent
+/// again on the new socket, once; a dump failing midway is reported as an error.
+///
+/// Built by [`RobinClient::builder`](crate::RobinClient::builder), which also
+/// configures retries, rate limiting, recording and the [`MockBackend`].
+#[derive(Debug, Clone, Default)]
+pub struct NetlinkTransport {
+    inner: Arc<NetlinkTransportInner>,
+}
+
+#[derive(Debug, Default)]
+struct NetlinkTransportInner {
+    retry: Option<RetryPolicy>,
+    rate_limit: Option<RateLimiter>,
+    record: Option<PathBuf>,
+    mock: Option<Arc<MockBackend>>,
+    debugfs: Option<PathBuf>,
+    mesh_by_name: bool,
+    rtnl: OnceCell<RtnlSocket>,
+    socket: Mutex<Option<BatadvSocket>>,
+    recorder: OnceCell<Recorder>,
+    nl80211: OnceCell<Nl80211Socket>,
+}
+
+impl NetlinkTransport {
+    /// Creates a transport with the given options. Nothing is connected yet.
+    pub(crate) fn new(
+        retry: Option<RetryPolicy>,
+        rate_limit: Option<RateLimit>,
+        record: Option<PathBuf>,
+        mock: Option<Arc<MockBackend>>,
+        debugfs: Option<PathBuf>,
+        mesh_by_name: bool,
+    ) -> Self {
+        Self {
+            inner: Arc::new(NetlinkTransportInner {
+                retry,
+                rate_limit: rate_limit.map(RateLimiter::new),
+                record,
+                mock,
+                debugfs,
+                mesh_by_name,
+                rtnl: OnceCell::new(),
+                socket: Mutex::new(None),
+                recorder: OnceCell::new(),
+                nl80211: OnceCell::new(),
+            }),
+        }
+    }
+
+    /// Returns the `batadv` socket, connecting on first use and after the socket
+    /// failed.
+    ///
+    /// Requests built with [`build_genl_msg`](crate::build_genl_msg) can be sent on
+    /// it directly; see [`BatadvSocket`] for an example.
+    pub async fn socket(&self) -> Result<BatadvSocket, RobinError> {
+        let inner = &self.inner;
+        let mut socket = inner.socket.lock().await;
+        if let Some(sock) = socket.as_ref().filter(|sock| !sock.has_failed()) {
+            return Ok(sock.clone());
+        }
+
+        let sock = match &inner.mock {
+            Some(mock) => BatadvSocket::mock(mock.clone()),
+            None => BatadvSocket::connect(self.rtnl().await?, inner.retry).await?,
+        };
+        let sock = match &inner.record {
+            Some(path) => {
+                let recorder = inner
+                    .recorder
+                    .get_or_try_init(|| async { Recorder::create(path) })
+                    .await?;
+                sock.with_recorder(recorder.clone())
+            }
+            None => sock,
+        };
+        *socket = Some(sock.clone());
+        Ok(sock)
+    }
+
+    /// Returns the rtnetlink socket, connecting on first use.
+    ///
+    /// A single rtnetlink connection serves all interface lookups and link changes,
+    /// including those made while parsing `batadv` replies.
+    pub(crate) async fn rtnl(&self) -> Result<RtnlSocket, RobinError> {
+        let inner = &self.inner;
+        inner
+            .rtnl
+            .get_or_try_init(|| async {
+                match &inner.mock {
+                    Some(mock) => Ok(RtnlSocket::mock(mock.clone())),
+                    None => RtnlSocket::connect().await,
+                }
+            })
+            .await
+            .cloned()
+    }
+
+    /// Returns the `nl80211` socket, connecting on first use.
+    ///
+    /// With a [`MockBackend`], no interface is wireless.
+    pub(crate) async fn nl80211(&self) -> Result<Nl80211Socket, RobinError> {
+        let inner = &self.inner;
+        inner
+            .nl80211
+            .get_or_try_init(|| async {
+                match &inner.mock {
+                    Some(_) => Ok(Nl80211Socket::unsupported()),
+                    None => Nl80211Socket::connect().await,
+                }
+            })
+            .await
+            .cloned()
+    }
+
+    /// Starts watching the link notifications of the kernel.
+    ///
+    /// Fails with a [`MockBackend`], which has no link notifications.
+    pub(crate) async fn link_watcher(&self) -> Result<LinkWatcher, RobinError> {
+        match &self.inner.mock {
+            Some(_) => Err(RobinError::Netlink(
+                "Link notifications are not supported by the mock backend".to_string(),
+            )),
+            None => LinkWatcher::connect().await,
+        }
+    }
+
+    /// Drops the cached interface names and indexes of the rtnetlink connection.
+    pub(crate) fn invalidate_links(&self) {
+        if let Some(rtnl) = self.inner.rtnl.get() {
+            rtnl.if_cache().invalidate();
+        }
+    }
+}
+
+impl MeshTransport for NetlinkTransport {
+    type Response = BatadvResponse;
+
+    async fn send(
+        &self,
+        flags: NlmF,
+        msg: Genlmsghdr<u8, u16>,
+    ) -> Result<BatadvResponse, RobinError> {
+        let sock = self.socket().await?;
+        match sock.send(flags, msg.clone()).await {
+            Ok(mut response) => {
+                if !response.failed_before_reply().await {
+                    return Ok(response);
+                }
+            }
+            Err(e) if !sock.has_failed() => return Err(e),
+            Err(_) => {}
+        }
+
+        // Nothing was received before the socket failed: send the request again,
+        // once, on a new socket.
+        self.socket().await?.send(flags, msg).await
+    }
+
+    async fn if_nametoindex(&self, ifname: &str) -> Result<u32, RobinError> {
+        commands::if_nametoindex(&self.rtnl().await?, ifname).await
+    }
+
+    async fn if_indextoname(&self, ifindex: u32) -> Result<String, RobinError> {
+        commands::if_indextoname(&self.rtnl().await?, ifindex).await
+    }
+
+    async fn link_state(&self, ifindex: u32) -> Result<Option<LinkState>, RobinError> {
+        self.rtnl().await?.get_link_state(ifindex).await
+    }
+
+    async fn link_info(
+        &self,
+        ifindex: u32,
+    ) -> Result<Option<(LinkState, Option<LinkStats>)>, RobinError> {
+        self.rtnl().await?.get_link_info(ifindex).await
+    }
+
+    async fn capabilities(&self) -> Result<Capabilities, RobinError> {
+        Ok(self.socket().await?.capabilities().clone())
+    }
+
+    fn can_administer(&self) -> Result<(), RobinError> {
+        match self.inner.mock {
+            Some(_) => Ok(()),
+            None => capability::can_administer(),
+        }
+    }
+
+    fn admit_change(&self) -> Result<(), RobinError> {
+        match &self.inner.rate_limit {
+            Some(limiter) => limiter.admit(),
+            None => Ok(()),
+        }
+    }
+
+    fn debugfs_root(&self) -> Option<&Path> {
+        self.inner.debugfs.as_deref()
+    }
+
+    fn mesh_by_name(&self) -> bool {
+        self.inner.mesh_by_name
+    }
+}
@@ -0,0 +1,32 @@
+//! MAC address vendor lookup.
+//!
+//! Vendors are looked up in an embedded copy of the IEEE MA-L, MA-M, MA-S, CID and
+//! IAB registries, so no network access or system database is needed.
+
+use macaddr::MacAddr6;
+
+/// Returns the organization the IEEE assigned the prefix of `mac` to.
+///
+/// Locally administered addresses, e.g. the randomized addresses of phones, and
+/// multicast addresses have no vendor and return `None`.
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::oui::vendor;
+///
+/// assert_eq!(vendor("50:a6:d8:12:34:56".parse().unwrap()), Some("Apple, Inc."));
+/// assert_eq!(vendor("02:00:00:00:00:01".parse().unwrap()), None);
+/// ```
+pub fn vendor(mac: MacAddr6) -> Option<&'static str> {
+    if mac.is_local() || mac.is_multicast() {
+        return None;
+    }
+
+    let hex: String = mac
+        .as_bytes()
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect();
+    oui_data::lookup(&hex).map(|record| record.organization())
+}
@@ -0,0 +1,868 @@
+use crate::debugfs::VLAN_HAS_TAG;
+use crate::error::RobinError;
+use crate::failover::GatewayChange;
+use crate::liveness::LivenessEvent;
+use crate::model::{
+    Bandwidth, ClientFlags, ClientPath, ClientSummary, DatCacheEntry, DuplicateClient, Gateway,
+    GatewayInfo, GwMode, HardifInfo, Interface, MeshWarning, Neighbor, Originator, StationInfo,
+    TransglobalEntry, TranslocalEntry, TtTableStats, VlanClients,
+};
+use crate::roaming::RoamEvent;
+
+use std::collections::BTreeMap;
+use std::time::UNIX_EPOCH;
+
+use macaddr::MacAddr6;
+use schemars::{JsonSchema, Schema, schema_for};
+use serde::Serialize;
+
+/// Version of the JSON output format.
+///
+/// Bumped whenever a field of a document is removed, renamed or changes meaning.
+/// Adding fields does not bump it, so consumers should ignore unknown fields.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Names of the commands with a JSON output document, as accepted by [`schema`].
+pub const COMMANDS: &[&str] = &[
+    "neighbors",
+    "originators",
+    "gateways",
+    "translocal",
+    "transglobal",
+    "dat_cache",
+    "translate",
+    "path",
+    "interface",
+    "hardif",
+    "gw_mode",
+    "aggregation",
+    "ap_isolation",
+    "bridge_loop_avoidance",
+    "routing_algo",
+    "record",
+    "tt_summary",
+    "check",
+    "duplicates",
+    "clients",
+    "vid_clients",
+    "monitor",
+    "gateway_change",
+    "roam_history",
+    "error",
+];
+
+/// JSON output of one command on one mesh interface.
+///
+/// The `data` types are dedicated to the output format and kept separate from the
+/// [`model`](crate::model) types, so the format only changes along with
+/// [`SCHEMA_VERSION`].
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::output::{Document, SettingOutput};
+///
+/// let doc = Document::new("bat0", SettingOutput { enabled: true });
+/// assert_eq!(
+///     serde_json::to_string(&doc).unwrap(),
+///     r#"{"schema_version":1,"mesh_if":"bat0","data":{"enabled":true}}"#
+/// );
+/// ```
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Document<T> {
+    /// Version of the output format, see [`SCHEMA_VERSION`].
+    pub schema_version: u32,
+    /// The mesh interface the command ran on.
+    pub mesh_if: String,
+    /// The output of the command.
+    pub data: T,
+}
+
+impl<T> Document<T> {
+    /// Wraps the output of a command on `mesh_if` in a document of the current version.
+    pub fn new(mesh_if: &str, data: T) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            mesh_if: mesh_if.to_string(),
+            data,
+        }
+    }
+}
+
+/// Returns the JSON Schema of the document printed by `command`, or `None` if the
+/// command has no JSON output.
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::output::{COMMANDS, schema};
+///
+/// assert!(COMMANDS.iter().all(|command| schema(command).is_some()));
+/// assert!(schema("apply").is_none());
+/// ```
+pub fn schema(command: &str) -> Option<Schema> {
+    Some(match command {
+        "neighbors" => schema_for!(Document<Vec<NeighborOutput>>),
+        "originators" => schema_for!(Document<Vec<OriginatorOutput>>),
+        "gateways" => schema_for!(Document<Vec<GatewayOutput>>),
+        "translocal" => schema_for!(Document<Vec<TranslocalOutput>>),
+        "transglobal" => schema_for!(Document<Vec<TransglobalOutput>>),
+        "dat_cache" => schema_for!(Document<Vec<DatCacheOutput>>),
+        "translate" => schema_for!(Document<TranslateOutput>),
+        "path" => schema_for!(Document<PathOutput>),
+        "interface" => schema_for!(Document<Vec<InterfaceOutput>>),
+        "hardif" => schema_for!(Document<HardifOutput>),
+        "gw_mode" => schema_for!(Document<GwModeOutput>),
+        "aggregation" | "ap_isolation" | "bridge_loop_avoidance" => {
+            schema_for!(Document<SettingOutput>)
+        }
+        "routing_algo" => schema_for!(Document<RoutingAlgoOutput>),
+        "record" => schema_for!(Document<SnapshotOutput>),
+        // Printed by `translocal --summary` and `transglobal --summary`.
+        "tt_summary" => schema_for!(Document<TtSummaryOutput>),
+        "check" => schema_for!(Document<Vec<WarningOutput>>),
+        // Printed by `check duplicates`.
+        "duplicates" => schema_for!(Document<Vec<DuplicateClientOutput>>),
+        "clients" => schema_for!(Document<ClientSummaryOutput>),
+        // Printed by `vid <vid> clients`.
+        "vid_clients" => schema_for!(Document<VlanClientsOutput>),
+        // One document per event, printed by `monitor liveness`.
+        "monitor" => schema_for!(Document<LivenessOutput>),
+        // One document per change, printed by `monitor gateway`.
+        "gateway_change" => schema_for!(Document<GatewayChangeOutput>),
+        // One document per move, printed by `roam-history`.
+        "roam_history" => schema_for!(Document<RoamOutput>),
+        // Printed on stderr by a command failing with `--json`.
+        "error" => schema_for!(ErrorDocument),
+        _ => return None,
+    })
+}
+
+/// JSON output of a failed command, printed instead of the error message.
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::RobinError;
+/// use batman_robin::output::ErrorDocument;
+///
+/// let doc = ErrorDocument::from(&RobinError::PermissionDenied);
+/// assert_eq!(
+///     serde_json::to_string(&doc).unwrap(),
+///     r#"{"schema_version":1,"error":{"kind":"PermissionDenied","message":"permission denied (CAP_NET_ADMIN required)","errno":1}}"#
+/// );
+/// ```
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ErrorDocument {
+    /// Version of the output format, see [`SCHEMA_VERSION`].
+    pub schema_version: u32,
+    /// The error.
+    pub error: ErrorOutput,
+}
+
+/// A [`RobinError`], in an [`ErrorDocument`].
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ErrorOutput {
+    /// Kind of error, see [`RobinError::kind`], e.g. `"InterfaceNotFound"`.
+    pub kind: String,
+    /// Human-readable description.
+    pub message: String,
+    /// The errno reported by the kernel, if any.
+    pub errno: Option<i32>,
+}
+
+impl From<&RobinError> for ErrorDocument {
+    fn from(err: &RobinError) -> Self {
+        let message = err.to_string();
+        Self {
+            schema_version: SCHEMA_VERSION,
+            error: ErrorOutput {
+                kind: err.kind().to_string(),
+                message: message
+                    .strip_prefix("Error - ")
+                    .unwrap_or(&message)
+                    .to_string(),
+                errno: err.errno(),
+            },
+        }
+    }
+}
+
+/// A neighbor, from `neighbors`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct NeighborOutput {
+    /// MAC address of the neighbor.
+    pub neighbor: String,
+    /// Hard interface the neighbor is reached over.
+    pub hard_if: String,
+    /// Index of the hard interface the neighbor is reached over.
+    pub hard_ifindex: Option<u32>,
+    /// MAC address of the hard interface the neighbor is reached over.
+    pub hard_addr: Option<String>,
+    /// Whether this link is the selected router towards the neighbor.
+    pub best: bool,
+    /// Time since the neighbor was last seen, in milliseconds.
+    pub last_seen_ms: u32,
+    /// Throughput towards the neighbor in kbit/s (BATMAN_V only).
+    pub throughput_kbps: Option<u32>,
+    /// Signal strength in dBm, for neighbors reached over a wireless interface.
+    pub signal_dbm: Option<i8>,
+    /// Bitrate of the last frame sent to the neighbor in kbit/s, for neighbors
+    /// reached over a wireless interface.
+    pub tx_bitrate_kbps: Option<u32>,
+}
+
+impl NeighborOutput {
+    /// Converts a neighbor and the state of its wireless link, if any.
+    pub fn new(neighbor: &Neighbor, station: Option<StationInfo>) -> Self {
+        Self {
+            neighbor: neighbor.neigh.to_string(),
+            hard_if: neighbor.outgoing_if.clone(),
+            hard_ifindex: neighbor.hard_ifindex,
+            hard_addr: neighbor.hard_addr.map(|mac| mac.to_string()),
+            best: neighbor.is_best,
+            last_seen_ms: neighbor.last_seen.as_millis(),
+            throughput_kbps: neighbor.throughput_kbps,
+            signal_dbm: station.and_then(|sta| sta.signal_avg_dbm.or(sta.signal_dbm)),
+            tx_bitrate_kbps: station.and_then(|sta| sta.tx_bitrate_kbps),
+        }
+    }
+}
+
+/// An originator, from `originators`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct OriginatorOutput {
+    /// MAC address of the originator.
+    pub originator: String,
+    /// MAC address of the next hop towards the originator.
+    pub next_hop: String,
+    /// Hard interface the next hop is reached over.
+    pub hard_if: String,
+    /// Time since the originator was last seen, in milliseconds.
+    pub last_seen_ms: u32,
+    /// Transmission quality, 0 to 255 (BATMAN_IV only).
+    pub tq: Option<u8>,
+    /// Throughput towards the originator in kbit/s (BATMAN_V only).
+    pub throughput_kbps: Option<u32>,
+    /// Whether the next hop is the selected router towards the originator.
+    pub best: bool,
+}
+
+impl From<&Originator> for OriginatorOutput {
+    fn from(o: &Originator) -> Self {
+        Self {
+            originator: o.originator.to_string(),
+            next_hop: o.next_hop.to_string(),
+            hard_if: o.outgoing_if.clone(),
+            last_seen_ms: o.last_seen.as_millis(),
+            tq: o.tq,
+            throughput_kbps: o.throughput,
+            best: o.is_best,
+        }
+    }
+}
+
+/// A gateway, from `gateways`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct GatewayOutput {
+    /// MAC address of the gateway.
+    pub gateway: String,
+    /// MAC address of the next hop towards the gateway.
+    pub router: String,
+    /// Hard interface the next hop is reached over.
+    pub hard_if: String,
+    /// Announced downstream bandwidth in kbit/s.
+    pub bandwidth_down_kbps: Option<u32>,
+    /// Announced upstream bandwidth in kbit/s.
+    pub bandwidth_up_kbps: Option<u32>,
+    /// Throughput towards the gateway in kbit/s (BATMAN_V only).
+    pub throughput_kbps: Option<u32>,
+    /// Transmission quality, 0 to 255 (BATMAN_IV only).
+    pub tq: Option<u8>,
+    /// Whether this is the selected gateway.
+    pub best: bool,
+}
+
+impl From<&Gateway> for GatewayOutput {
+    fn from(g: &Gateway) -> Self {
+        Self {
+            gateway: g.mac_addr.to_string(),
+            router: g.router.to_string(),
+            hard_if: g.outgoing_if.clone(),
+            bandwidth_down_kbps: g.bandwidth_down.map(Bandwidth::kbps),
+            bandwidth_up_kbps: g.bandwidth_up.map(Bandwidth::kbps),
+            throughput_kbps: g.throughput.map(Bandwidth::kbps),
+            tq: g.tq,
+            best: g.is_best,
+        }
+    }
+}
+
+/// An entry of the local translation table, from `translocal`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct TranslocalOutput {
+    /// MAC address of the client.
+    pub client: String,
+    /// VLAN id of the entry, `null` if untagged.
+    pub vid: Option<u16>,
+    /// Names of the flags set on the entry, e.g. `"WIFI"`.
+    pub flags: Vec<String>,
+    /// CRC32 of the entries of the VLAN.
+    pub crc32: u32,
+    /// Time since the client was last seen, in milliseconds.
+    pub last_seen_ms: u32,
+}
+
+impl From<&TranslocalEntry> for TranslocalOutput {
+    fn from(e: &TranslocalEntry) -> Self {
+        Self {
+            client: e.client.to_string(),
+            vid: vid(e.vid),
+            flags: flag_names(e.flags),
+            crc32: e.crc32,
+            last_seen_ms: e.last_seen.as_millis(),
+        }
+    }
+}
+
+/// An entry of the global translation table, from `transglobal`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct TransglobalOutput {
+    /// MAC address of the client.
+    pub client: String,
+    /// MAC address of the originator announcing the client.
+    pub originator: String,
+    /// VLAN id of the entry, `null` if untagged.
+    pub vid: Option<u16>,
+    /// Translation table version number of the announcement.
+    pub ttvn: u8,
+    /// Last translation table version number of the originator.
+    pub last_ttvn: u8,
+    /// Names of the flags set on the entry, e.g. `"ROAM"`.
+    pub flags: Vec<String>,
+    /// CRC32 of the entries of the VLAN.
+    pub crc32: u32,
+    /// Whether this is the entry used to reach the client.
+    pub best: bool,
+}
+
+impl From<&TransglobalEntry> for TransglobalOutput {
+    fn from(e: &TransglobalEntry) -> Self {
+        Self {
+            client: e.client.to_string(),
+            originator: e.orig.to_string(),
+            vid: vid(e.vid),
+            ttvn: e.ttvn,
+            last_ttvn: e.last_ttvn,
+            flags: flag_names(e.flags),
+            crc32: e.crc32,
+            best: e.is_best,
+        }
+    }
+}
+
+/// Summary of a translation table, from `translocal --summary` and
+/// `transglobal --summary`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct TtSummaryOutput {
+    /// Number of entries in the table.
+    pub entries: usize,
+    /// Number of distinct clients, by MAC address and VLAN.
+    pub clients: usize,
+    /// Number of entries per VLAN.
+    pub per_vid: Vec<VidCountOutput>,
+    /// Number of entries with each flag, by flag name, e.g. `"ROAM"`.
+    pub per_flag: BTreeMap<String, usize>,
+    /// Number of entries announced by each originator, by MAC address; empty for
+    /// the local table.
+    pub per_originator: BTreeMap<String, usize>,
+}
+
+/// Number of translation table entries of a VLAN.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct VidCountOutput {
+    /// VLAN id, `null` if untagged.
+    pub vid: Option<u16>,
+    /// Number of entries.
+    pub entries: usize,
+}
+
+impl From<&TtTableStats> for TtSummaryOutput {
+    fn from(stats: &TtTableStats) -> Self {
+        Self {
+            entries: stats.entries,
+            clients: stats.clients,
+            per_vid: stats
+                .per_vid
+                .iter()
+                .map(|(&raw, &entries)| VidCountOutput {
+                    vid: vid(raw),
+                    entries,
+                })
+                .collect(),
+            per_flag: stats
+                .per_flag
+                .iter()
+                .map(|(name, &count)| (name.to_string(), count))
+                .collect(),
+            per_originator: stats
+                .per_originator
+                .iter()
+                .map(|(mac, &count)| (mac.to_string(), count))
+                .collect(),
+        }
+    }
+}
+
+/// Number of clients in the mesh, from `clients`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ClientSummaryOutput {
+    /// Number of distinct clients, by MAC address and VLAN.
+    pub clients: usize,
+    /// Number of clients announced by each originator, by MAC address.
+    pub per_originator: BTreeMap<String, usize>,
+    /// Number of clients per VLAN.
+    pub per_vid: Vec<VidClientsOutput>,
+}
+
+/// Number of clients of a VLAN.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct VidClientsOutput {
+    /// VLAN id, `null` if untagged.
+    pub vid: Option<u16>,
+    /// Number of clients.
+    pub clients: usize,
+}
+
+impl From<&ClientSummary> for ClientSummaryOutput {
+    fn from(summary: &ClientSummary) -> Self {
+        Self {
+            clients: summary.clients,
+            per_originator: summary
+                .per_originator
+                .iter()
+                .map(|(mac, &count)| (mac.to_string(), count))
+                .collect(),
+            per_vid: summary
+                .per_vid
+                .iter()
+                .map(|(&raw, &clients)| VidClientsOutput {
+                    vid: vid(raw),
+                    clients,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The clients of a VLAN, from `vid <vid> clients`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct VlanClientsOutput {
+    /// VLAN id.
+    pub vid: u16,
+    /// Number of distinct clients on the VLAN.
+    pub clients: usize,
+    /// Clients of this node on the VLAN.
+    pub local: Vec<TranslocalOutput>,
+    /// Clients of other nodes on the VLAN, by originator.
+    pub originators: Vec<OriginatorClientsOutput>,
+}
+
+/// The clients an originator announces on a VLAN.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct OriginatorClientsOutput {
+    /// MAC address of the originator.
+    pub originator: String,
+    /// The global translation table entries of the originator.
+    pub clients: Vec<TransglobalOutput>,
+}
+
+impl From<&VlanClients> for VlanClientsOutput {
+    fn from(vlan: &VlanClients) -> Self {
+        Self {
+            vid: vlan.vid,
+            clients: vlan.clients(),
+            local: vlan.local.iter().map(TranslocalOutput::from).collect(),
+            originators: vlan
+                .per_originator
+                .iter()
+                .map(|(orig, entries)| OriginatorClientsOutput {
+                    originator: orig.to_string(),
+                    clients: entries.iter().map(TransglobalOutput::from).collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// An entry of the distributed ARP table cache, from `dat_cache`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DatCacheOutput {
+    /// IPv4 address.
+    pub ip: String,
+    /// MAC address the IPv4 address resolves to.
+    pub mac: String,
+    /// VLAN id of the entry, `null` if untagged.
+    pub vid: Option<u16>,
+    /// Time since the entry was last updated, in milliseconds.
+    pub last_seen_ms: u32,
+}
+
+impl From<&DatCacheEntry> for DatCacheOutput {
+    fn from(e: &DatCacheEntry) -> Self {
+        Self {
+            ip: e.ip.to_string(),
+            mac: e.mac.to_string(),
+            vid: vid(e.vid),
+            last_seen_ms: e.last_seen.as_millis(),
+        }
+    }
+}
+
+/// The originator responsible for a destination, from `translate`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct TranslateOutput {
+    /// The destination as given, a MAC or IPv4 address.
+    pub destination: String,
+    /// MAC address of the originator responsible for the destination.
+    pub originator: String,
+}
+
+impl TranslateOutput {
+    /// Converts the result of translating `destination`.
+    pub fn new(destination: impl ToString, originator: MacAddr6) -> Self {
+        Self {
+            destination: destination.to_string(),
+            originator: originator.to_string(),
+        }
+    }
+}
+
+/// The path to a client, from `path`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct PathOutput {
+    /// MAC address of the client.
+    pub client: String,
+    /// VLAN id of the client, `null` if untagged.
+    pub vid: Option<u16>,
+    /// The best route towards the originator serving the client, `null` if the
+    /// client is attached to this node.
+    pub route: Option<OriginatorOutput>,
+}
+
+impl From<&ClientPath> for PathOutput {
+    fn from(path: &ClientPath) -> Self {
+        Self {
+            client: path.client.to_string(),
+            vid: vid(path.vid),
+            route: path.route.as_ref().map(OriginatorOutput::from),
+        }
+    }
+}
+
+/// A hard interface of the mesh, from `interface`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct InterfaceOutput {
+    /// Name of the interface.
+    pub ifname: String,
+    /// Whether batman-adv uses the interface.
+    pub active: bool,
+    /// Operational state of the link, e.g. `"UP"` or `"DOWN"`.
+    pub operstate: String,
+    /// Whether the link has a carrier, `null` if unknown.
+    pub carrier: Option<bool>,
+    /// Whether the interface is wireless.
+    pub wireless: bool,
+    /// Traffic counters of the interface, `null` if unknown.
+    pub stats: Option<LinkStatsOutput>,
+}
+
+impl InterfaceOutput {
+    /// Converts an interface and whether it is wireless.
+    pub fn new(iface: &Interface, wireless: bool) -> Self {
+        Self {
+            ifname: iface.ifname.clone(),
+            active: iface.active,
+            operstate: iface.operstate.to_string(),
+            carrier: iface.carrier,
+            wireless,
+            stats: iface.stats.map(|stats| LinkStatsOutput {
+                rx_bytes: stats.rx_bytes,
+                rx_packets: stats.rx_packets,
+                tx_bytes: stats.tx_bytes,
+                tx_packets: stats.tx_packets,
+            }),
+        }
+    }
+}
+
+/// Traffic counters of an interface, totals since it was created.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct LinkStatsOutput {
+    /// Bytes received.
+    pub rx_bytes: u64,
+    /// Packets received.
+    pub rx_packets: u64,
+    /// Bytes sent.
+    pub tx_bytes: u64,
+    /// Packets sent.
+    pub tx_packets: u64,
+}
+
+/// Settings of a hard interface, from `hardif`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct HardifOutput {
+    /// Name of the interface.
+    pub ifname: String,
+    /// Index of the interface.
+    pub ifindex: u32,
+    /// MAC address of the interface.
+    pub mac: Option<String>,
+    /// Whether batman-adv uses the interface.
+    pub active: bool,
+    /// Hop penalty of the interface.
+    pub hop_penalty: Option<u8>,
+    /// ELP interval in milliseconds (BATMAN_V only).
+    pub elp_interval_ms: Option<u32>,
+    /// Throughput override in kbit/s, 0 if measured (BATMAN_V only).
+    pub throughput_override_kbps: Option<u32>,
+}
+
+impl From<&HardifInfo> for HardifOutput {
+    fn from(info: &HardifInfo) -> Self {
+        Self {
+            ifname: info.ifname.clone(),
+            ifindex: info.ifindex,
+            mac: info.mac.map(|mac| mac.to_string()),
+            active: info.active,
+            hop_penalty: info.hop_penalty,
+            elp_interval_ms: info.elp_interval_ms,
+            throughput_override_kbps: info.throughput_override.map(Bandwidth::kbps),
+        }
+    }
+}
+
+/// Gateway mode of the mesh interface, from `gw_mode`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct GwModeOutput {
+    /// `"off"`, `"client"`, `"server"` or `"unknown"`.
+    pub mode: String,
+    /// Gateway selection class.
+    pub sel_class: u32,
+    /// Announced downstream bandwidth in kbit/s.
+    pub bandwidth_down_kbps: u32,
+    /// Announced upstream bandwidth in kbit/s.
+    pub bandwidth_up_kbps: u32,
+    /// Raw `BATADV_ATTR_GW_MODE` value if the mode is `"unknown"`.
+    pub raw_mode: Option<u8>,
+    /// Routing algorithm of the mesh interface, if reported by the kernel.
+    pub algo: Option<String>,
+}
+
+impl From<&GatewayInfo> for GwModeOutput {
+    fn from(info: &GatewayInfo) -> Self {
+        let mode = match info.mode {
+            GwMode::Off => "off",
+            GwMode::Client => "client",
+            GwMode::Server => "server",
+            GwMode::Unknown(_) => "unknown",
+        };
+        let raw_mode = match info.mode {
+            GwMode::Unknown(raw) => Some(raw),
+            _ => None,
+        };
+        Self {
+            mode: mode.to_string(),
+            raw_mode,
+            sel_class: info.sel_class,
+            bandwidth_down_kbps: info.bandwidth_down.kbps(),
+            bandwidth_up_kbps: info.bandwidth_up.kbps(),
+            algo: info.algo.clone(),
+        }
+    }
+}
+
+/// An on/off setting of the mesh interface, from `aggregation`, `ap_isolation`
+/// and `bridge_loop_avoidance`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct SettingOutput {
+    /// Whether the setting is enabled.
+    pub enabled: bool,
+}
+
+/// A likely misconfiguration, from `check`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct WarningOutput {
+    /// Kind of warning, e.g. `"bridged_without_bla"`.
+    pub code: String,
+    /// Human-readable description.
+    pub message: String,
+    /// The `robctl` command fixing the problem.
+    pub advice: String,
+}
+
+impl From<&MeshWarning> for WarningOutput {
+    fn from(warning: &MeshWarning) -> Self {
+        Self {
+            code: warning.code().to_string(),
+            message: warning.to_string(),
+            advice: warning.advice(),
+        }
+    }
+}
+
+/// A client announced by several originators, from `check duplicates`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DuplicateClientOutput {
+    /// MAC address of the client.
+    pub client: String,
+    /// VLAN id of the entries, `null` if untagged.
+    pub vid: Option<u16>,
+    /// MAC addresses of the originators announcing the client.
+    pub originators: Vec<String>,
+}
+
+impl From<&DuplicateClient> for DuplicateClientOutput {
+    fn from(d: &DuplicateClient) -> Self {
+        Self {
+            client: d.client.to_string(),
+            vid: vid(d.vid),
+            originators: d.originators.iter().map(|o| o.to_string()).collect(),
+        }
+    }
+}
+
+/// A node going down or coming back up, from `monitor liveness`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct LivenessOutput {
+    /// `"down"` or `"up"`.
+    pub event: String,
+    /// MAC address of the originator.
+    pub originator: String,
+    /// Time the originator had not been seen for, in milliseconds.
+    pub silent_ms: u64,
+}
+
+impl From<&LivenessEvent> for LivenessOutput {
+    fn from(event: &LivenessEvent) -> Self {
+        let (name, silent_for) = match *event {
+            LivenessEvent::Down { silent_for, .. } => ("down", silent_for),
+            LivenessEvent::Up { silent_for, .. } => ("up", silent_for),
+        };
+        Self {
+            event: name.to_string(),
+            originator: event.originator().to_string(),
+            silent_ms: silent_for.as_millis() as u64,
+        }
+    }
+}
+
+/// A change of the selected gateway, from `monitor gateway`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct GatewayChangeOutput {
+    /// `"selected"`, `"changed"` or `"lost"`.
+    pub event: String,
+    /// MAC address of the gateway selected before, if any.
+    pub old: Option<String>,
+    /// MAC address of the gateway selected now, if any.
+    pub new: Option<String>,
+}
+
+impl From<&GatewayChange> for GatewayChangeOutput {
+    fn from(change: &GatewayChange) -> Self {
+        Self {
+            event: change.kind().to_string(),
+            old: change.old.as_ref().map(|g| g.mac_addr.to_string()),
+            new: change.new.as_ref().map(|g| g.mac_addr.to_string()),
+        }
+    }
+}
+
+/// A client moving between originators, from `roam-history`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct RoamOutput {
+    /// MAC address of the client.
+    pub client: String,
+    /// VLAN id of the client, `null` if untagged.
+    pub vid: Option<u16>,
+    /// MAC address of the originator announcing the client before, `null` if it
+    /// appeared.
+    pub from: Option<String>,
+    /// MAC address of the originator announcing the client now.
+    pub to: String,
+    /// When the move was seen, in milliseconds since the Unix epoch.
+    pub timestamp_ms: u64,
+    /// Whether the old originator flagged the client `ROAM`.
+    pub roaming: bool,
+}
+
+impl From<&RoamEvent> for RoamOutput {
+    fn from(event: &RoamEvent) -> Self {
+        Self {
+            client: event.client.to_string(),
+            vid: vid(event.vid),
+            from: event.from.map(|from| from.to_string()),
+            to: event.to.to_string(),
+            timestamp_ms: event
+                .at
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_millis() as u64),
+            roaming: event.roam_flagged,
+        }
+    }
+}
+
+/// Routing algorithms, from `routing_algo`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct RoutingAlgoOutput {
+    /// The routing algorithm of each mesh interface.
+    pub active: Vec<ActiveRoutingAlgo>,
+    /// The algorithm used for the next mesh interface created.
+    pub default: String,
+    /// The algorithms supported by the kernel.
+    pub available: Vec<String>,
+}
+
+/// The routing algorithm of a mesh interface.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ActiveRoutingAlgo {
+    /// Name of the mesh interface.
+    pub mesh_if: String,
+    /// Its routing algorithm.
+    pub algo: String,
+}
+
+/// Converts a VLAN id as reported by the kernel, `None` if untagged.
+fn vid(vid: u16) -> Option<u16> {
+    (vid & VLAN_HAS_TAG != 0).then_some(vid & 0x0fff)
+}
+
+/// Returns the names of the flags set in `flags`.
+fn flag_names(flags: ClientFlags) -> Vec<String> {
+    flags
+        .iter_names()
+        .map(|(name, _)| name.to_string())
+        .collect()
+}
+
+/// The tables of a mesh interface at one point in time, from `record`.
+///
+/// A table that could not be read is left empty and its error added to `errors`, so
+/// a recording goes on while the mesh misbehaves.
+#[derive(Debug, Clone, Default, Serialize, JsonSchema)]
+pub struct SnapshotOutput {
+    /// When the tables were read, in milliseconds since the Unix epoch.
+    pub timestamp_ms: u64,
+    /// The originator table.
+    pub originators: Vec<OriginatorOutput>,
+    /// The neighbor table, without station information.
+    pub neighbors: Vec<NeighborOutput>,
+    /// The gateway list.
+    pub gateways: Vec<GatewayOutput>,
+    /// The local translation table.
+    pub translocal: Vec<TranslocalOutput>,
+    /// The global translation table.
+    pub transglobal: Vec<TransglobalOutput>,
+    /// The errors of the tables that could not be read.
+    pub errors: Vec<String>,
+}
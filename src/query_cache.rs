@@ -0,0 +1,213 @@
+use crate::error::RobinError;
+use crate::model::{Command, IfaceRef};
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use futures::future::{BoxFuture, FutureExt, Shared};
+
+/// Entries of a table, as returned by a query.
+type Entries = Arc<dyn Any + Send + Sync>;
+
+/// Result of a query, shared by all callers waiting on it.
+type SharedResult = Result<Entries, Arc<RobinError>>;
+
+/// A query in flight.
+#[derive(Clone)]
+struct InFlight {
+    result: Shared<BoxFuture<'static, SharedResult>>,
+    /// When the query was started, i.e. how old its entries are.
+    started: Instant,
+    /// The generation of the cache when the query was started.
+    generation: u64,
+}
+
+/// Identifies a table query: callers asking for the same table of the same
+/// interfaces get the same entries.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Query {
+    cmd: Command,
+    mesh_if: String,
+    hard_if: Option<IfaceRef>,
+}
+
+impl Query {
+    /// The `cmd` dump of `mesh_if`, restricted to `hard_if` if set.
+    pub(crate) fn new(cmd: Command, mesh_if: &str, hard_if: Option<&IfaceRef>) -> Self {
+        Self {
+            cmd,
+            mesh_if: mesh_if.to_string(),
+            hard_if: hard_if.cloned(),
+        }
+    }
+}
+
+/// Coalesces concurrent identical queries and caches their results.
+///
+/// The first caller of a query starts it; callers arriving while it is in flight
+/// wait for the same dump and get a copy of its result (single-flight). With a
+/// TTL configured for the table, the result is then served until it expires or
+/// the cache is invalidated.
+///
+/// Results of queries overlapping an invalidation are not cached, so a change to
+/// the mesh is never hidden by a dump started before it.
+pub(crate) struct QueryCache {
+    ttl: Option<Duration>,
+    table_ttl: HashMap<Command, Duration>,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    /// Bumped by every invalidation.
+    generation: u64,
+    in_flight: HashMap<Query, InFlight>,
+    cached: HashMap<Query, (Instant, Entries)>,
+}
+
+impl fmt::Debug for QueryCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.lock();
+        f.debug_struct("QueryCache")
+            .field("ttl", &self.ttl)
+            .field("table_ttl", &self.table_ttl)
+            .field("in_flight", &state.in_flight.len())
+            .field("cached", &state.cached.len())
+            .finish()
+    }
+}
+
+impl QueryCache {
+    /// Creates a cache keeping results for `ttl`, or `table_ttl` for the tables
+    /// listed there. Without a TTL, queries are only coalesced.
+    pub(crate) fn new(ttl: Option<Duration>, table_ttl: HashMap<Command, Duration>) -> Self {
+        Self {
+            ttl,
+            table_ttl,
+            state: Mutex::default(),
+        }
+    }
+
+    /// Returns the cached result of `query`, or runs `run` for it unless the
+    /// identical query is already in flight.
+    ///
+    /// `T` must be the same for all calls with the same `query.cmd`.
+    pub(crate) async fn run<T, F>(&self, query: Query, run: F) -> Result<T, RobinError>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: Future<Output = Result<T, RobinError>> + Send + 'static,
+    {
+        let ttl = self.ttl(query.cmd);
+        let in_flight = {
+            let mut state = self.lock();
+            if let Some((at, entries)) = state.cached.get(&query)
+                && ttl.is_some_and(|ttl| at.elapsed() < ttl)
+            {
+                return Ok(downcast(entries));
+            }
+
+            let generation = state.generation;
+            state
+                .in_flight
+                .entry(query.clone())
+                .or_insert_with(|| InFlight {
+                    result: run
+                        .map(|result| match result {
+                            Ok(entries) => Ok(Arc::new(entries) as Entries),
+                            Err(e) => Err(Arc::new(e)),
+                        })
+                        .boxed()
+                        .shared(),
+                    started: Instant::now(),
+                    generation,
+                })
+                .clone()
+        };
+
+        // Also forgets the query when this caller gives up waiting: the callers
+        // still waiting keep the dump going, later ones start a new one.
+        let done = Done {
+            cache: self,
+            query,
+            in_flight: in_flight.clone(),
+        };
+
+        match in_flight.result.await {
+            Ok(entries) => {
+                if ttl.is_some() {
+                    done.store(&entries);
+                }
+                Ok(downcast(&entries))
+            }
+            Err(e) => Err(e.duplicate()),
+        }
+    }
+
+    /// Drops all cached results. Queries in flight are not joined anymore and their
+    /// results are not cached.
+    pub(crate) fn invalidate(&self) {
+        let mut state = self.lock();
+        state.generation += 1;
+        state.in_flight.clear();
+        state.cached.clear();
+    }
+
+    /// Returns how long results of the `cmd` table are kept, if at all.
+    fn ttl(&self, cmd: Command) -> Option<Duration> {
+        self.table_ttl
+            .get(&cmd)
+            .copied()
+            .or(self.ttl)
+            .filter(|ttl| !ttl.is_zero())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Returns a copy of the entries of a query.
+fn downcast<T: Clone + 'static>(entries: &Entries) -> T {
+    entries
+        .downcast_ref::<T>()
+        .expect("queries with the same command have the same result type")
+        .clone()
+}
+
+/// Removes a query from the queries in flight once a caller is done waiting on it,
+/// unless it has been replaced by a newer one in the meantime.
+struct Done<'a> {
+    cache: &'a QueryCache,
+    query: Query,
+    in_flight: InFlight,
+}
+
+impl Done<'_> {
+    /// Caches the `entries` of the query, unless the cache was invalidated since
+    /// it was started.
+    fn store(&self, entries: &Entries) {
+        let mut state = self.cache.lock();
+        if state.generation == self.in_flight.generation {
+            state.cached.insert(
+                self.query.clone(),
+                (self.in_flight.started, entries.clone()),
+            );
+        }
+    }
+}
+
+impl Drop for Done<'_> {
+    fn drop(&mut self) {
+        let mut state = self.cache.lock();
+        if state
+            .in_flight
+            .get(&self.query)
+            .is_some_and(|current| current.result.ptr_eq(&self.in_flight.result))
+        {
+            state.in_flight.remove(&self.query);
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use crate::client::RobinClient;
+use crate::error::RobinError;
+use crate::netlink::MeshTransport;
+use crate::output::{
+    Document, GatewayOutput, NeighborOutput, OriginatorOutput, SnapshotOutput, TransglobalOutput,
+    TranslocalOutput,
+};
+
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use tokio::time::{self, MissedTickBehavior};
+
+/// Prefix of the files written by a [`Recorder`].
+const FILE_PREFIX: &str = "robin-";
+/// Extension of the files written by a [`Recorder`].
+const FILE_SUFFIX: &str = ".jsonl";
+
+/// Writes JSON output documents to rotating files in a directory.
+///
+/// Documents are appended one per line (JSON Lines) to `robin-<timestamp>.jsonl`,
+/// where the timestamp is the creation time of the file in milliseconds since the
+/// Unix epoch. A new file is started once the current one exceeds the size limit,
+/// and the oldest files are deleted beyond the file limit, so a long recording
+/// keeps a bounded amount of history.
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::output::{Document, SettingOutput};
+/// use batman_robin::record::Recorder;
+///
+/// let dir = std::env::temp_dir().join(format!("robin-record-{}", std::process::id()));
+/// let mut recorder = Recorder::new(&dir).max_file_bytes(1).max_files(2);
+/// for _ in 0..3 {
+///     recorder.write(&Document::new("bat0", SettingOutput { enabled: true }))?;
+///     std::thread::sleep(std::time::Duration::from_millis(2));
+/// }
+///
+/// // Every document filled a file; the oldest one was deleted.
+/// assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 2);
+/// # std::fs::remove_dir_all(&dir).unwrap();
+/// # Ok::<(), batman_robin::RobinError>(())
+/// ```
+#[derive(Debug)]
+pub struct Recorder {
+    dir: PathBuf,
+    max_file_bytes: u64,
+    max_files: usize,
+    current: Option<(File, u64)>,
+}
+
+impl Recorder {
+    /// Creates a recorder writing to `dir`, which is created on the first write.
+    ///
+    /// Files are rotated at 10 MiB and the 10 most recent ones are kept.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            max_file_bytes: 10 * 1024 * 1024,
+            max_files: 10,
+            current: None,
+        }
+    }
+
+    /// Starts a new file once the current one holds at least `bytes` bytes.
+    pub fn max_file_bytes(mut self, bytes: u64) -> Self {
+        self.max_file_bytes = bytes;
+        self
+    }
+
+    /// Keeps at most `files` files, deleting the oldest ones. At least the current
+    /// file is always kept.
+    pub fn max_files(mut self, files: usize) -> Self {
+        self.max_files = files.max(1);
+        self
+    }
+
+    /// Returns the directory the files are written to.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Appends `doc` to the current file, starting a new one first if it is full.
+    pub fn write<T: Serialize>(&mut self, doc: &Document<T>) -> Result<(), RobinError> {
+        let mut line = serde_json::to_vec(doc)
+            .map_err(|e| RobinError::Parse(format!("Failed to serialize document: {}", e)))?;
+        line.push(b'\n');
+
+        let (file, written) = match self.current.take() {
+            Some((file, written)) if written < self.max_file_bytes => {
+                self.current.insert((file, written))
+            }
+            _ => self.current.insert((self.rotate()?, 0)),
+        };
+        file.write_all(&line).map_err(|e| RobinError::Io {
+            context: format!("Failed to write to {}", self.dir.display()),
+            source: e,
+        })?;
+        *written += line.len() as u64;
+        Ok(())
+    }
+
+    /// Creates a new file and deletes the oldest ones beyond the file limit.
+    fn rotate(&self) -> Result<File, RobinError> {
+        let io_error = |context: String| {
+            move |source| RobinError::Io {
+                context: context.clone(),
+                source,
+            }
+        };
+
+        fs::create_dir_all(&self.dir)
+            .map_err(io_error(format!("Failed to create {}", self.dir.display())))?;
+        // Zero-padded, so the file names sort by age.
+        let path = self
+            .dir
+            .join(format!("{}{:013}{}", FILE_PREFIX, unix_ms(), FILE_SUFFIX));
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(io_error(format!("Failed to create {}", path.display())))?;
+
+        let mut files: Vec<PathBuf> = fs::read_dir(&self.dir)
+            .map_err(io_error(format!("Failed to read {}", self.dir.display())))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| {
+                        name.starts_with(FILE_PREFIX) && name.ends_with(FILE_SUFFIX)
+                    })
+            })
+            .collect();
+        files.sort();
+        let excess = files.len().saturating_sub(self.max_files);
+        for old in &files[..excess] {
+            fs::remove_file(old)
+                .map_err(io_error(format!("Failed to delete {}", old.display())))?;
+        }
+
+        Ok(file)
+    }
+}
+
+/// Reads the tables of `mesh_if` into a snapshot document.
+///
+/// Tables that cannot be read are listed in the `errors` of the snapshot instead of
+/// failing it.
+pub async fn snapshot<T: MeshTransport>(
+    client: &RobinClient<T>,
+    mesh_if: &str,
+) -> Document<SnapshotOutput> {
+    let mut data = SnapshotOutput {
+        timestamp_ms: unix_ms(),
+        ..Default::default()
+    };
+
+    let mut errors = Vec::new();
+    let mut table = |name: &str, res: Result<(), RobinError>| {
+        if let Err(e) = res {
+            errors.push(format!("{}: {}", name, e));
+        }
+    };
+    table(
+        "originators",
+        client.originators(mesh_if).await.map(|entries| {
+            data.originators = entries.iter().map(OriginatorOutput::from).collect();
+        }),
+    );
+    table(
+        "neighbors",
+        client.neighbors(mesh_if).await.map(|entries| {
+            data.neighbors = entries
+                .iter()
+                .map(|n| NeighborOutput::new(n, None))
+                .collect();
+        }),
+    );
+    table(
+        "gateways",
+        client.gateways(mesh_if).await.map(|entries| {
+            data.gateways = entries.iter().map(GatewayOutput::from).collect();
+        }),
+    );
+    table(
+        "translocal",
+        client.translocal(mesh_if).await.map(|entries| {
+            data.translocal = entries.iter().map(TranslocalOutput::from).collect();
+        }),
+    );
+    table(
+        "transglobal",
+        client.transglobal(mesh_if).await.map(|entries| {
+            data.transglobal = entries.iter().map(TransglobalOutput::from).collect();
+        }),
+    );
+    data.errors = errors;
+
+    Document::new(mesh_if, data)
+}
+
+/// Writes a [`snapshot`] of `mesh_if` to `recorder` every `interval`, starting now.
+///
+/// Runs until a snapshot cannot be written; spawn it as a task to record in the
+/// background.
+///
+/// # Example
+///
+/// ```no_run
+/// use batman_robin::RobinClient;
+/// use batman_robin::record::{Recorder, record};
+/// use std::time::Duration;
+///
+/// # async fn example() -> Result<(), batman_robin::RobinError> {
+/// let client = RobinClient::new();
+/// let mut recorder = Recorder::new("/var/log/robin");
+/// record(&client, "bat0", Duration::from_secs(10), &mut recorder).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn record<T: MeshTransport>(
+    client: &RobinClient<T>,
+    mesh_if: &str,
+    interval: Duration,
+    recorder: &mut Recorder,
+) -> Result<(), RobinError> {
+    let mut ticks = time::interval(interval);
+    ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        ticks.tick().await;
+        recorder.write(&snapshot(client, mesh_if).await)?;
+    }
+}
+
+/// Returns the current time in milliseconds since the Unix epoch.
+fn unix_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64)
+}
@@ -0,0 +1,244 @@
+use crate::client::RobinClient;
+use crate::debugfs::VLAN_HAS_TAG;
+use crate::error::RobinError;
+use crate::model::{ClientFlags, TransglobalEntry};
+use crate::netlink::MeshTransport;
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::time::{Duration, SystemTime};
+
+use macaddr::MacAddr6;
+use tokio::time::{self, MissedTickBehavior};
+
+/// A client moving to another originator, recorded by [`RoamHistory`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RoamEvent {
+    /// MAC address of the client.
+    pub client: MacAddr6,
+    /// VLAN id of the client, as reported in `BATADV_ATTR_TT_VID`.
+    pub vid: u16,
+    /// The originator announcing the client before, `None` if it appeared.
+    pub from: Option<MacAddr6>,
+    /// The originator announcing the client now.
+    pub to: MacAddr6,
+    /// When the move was seen.
+    pub at: SystemTime,
+    /// Whether the old originator flagged the client `ROAM`, i.e. the mesh
+    /// handled the move as roaming instead of the client just showing up elsewhere.
+    pub roam_flagged: bool,
+}
+
+impl fmt::Display for RoamEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "client {}", self.client)?;
+        if self.vid & VLAN_HAS_TAG != 0 {
+            write!(f, " on VLAN {}", self.vid & 0x0fff)?;
+        }
+        match self.from {
+            Some(from) => write!(f, " moved from {} to {}", from, self.to)?,
+            None => write!(f, " appeared at {}", self.to)?,
+        }
+        if self.roam_flagged {
+            write!(f, " (roaming)")?;
+        }
+        Ok(())
+    }
+}
+
+/// What the history knows about a client on a VLAN.
+#[derive(Debug, Clone, Default)]
+struct Client {
+    /// The originator announcing the client, `None` while it was only seen roaming.
+    owner: Option<MacAddr6>,
+    /// Whether the owner flagged the client `ROAM` since it took it over.
+    roaming: bool,
+    /// The most recent moves, oldest first.
+    moves: VecDeque<RoamEvent>,
+}
+
+/// History of the originators each client of the global translation table was
+/// announced by.
+///
+/// Instantaneous dumps only show where a client is now; fed with the global
+/// translation table as it is read, e.g. by polling
+/// [`RobinClient::transglobal`](crate::RobinClient::transglobal) or with
+/// [`monitor`], the history records when clients moved between originators. The
+/// owner of a client is the originator announcing it without the `ROAM` flag,
+/// preferring the best entry; the first table sets the initial owners without
+/// recording moves. Each client keeps its last `capacity` moves.
+///
+/// # Example
+///
+/// ```
+/// use batman_robin::{ClientFlags, TransglobalEntry};
+/// use batman_robin::roaming::RoamHistory;
+/// use std::time::{Duration, SystemTime};
+///
+/// let client = "02:00:00:00:00:aa".parse().unwrap();
+/// let entry = |orig: &str, flags| {
+///     let mut entry = TransglobalEntry::new(client, orig.parse().unwrap());
+///     entry.flags = flags;
+///     entry
+/// };
+///
+/// let mut history = RoamHistory::new(16);
+/// let start = SystemTime::UNIX_EPOCH;
+/// assert!(history.update_at(start, &[entry("02:00:00:00:00:01", ClientFlags::empty())]).is_empty());
+///
+/// // The client roams: the old node flags it, the new one announces it.
+/// let events = history.update_at(
+///     start + Duration::from_secs(10),
+///     &[
+///         entry("02:00:00:00:00:01", ClientFlags::ROAM),
+///         entry("02:00:00:00:00:02", ClientFlags::empty()),
+///     ],
+/// );
+/// assert_eq!(events.len(), 1);
+/// assert_eq!(events[0].from.map(|m| m.to_string()), Some("02:00:00:00:00:01".into()));
+/// assert_eq!(events[0].to.to_string(), "02:00:00:00:00:02");
+/// assert!(events[0].roam_flagged);
+///
+/// assert_eq!(history.moves(client).count(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RoamHistory {
+    capacity: usize,
+    clients: HashMap<(MacAddr6, u16), Client>,
+    /// Whether a table was seen, after which new clients are recorded as appearing.
+    started: bool,
+}
+
+impl RoamHistory {
+    /// Creates a history keeping the last `capacity` moves of each client.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            clients: HashMap::new(),
+            started: false,
+        }
+    }
+
+    /// Updates the history with a global translation table read now.
+    pub fn update(&mut self, entries: &[TransglobalEntry]) -> Vec<RoamEvent> {
+        self.update_at(SystemTime::now(), entries)
+    }
+
+    /// Updates the history with a global translation table read at `at`, returning
+    /// the moves it shows, ordered by VID and client.
+    ///
+    /// Clients missing from the table keep their owner, so a client leaving and
+    /// coming back to the same originator is not a move.
+    pub fn update_at(&mut self, at: SystemTime, entries: &[TransglobalEntry]) -> Vec<RoamEvent> {
+        // The entries of each client: whether the owner flags it, and the new owner.
+        let mut seen: HashMap<(MacAddr6, u16), (bool, Option<&TransglobalEntry>)> = HashMap::new();
+        for e in entries {
+            let (roam_flagged, owner) = seen.entry((e.client, e.vid)).or_default();
+            let owner_flags = self
+                .clients
+                .get(&(e.client, e.vid))
+                .is_some_and(|c| c.owner == Some(e.orig));
+            if e.flags.contains(ClientFlags::ROAM) {
+                *roam_flagged |= owner_flags;
+            } else if owner.is_none_or(|o| !o.is_best && e.is_best) {
+                *owner = Some(e);
+            }
+        }
+
+        let mut events = Vec::new();
+        for ((client, vid), (roam_flagged, owner)) in seen {
+            let started = self.started;
+            let state = self.clients.entry((client, vid)).or_default();
+            state.roaming |= roam_flagged;
+            let Some(owner) = owner.map(|e| e.orig) else {
+                continue;
+            };
+            if state.owner == Some(owner) {
+                // Announced without the flag again: the roaming was called off.
+                state.roaming = false;
+                continue;
+            }
+
+            let from = state.owner.replace(owner);
+            if from.is_none() && !started {
+                continue;
+            }
+            let event = RoamEvent {
+                client,
+                vid,
+                from,
+                to: owner,
+                at,
+                roam_flagged: std::mem::take(&mut state.roaming),
+            };
+            if state.moves.len() == self.capacity {
+                state.moves.pop_front();
+            }
+            if self.capacity > 0 {
+                state.moves.push_back(event);
+            }
+            events.push(event);
+        }
+        self.started = true;
+
+        events.sort_by_key(|e| (e.vid, e.client));
+        events
+    }
+
+    /// Returns the recorded moves of `client` on any VLAN, oldest first.
+    pub fn moves(&self, client: MacAddr6) -> impl Iterator<Item = &RoamEvent> {
+        let mut moves: Vec<&RoamEvent> = self
+            .clients
+            .iter()
+            .filter(|((mac, _), _)| *mac == client)
+            .flat_map(|(_, c)| &c.moves)
+            .collect();
+        moves.sort_by_key(|e| e.at);
+        moves.into_iter()
+    }
+
+    /// Returns the originator currently announcing `client` on `vid`, if known.
+    pub fn owner(&self, client: MacAddr6, vid: u16) -> Option<MacAddr6> {
+        self.clients.get(&(client, vid)).and_then(|c| c.owner)
+    }
+}
+
+/// Reads the global translation table of `mesh_if` every `interval`, starting now,
+/// and passes the moves recorded in `history` to `on_move`.
+///
+/// Runs until the table cannot be read; spawn it as a task to track clients in
+/// the background.
+///
+/// # Example
+///
+/// ```no_run
+/// use batman_robin::RobinClient;
+/// use batman_robin::roaming::{RoamHistory, monitor};
+/// use std::time::Duration;
+///
+/// # async fn example() -> Result<(), batman_robin::RobinError> {
+/// let client = RobinClient::new();
+/// let mut history = RoamHistory::new(100);
+/// monitor(&client, "bat0", Duration::from_secs(5), &mut history, |event| {
+///     println!("{}", event);
+/// })
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn monitor<T: MeshTransport>(
+    client: &RobinClient<T>,
+    mesh_if: &str,
+    interval: Duration,
+    history: &mut RoamHistory,
+    mut on_move: impl FnMut(RoamEvent),
+) -> Result<(), RobinError> {
+    let mut ticks = time::interval(interval);
+    ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        ticks.tick().await;
+        let entries = client.transglobal(mesh_if).await?;
+        history.update(&entries).into_iter().for_each(&mut on_move);
+    }
+}
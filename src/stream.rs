@@ -0,0 +1,138 @@
+//! Early termination of streamed dumps.
+//!
+//! The `*_stream` methods of [`RobinClient`](crate::RobinClient) read a table
+//! while the kernel is still dumping it. Stopping early with `StreamExt::take`
+//! keeps the dump alive until the stream itself is dropped: the remaining parts
+//! keep arriving and queue up behind it. The adapters of [`DumpStreamExt`] drop
+//! the dump as soon as they stop, so the rest of the table is discarded unread.
+
+use crate::error::RobinError;
+
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use futures::Stream;
+
+/// Adapters stopping a streamed dump early.
+///
+/// Implemented for all streams of `Result<T, RobinError>`, such as the ones
+/// returned by [`RobinClient::originators_stream`](crate::RobinClient::originators_stream).
+/// The adapters also stop after the first error.
+///
+/// # Example
+///
+/// ```no_run
+/// # use batman_robin::{DumpStreamExt, RobinClient};
+/// use futures::TryStreamExt;
+/// # async fn example() -> Result<(), batman_robin::RobinError> {
+/// # let client = RobinClient::new();
+/// // Only the first 20 originators are read from the kernel.
+/// let first: Vec<_> = client.originators_stream("bat0").limit(20).try_collect().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait DumpStreamExt<T>: Stream<Item = Result<T, RobinError>> + Sized {
+    /// Yields at most `n` entries, then drops the dump.
+    ///
+    /// ```
+    /// use batman_robin::DumpStreamExt;
+    /// use futures::{TryStreamExt, executor::block_on, stream};
+    ///
+    /// let entries = stream::iter((1..=100).map(Ok));
+    /// let first: Vec<i32> = block_on(entries.limit(3).try_collect())?;
+    /// assert_eq!(first, [1, 2, 3]);
+    /// # Ok::<(), batman_robin::RobinError>(())
+    /// ```
+    fn limit(self, n: usize) -> Limit<Self> {
+        Limit {
+            stream: (n > 0).then(|| Box::pin(self)),
+            remaining: n,
+        }
+    }
+
+    /// Yields entries while `predicate` holds, then drops the dump. The entry
+    /// failing `predicate` is not yielded.
+    ///
+    /// ```
+    /// use batman_robin::DumpStreamExt;
+    /// use futures::{TryStreamExt, executor::block_on, stream};
+    ///
+    /// let entries = stream::iter((1..=100).map(Ok));
+    /// let small: Vec<i32> = block_on(entries.take_while_ok(|n| *n < 4).try_collect())?;
+    /// assert_eq!(small, [1, 2, 3]);
+    /// # Ok::<(), batman_robin::RobinError>(())
+    /// ```
+    fn take_while_ok<F>(self, predicate: F) -> TakeWhileOk<Self, F>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        TakeWhileOk {
+            stream: Some(Box::pin(self)),
+            predicate,
+        }
+    }
+}
+
+impl<T, S> DumpStreamExt<T> for S where S: Stream<Item = Result<T, RobinError>> {}
+
+/// Stream returned by [`DumpStreamExt::limit`].
+#[must_use = "streams do nothing unless polled"]
+pub struct Limit<S> {
+    stream: Option<Pin<Box<S>>>,
+    remaining: usize,
+}
+
+impl<T, S> Stream for Limit<S>
+where
+    S: Stream<Item = Result<T, RobinError>>,
+{
+    type Item = Result<T, RobinError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let Some(stream) = self.stream.as_mut() else {
+            return Poll::Ready(None);
+        };
+
+        let item = ready!(stream.as_mut().poll_next(cx));
+        self.remaining = self.remaining.saturating_sub(1);
+        if self.remaining == 0 || !matches!(item, Some(Ok(_))) {
+            self.stream = None;
+        }
+        Poll::Ready(item)
+    }
+}
+
+/// Stream returned by [`DumpStreamExt::take_while_ok`].
+#[must_use = "streams do nothing unless polled"]
+pub struct TakeWhileOk<S, F> {
+    stream: Option<Pin<Box<S>>>,
+    predicate: F,
+}
+
+impl<T, S, F> Stream for TakeWhileOk<S, F>
+where
+    S: Stream<Item = Result<T, RobinError>>,
+    F: FnMut(&T) -> bool + Unpin,
+{
+    type Item = Result<T, RobinError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let Some(stream) = this.stream.as_mut() else {
+            return Poll::Ready(None);
+        };
+
+        let item = ready!(stream.as_mut().poll_next(cx));
+        match item {
+            Some(Ok(entry)) if (this.predicate)(&entry) => Poll::Ready(Some(Ok(entry))),
+            Some(Ok(_)) | None => {
+                this.stream = None;
+                Poll::Ready(None)
+            }
+            Some(Err(e)) => {
+                this.stream = None;
+                Poll::Ready(Some(Err(e)))
+            }
+        }
+    }
+}
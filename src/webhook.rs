@@ -0,0 +1,143 @@
+use crate::error::RobinError;
+use crate::netlink::RetryPolicy;
+use crate::output::Document;
+
+use std::fmt;
+use std::io;
+use std::time::Duration;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde::Serialize;
+use ureq::Agent;
+
+/// Time after which a webhook request is abandoned, and retried.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// An HTTP endpoint JSON output documents are POSTed to, e.g. the incoming webhook
+/// of a chat or alerting service.
+///
+/// Requests failing with a transport error, a timeout, `429 Too Many Requests` or
+/// a `5xx` status are retried with the delays of a [`RetryPolicy`]; other statuses
+/// fail at once. Sending blocks the calling thread, so use `spawn_blocking` in
+/// async code that must not stall.
+///
+/// # Example
+///
+/// ```no_run
+/// use batman_robin::liveness::LivenessEvent;
+/// use batman_robin::output::{Document, LivenessOutput};
+/// use batman_robin::webhook::Webhook;
+///
+/// # fn example(event: LivenessEvent) -> Result<(), batman_robin::RobinError> {
+/// let webhook = Webhook::new("https://alerts.example.com/hooks/mesh")
+///     .basic_auth("robin", "secret");
+/// webhook.post(&Document::new("bat0", LivenessOutput::from(&event)))?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Webhook {
+    url: String,
+    authorization: Option<String>,
+    retry: RetryPolicy,
+    agent: Agent,
+}
+
+impl Webhook {
+    /// Creates a webhook POSTing to `url`, retrying with the default [`RetryPolicy`].
+    pub fn new(url: impl Into<String>) -> Self {
+        let agent = Agent::config_builder()
+            .timeout_global(Some(TIMEOUT))
+            .build()
+            .into();
+        Self {
+            url: url.into(),
+            authorization: None,
+            retry: RetryPolicy::default(),
+            agent,
+        }
+    }
+
+    /// Authenticates the requests with HTTP basic authentication.
+    ///
+    /// The credentials are left out of the `Debug` output:
+    ///
+    /// ```
+    /// use batman_robin::webhook::Webhook;
+    ///
+    /// let webhook = Webhook::new("https://alerts.example.com/hooks/mesh")
+    ///     .basic_auth("robin", "secret");
+    /// let debug = format!("{:?}", webhook);
+    /// assert!(debug.contains("<redacted>"));
+    /// assert!(!debug.contains("Basic"));
+    /// ```
+    pub fn basic_auth(mut self, user: &str, password: &str) -> Self {
+        let credentials = STANDARD.encode(format!("{}:{}", user, password));
+        self.authorization = Some(format!("Basic {}", credentials));
+        self
+    }
+
+    /// Sets how failed requests are retried.
+    pub fn retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Returns the URL the documents are POSTed to.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// POSTs `doc` as JSON, retrying transient failures.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` once the endpoint accepted the document, or a
+    /// `RobinError::Io` describing the last failure.
+    pub fn post<T: Serialize>(&self, doc: &Document<T>) -> Result<(), RobinError> {
+        let body = serde_json::to_vec(doc)
+            .map_err(|e| RobinError::Parse(format!("Failed to serialize document: {}", e)))?;
+
+        let mut attempt = 0;
+        loop {
+            let mut request = self
+                .agent
+                .post(&self.url)
+                .header("Content-Type", "application/json");
+            if let Some(authorization) = &self.authorization {
+                request = request.header("Authorization", authorization);
+            }
+
+            let err = match request.send(&body[..]) {
+                Ok(_) => return Ok(()),
+                Err(err) => err,
+            };
+            let transient = match err {
+                ureq::Error::StatusCode(status) => status == 429 || status >= 500,
+                _ => true,
+            };
+            if !transient || attempt >= self.retry.max_retries() {
+                return Err(RobinError::Io {
+                    context: format!("Failed to POST to {}", self.url),
+                    source: io::Error::other(err),
+                });
+            }
+            std::thread::sleep(self.retry.delay(attempt));
+            attempt += 1;
+        }
+    }
+}
+
+impl fmt::Debug for Webhook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Webhook")
+            .field("url", &self.url)
+            .field(
+                "authorization",
+                &self.authorization.as_ref().map(|_| "<redacted>"),
+            )
+            .field("retry", &self.retry)
+            .finish_non_exhaustive()
+    }
+}
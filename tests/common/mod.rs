@@ -0,0 +1,127 @@
+//! Network namespace harness for the integration tests against a real kernel.
+//!
+//! Each [`Netns`] is a named network namespace created with `ip netns`, deleted
+//! again on drop. [`Netns::run`] drives a [`RobinClient`] from a thread moved into
+//! the namespace, so its netlink sockets only see the links of that namespace.
+
+use batman_robin::RobinClient;
+
+use std::fs::File;
+use std::future::Future;
+use std::os::fd::AsRawFd;
+use std::path::Path;
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Returns why the integration tests cannot run on this host, if they cannot.
+///
+/// They need root and the `batman-adv` kernel module, which is loaded if needed.
+pub fn skip_reason() -> Option<&'static str> {
+    if unsafe { libc::geteuid() } != 0 {
+        return Some("not running as root");
+    }
+    if !Path::new("/sys/module/batman_adv").exists() {
+        // The module may just not be loaded yet.
+        let _ = Command::new("modprobe").arg("batman-adv").status();
+        if !Path::new("/sys/module/batman_adv").exists() {
+            return Some("the batman-adv module is not available");
+        }
+    }
+    None
+}
+
+/// Returns early from a test if [`skip_reason`] reports it cannot run.
+#[macro_export]
+macro_rules! require_kernel {
+    () => {
+        if let Some(reason) = $crate::common::skip_reason() {
+            eprintln!("skipped: {}", reason);
+            return;
+        }
+    };
+}
+
+/// A network namespace, deleted with everything in it on drop.
+pub struct Netns {
+    name: String,
+}
+
+impl Netns {
+    /// Creates a namespace named after `label`, unique within the test run.
+    pub fn new(label: &str) -> Self {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let name = format!(
+            "robin-{}-{}-{}",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed),
+            label
+        );
+        run_ip(&["netns", "add", &name]);
+        let netns = Self { name };
+        netns.ip(&["link", "set", "lo", "up"]);
+        netns
+    }
+
+    /// Runs `ip` with `args` inside the namespace, panicking if it fails.
+    pub fn ip(&self, args: &[&str]) {
+        let mut full = vec!["-n", self.name.as_str()];
+        full.extend_from_slice(args);
+        run_ip(&full);
+    }
+
+    /// Sets the link `ifname` up.
+    pub fn link_up(&self, ifname: &str) {
+        self.ip(&["link", "set", ifname, "up"]);
+    }
+
+    /// Runs `f` with a [`RobinClient`] living in the namespace and returns its result.
+    ///
+    /// The future runs on a dedicated thread with its own single-threaded runtime,
+    /// since a namespace switch only applies to the calling thread.
+    pub fn run<F, Fut, T>(&self, f: F) -> T
+    where
+        F: FnOnce(RobinClient) -> Fut + Send,
+        Fut: Future<Output = T>,
+        T: Send,
+    {
+        let path = format!("/run/netns/{}", self.name);
+        std::thread::scope(|scope| {
+            scope
+                .spawn(move || {
+                    let file = File::open(&path).expect("open the namespace");
+                    let rc = unsafe { libc::setns(file.as_raw_fd(), libc::CLONE_NEWNET) };
+                    assert_eq!(rc, 0, "setns: {}", std::io::Error::last_os_error());
+
+                    tokio::runtime::Builder::new_current_thread()
+                        .enable_all()
+                        .build()
+                        .expect("build the runtime")
+                        .block_on(f(RobinClient::new()))
+                })
+                .join()
+                .expect("the namespace thread panicked")
+        })
+    }
+}
+
+impl Drop for Netns {
+    fn drop(&mut self) {
+        let _ = Command::new("ip")
+            .args(["netns", "del", &self.name])
+            .status();
+    }
+}
+
+/// Connects `a` and `b` with a veth pair: `a_if` with MAC `a_mac` in `a`, and
+/// `b_if` with MAC `b_mac` in `b`. Both ends are left down.
+pub fn veth(a: &Netns, a_if: &str, a_mac: &str, b: &Netns, b_if: &str, b_mac: &str) {
+    a.ip(&[
+        "link", "add", a_if, "address", a_mac, "type", "veth", "peer", "name", b_if, "address",
+        b_mac, "netns", &b.name,
+    ]);
+}
+
+fn run_ip(args: &[&str]) {
+    let status = Command::new("ip").args(args).status().expect("run ip");
+    assert!(status.success(), "ip {} failed", args.join(" "));
+}
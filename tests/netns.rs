@@ -0,0 +1,220 @@
+//! End-to-end tests against the batman-adv kernel module.
+//!
+//! Ignored by default, since they need root and the module:
+//!
+//! ```sh
+//! sudo -E cargo test --test netns -- --ignored
+//! ```
+//!
+//! Every test builds its meshes from veth links in fresh network namespaces,
+//! leaving the host configuration alone.
+
+mod common;
+
+use batman_robin::{Command, LinkEvent, MeshSettings, MeshWarning, RobinClient, RobinError};
+use common::{Netns, veth};
+
+use std::time::Duration;
+
+use futures::TryStreamExt;
+
+const A_MAC: &str = "02:00:00:00:0a:01";
+const B_MAC: &str = "02:00:00:00:0b:01";
+
+/// Creates `bat0` with `algo` on `client` and attaches `hardif` to it.
+async fn mesh(client: &RobinClient, algo: &str, hardif: &str) -> Result<(), RobinError> {
+    client.create_interface("bat0", Some(algo)).await?;
+    client.set_interface(hardif, Some("bat0")).await
+}
+
+#[test]
+#[ignore = "needs root and the batman-adv module"]
+fn create_attach_and_destroy() {
+    require_kernel!();
+    let ns = Netns::new("iface");
+    let peer = Netns::new("peer");
+    veth(&ns, "veth0", A_MAC, &peer, "veth1", B_MAC);
+
+    ns.run(|client| async move {
+        mesh(&client, "BATMAN_IV", "veth0").await.unwrap();
+        assert_eq!(client.list_meshes().await.unwrap(), ["bat0"]);
+
+        let hardifs = client.get_interface("bat0").await.unwrap();
+        assert_eq!(hardifs.len(), 1);
+        assert_eq!(hardifs[0].ifname, "veth0");
+
+        client.set_interface("veth0", None).await.unwrap();
+        assert!(client.get_interface("bat0").await.unwrap().is_empty());
+
+        client.destroy_interface("bat0").await.unwrap();
+        assert!(client.list_meshes().await.unwrap().is_empty());
+    });
+}
+
+#[test]
+#[ignore = "needs root and the batman-adv module"]
+fn capabilities() {
+    require_kernel!();
+    let ns = Netns::new("caps");
+
+    ns.run(|client| async move {
+        let caps = client.capabilities().await.unwrap();
+        assert!(caps.supports(Command::BatadvCmdGetMeshInfo));
+        assert!(caps.supports(Command::BatadvCmdGetOriginators));
+        assert!(!caps.supports(Command::BatadvCmdUnspec));
+    });
+}
+
+#[test]
+#[ignore = "needs root and the batman-adv module"]
+fn settings_round_trip() {
+    require_kernel!();
+    let ns = Netns::new("settings");
+    ns.ip(&["link", "add", "dummy0", "type", "dummy"]);
+
+    ns.run(|client| async move {
+        mesh(&client, "BATMAN_IV", "dummy0").await.unwrap();
+
+        let wanted = MeshSettings {
+            aggregation: Some(false),
+            bridge_loop_avoidance: Some(false),
+            hop_penalty: Some(42),
+            orig_interval_ms: Some(500),
+            ..Default::default()
+        };
+        client.apply_settings("bat0", &wanted).await.unwrap();
+
+        let running = client.get_settings("bat0").await.unwrap();
+        assert_eq!(running.aggregation, Some(false));
+        assert_eq!(running.bridge_loop_avoidance, Some(false));
+        assert_eq!(running.hop_penalty, Some(42));
+        assert_eq!(running.orig_interval_ms, Some(500));
+        assert!(!client.get_aggregation("bat0").await.unwrap());
+    });
+}
+
+#[test]
+#[ignore = "needs root and the batman-adv module"]
+fn bridged_without_bla() {
+    require_kernel!();
+    let ns = Netns::new("bridge");
+    let peer = Netns::new("bridge-peer");
+    veth(&ns, "veth0", A_MAC, &peer, "veth1", B_MAC);
+    ns.ip(&["link", "add", "br0", "type", "bridge"]);
+
+    ns.run(|client| async move {
+        mesh(&client, "BATMAN_IV", "veth0").await.unwrap();
+        client.set_interface("bat0", Some("br0")).await.unwrap();
+
+        client
+            .set_bridge_loop_avoidance("bat0", false)
+            .await
+            .unwrap();
+        let warnings = client.check("bat0").await.unwrap();
+        assert_eq!(
+            warnings,
+            [MeshWarning::BridgedWithoutBla {
+                mesh_if: "bat0".into(),
+                ifname: "bat0".into(),
+                bridge: "br0".into(),
+            }]
+        );
+
+        client
+            .set_bridge_loop_avoidance("bat0", true)
+            .await
+            .unwrap();
+        assert!(client.check("bat0").await.unwrap().is_empty());
+    });
+}
+
+#[test]
+#[ignore = "needs root and the batman-adv module"]
+fn originators_across_veth() {
+    require_kernel!();
+    let a = Netns::new("a");
+    let b = Netns::new("b");
+    veth(&a, "veth0", A_MAC, &b, "veth1", B_MAC);
+
+    for (ns, hardif) in [(&a, "veth0"), (&b, "veth1")] {
+        ns.run(|client| async move {
+            mesh(&client, "BATMAN_IV", hardif).await.unwrap();
+            client
+                .apply_settings(
+                    "bat0",
+                    &MeshSettings {
+                        orig_interval_ms: Some(200),
+                        ..Default::default()
+                    },
+                )
+                .await
+                .unwrap();
+        });
+        ns.link_up(hardif);
+        ns.link_up("bat0");
+    }
+
+    // Each node learns the other once a few OGMs went through.
+    let peer = B_MAC.parse().unwrap();
+    let found = a.run(|client| async move {
+        for _ in 0..50 {
+            let originators = client.originators("bat0").await.unwrap();
+            if let Some(o) = originators.into_iter().find(|o| o.originator == peer) {
+                return Some(o);
+            }
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+        None
+    });
+
+    let originator = found.expect("b never showed up in the originators of a");
+    assert_eq!(originator.next_hop, peer);
+    assert_eq!(originator.outgoing_if, "veth0");
+}
+
+#[test]
+#[ignore = "needs root and the batman-adv module"]
+fn link_events() {
+    require_kernel!();
+    let ns = Netns::new("events");
+    let peer = Netns::new("events-peer");
+    veth(&ns, "veth0", A_MAC, &peer, "veth1", B_MAC);
+
+    ns.run(|client| async move {
+        let mut events = std::pin::pin!(client.link_events());
+        // The watcher reads the current links on its first poll.
+        let first = tokio::time::timeout(Duration::from_millis(200), events.try_next()).await;
+        assert!(first.is_err(), "unexpected event {:?}", first);
+
+        mesh(&client, "BATMAN_IV", "veth0").await.unwrap();
+        client.set_interface("veth0", None).await.unwrap();
+        client.destroy_interface("bat0").await.unwrap();
+
+        let mut seen = Vec::new();
+        while let Ok(event) = tokio::time::timeout(Duration::from_secs(1), events.try_next()).await
+        {
+            match event.unwrap().unwrap() {
+                LinkEvent::StateChanged { .. } => {}
+                event => seen.push(event),
+            }
+        }
+
+        let names: Vec<_> = seen
+            .iter()
+            .map(|event| match event {
+                LinkEvent::MeshAdded { ifname, .. } => format!("+{}", ifname),
+                LinkEvent::MeshRemoved { ifname, .. } => format!("-{}", ifname),
+                LinkEvent::HardifAdded {
+                    ifname, mesh_if, ..
+                } => format!("{}+{}", mesh_if, ifname),
+                LinkEvent::HardifRemoved {
+                    ifname, mesh_if, ..
+                } => {
+                    format!("{}-{}", mesh_if, ifname)
+                }
+                event => format!("{:?}", event),
+            })
+            .collect();
+        assert_eq!(names, ["+bat0", "bat0+veth0", "bat0-veth0", "-bat0"]);
+    });
+}